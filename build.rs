@@ -0,0 +1,207 @@
+//! Regenerate the Unicode data tables under `src/tables` from UCD files.
+//!
+//! By default the checked-in tables are used. To regenerate them, e.g. for a
+//! newer Unicode version, point `ASCII_FILTER_UCD_DIR` at a directory holding
+//! the UCD files and set `ASCII_FILTER_UNICODE_VERSION` accordingly:
+//!
+//! ```bash
+//! ASCII_FILTER_UCD_DIR=path/to/ucd ASCII_FILTER_UNICODE_VERSION=15.1.0 \
+//!     cargo build
+//! ```
+//!
+//! The regenerated tables are written to `OUT_DIR` and used in place of the
+//! checked-in ones. Copy them into `src/tables` to update the latter.
+
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+fn main() {
+    println!("cargo:rerun-if-env-changed=ASCII_FILTER_UCD_DIR");
+    println!("cargo:rerun-if-env-changed=ASCII_FILTER_UNICODE_VERSION");
+    println!("cargo::rustc-check-cfg=cfg(ascii_filter_ucd)");
+    let Ok(ucd_dir) = env::var("ASCII_FILTER_UCD_DIR") else {
+        return;
+    };
+    let ucd_dir = Path::new(&ucd_dir);
+    let version = env::var("ASCII_FILTER_UNICODE_VERSION")
+        .expect("ASCII_FILTER_UNICODE_VERSION must be set with the UCD dir");
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let out_dir = Path::new(&out_dir);
+
+    fs::write(
+        out_dir.join("unicode_version.rs"),
+        format!(
+            "{}\npub const UNICODE_VERSION: &str = \"{}\";\n",
+            HEADER, version
+        ),
+    )
+    .unwrap();
+
+    let unicode_data = ucd_dir.join("UnicodeData.txt");
+    println!("cargo:rerun-if-changed={}", unicode_data.display());
+    let unicode_data = fs::read_to_string(unicode_data).unwrap();
+    fs::write(
+        out_dir.join("general_category.rs"),
+        gen_general_category(&unicode_data),
+    )
+    .unwrap();
+
+    let scripts = ucd_dir.join("Scripts.txt");
+    println!("cargo:rerun-if-changed={}", scripts.display());
+    let scripts = fs::read_to_string(scripts).unwrap();
+    fs::write(out_dir.join("scripts.rs"), gen_scripts(&scripts)).unwrap();
+
+    let confusables = ucd_dir.join("confusables.txt");
+    println!("cargo:rerun-if-changed={}", confusables.display());
+    let confusables = fs::read_to_string(confusables).unwrap();
+    fs::write(
+        out_dir.join("confusables.rs"),
+        gen_confusables(&confusables),
+    )
+    .unwrap();
+
+    println!("cargo:rustc-cfg=ascii_filter_ucd");
+}
+
+const HEADER: &str = "// Generated by build.rs from the UCD. Do not edit.\n";
+
+/// Generate the table of `(first, last, category)` ranges from
+/// `UnicodeData.txt`. Unlisted codepoints are unassigned (Cn).
+fn gen_general_category(unicode_data: &str) -> String {
+    let mut ranges: Vec<(u32, u32, String)> = Vec::new();
+    let mut range_first: Option<u32> = None;
+    for line in unicode_data.lines() {
+        let fields: Vec<&str> = line.split(';').collect();
+        if fields.len() < 3 {
+            continue;
+        }
+        let cp = u32::from_str_radix(fields[0], 16).unwrap();
+        let (name, gc) = (fields[1], fields[2]);
+        // Large ranges are listed as a pair of "<..., First>" and
+        // "<..., Last>" entries.
+        if name.ends_with(", First>") {
+            range_first = Some(cp);
+            continue;
+        }
+        let first = match range_first.take() {
+            Some(first) if name.ends_with(", Last>") => first,
+            _ => cp,
+        };
+        match ranges.last_mut() {
+            Some((_, last, prev_gc)) if *last + 1 == first && prev_gc == gc => {
+                *last = cp;
+            }
+            _ => ranges.push((first, cp, gc.to_string())),
+        }
+    }
+
+    let mut out = String::new();
+    writeln!(out, "{}", HEADER).unwrap();
+    writeln!(out, "#[rustfmt::skip]").unwrap();
+    writeln!(out, "const GENERAL_CATEGORY: &[(u32, u32, GeneralCategory)] = &[")
+        .unwrap();
+    for (first, last, gc) in ranges {
+        writeln!(out, "    ({:#x}, {:#x}, {}),", first, last, gc).unwrap();
+    }
+    writeln!(out, "];").unwrap();
+    out
+}
+
+/// Generate the list of script names and the table of `(first, last, script)`
+/// ranges from `Scripts.txt`. Unlisted codepoints are of the script `Unknown`,
+/// the first in the list.
+fn gen_scripts(scripts: &str) -> String {
+    let mut entries: Vec<(u32, u32, &str)> = Vec::new();
+    for line in scripts.lines() {
+        let line = line.split('#').next().unwrap().trim();
+        let Some((range, name)) = line.split_once(';') else {
+            continue;
+        };
+        let range = range.trim();
+        let (first, last) = range.split_once("..").unwrap_or((range, range));
+        entries.push((
+            u32::from_str_radix(first, 16).unwrap(),
+            u32::from_str_radix(last, 16).unwrap(),
+            name.trim(),
+        ));
+    }
+    entries.sort();
+
+    let mut names: Vec<&str> = entries.iter().map(|&(_, _, name)| name).collect();
+    names.sort();
+    names.dedup();
+    names.insert(0, "Unknown");
+
+    let mut ranges: Vec<(u32, u32, usize)> = Vec::new();
+    for (first, last, name) in entries {
+        let sc = names.iter().position(|&n| n == name).unwrap();
+        match ranges.last_mut() {
+            Some((_, prev_last, prev_sc)) if *prev_last + 1 == first && *prev_sc == sc => {
+                *prev_last = last;
+            }
+            _ => ranges.push((first, last, sc)),
+        }
+    }
+
+    let mut out = String::new();
+    writeln!(out, "{}", HEADER).unwrap();
+    writeln!(out, "#[rustfmt::skip]").unwrap();
+    writeln!(out, "const SCRIPT_NAMES: &[&str] = &[").unwrap();
+    for name in &names {
+        writeln!(out, "    {:?},", name).unwrap();
+    }
+    writeln!(out, "];").unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "#[rustfmt::skip]").unwrap();
+    writeln!(out, "const SCRIPT: &[(u32, u32, Script)] = &[").unwrap();
+    for (first, last, sc) in ranges {
+        writeln!(out, "    ({:#x}, {:#x}, Script({})),", first, last, sc).unwrap();
+    }
+    writeln!(out, "];").unwrap();
+    out
+}
+
+/// Generate the table of `(char, ascii)` pairs from `confusables.txt` of
+/// UTS #39, where `char` is a non-ASCII char confusable with the printable
+/// ASCII string `ascii`. Since the data maps to skeletons rather than to
+/// lookalikes, e.g. m to rn, a skeleton of several chars is replaced by the
+/// ASCII char of that skeleton, if any.
+fn gen_confusables(confusables: &str) -> String {
+    let mut skeletons: BTreeMap<u32, String> = BTreeMap::new();
+    for line in confusables.lines() {
+        let line = line.split('#').next().unwrap();
+        let fields: Vec<&str> = line.split(';').map(str::trim).collect();
+        if fields.len() < 2 {
+            continue;
+        }
+        let source = u32::from_str_radix(fields[0], 16).unwrap();
+        let skeleton: String = fields[1]
+            .split_whitespace()
+            .map(|cp| char::from_u32(u32::from_str_radix(cp, 16).unwrap()).unwrap())
+            .collect();
+        if skeleton.chars().all(|c| matches!(c, ' '..='~')) {
+            skeletons.insert(source, skeleton);
+        }
+    }
+    let mut ascii_of: HashMap<&str, u32> = HashMap::new();
+    for (&source, skeleton) in skeletons.range(..0x80) {
+        ascii_of.entry(skeleton).or_insert(source);
+    }
+
+    let mut out = String::new();
+    writeln!(out, "{}", HEADER).unwrap();
+    writeln!(out, "#[rustfmt::skip]").unwrap();
+    writeln!(out, "const CONFUSABLES: &[(u32, &str)] = &[").unwrap();
+    for (&source, skeleton) in skeletons.range(0x80..) {
+        let ascii = match ascii_of.get(skeleton.as_str()) {
+            Some(&c) if skeleton.len() > 1 => char::from_u32(c).unwrap().to_string(),
+            _ => skeleton.clone(),
+        };
+        writeln!(out, "    ({:#x}, {:?}),", source, ascii).unwrap();
+    }
+    writeln!(out, "];").unwrap();
+    out
+}
@@ -0,0 +1,96 @@
+//! The classification of chars shared by the filters.
+
+/// Return true if `c` is an ASCII letter, ASCII punctuation, ASCII digit,
+/// space, tab, or '\n'.
+pub fn is_ascii_subset(c: char) -> bool {
+    matches!(c, '\t' | '\n' | ' '..='~')
+}
+
+/// Return the length of the run of the ASCII subset which `bytes` starts
+/// with, checking 8 bytes at a time for one outside of the printable range,
+/// i.e. a control, DEL or a byte of a multibyte char.
+pub fn ascii_subset_run(bytes: &[u8]) -> usize {
+    const ONES: u64 = u64::from_ne_bytes([0x01; 8]);
+    const HIGH: u64 = u64::from_ne_bytes([0x80; 8]);
+    let in_subset = |&&b: &&u8| matches!(b, b'\t' | b'\n' | b' '..=b'~');
+    let mut n: usize = 0;
+    for word in bytes.chunks_exact(8) {
+        let w = u64::from_ne_bytes(word.try_into().unwrap());
+        // The high bit of a byte is set in `w` if it is 0x80 or above, in
+        // `w - 0x20` if below 0x20, and in `w + 1` if 0x7F. A borrow or carry
+        // across bytes only flags a byte next to one flagged already.
+        if (w | w.wrapping_sub(ONES * 0x20) | w.wrapping_add(ONES)) & HIGH == 0
+        {
+            n += 8;
+            continue;
+        }
+        // tab, '\n', or the end of the run
+        let run = word.iter().take_while(in_subset).count();
+        n += run;
+        if run < 8 {
+            return n;
+        }
+    }
+    n + bytes[n..].iter().take_while(in_subset).count()
+}
+
+/// Return true if `c` is invisible, i.e. a zero-width or formatting char
+/// like ZWSP, ZWJ, the BOM, the soft hyphen, a bidi control or a variation
+/// selector, rendered as nothing at all.
+pub fn is_invisible(c: char) -> bool {
+    matches!(
+        c,
+        '\u{ad}'
+            | '\u{34f}'
+            | '\u{61c}'
+            | '\u{115f}'..='\u{1160}'
+            | '\u{17b4}'..='\u{17b5}'
+            | '\u{180b}'..='\u{180f}'
+            | '\u{200b}'..='\u{200f}'
+            | '\u{202a}'..='\u{202e}'
+            | '\u{2060}'..='\u{2064}'
+            | '\u{2066}'..='\u{206f}'
+            | '\u{3164}'
+            | '\u{fe00}'..='\u{fe0f}'
+            | '\u{feff}'
+            | '\u{ffa0}'
+            | '\u{fff9}'..='\u{fffb}'
+            | '\u{1bca0}'..='\u{1bca3}'
+            | '\u{1d173}'..='\u{1d17a}'
+            | '\u{e0000}'..='\u{e007f}'
+            | '\u{e0100}'..='\u{e01ef}'
+    )
+}
+
+/// Return true if `c` is a printable char of ISO 8859-1 or ISO 8859-15 above
+/// the ASCII range, i.e. of U+00A0 to U+00FF, e.g. the accented letters of
+/// Western European languages, or one of the chars ISO 8859-15 has in place
+/// of some of them, e.g. '€'.
+pub fn is_latin1_printable(c: char) -> bool {
+    matches!(
+        c,
+        '\u{a0}'..='\u{ff}' | '€' | 'Š' | 'š' | 'Ž' | 'ž' | 'Œ' | 'œ' | 'Ÿ'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ascii_subset_run;
+
+    #[test]
+    fn test_ascii_subset_run() {
+        let text = b"The quick brown fox\tjumps over\nthe lazy dog.~";
+        for end in 0..=text.len() {
+            for (i, b) in [0x00, b'\r', 0x1f, 0x7f, 0x80, 0xff]
+                .into_iter()
+                .enumerate()
+            {
+                let mut bytes = text[..end].to_vec();
+                bytes.push(b);
+                bytes.extend_from_slice(&text[..i]);
+                assert_eq!(ascii_subset_run(&bytes), end, "{:?}", bytes);
+            }
+        }
+        assert_eq!(ascii_subset_run(text), text.len());
+    }
+}
@@ -0,0 +1,16 @@
+//! The `no_std` core of `ascii-filter`: the classification of chars, the
+//! incremental utf-8 decoding, and a streaming filter of the ASCII subset
+//! which needs no allocator, e.g. for the data of a serial port on a
+//! microcontroller. The `ascii-filter` crate layers its `Read` and `Write`
+//! adapters and the rest of its options on top.
+
+#![no_std]
+
+#[cfg(test)]
+extern crate std;
+
+pub mod class;
+mod stream;
+pub mod utf8;
+
+pub use stream::StreamFilter;
@@ -0,0 +1,140 @@
+//! A streaming filter which needs no allocator.
+
+use crate::class::is_ascii_subset;
+use crate::utf8::{Utf8Chunk, Utf8Decoder};
+
+/// Filters the bytes pushed to it in any chunks, keeping the chars for which
+/// `keep` returns true, by default those of the ASCII subset. The output is
+/// written to a sink as slices of the input and of the replacement, so that
+/// the filter holds nothing but the up to 3 bytes of a char split across
+/// chunks. Invalid utf-8 sequences are dropped, or replaced like the chars
+/// dropped, one replacement per sequence.
+#[derive(Debug, Clone)]
+pub struct StreamFilter<K = fn(char) -> bool> {
+    utf8: Utf8Decoder,
+    keep: K,
+    /// If not `None`, what each char dropped is replaced with.
+    replacement: Option<&'static str>,
+}
+
+impl StreamFilter {
+    /// Create the filter of the ASCII subset.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Default for StreamFilter {
+    fn default() -> Self {
+        StreamFilter {
+            utf8: Utf8Decoder::default(),
+            keep: is_ascii_subset,
+            replacement: None,
+        }
+    }
+}
+
+impl<K: FnMut(char) -> bool> StreamFilter<K> {
+    /// Keep the chars for which `keep` returns true instead.
+    pub fn with_keep<L: FnMut(char) -> bool>(self, keep: L) -> StreamFilter<L> {
+        StreamFilter {
+            utf8: self.utf8,
+            keep,
+            replacement: self.replacement,
+        }
+    }
+
+    /// Replace each char dropped with `replacement`.
+    pub fn with_replacement(mut self, replacement: &'static str) -> Self {
+        self.replacement = Some(replacement);
+        self
+    }
+
+    /// Filter `input`, the next bytes of the stream, calling `out` with the
+    /// output in order. Return the first error of `out`, if any.
+    pub fn push<E, F>(&mut self, input: &[u8], mut out: F) -> Result<(), E>
+    where
+        F: FnMut(&[u8]) -> Result<(), E>,
+    {
+        let keep = &mut self.keep;
+        let replacement = self.replacement;
+        self.utf8.decode(input, |chunk| match chunk {
+            Utf8Chunk::Valid(s) => {
+                let mut start = 0;
+                for (i, c) in s.char_indices() {
+                    if keep(c) {
+                        continue;
+                    }
+                    if start < i {
+                        out(&s.as_bytes()[start..i])?;
+                    }
+                    if let Some(replacement) = replacement {
+                        out(replacement.as_bytes())?;
+                    }
+                    start = i + c.len_utf8();
+                }
+                match start < s.len() {
+                    true => out(&s.as_bytes()[start..]),
+                    false => Ok(()),
+                }
+            }
+            Utf8Chunk::Invalid(_) => match replacement {
+                Some(replacement) => out(replacement.as_bytes()),
+                None => Ok(()),
+            },
+        })
+    }
+
+    /// End the stream, handling an incomplete char pushed last as invalid.
+    pub fn finish<E, F>(&mut self, mut out: F) -> Result<(), E>
+    where
+        F: FnMut(&[u8]) -> Result<(), E>,
+    {
+        match (self.utf8.finish().is_empty(), self.replacement) {
+            (false, Some(replacement)) => out(replacement.as_bytes()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StreamFilter;
+    use core::convert::Infallible;
+    use std::vec::Vec;
+
+    /// Filter `chunks` in turn with `filter`, returning the output.
+    fn run<K: FnMut(char) -> bool>(
+        mut filter: StreamFilter<K>,
+        chunks: &[&[u8]],
+    ) -> Vec<u8> {
+        let mut output = Vec::new();
+        let mut out = |bytes: &[u8]| -> Result<(), Infallible> {
+            output.extend_from_slice(bytes);
+            Ok(())
+        };
+        for chunk in chunks {
+            filter.push(chunk, &mut out).unwrap();
+        }
+        filter.finish(&mut out).unwrap();
+        output
+    }
+
+    #[test]
+    fn test_stream_filter() {
+        let input = "a你b\u{7}c\n".as_bytes();
+        for len in 1..=input.len() {
+            let chunks: Vec<&[u8]> = input.chunks(len).collect();
+            assert_eq!(run(StreamFilter::new(), &chunks), b"abc\n");
+            assert_eq!(
+                run(StreamFilter::new().with_replacement("?"), &chunks),
+                b"a?b?c\n"
+            );
+        }
+        let filter = StreamFilter::new().with_replacement("?");
+        assert_eq!(run(filter.clone(), &[b"a\xffb\xe4\xbd"]), b"a?b?");
+        assert_eq!(run(filter, &[b"a\xe4", b"\xbd"]), b"a?");
+        let filter = StreamFilter::new().with_keep(|c: char| c != 'b');
+        assert_eq!(run(filter, &["ab你".as_bytes()]), "a你".as_bytes());
+    }
+}
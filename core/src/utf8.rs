@@ -0,0 +1,283 @@
+//! Incremental utf-8 decoding, which validates each input byte once and
+//! carries at most the 3 leading bytes of an incomplete char over to the
+//! next chunk, so that any bytes can be decoded in any chunks.
+
+use core::str;
+
+/// A span of the decoded input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Utf8Chunk<'a> {
+    /// Valid utf-8 text.
+    Valid(&'a str),
+    /// Invalid bytes, which `String::from_utf8_lossy` would replace with
+    /// one U+FFFD.
+    Invalid(&'a [u8]),
+}
+
+/// The state of the decoding between chunks.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Utf8Decoder {
+    /// The leading bytes of an incomplete char, in `pending[..len]`.
+    pending: [u8; 3],
+    len: usize,
+}
+
+impl Utf8Decoder {
+    /// Return true if no incomplete char is carried over.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Return the number of bytes carried over.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Decode `input` after the bytes carried over, calling `f` for each
+    /// span of it in order, and carry an incomplete char at the end over to
+    /// the next call. Return the first error of `f`, if any.
+    pub fn decode<E, F>(&mut self, mut input: &[u8], mut f: F) -> Result<(), E>
+    where
+        F: FnMut(Utf8Chunk) -> Result<(), E>,
+    {
+        if self.len > 0 {
+            // complete the char carried over from the first bytes of `input`
+            let n = input.len().min(4 - self.len);
+            let mut joined = [0u8; 4];
+            joined[..self.len].copy_from_slice(&self.pending[..self.len]);
+            joined[self.len..self.len + n].copy_from_slice(&input[..n]);
+            let joined = &joined[..self.len + n];
+            let valid_up_to = match str::from_utf8(joined) {
+                Ok(_) => joined.len(),
+                Err(e) => e.valid_up_to(),
+            };
+            let used = if valid_up_to > 0 {
+                // `joined[..valid_up_to]` is valid utf-8, of which only the
+                // first char starts with the bytes carried over.
+                let s = str::from_utf8(&joined[..valid_up_to]).unwrap();
+                let len = s.chars().next().unwrap().len_utf8();
+                f(Utf8Chunk::Valid(&s[..len]))?;
+                len
+            } else {
+                match str::from_utf8(joined).unwrap_err().error_len() {
+                    Some(len) => {
+                        f(Utf8Chunk::Invalid(&joined[..len]))?;
+                        len
+                    }
+                    None => {
+                        // still incomplete, and `input` is used up
+                        self.pending[self.len..self.len + n]
+                            .copy_from_slice(input);
+                        self.len += n;
+                        return Ok(());
+                    }
+                }
+            };
+            // The bytes carried over were a valid prefix, so at least they
+            // are used.
+            input = &input[used - self.len..];
+            self.len = 0;
+        }
+        while !input.is_empty() {
+            match str::from_utf8(input) {
+                Ok(s) => return f(Utf8Chunk::Valid(s)),
+                Err(e) => {
+                    let (valid, invalid) = input.split_at(e.valid_up_to());
+                    if !valid.is_empty() {
+                        // `valid` is valid utf-8 per `e`.
+                        f(Utf8Chunk::Valid(str::from_utf8(valid).unwrap()))?;
+                    }
+                    match e.error_len() {
+                        Some(n) => {
+                            f(Utf8Chunk::Invalid(&invalid[..n]))?;
+                            input = &invalid[n..];
+                        }
+                        None => {
+                            // wait for the rest of the char
+                            self.pending[..invalid.len()]
+                                .copy_from_slice(invalid);
+                            self.len = invalid.len();
+                            return Ok(());
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Return the incomplete char carried over, which is invalid at the end
+    /// of input, and reset the state.
+    pub fn finish(&mut self) -> &[u8] {
+        let len = core::mem::take(&mut self.len);
+        &self.pending[..len]
+    }
+}
+
+/// Splits a stream of bytes fed in any chunks into spans of valid utf-8 and
+/// of invalid bytes, each with the offset of its first byte in the stream,
+/// carrying an incomplete char over to the next chunk as [`Utf8Decoder`]
+/// does, e.g. to report where a stream is invalid.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Utf8Chunker {
+    decoder: Utf8Decoder,
+    /// The number of bytes fed so far.
+    fed: u64,
+}
+
+impl Utf8Chunker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the number of bytes fed so far, including those carried over.
+    pub fn fed(&self) -> u64 {
+        self.fed
+    }
+
+    /// Split `input`, the next bytes of the stream, after the bytes carried
+    /// over, calling `f` with each span of it in order and its offset, and
+    /// carry an incomplete char at the end over to the next call. A span
+    /// may start with bytes carried over, and so before `input`. Return the
+    /// first error of `f`, if any.
+    pub fn feed<E, F>(&mut self, input: &[u8], mut f: F) -> Result<(), E>
+    where
+        F: FnMut(u64, Utf8Chunk) -> Result<(), E>,
+    {
+        let mut offset = self.fed - self.decoder.len() as u64;
+        self.fed += input.len() as u64;
+        self.decoder.decode(input, |chunk| {
+            let len = match chunk {
+                Utf8Chunk::Valid(s) => s.len(),
+                Utf8Chunk::Invalid(bytes) => bytes.len(),
+            };
+            f(offset, chunk)?;
+            offset += len as u64;
+            Ok(())
+        })
+    }
+
+    /// End the stream, returning the incomplete char carried over, which
+    /// is invalid at the end of input, with its offset, if any.
+    pub fn finish(&mut self) -> Option<(u64, &[u8])> {
+        let offset = self.fed - self.decoder.len() as u64;
+        let rest = self.decoder.finish();
+        (!rest.is_empty()).then_some((offset, rest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Utf8Chunk, Utf8Chunker, Utf8Decoder};
+    use core::convert::Infallible;
+    use std::string::String;
+    use std::vec::Vec;
+
+    /// Decode `chunks` in turn, returning the spans as owned bytes, with
+    /// true if valid.
+    fn decode(chunks: &[&[u8]]) -> Vec<(bool, Vec<u8>)> {
+        let mut decoder = Utf8Decoder::default();
+        let mut spans: Vec<(bool, Vec<u8>)> = Vec::new();
+        for chunk in chunks {
+            decoder
+                .decode(chunk, |span| -> Result<(), Infallible> {
+                    spans.push(match span {
+                        Utf8Chunk::Valid(s) => (true, s.as_bytes().to_vec()),
+                        Utf8Chunk::Invalid(b) => (false, b.to_vec()),
+                    });
+                    Ok(())
+                })
+                .unwrap();
+        }
+        let rest = decoder.finish();
+        if !rest.is_empty() {
+            spans.push((false, rest.to_vec()));
+        }
+        spans
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(decode(&[b"ab"]), [(true, b"ab".to_vec())]);
+        assert_eq!(
+            decode(&[b"a\xffb"]),
+            [
+                (true, b"a".to_vec()),
+                (false, b"\xff".to_vec()),
+                (true, b"b".to_vec())
+            ]
+        );
+        // a char split across chunks, one byte at a time
+        assert_eq!(
+            decode(&[b"a\xe4", b"\xbd", b"\xa0b"]),
+            [
+                (true, b"a".to_vec()),
+                (true, "你".as_bytes().to_vec()),
+                (true, b"b".to_vec())
+            ]
+        );
+        // an incomplete char cut short by the next chunk
+        assert_eq!(
+            decode(&[b"\xe4\xbd", b"a"]),
+            [(false, b"\xe4\xbd".to_vec()), (true, b"a".to_vec())]
+        );
+        // and by the end of input
+        assert_eq!(
+            decode(&[b"a", b"\xf0\x9f"]),
+            [(true, b"a".to_vec()), (false, b"\xf0\x9f".to_vec())]
+        );
+        assert!(decode(&[b"", b""]).is_empty());
+    }
+
+    #[test]
+    fn test_chunker() {
+        let mut chunker = Utf8Chunker::new();
+        let mut spans: Vec<(u64, bool, Vec<u8>)> = Vec::new();
+        for chunk in [&b"a\xe4"[..], b"\xbd", b"\xa0\xff", b"b\xf0\x9f"] {
+            chunker
+                .feed(chunk, |offset, span| -> Result<(), Infallible> {
+                    spans.push(match span {
+                        Utf8Chunk::Valid(s) => {
+                            (offset, true, s.as_bytes().to_vec())
+                        }
+                        Utf8Chunk::Invalid(b) => (offset, false, b.to_vec()),
+                    });
+                    Ok(())
+                })
+                .unwrap();
+        }
+        assert_eq!(
+            spans,
+            [
+                (0, true, b"a".to_vec()),
+                // the char split across chunks starts in the first
+                (1, true, "你".as_bytes().to_vec()),
+                (4, false, b"\xff".to_vec()),
+                (5, true, b"b".to_vec())
+            ]
+        );
+        assert_eq!(chunker.fed(), 8);
+        assert_eq!(chunker.finish(), Some((6, &b"\xf0\x9f"[..])));
+        assert_eq!(chunker.finish(), None);
+    }
+
+    #[test]
+    fn test_decode_lossy() {
+        // the same spans as `from_utf8_lossy`, however chunked
+        let input = include_bytes!("../../corrupted_lipsum.txt");
+        let expected = String::from_utf8_lossy(input);
+        for len in [1, 2, 3, 7, input.len()] {
+            let chunks: Vec<&[u8]> = input.chunks(len).collect();
+            let mut decoded = String::new();
+            for (valid, bytes) in decode(&chunks) {
+                match valid {
+                    true => {
+                        decoded.push_str(std::str::from_utf8(&bytes).unwrap())
+                    }
+                    false => decoded.push('\u{FFFD}'),
+                }
+            }
+            assert_eq!(decoded, expected);
+        }
+    }
+}
@@ -0,0 +1,47 @@
+/* The C interface of ascii-filter, built with the `capi` feature, e.g.
+ *
+ *     cargo build --release --lib --features capi
+ *
+ * Functions returning an int return 0 on success, and -1 on failure, after
+ * which ascii_filter_last_error() describes it. */
+
+#ifndef ASCII_FILTER_H
+#define ASCII_FILTER_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct AsciiFilterBuilder AsciiFilterBuilder;
+typedef struct AsciiFilter AsciiFilter;
+
+/* The description of the last failure on this thread, valid until the next
+ * failure, or "" if none. */
+const char *ascii_filter_last_error(void);
+
+/* A builder with the default options. */
+AsciiFilterBuilder *ascii_filter_builder_new(void);
+void ascii_filter_builder_free(AsciiFilterBuilder *builder);
+/* Set the option `key` to `value`, both named as on the command line, e.g.
+ * "replace" and "?", or "strip-ansi" and "true". */
+int ascii_filter_builder_set(AsciiFilterBuilder *builder, const char *key,
+                             const char *value);
+
+/* A filter per the options of `builder`, which may be freed afterwards. */
+AsciiFilter *ascii_filter_new(const AsciiFilterBuilder *builder);
+void ascii_filter_free(AsciiFilter *filter);
+/* Filter the next `len` bytes of the input, split anywhere. */
+int ascii_filter_feed(AsciiFilter *filter, const uint8_t *buf, size_t len);
+/* End the input. */
+int ascii_filter_finish(AsciiFilter *filter);
+/* The output of the last feed or finish, valid until the next. */
+const uint8_t *ascii_filter_output(const AsciiFilter *filter, size_t *len);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
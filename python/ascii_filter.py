@@ -0,0 +1,144 @@
+"""Python bindings of the ascii-filter library, through its C interface.
+
+The shared library is built with the ``capi`` feature::
+
+    cargo build --release --lib --features capi
+
+and found at ``$ASCII_FILTER_LIB``, or as ``libascii_filter.so`` (or
+``.dylib``, or ``ascii_filter.dll``) on the search path of the platform.
+Options are named as on the command line, with ``_`` for ``-``, e.g.::
+
+    >>> f = Filter(ascii_only=True, replace="?")
+    >>> f.filter_str("naïve")
+    'na?ve'
+"""
+
+import ctypes
+import ctypes.util
+import io
+import os
+import sys
+
+__all__ = ["Filter", "FilterWriter", "filter_str", "filter_bytes"]
+
+
+def _load():
+    path = os.environ.get("ASCII_FILTER_LIB")
+    if not path:
+        path = ctypes.util.find_library("ascii_filter")
+    if not path:
+        path = {
+            "win32": "ascii_filter.dll",
+            "darwin": "libascii_filter.dylib",
+        }.get(sys.platform, "libascii_filter.so")
+    lib = ctypes.CDLL(path)
+    p = ctypes.c_void_p
+    lib.ascii_filter_last_error.restype = ctypes.c_char_p
+    lib.ascii_filter_builder_new.restype = p
+    lib.ascii_filter_builder_free.argtypes = [p]
+    lib.ascii_filter_builder_set.argtypes = [p, ctypes.c_char_p,
+                                             ctypes.c_char_p]
+    lib.ascii_filter_new.argtypes = [p]
+    lib.ascii_filter_new.restype = p
+    lib.ascii_filter_free.argtypes = [p]
+    lib.ascii_filter_feed.argtypes = [p, ctypes.c_char_p, ctypes.c_size_t]
+    lib.ascii_filter_finish.argtypes = [p]
+    lib.ascii_filter_output.argtypes = [p, ctypes.POINTER(ctypes.c_size_t)]
+    lib.ascii_filter_output.restype = ctypes.POINTER(ctypes.c_char)
+    return lib
+
+
+_lib = _load()
+
+
+def _check(status):
+    if status != 0:
+        raise ValueError(_lib.ascii_filter_last_error().decode())
+
+
+def _option_value(value):
+    if isinstance(value, bool):
+        return "true" if value else "false"
+    return str(value)
+
+
+class Filter:
+    """The options of a filter, which filters strings and bytes, and
+    creates :class:`FilterWriter` streams."""
+
+    def __init__(self, **options):
+        self._builder = _lib.ascii_filter_builder_new()
+        for key, value in options.items():
+            _check(_lib.ascii_filter_builder_set(
+                self._builder, key.replace("_", "-").encode(),
+                _option_value(value).encode()))
+
+    def __del__(self):
+        if getattr(self, "_builder", None):
+            _lib.ascii_filter_builder_free(self._builder)
+            self._builder = None
+
+    def filter_bytes(self, data):
+        """Return the bytes `data` filtered."""
+        handle = _lib.ascii_filter_new(self._builder)
+        try:
+            _check(_lib.ascii_filter_feed(handle, data, len(data)))
+            out = _output(handle)
+            _check(_lib.ascii_filter_finish(handle))
+            return out + _output(handle)
+        finally:
+            _lib.ascii_filter_free(handle)
+
+    def filter_str(self, text):
+        """Return the string `text` filtered."""
+        return self.filter_bytes(text.encode()).decode()
+
+    def writer(self, raw):
+        """Return a :class:`FilterWriter` to the binary file `raw`."""
+        return FilterWriter(self, raw)
+
+
+def _output(handle):
+    n = ctypes.c_size_t()
+    out = _lib.ascii_filter_output(handle, ctypes.byref(n))
+    return ctypes.string_at(out, n.value) if n.value else b""
+
+
+class FilterWriter(io.RawIOBase):
+    """A binary file-like object which filters what is written through it
+    to `raw`, and ends the input when closed, without closing `raw`."""
+
+    def __init__(self, filter, raw):
+        super().__init__()
+        self._filter = _lib.ascii_filter_new(filter._builder)
+        self._raw = raw
+
+    def writable(self):
+        return True
+
+    def write(self, b):
+        b = bytes(b)
+        _check(_lib.ascii_filter_feed(self._filter, b, len(b)))
+        self._raw.write(_output(self._filter))
+        return len(b)
+
+    def close(self):
+        if self._filter:
+            try:
+                _check(_lib.ascii_filter_finish(self._filter))
+                self._raw.write(_output(self._filter))
+                self._raw.flush()
+            finally:
+                _lib.ascii_filter_free(self._filter)
+                self._filter = None
+        super().close()
+
+
+def filter_bytes(data, **options):
+    """Return the bytes `data` filtered per `options`."""
+    return Filter(**options).filter_bytes(data)
+
+
+def filter_str(text, **options):
+    """Return the string `text` filtered per `options`."""
+    return Filter(**options).filter_str(text)
@@ -0,0 +1,48 @@
+"""Tests of the Python bindings, run by tests/python.rs against the shared
+library given as ``$ASCII_FILTER_LIB``, with the doctests of the module."""
+
+import doctest
+import io
+import unittest
+
+import ascii_filter
+
+
+def load_tests(loader, tests, ignore):
+    tests.addTests(doctest.DocTestSuite(ascii_filter))
+    return tests
+
+
+class TestAsciiFilter(unittest.TestCase):
+    def test_filter_str(self):
+        self.assertEqual(ascii_filter.filter_str("naïve", ascii_only=True),
+                         "nave")
+
+    def test_filter_bytes(self):
+        self.assertEqual(
+            ascii_filter.filter_bytes(b"a\xffb\xc3\xa9", ascii_only=True),
+            b"ab")
+
+    def test_filter(self):
+        f = ascii_filter.Filter(ascii_only=True, replace="?")
+        self.assertEqual(f.filter_str("你好, world"), "??, world")
+        self.assertEqual(f.filter_bytes(b"caf\xc3\xa9"), b"caf?")
+
+    def test_filter_writer(self):
+        raw = io.BytesIO()
+        f = ascii_filter.Filter(ascii_only=True)
+        with f.writer(raw) as w:
+            # a char split across writes
+            w.write(b"na\xc3")
+            w.write(b"\xafve\n")
+        self.assertEqual(raw.getvalue(), b"nave\n")
+        self.assertFalse(raw.closed)
+
+    def test_unknown_option(self):
+        with self.assertRaisesRegex(ValueError,
+                                    'unknown option "no-such-option"'):
+            ascii_filter.Filter(no_such_option=True)
+
+
+if __name__ == "__main__":
+    unittest.main()
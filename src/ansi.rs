@@ -0,0 +1,125 @@
+//! Stripping of ANSI escape sequences, e.g. the color codes in captured
+//! terminal output, as whole units, before the char filter would drop their
+//! ESC and keep the rest as stray `[31m` fragments.
+
+use std::borrow::Cow;
+
+/// Where the stripper is in a sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum State {
+    /// Outside any sequence.
+    #[default]
+    Ground,
+    /// After ESC.
+    Escape,
+    /// In the intermediate bytes of an escape sequence, e.g. `ESC ( B`.
+    EscapeIntermediate,
+    /// In a control sequence, e.g. `ESC [ 31 m`.
+    Csi,
+    /// In a control string terminated by ST or BEL, e.g. the OSC
+    /// `ESC ] 0 ; title BEL`.
+    String,
+    /// After ESC in a control string, which is ST if `\` follows.
+    StringEscape,
+}
+
+/// Return true if `c` starts a sequence, i.e. ESC or a C1 control which
+/// introduces a control sequence or string.
+fn is_introducer(c: char) -> bool {
+    matches!(
+        c,
+        '\x1b' | '\u{90}' | '\u{98}' | '\u{9b}' | '\u{9d}'..='\u{9f}'
+    )
+}
+
+/// Return true if `s` contains what [`AnsiStripper`] may strip.
+pub(crate) fn has_escapes(s: &str) -> bool {
+    s.chars().any(is_introducer)
+}
+
+/// Strips the escape sequences from text pushed in pieces, which may split
+/// a sequence anywhere. A sequence interrupted by a char it may not contain
+/// is dropped up to that char, and a control string ends at '\n' too, so
+/// that an unterminated one does not swallow the rest of the input.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AnsiStripper {
+    state: State,
+}
+
+impl AnsiStripper {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Advance over `c`, returning true if it is outside any sequence.
+    fn step(&mut self, c: char) -> bool {
+        let (state, outside) = match (self.state, c) {
+            (State::Ground, '\x1b') => (State::Escape, false),
+            (State::Ground, '\u{9b}') => (State::Csi, false),
+            (State::Ground, c) if is_introducer(c) => (State::String, false),
+            (State::Ground, _) => (State::Ground, true),
+            (State::Escape, '[') => (State::Csi, false),
+            (State::Escape, ']' | 'P' | 'X' | '^' | '_') => {
+                (State::String, false)
+            }
+            (State::Escape | State::EscapeIntermediate, '\x20'..='\x2f') => {
+                (State::EscapeIntermediate, false)
+            }
+            (State::Escape | State::EscapeIntermediate, '\x30'..='\x7e') => {
+                (State::Ground, false)
+            }
+            (State::Csi, '\x20'..='\x3f') => (State::Csi, false),
+            (State::Csi, '\x40'..='\x7e') => (State::Ground, false),
+            (State::String | State::StringEscape, '\x07' | '\u{9c}') => {
+                (State::Ground, false)
+            }
+            (State::StringEscape, '\\') => (State::Ground, false),
+            (State::String | State::StringEscape, '\x1b') => {
+                (State::StringEscape, false)
+            }
+            (State::String | State::StringEscape, '\n') => {
+                (State::Ground, true)
+            }
+            (State::String | State::StringEscape, _) => (State::String, false),
+            // an interrupted sequence, after which `c` starts afresh
+            (_, c) => {
+                self.state = State::Ground;
+                return self.step(c);
+            }
+        };
+        self.state = state;
+        outside
+    }
+
+    /// Return `s` without the escape sequences, continuing those left
+    /// incomplete by the text pushed before.
+    pub(crate) fn strip<'a>(&mut self, s: &'a str) -> Cow<'a, str> {
+        if self.state == State::Ground && !has_escapes(s) {
+            return Cow::Borrowed(s);
+        }
+        Cow::Owned(s.chars().filter(|&c| self.step(c)).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AnsiStripper;
+
+    #[test]
+    fn test_ansi_stripper() {
+        let mut ansi = AnsiStripper::new();
+        let mut out = String::new();
+        for piece in [
+            "\x1b[1;31mred\x1b",
+            "[0m \x1b]0;tïtle\x07ok\x1b]8;;http://x\x1b",
+            "\\link\x1b(B \u{9b}2J",
+        ] {
+            out += &ansi.strip(piece);
+        }
+        assert_eq!(out, "red oklink ");
+
+        // an interrupted or unterminated sequence
+        let mut ansi = AnsiStripper::new();
+        assert_eq!(ansi.strip("a\x1b[31\nb\x1b]0;x\nc"), "a\nb\nc");
+    }
+}
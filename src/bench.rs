@@ -0,0 +1,177 @@
+//! The `bench-compare` subcommand, which runs a corpus through `iconv`, `tr`
+//! and the modes of this tool, and reports their throughput and how their
+//! outputs differ.
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::{self, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::exit;
+
+/// The number of runs of each tool, of which the fastest counts.
+const RUNS: usize = 3;
+
+/// A command the corpus is piped through.
+struct Tool {
+    name: String,
+    program: PathBuf,
+    args: Vec<&'static str>,
+}
+
+/// Return the tools compared, this tool first.
+fn tools() -> Vec<Tool> {
+    let exe = std::env::current_exe().unwrap_or_else(|e| {
+        eprintln!("ascii-filter: {}", e);
+        process::exit(exit::IO);
+    });
+    let ours = |args: Vec<&'static str>| {
+        let name = ["ascii-filter"]
+            .into_iter()
+            .chain(args.iter().copied())
+            .collect::<Vec<_>>()
+            .join(" ");
+        Tool {
+            name,
+            program: exe.clone(),
+            args,
+        }
+    };
+    vec![
+        ours(vec!["-a"]),
+        ours(vec![]),
+        ours(vec!["-a", "--symbols", "--arrows", "--math"]),
+        Tool {
+            name: "iconv -c -t ascii//TRANSLIT".to_string(),
+            program: PathBuf::from("iconv"),
+            args: vec!["-c", "-t", "ascii//TRANSLIT"],
+        },
+        Tool {
+            name: "tr -cd '[:print:]\\n\\t'".to_string(),
+            program: PathBuf::from("tr"),
+            args: vec!["-cd", "[:print:]\n\t"],
+        },
+    ]
+}
+
+/// Pipe `corpus` through `tool` once, returning the time taken and the
+/// output.
+fn run_once(tool: &Tool, corpus: &[u8]) -> io::Result<(Duration, Vec<u8>)> {
+    let start = Instant::now();
+    let mut child = Command::new(&tool.program)
+        .args(&tool.args)
+        // keep the config file of the user out of the comparison
+        .env("XDG_CONFIG_HOME", "/nonexistent")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+    let mut stdin = child.stdin.take().unwrap();
+    let output = thread::scope(|scope| {
+        // write while reading, so that neither pipe fills up
+        let writer = scope.spawn(move || stdin.write_all(corpus));
+        let output = child.wait_with_output();
+        match writer.join().unwrap() {
+            // the tool may stop reading early
+            Err(e) if e.kind() != io::ErrorKind::BrokenPipe => Err(e),
+            _ => output,
+        }
+    })?;
+    Ok((start.elapsed(), output.stdout))
+}
+
+/// Pipe `corpus` through `tool` [`RUNS`] times, returning the fastest time
+/// and the output.
+fn run_tool(tool: &Tool, corpus: &[u8]) -> io::Result<(Duration, Vec<u8>)> {
+    let (mut best, output) = run_once(tool, corpus)?;
+    for _ in 1..RUNS {
+        best = best.min(run_once(tool, corpus)?.0);
+    }
+    Ok((best, output))
+}
+
+/// Return the number of lines in which `a` and `b` differ, and the number
+/// of lines of the longer.
+fn differing_lines(a: &[u8], b: &[u8]) -> (usize, usize) {
+    let a: Vec<&[u8]> = a.split(|&b| b == b'\n').collect();
+    let b: Vec<&[u8]> = b.split(|&b| b == b'\n').collect();
+    let n = a.len().max(b.len());
+    let differing = (0..n).filter(|&i| a.get(i) != b.get(i)).count();
+    (differing, n)
+}
+
+/// Load the corpus from `files`, exiting on failure.
+fn load(files: &[PathBuf]) -> Vec<u8> {
+    let mut corpus: Vec<u8> = Vec::new();
+    for file in files {
+        match fs::read(file) {
+            Ok(bytes) => corpus.extend(bytes),
+            Err(e) => {
+                eprintln!("ascii-filter: {}: {}", file.display(), e);
+                process::exit(exit::IO);
+            }
+        }
+    }
+    corpus
+}
+
+fn throughput(bytes: usize, time: Duration) -> f64 {
+    bytes as f64 / 1e6 / time.as_secs_f64().max(1e-9)
+}
+
+/// Run the corpus in `files` through the tools, and print their throughput
+/// and output sizes, and how many lines of their outputs differ from that
+/// of the first.
+pub(crate) fn run(files: &[PathBuf]) {
+    let corpus = load(files);
+    println!("corpus: {} bytes in {} files", corpus.len(), files.len());
+    println!(
+        "{:<44} {:>8} {:>10}  differing lines",
+        "tool", "MB/s", "output"
+    );
+    let mut reference: Option<(String, Vec<u8>)> = None;
+    for tool in tools() {
+        let (time, output) = match run_tool(&tool, &corpus) {
+            Ok(run) => run,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                println!("{:<44} not available", tool.name);
+                continue;
+            }
+            Err(e) => {
+                println!("{:<44} failed: {}", tool.name, e);
+                continue;
+            }
+        };
+        let differing = match &reference {
+            None => "(reference)".to_string(),
+            Some((name, expected)) => {
+                let (differing, lines) = differing_lines(expected, &output);
+                format!("{} of {} vs {}", differing, lines, name)
+            }
+        };
+        println!(
+            "{:<44} {:>8.2} {:>10}  {}",
+            tool.name,
+            throughput(corpus.len(), time),
+            output.len(),
+            differing
+        );
+        if reference.is_none() {
+            reference = Some((tool.name, output));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::differing_lines;
+
+    #[test]
+    fn test_differing_lines() {
+        assert_eq!(differing_lines(b"a\nb\nc", b"a\nb\nc"), (0, 3));
+        assert_eq!(differing_lines(b"a\nb\nc", b"a\nx\nc\nd"), (2, 4));
+        assert_eq!(differing_lines(b"", b""), (0, 1));
+    }
+}
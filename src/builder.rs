@@ -0,0 +1,609 @@
+//! A builder of [`Options`], and of the filters per them.
+
+use std::fmt;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use regex::Regex;
+
+#[cfg(feature = "unicode-category")]
+use crate::tables::GeneralCategory;
+#[cfg(feature = "unicode-script")]
+use crate::tables::Script;
+#[cfg(feature = "unicode-normalization")]
+use crate::Normalization;
+use crate::{
+    filter, CharSet, Checkpoint, Compression, ControlEscape, EmojiMode,
+    Encoding, Error, Escape, FilterReader, FilterWriter, Format, HardLinks,
+    Histogram, InvalidUtf8, LegacyCharset, Mapping, Newlines, Options,
+    Overlong, ReportFormat, Stats,
+};
+
+/// Configures a filter option by option, starting from the defaults of
+/// [`Options`], and creates the reader and writer adapters and the stream
+/// filter per them.
+#[derive(Debug, Clone, Default)]
+pub struct FilterBuilder {
+    opts: Options,
+    /// If not `None`, the callback of the adapters on each dropped char.
+    on_drop: Option<OnDrop>,
+}
+
+/// A callback shared by the adapters created by a builder.
+#[derive(Clone)]
+struct OnDrop(Arc<Mutex<dyn FnMut(char, usize) + Send>>);
+
+impl fmt::Debug for OnDrop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("OnDrop")
+    }
+}
+
+impl OnDrop {
+    /// Return a callback calling the shared one.
+    fn call(&self) -> impl FnMut(char, usize) + 'static {
+        let on_drop = Arc::clone(&self.0);
+        move |c, offset| (on_drop.lock().unwrap())(c, offset)
+    }
+}
+
+impl FilterBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// See [`Options::buf_size`].
+    pub fn buf_size(mut self, buf_size: usize) -> Self {
+        self.opts.buf_size = buf_size;
+        self
+    }
+
+    /// See [`Options::ascii_only`].
+    pub fn ascii_only(mut self, ascii_only: bool) -> Self {
+        self.opts.ascii_only = ascii_only;
+        self
+    }
+
+    /// See [`Options::allowed`].
+    pub fn allowed(mut self, allowed: CharSet) -> Self {
+        self.opts.allowed = Some(allowed);
+        self
+    }
+
+    /// See [`Options::keep_categories`].
+    #[cfg(feature = "unicode-category")]
+    pub fn keep_categories(mut self, categories: Vec<GeneralCategory>) -> Self {
+        self.opts.keep_categories = categories;
+        self
+    }
+
+    /// See [`Options::drop_categories`].
+    #[cfg(feature = "unicode-category")]
+    pub fn drop_categories(mut self, categories: Vec<GeneralCategory>) -> Self {
+        self.opts.drop_categories = categories;
+        self
+    }
+
+    /// See [`Options::keep_scripts`].
+    #[cfg(feature = "unicode-script")]
+    pub fn keep_scripts(mut self, scripts: Vec<Script>) -> Self {
+        self.opts.keep_scripts = scripts;
+        self
+    }
+
+    /// See [`Options::encodable_in`].
+    pub fn encodable_in(mut self, charset: LegacyCharset) -> Self {
+        self.opts.encodable_in = Some(charset);
+        self
+    }
+
+    /// See [`Options::keep_latin1`].
+    pub fn keep_latin1(mut self, keep_latin1: bool) -> Self {
+        self.opts.keep_latin1 = keep_latin1;
+        self
+    }
+
+    /// See [`Options::strip_invisible`].
+    pub fn strip_invisible(mut self, strip_invisible: bool) -> Self {
+        self.opts.strip_invisible = strip_invisible;
+        self
+    }
+
+    /// See [`Options::decompress`].
+    pub fn decompress(mut self, decompress: bool) -> Self {
+        self.opts.decompress = decompress;
+        self
+    }
+
+    /// See [`Options::compress`].
+    pub fn compress(mut self, compression: Compression) -> Self {
+        self.opts.compress = Some(compression);
+        self
+    }
+
+    /// See [`Options::from_encoding`].
+    pub fn from_encoding(mut self, from_encoding: Encoding) -> Self {
+        self.opts.from_encoding = Some(from_encoding);
+        self
+    }
+
+    /// See [`Options::to_encoding`].
+    pub fn to_encoding(mut self, to_encoding: Encoding) -> Self {
+        self.opts.to_encoding = Some(to_encoding);
+        self
+    }
+
+    /// See [`Options::invalid_utf8`].
+    pub fn invalid_utf8(mut self, policy: InvalidUtf8) -> Self {
+        self.opts.invalid_utf8 = policy;
+        self
+    }
+
+    /// See [`Options::keep_controls`].
+    pub fn keep_controls(mut self, controls: CharSet) -> Self {
+        self.opts.keep_controls = Some(controls);
+        self
+    }
+
+    /// See [`Options::keep_cr`].
+    pub fn keep_cr(mut self, keep_cr: bool) -> Self {
+        self.opts.keep_cr = keep_cr;
+        self
+    }
+
+    /// See [`Options::newlines`].
+    pub fn newlines(mut self, newlines: Newlines) -> Self {
+        self.opts.newlines = Some(newlines);
+        self
+    }
+
+    /// See [`Options::expand_tabs`].
+    pub fn expand_tabs(mut self, width: usize) -> Self {
+        self.opts.expand_tabs = Some(width);
+        self
+    }
+
+    /// See [`Options::max_line_length`].
+    pub fn max_line_length(mut self, max: usize) -> Self {
+        self.opts.max_line_length = Some(max);
+        self
+    }
+
+    /// See [`Options::overlong`].
+    pub fn overlong(mut self, overlong: Overlong) -> Self {
+        self.opts.overlong = overlong;
+        self
+    }
+
+    /// See [`Options::invert`].
+    pub fn invert(mut self, invert: bool) -> Self {
+        self.opts.invert = invert;
+        self
+    }
+
+    /// See [`Options::write_bom`].
+    pub fn write_bom(mut self, write_bom: bool) -> Self {
+        self.opts.write_bom = write_bom;
+        self
+    }
+
+    /// See [`Options::strip_bom`].
+    pub fn strip_bom(mut self, strip_bom: bool) -> Self {
+        self.opts.strip_bom = strip_bom;
+        self
+    }
+
+    /// See [`Options::out_record_sep`].
+    pub fn out_record_sep<S: Into<String>>(mut self, sep: S) -> Self {
+        self.opts.out_record_sep = Some(sep.into());
+        self
+    }
+
+    /// See [`Options::null_data`].
+    pub fn null_data(mut self, null_data: bool) -> Self {
+        self.opts.null_data = null_data;
+        self
+    }
+
+    /// See [`Options::format`].
+    pub fn format(mut self, format: Format) -> Self {
+        self.opts.format = format;
+        self
+    }
+
+    /// See [`Options::columns`].
+    pub fn columns<I: IntoIterator<Item = usize>>(
+        mut self,
+        columns: I,
+    ) -> Self {
+        self.opts.columns = Some(columns.into_iter().collect());
+        self
+    }
+
+    /// See [`Options::normalization`].
+    #[cfg(feature = "unicode-normalization")]
+    pub fn normalization(mut self, form: Normalization) -> Self {
+        self.opts.normalization = Some(form);
+        self
+    }
+
+    /// See [`Options::emoji`].
+    pub fn emoji(mut self, mode: EmojiMode) -> Self {
+        self.opts.emoji = Some(mode);
+        self
+    }
+
+    /// See [`Options::graphemes`].
+    #[cfg(feature = "unicode-segmentation")]
+    pub fn graphemes(mut self, graphemes: bool) -> Self {
+        self.opts.graphemes = graphemes;
+        self
+    }
+
+    /// See [`Options::strip_ansi`].
+    pub fn strip_ansi(mut self, strip_ansi: bool) -> Self {
+        self.opts.strip_ansi = strip_ansi;
+        self
+    }
+
+    /// See [`Options::drop_regex`].
+    pub fn drop_regex(mut self, re: Regex) -> Self {
+        self.opts.drop_regex = Some(re);
+        self
+    }
+
+    /// See [`Options::keep_regex`].
+    pub fn keep_regex(mut self, re: Regex) -> Self {
+        self.opts.keep_regex = Some(re);
+        self
+    }
+
+    /// See [`Options::punycode`].
+    pub fn punycode(mut self, punycode: bool) -> Self {
+        self.opts.punycode = punycode;
+        self
+    }
+
+    /// Consult `mapping` after those added before it, see
+    /// [`Options::mappings`].
+    pub fn mapping(mut self, mapping: Mapping) -> Self {
+        self.opts.mappings.push(mapping);
+        self
+    }
+
+    /// See [`Options::mappings`].
+    pub fn mappings(mut self, mappings: Vec<Mapping>) -> Self {
+        self.opts.mappings = mappings;
+        self
+    }
+
+    /// See [`Options::strip_diacritics`].
+    #[cfg(feature = "unicode-normalization")]
+    pub fn strip_diacritics(mut self, strip_diacritics: bool) -> Self {
+        self.opts.strip_diacritics = strip_diacritics;
+        self
+    }
+
+    /// See [`Options::replacement`].
+    pub fn replacement<S: Into<String>>(mut self, replacement: S) -> Self {
+        self.opts.replacement = Some(replacement.into());
+        self
+    }
+
+    /// See [`Options::squeeze_replacements`].
+    pub fn squeeze_replacements(mut self, max: usize) -> Self {
+        self.opts.squeeze_replacements = Some(max);
+        self
+    }
+
+    /// See [`Options::word_sep`].
+    pub fn word_sep<S: Into<String>>(mut self, separator: S) -> Self {
+        self.opts.word_sep = Some(separator.into());
+        self
+    }
+
+    /// See [`Options::collapse`].
+    pub fn collapse<S: Into<String>>(mut self, separator: S) -> Self {
+        self.opts.collapse = Some(separator.into());
+        self
+    }
+
+    /// See [`Options::drop_lines`].
+    pub fn drop_lines(mut self, drop_lines: bool) -> Self {
+        self.opts.drop_lines = drop_lines;
+        self
+    }
+
+    /// See [`Options::line_numbers`].
+    pub fn line_numbers(mut self, line_numbers: bool) -> Self {
+        self.opts.line_numbers = line_numbers;
+        self
+    }
+
+    /// See [`Options::highlight`].
+    pub fn highlight(mut self, highlight: bool) -> Self {
+        self.opts.highlight = highlight;
+        self
+    }
+
+    /// See [`Options::escape`].
+    pub fn escape(mut self, escape: Escape) -> Self {
+        self.opts.escape = Some(escape);
+        self
+    }
+
+    /// See [`Options::control_escape`].
+    pub fn control_escape(mut self, escape: ControlEscape) -> Self {
+        self.opts.control_escape = Some(escape);
+        self
+    }
+
+    /// See [`Options::verify`].
+    pub fn verify(mut self, verify: bool) -> Self {
+        self.opts.verify = verify;
+        self
+    }
+
+    /// See [`Options::check_idempotent`].
+    pub fn check_idempotent(mut self, check_idempotent: bool) -> Self {
+        self.opts.check_idempotent = check_idempotent;
+        self
+    }
+
+    /// See [`Options::assert_clean`].
+    pub fn assert_clean(mut self, assert_clean: bool) -> Self {
+        self.opts.assert_clean = assert_clean;
+        self
+    }
+
+    /// See [`Options::strict`].
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.opts.strict = strict;
+        self
+    }
+
+    /// See [`Options::rejects`].
+    pub fn rejects<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.opts.rejects = Some(path.into());
+        self
+    }
+
+    /// See [`Options::reject_positions`].
+    pub fn reject_positions(mut self, reject_positions: bool) -> Self {
+        self.opts.reject_positions = reject_positions;
+        self
+    }
+
+    /// See [`Options::rejects_hex`].
+    pub fn rejects_hex<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.opts.rejects_hex = Some(path.into());
+        self
+    }
+
+    /// See [`Options::report`].
+    pub fn report(mut self, format: ReportFormat) -> Self {
+        self.opts.report = Some(format);
+        self
+    }
+
+    /// See [`Options::report_output`].
+    pub fn report_output<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.opts.report_output = Some(path.into());
+        self
+    }
+
+    /// See [`Options::undo_log`].
+    pub fn undo_log<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.opts.undo_log = Some(path.into());
+        self
+    }
+
+    /// See [`Options::source_map`].
+    pub fn source_map<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.opts.source_map = Some(path.into());
+        self
+    }
+
+    /// See [`Options::histogram`].
+    pub fn histogram(mut self, histogram: Arc<Mutex<Histogram>>) -> Self {
+        self.opts.histogram = Some(histogram);
+        self
+    }
+
+    /// See [`Options::resume_state`].
+    pub fn resume_state<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.opts.resume_state = Some(path.into());
+        self
+    }
+
+    /// See [`Options::checkpoint`].
+    pub fn checkpoint(mut self, checkpoint: Checkpoint) -> Self {
+        self.opts.checkpoint = Some(checkpoint);
+        self
+    }
+
+    /// See [`Options::clean_output`].
+    pub fn clean_output<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.opts.clean_output = Some(path.into());
+        self
+    }
+
+    /// See [`Options::dirty_output`].
+    pub fn dirty_output<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.opts.dirty_output = Some(path.into());
+        self
+    }
+
+    /// See [`Options::skip_binary`].
+    pub fn skip_binary(mut self, skip_binary: bool) -> Self {
+        self.opts.skip_binary = skip_binary;
+        self
+    }
+
+    /// See [`Options::ignore`].
+    pub fn ignore(mut self, ignore: bool) -> Self {
+        self.opts.ignore = ignore;
+        self
+    }
+
+    /// See [`Options::hidden`].
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.opts.hidden = hidden;
+        self
+    }
+
+    /// See [`Options::follow_symlinks`].
+    pub fn follow_symlinks(mut self, follow_symlinks: bool) -> Self {
+        self.opts.follow_symlinks = follow_symlinks;
+        self
+    }
+
+    /// See [`Options::hard_links`].
+    pub fn hard_links(mut self, hard_links: HardLinks) -> Self {
+        self.opts.hard_links = hard_links;
+        self
+    }
+
+    /// See [`Options::output`].
+    pub fn output<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.opts.output = Some(path.into());
+        self
+    }
+
+    /// See [`Options::threads`].
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.opts.threads = Some(threads);
+        self
+    }
+
+    /// See [`Options::output_fd`].
+    pub fn output_fd(mut self, fd: i32) -> Self {
+        self.opts.output_fd = Some(fd);
+        self
+    }
+
+    /// See [`Options::timeout`].
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.opts.timeout = Some(timeout);
+        self
+    }
+
+    /// See [`Options::line_buffered`].
+    pub fn line_buffered(mut self, line_buffered: bool) -> Self {
+        self.opts.line_buffered = line_buffered;
+        self
+    }
+
+    /// See [`Options::follow`].
+    pub fn follow(mut self, follow: bool) -> Self {
+        self.opts.follow = follow;
+        self
+    }
+
+    /// Call `on_drop(c, offset)` for each char `c` dropped or replaced at
+    /// input `offset` by the reader and writer adapters created, see
+    /// [`FilterWriter::with_on_drop`]. Does not apply to
+    /// [`FilterBuilder::filter`].
+    pub fn on_drop<F: FnMut(char, usize) + Send + 'static>(
+        mut self,
+        on_drop: F,
+    ) -> Self {
+        self.on_drop = Some(OnDrop(Arc::new(Mutex::new(on_drop))));
+        self
+    }
+
+    /// Return the options configured.
+    pub fn build(self) -> Options {
+        self.opts
+    }
+
+    /// Create the [`FilterReader`] of `inner` per the options.
+    pub fn reader<R: Read>(&self, inner: R) -> FilterReader<R> {
+        let reader = FilterReader::new(inner, &self.opts);
+        match &self.on_drop {
+            Some(on_drop) => reader.with_on_drop(on_drop.call()),
+            None => reader,
+        }
+    }
+
+    /// Create the [`FilterWriter`] to `backend` per the options.
+    pub fn writer<W: Write>(&self, backend: W) -> FilterWriter<W> {
+        let writer = FilterWriter::from_options(backend, &self.opts);
+        match &self.on_drop {
+            Some(on_drop) => writer.with_on_drop(on_drop.call()),
+            None => writer,
+        }
+    }
+
+    /// Filter `reader` to `writer` per the options, see [`filter`].
+    pub fn filter<R: Read, W: Write>(
+        &self,
+        reader: R,
+        writer: W,
+    ) -> Result<Stats, Error> {
+        filter(reader, writer, &self.opts)
+    }
+}
+
+impl From<Options> for FilterBuilder {
+    fn from(opts: Options) -> Self {
+        Self {
+            opts,
+            on_drop: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FilterBuilder;
+    use std::io::{Read, Write};
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_filter_builder() {
+        let builder = FilterBuilder::new()
+            .buf_size(16)
+            .ascii_only(true)
+            .out_record_sep("\0");
+        let opts = builder.clone().build();
+        assert_eq!(opts.buf_size, 16);
+        assert!(opts.ascii_only);
+
+        let input = "héllo\nwörld\n".as_bytes();
+        let expected = b"hllo\0wrld\0";
+        let mut out: Vec<u8> = Vec::new();
+        builder.filter(input, &mut out).unwrap();
+        assert_eq!(out, expected);
+
+        let mut out: Vec<u8> = Vec::new();
+        builder.reader(input).read_to_end(&mut out).unwrap();
+        assert_eq!(out, expected);
+
+        let mut w = builder.writer(Vec::new());
+        w.write_all(input).unwrap();
+        assert_eq!(w.into_inner(), expected);
+    }
+
+    #[test]
+    fn test_filter_builder_on_drop() {
+        let dropped = Arc::new(Mutex::new(Vec::new()));
+        let dropped_clone = Arc::clone(&dropped);
+        let builder =
+            FilterBuilder::new()
+                .ascii_only(true)
+                .on_drop(move |c, offset| {
+                    dropped_clone.lock().unwrap().push((c, offset))
+                });
+        let input = "héllo\nwörld\n".as_bytes();
+        let mut w = builder.writer(Vec::new());
+        w.write_all(input).unwrap();
+        builder.reader(input).read_to_end(&mut Vec::new()).unwrap();
+        assert_eq!(
+            *dropped.lock().unwrap(),
+            vec![('é', 1), ('ö', 8), ('é', 1), ('ö', 8)]
+        );
+    }
+}
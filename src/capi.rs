@@ -0,0 +1,321 @@
+//! The C interface, behind the `capi` feature, declared in
+//! `include/ascii_filter.h`. A builder is configured option by option with
+//! the long names and values of the command line, and creates filters, each
+//! fed the input in chunks of any bytes and finished at its end, e.g.
+//!
+//! ```c
+//! AsciiFilterBuilder *builder = ascii_filter_builder_new();
+//! ascii_filter_builder_set(builder, "replace", "?");
+//! AsciiFilter *filter = ascii_filter_new(builder);
+//! ascii_filter_builder_free(builder);
+//! ascii_filter_feed(filter, buf, len);
+//! out = ascii_filter_output(filter, &out_len);
+//! ...
+//! ascii_filter_finish(filter);
+//! out = ascii_filter_output(filter, &out_len);
+//! ascii_filter_free(filter);
+//! ```
+//!
+//! The functions returning an `int` return 0 on success, and -1 on failure,
+//! after which [`ascii_filter_last_error`] describes it.
+
+use std::cell::RefCell;
+use std::ffi::{c_char, c_int, CStr, CString};
+use std::io::Write;
+use std::ptr;
+
+#[cfg(feature = "unicode-normalization")]
+use crate::Normalization;
+use crate::{
+    CharSet, ControlEscape, EmojiMode, Escape, FilterBuilder, FilterWriter,
+    InvalidUtf8, LegacyCharset, Newlines, Overlong,
+};
+
+thread_local! {
+    /// The description of the last failure on this thread.
+    static LAST_ERROR: RefCell<CString> = RefCell::new(CString::default());
+}
+
+/// Record the failure `e` and return -1.
+fn fail(e: impl ToString) -> c_int {
+    let e = CString::new(e.to_string().replace('\0', "\\0")).unwrap();
+    LAST_ERROR.with(|last| *last.borrow_mut() = e);
+    -1
+}
+
+/// A builder of filters, see [`FilterBuilder`].
+pub struct AsciiFilterBuilder(FilterBuilder);
+
+/// A filter of a stream, holding the output of the last feed or finish.
+pub struct AsciiFilter {
+    writer: FilterWriter<Vec<u8>>,
+    output: Vec<u8>,
+}
+
+impl AsciiFilter {
+    /// Make the output of `f` the output of the filter, replacing the last.
+    fn run(
+        &mut self,
+        f: impl FnOnce(&mut FilterWriter<Vec<u8>>) -> std::io::Result<()>,
+    ) -> c_int {
+        self.output.clear();
+        let result = f(&mut self.writer);
+        std::mem::swap(&mut self.output, self.writer.get_mut());
+        match result {
+            Ok(()) => 0,
+            Err(e) => fail(e),
+        }
+    }
+}
+
+/// Return the description of the last failure on this thread, valid until
+/// the next failure, or an empty string if none.
+#[no_mangle]
+pub extern "C" fn ascii_filter_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ptr())
+}
+
+/// Return a builder with the default options, to be freed with
+/// [`ascii_filter_builder_free`].
+#[no_mangle]
+pub extern "C" fn ascii_filter_builder_new() -> *mut AsciiFilterBuilder {
+    Box::into_raw(Box::new(AsciiFilterBuilder(FilterBuilder::new())))
+}
+
+/// Free `builder`, if not null.
+///
+/// # Safety
+///
+/// `builder` is null or returned by [`ascii_filter_builder_new`], and not
+/// freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn ascii_filter_builder_free(
+    builder: *mut AsciiFilterBuilder,
+) {
+    if !builder.is_null() {
+        drop(Box::from_raw(builder));
+    }
+}
+
+/// Parse `value` for the option `key`.
+fn parse<T: std::str::FromStr>(key: &str, value: &str) -> Result<T, String>
+where
+    T::Err: ToString,
+{
+    value
+        .parse()
+        .map_err(|e: T::Err| format!("{}: {}", key, e.to_string()))
+}
+
+/// Return `builder` with the option `key` set to `value`.
+fn set(
+    builder: FilterBuilder,
+    key: &str,
+    value: &str,
+) -> Result<FilterBuilder, String> {
+    Ok(match key {
+        "ascii-only" => builder.ascii_only(parse(key, value)?),
+        "allow" => builder.allowed(parse::<CharSet>(key, value)?),
+        "keep-latin1" => builder.keep_latin1(parse(key, value)?),
+        "encodable-in" => {
+            builder.encodable_in(parse::<LegacyCharset>(key, value)?)
+        }
+        "strip-invisible" => builder.strip_invisible(parse(key, value)?),
+        "strip-ansi" => builder.strip_ansi(parse(key, value)?),
+        "emoji" => builder.emoji(parse::<EmojiMode>(key, value)?),
+        #[cfg(feature = "unicode-segmentation")]
+        "graphemes" => builder.graphemes(parse(key, value)?),
+        "drop-regex" => builder.drop_regex(parse(key, value)?),
+        "keep-regex" => builder.keep_regex(parse(key, value)?),
+        "punycode" => builder.punycode(parse(key, value)?),
+        "invalid-utf8" => {
+            builder.invalid_utf8(parse::<InvalidUtf8>(key, value)?)
+        }
+        "keep-cr" => builder.keep_cr(parse(key, value)?),
+        "keep-controls" => builder.keep_controls(
+            CharSet::parse_controls(value)
+                .map_err(|e| format!("{}: {}", key, e))?,
+        ),
+        "newlines" => builder.newlines(parse::<Newlines>(key, value)?),
+        "expand-tabs" => builder.expand_tabs(parse(key, value)?),
+        "max-line-length" => builder.max_line_length(parse(key, value)?),
+        "overlong" => builder.overlong(parse::<Overlong>(key, value)?),
+        "invert" => builder.invert(parse(key, value)?),
+        "strict" => builder.strict(parse(key, value)?),
+        "strip-bom" => builder.strip_bom(parse(key, value)?),
+        #[cfg(feature = "unicode-normalization")]
+        "normalize" => {
+            builder.normalization(parse::<Normalization>(key, value)?)
+        }
+        #[cfg(feature = "unicode-normalization")]
+        "strip-diacritics" => builder.strip_diacritics(parse(key, value)?),
+        "replace" => builder.replacement(value),
+        "collapse" => builder.collapse(value),
+        "squeeze-replacements" => {
+            builder.squeeze_replacements(parse(key, value)?)
+        }
+        "word-sep" => builder.word_sep(value),
+        "drop-lines" => builder.drop_lines(parse(key, value)?),
+        "line-numbers" => builder.line_numbers(parse(key, value)?),
+        "escape" => builder.escape(parse::<Escape>(key, value)?),
+        "escape-controls" => {
+            builder.control_escape(parse::<ControlEscape>(key, value)?)
+        }
+        key => return Err(format!("unknown option {:?}", key)),
+    })
+}
+
+/// Set the option `key` of `builder` to `value`, both named as on the
+/// command line, e.g. "replace" and "?", or "strip-ansi" and "true".
+///
+/// # Safety
+///
+/// `builder` is returned by [`ascii_filter_builder_new`], and `key` and
+/// `value` are nul-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn ascii_filter_builder_set(
+    builder: *mut AsciiFilterBuilder,
+    key: *const c_char,
+    value: *const c_char,
+) -> c_int {
+    let builder = &mut (*builder).0;
+    let (key, value) =
+        match (CStr::from_ptr(key).to_str(), CStr::from_ptr(value).to_str()) {
+            (Ok(key), Ok(value)) => (key, value),
+            _ => return fail("invalid utf-8 in option"),
+        };
+    match set(builder.clone(), key, value) {
+        Ok(configured) => {
+            *builder = configured;
+            0
+        }
+        Err(e) => fail(e),
+    }
+}
+
+/// Return a filter per the options of `builder`, to be freed with
+/// [`ascii_filter_free`].
+///
+/// # Safety
+///
+/// `builder` is returned by [`ascii_filter_builder_new`].
+#[no_mangle]
+pub unsafe extern "C" fn ascii_filter_new(
+    builder: *const AsciiFilterBuilder,
+) -> *mut AsciiFilter {
+    Box::into_raw(Box::new(AsciiFilter {
+        writer: (*builder).0.writer(Vec::new()),
+        output: Vec::new(),
+    }))
+}
+
+/// Free `filter`, if not null.
+///
+/// # Safety
+///
+/// `filter` is null or returned by [`ascii_filter_new`], and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn ascii_filter_free(filter: *mut AsciiFilter) {
+    if !filter.is_null() {
+        drop(Box::from_raw(filter));
+    }
+}
+
+/// Filter the `len` bytes at `buf`, the next of the input of `filter`.
+///
+/// # Safety
+///
+/// `filter` is returned by [`ascii_filter_new`], and `buf` points to `len`
+/// bytes, or is null if `len` is 0.
+#[no_mangle]
+pub unsafe extern "C" fn ascii_filter_feed(
+    filter: *mut AsciiFilter,
+    buf: *const u8,
+    len: usize,
+) -> c_int {
+    let input = match len {
+        0 => &[][..],
+        len => std::slice::from_raw_parts(buf, len),
+    };
+    (*filter).run(|writer| writer.write_all(input))
+}
+
+/// End the input of `filter`, see [`FilterWriter::finish`].
+///
+/// # Safety
+///
+/// `filter` is returned by [`ascii_filter_new`].
+#[no_mangle]
+pub unsafe extern "C" fn ascii_filter_finish(
+    filter: *mut AsciiFilter,
+) -> c_int {
+    (*filter).run(|writer| writer.finish())
+}
+
+/// Return the output of the last [`ascii_filter_feed`] or
+/// [`ascii_filter_finish`] of `filter`, valid until the next, and store its
+/// length in `len`.
+///
+/// # Safety
+///
+/// `filter` is returned by [`ascii_filter_new`], and `len` is valid for
+/// writes.
+#[no_mangle]
+pub unsafe extern "C" fn ascii_filter_output(
+    filter: *const AsciiFilter,
+    len: *mut usize,
+) -> *const u8 {
+    let output = &(*filter).output;
+    *len = output.len();
+    if output.is_empty() {
+        ptr::null()
+    } else {
+        output.as_ptr()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Return the output of the last call on `filter`.
+    unsafe fn output(filter: *const AsciiFilter) -> Vec<u8> {
+        let mut len = 0;
+        let out = ascii_filter_output(filter, &mut len);
+        match len {
+            0 => Vec::new(),
+            len => std::slice::from_raw_parts(out, len).to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_capi() {
+        unsafe {
+            let builder = ascii_filter_builder_new();
+            let set = |key: &CStr, value: &CStr| {
+                ascii_filter_builder_set(builder, key.as_ptr(), value.as_ptr())
+            };
+            assert_eq!(set(c"ascii-only", c"true"), 0);
+            assert_eq!(set(c"replace", c"?"), 0);
+            assert_eq!(set(c"strip-ansi", c"true"), 0);
+            assert_eq!(set(c"strip-ansi", c"yes"), -1);
+            assert_eq!(set(c"no-such-option", c"1"), -1);
+            let e = CStr::from_ptr(ascii_filter_last_error());
+            assert_eq!(e.to_str(), Ok("unknown option \"no-such-option\""));
+            let filter = ascii_filter_new(builder);
+            ascii_filter_builder_free(builder);
+
+            let input = "a\x1b[1mb\u{4F60}c".as_bytes();
+            assert_eq!(ascii_filter_feed(filter, input.as_ptr(), 7), 0);
+            assert_eq!(output(filter), b"ab");
+            let rest = &input[7..];
+            assert_eq!(ascii_filter_feed(filter, rest.as_ptr(), 3), 0);
+            assert_eq!(output(filter), b"?c");
+            assert_eq!(ascii_filter_feed(filter, ptr::null(), 0), 0);
+            assert_eq!(output(filter), b"");
+            assert_eq!(ascii_filter_finish(filter), 0);
+            assert_eq!(output(filter), b"");
+            ascii_filter_free(filter);
+        }
+    }
+}
@@ -0,0 +1,439 @@
+//! The logs of the changes of the filtering to the text, keeping track of
+//! the input and the output offsets:
+//!
+//! - The undo log, from which the original is restored from the output,
+//!   e.g. to audit or revert a sanitization. Each line `OFFSET LEN HEX` is a
+//!   change at byte `OFFSET` of the output, whose `LEN` bytes, none if
+//!   removed, stand for the original bytes in hex, e.g. `5 1 c3a9` for é
+//!   replaced with e at offset 5. The changes are in order, adjacent ones
+//!   merged.
+//! - The source map, from which the offsets of the output are translated to
+//!   the input, e.g. for the positions reported by the tools downstream.
+//!   Each line is a segment of the output in order, `N` for `N` bytes output
+//!   as they are input, and `OUT IN` for `OUT` bytes, none if removed,
+//!   standing for `IN` bytes of the input, e.g. `2`, `1 2` and `2` for naïve
+//!   filtered to naive. Removals merge into the change before them.
+
+use std::collections::VecDeque;
+use std::io::{self, BufRead, Read, Write};
+
+/// A change to the text: at output `offset`, `len` bytes for `original`.
+struct Change {
+    offset: u64,
+    len: u64,
+    original: Vec<u8>,
+}
+
+/// The log written by a [`ChangeLog`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Form {
+    Undo,
+    SourceMap,
+}
+
+/// Writes the undo log or the source map of the text filtered, keeping
+/// track of the input and the output offsets across pieces.
+pub(crate) struct ChangeLog {
+    out: Box<dyn Write + Send>,
+    form: Form,
+    /// The input offset of the text filtered next.
+    input: u64,
+    /// The output offset of the text filtered next.
+    offset: u64,
+    /// The bytes output as they are input since the change recorded last,
+    /// not yet written to a source map.
+    kept: u64,
+    /// The change recorded last, held back to merge with the next if
+    /// adjacent.
+    held: Option<Change>,
+    /// The invalid bytes dropped at their input offsets ahead of the text
+    /// before them, which the regex rules hold back to the end of its line.
+    dropped: VecDeque<(u64, Vec<u8>)>,
+    /// The lines of the changes not yet written.
+    lines: Vec<u8>,
+}
+
+impl ChangeLog {
+    fn new(out: Box<dyn Write + Send>, form: Form) -> Self {
+        Self {
+            out,
+            form,
+            input: 0,
+            offset: 0,
+            kept: 0,
+            held: None,
+            dropped: VecDeque::new(),
+            lines: Vec::new(),
+        }
+    }
+
+    /// Return a writer of the undo log to `out`.
+    pub(crate) fn undo(out: Box<dyn Write + Send>) -> Self {
+        Self::new(out, Form::Undo)
+    }
+
+    /// Return a writer of the source map to `out`.
+    pub(crate) fn source_map(out: Box<dyn Write + Send>) -> Self {
+        Self::new(out, Form::SourceMap)
+    }
+
+    /// Advance the offsets over the `len` bytes output as they are input.
+    pub(crate) fn advance(&mut self, len: usize) {
+        if len == 0 {
+            return;
+        }
+        let end = self.input + len as u64;
+        self.record_dropped(end - 1);
+        self.keep(end - self.input);
+    }
+
+    /// Record the `original` bytes output as `len` bytes, none if removed.
+    pub(crate) fn change(
+        &mut self,
+        original: &[u8],
+        len: usize,
+    ) -> io::Result<()> {
+        self.record_dropped(self.input);
+        self.record(original, len);
+        self.write_lines()
+    }
+
+    /// Record the invalid `bytes` dropped at input `offset`.
+    pub(crate) fn drop_invalid(
+        &mut self,
+        bytes: &[u8],
+        offset: u64,
+    ) -> io::Result<()> {
+        self.dropped.push_back((offset, bytes.to_vec()));
+        self.record_dropped(self.input);
+        self.write_lines()
+    }
+
+    /// Record the invalid bytes dropped up to input offset `last`.
+    fn record_dropped(&mut self, last: u64) {
+        while let Some(&(at, _)) = self.dropped.front() {
+            if at > last {
+                break;
+            }
+            let (at, bytes) = self.dropped.pop_front().unwrap();
+            self.keep(at - self.input);
+            self.record(&bytes, 0);
+        }
+    }
+
+    /// Advance the offsets over the `len` bytes kept.
+    fn keep(&mut self, len: u64) {
+        if len == 0 {
+            return;
+        }
+        self.write_held();
+        self.kept += len;
+        self.input += len;
+        self.offset += len;
+    }
+
+    fn record(&mut self, original: &[u8], len: usize) {
+        match &mut self.held {
+            // Replacements in a source map stay apart, to map each of them
+            // to its own input.
+            Some(held) if self.form == Form::Undo || len == 0 => {
+                held.len += len as u64;
+                held.original.extend_from_slice(original);
+            }
+            _ => {
+                self.write_held();
+                self.write_kept();
+                self.held = Some(Change {
+                    offset: self.offset,
+                    len: len as u64,
+                    original: original.to_vec(),
+                });
+            }
+        }
+        self.input += original.len() as u64;
+        self.offset += len as u64;
+    }
+
+    fn write_held(&mut self) {
+        let Some(change) = self.held.take() else {
+            return;
+        };
+        match self.form {
+            Form::Undo => {
+                let line = format!("{} {} ", change.offset, change.len);
+                self.lines.extend_from_slice(line.as_bytes());
+                for b in &change.original {
+                    self.lines
+                        .extend_from_slice(format!("{:02x}", b).as_bytes());
+                }
+                self.lines.push(b'\n');
+            }
+            Form::SourceMap => {
+                let line =
+                    format!("{} {}\n", change.len, change.original.len());
+                self.lines.extend_from_slice(line.as_bytes());
+            }
+        }
+    }
+
+    fn write_kept(&mut self) {
+        let kept = std::mem::take(&mut self.kept);
+        if self.form == Form::SourceMap && kept > 0 {
+            self.lines
+                .extend_from_slice(format!("{}\n", kept).as_bytes());
+        }
+    }
+
+    fn write_lines(&mut self) -> io::Result<()> {
+        let lines = std::mem::take(&mut self.lines);
+        self.out.write_all(&lines)
+    }
+
+    pub(crate) fn flush(&mut self) -> io::Result<()> {
+        self.record_dropped(u64::MAX);
+        self.write_held();
+        self.write_kept();
+        self.write_lines()?;
+        self.out.flush()
+    }
+}
+
+fn invalid(log: &str, line: usize, message: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{} line {}: {}", log, line, message),
+    )
+}
+
+/// Parse a line of the undo log, the `n`th from 1.
+fn parse_change(line: &str, n: usize) -> io::Result<Change> {
+    let mut fields = line.split(' ');
+    let mut number = |name: &str| {
+        fields
+            .next()
+            .and_then(|field| field.parse::<u64>().ok())
+            .ok_or_else(|| invalid("undo log", n, &format!("invalid {}", name)))
+    };
+    let offset = number("offset")?;
+    let len = number("length")?;
+    let hex = fields.next().unwrap_or_default();
+    if fields.next().is_some() || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(invalid("undo log", n, "invalid original bytes"));
+    }
+    let original = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect::<Option<Vec<u8>>>()
+        .ok_or_else(|| invalid("undo log", n, "invalid original bytes"))?;
+    Ok(Change {
+        offset,
+        len,
+        original,
+    })
+}
+
+/// Copy `len` bytes of `r` to `w`, failing with
+/// [`io::ErrorKind::UnexpectedEof`] if `r` ends first.
+fn copy_exact<R: Read, W: Write>(
+    r: &mut R,
+    w: &mut W,
+    len: u64,
+) -> io::Result<()> {
+    if io::copy(&mut r.take(len), w)? < len {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "the filtered text ends before the changes of the undo log",
+        ));
+    }
+    Ok(())
+}
+
+/// Write the original of the `filtered` text to `out`, undoing the changes
+/// of its undo `log`, as written with `--undo-log`. Fails with
+/// [`io::ErrorKind::InvalidData`] on a line of the log which is invalid, or
+/// out of order, and with [`io::ErrorKind::UnexpectedEof`] if the text ends
+/// before a change.
+pub fn restore<R: Read, L: BufRead, W: Write>(
+    mut filtered: R,
+    log: L,
+    mut out: W,
+) -> io::Result<()> {
+    // the output offset of the rest of `filtered`
+    let mut offset: u64 = 0;
+    for (i, line) in log.lines().enumerate() {
+        let change = parse_change(&line?, i + 1)?;
+        let Some(kept) = change.offset.checked_sub(offset) else {
+            return Err(invalid("undo log", i + 1, "change out of order"));
+        };
+        copy_exact(&mut filtered, &mut out, kept)?;
+        copy_exact(&mut filtered, &mut io::sink(), change.len)?;
+        out.write_all(&change.original)?;
+        offset = change.offset + change.len;
+    }
+    io::copy(&mut filtered, &mut out)?;
+    out.flush()
+}
+
+/// A segment of the output in a [`SourceMap`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Segment {
+    /// The output offset of the segment.
+    offset: u64,
+    /// The input offset of the segment.
+    input: u64,
+    /// The length of the segment in the output.
+    len: u64,
+    /// If true, the segment is output as it is input.
+    kept: bool,
+}
+
+/// The mapping of the offsets of a filtered text to its input, as written
+/// with `--source-map`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceMap {
+    /// The segments of the output, of which none is empty.
+    segments: Vec<Segment>,
+    /// The length of the output.
+    len: u64,
+    /// The length of the input.
+    input_len: u64,
+}
+
+impl SourceMap {
+    /// Read a source map from `r`. Fails with
+    /// [`io::ErrorKind::InvalidData`] on a line which is invalid.
+    pub fn read<R: BufRead>(r: R) -> io::Result<Self> {
+        let mut map = Self::default();
+        for (i, line) in r.lines().enumerate() {
+            let line = line?;
+            let mut fields = line.split(' ').map(|field| field.parse::<u64>());
+            let (len, input_len, kept) =
+                match (fields.next(), fields.next(), fields.next()) {
+                    (Some(Ok(len)), None, None) => (len, len, true),
+                    (Some(Ok(len)), Some(Ok(input_len)), None) => {
+                        (len, input_len, false)
+                    }
+                    _ => {
+                        return Err(invalid(
+                            "source map",
+                            i + 1,
+                            "invalid segment",
+                        ))
+                    }
+                };
+            if len > 0 {
+                map.segments.push(Segment {
+                    offset: map.len,
+                    input: map.input_len,
+                    len,
+                    kept,
+                });
+            }
+            map.len += len;
+            map.input_len += input_len;
+        }
+        Ok(map)
+    }
+
+    /// Return the input offset of the byte at output `offset`: as far into
+    /// the input of its segment if output as it is input, and else the start
+    /// of the input of its segment. Offsets past the output map as far past
+    /// the input.
+    pub fn input_offset(&self, offset: u64) -> u64 {
+        let i = self
+            .segments
+            .partition_point(|segment| segment.offset + segment.len <= offset);
+        match self.segments.get(i) {
+            Some(segment) if !segment.kept => segment.input,
+            Some(segment) => segment.input + (offset - segment.offset),
+            None => self.input_len + (offset - self.len),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{restore, ChangeLog, SourceMap};
+    use std::io::{self, Write};
+    use std::sync::{Arc, Mutex};
+
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_undo_log() {
+        // ab“é”c and an invalid byte filtered to ab"e"c, the invalid byte
+        // ahead of the c before it
+        let out = Arc::new(Mutex::new(Vec::new()));
+        let mut log = ChangeLog::undo(Box::new(Shared(out.clone())));
+        log.advance(2);
+        log.change("“".as_bytes(), 1).unwrap();
+        log.change("é".as_bytes(), 1).unwrap();
+        log.change("”".as_bytes(), 1).unwrap();
+        log.drop_invalid(b"\xff", 11).unwrap();
+        log.advance(1);
+        log.flush().unwrap();
+        let log = std::mem::take(&mut *out.lock().unwrap());
+        assert_eq!(
+            String::from_utf8(log.clone()).unwrap(),
+            "2 3 e2809cc3a9e2809d\n6 0 ff\n"
+        );
+        let mut original = Vec::new();
+        restore(&b"ab\"e\"c"[..], log.as_slice(), &mut original).unwrap();
+        assert_eq!(original, ["ab“é”c".as_bytes(), b"\xff"].concat());
+    }
+
+    #[test]
+    fn test_source_map() {
+        let out = Arc::new(Mutex::new(Vec::new()));
+        let mut map = ChangeLog::source_map(Box::new(Shared(out.clone())));
+        map.advance(2);
+        map.change("“".as_bytes(), 1).unwrap();
+        map.change("é".as_bytes(), 1).unwrap();
+        map.change("”".as_bytes(), 1).unwrap();
+        map.drop_invalid(b"\xff", 11).unwrap();
+        map.advance(1);
+        map.flush().unwrap();
+        let map = std::mem::take(&mut *out.lock().unwrap());
+        assert_eq!(
+            String::from_utf8(map.clone()).unwrap(),
+            "2\n1 3\n1 2\n1 3\n1\n0 1\n"
+        );
+        let map = SourceMap::read(map.as_slice()).unwrap();
+        // ab"e"c
+        let offsets: Vec<u64> = (0..7).map(|i| map.input_offset(i)).collect();
+        assert_eq!(offsets, [0, 1, 2, 5, 7, 10, 12]);
+        let e = SourceMap::read(&b"2\n1 x\n"[..]).unwrap_err();
+        assert_eq!(e.to_string(), "source map line 2: invalid segment");
+    }
+
+    #[test]
+    fn test_restore_invalid() {
+        let restored = |log: &str| {
+            restore(&b"abc"[..], log.as_bytes(), &mut Vec::new()).unwrap_err()
+        };
+        assert_eq!(
+            restored("1 0 41\n0 0 42\n").to_string(),
+            "undo log line 2: change out of order"
+        );
+        assert_eq!(
+            restored("1 x 41\n").to_string(),
+            "undo log line 1: invalid length"
+        );
+        assert_eq!(
+            restored("1 0 4\n").to_string(),
+            "undo log line 1: invalid original bytes"
+        );
+        let e = restored("2 2 41\n");
+        assert_eq!(e.kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
@@ -0,0 +1,492 @@
+//! The decision of which chars the filter keeps, and the user-defined sets
+//! of chars kept in place of the ASCII subset.
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use ascii_filter_core::class::{
+    ascii_subset_run, is_ascii_subset, is_invisible, is_latin1_printable,
+};
+
+#[cfg(feature = "unicode-category")]
+use crate::tables::{general_category, GeneralCategory};
+#[cfg(feature = "unicode-script")]
+use crate::tables::{script, Script};
+use crate::{LegacyCharset, Options};
+
+/// What the filter does with a char, as decided by the predicate of
+/// [`FilterWriter::with_predicate`](crate::FilterWriter::with_predicate).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    /// Keep the char.
+    Keep,
+    /// Drop the char, substituted by the replacer, if any, as the chars the
+    /// built-in modes drop are.
+    Drop,
+    /// Substitute the char with the string, e.g. `"e"` for é, or remove it
+    /// if empty, whatever the replacer.
+    Replace(String),
+}
+
+impl Action {
+    /// Keep the ASCII subset, i.e. ASCII letters, ASCII punctuations, ASCII
+    /// digits, space, tab and '\n', and drop the rest, which is the policy of
+    /// the ASCII-only mode, e.g. to fall back on in a predicate.
+    pub fn ascii_subset(c: char) -> Action {
+        match is_ascii_subset(c) {
+            true => Action::Keep,
+            false => Action::Drop,
+        }
+    }
+}
+
+/// The predicate deciding the chars kept, by default
+/// [`Action::ascii_subset`].
+#[derive(Clone)]
+pub(crate) struct Predicate {
+    decide: Arc<dyn Fn(char) -> Action + Send + Sync>,
+    /// Its decisions on the ASCII chars, looked up in advance to skip the
+    /// runs of them it keeps.
+    keeps_ascii: [bool; 128],
+    /// True if it keeps exactly the ASCII subset of the ASCII chars, whose
+    /// runs are skipped a word at a time.
+    keeps_ascii_subset: bool,
+}
+
+impl Predicate {
+    pub(crate) fn new<F>(decide: F) -> Self
+    where
+        F: Fn(char) -> Action + Send + Sync + 'static,
+    {
+        let keeps_ascii: [bool; 128] =
+            std::array::from_fn(|b| decide(b as u8 as char) == Action::Keep);
+        let keeps_ascii_subset = keeps_ascii
+            .iter()
+            .enumerate()
+            .all(|(b, &keeps)| keeps == is_ascii_subset(b as u8 as char));
+        Self {
+            decide: Arc::new(decide),
+            keeps_ascii,
+            keeps_ascii_subset,
+        }
+    }
+
+    fn decide(&self, c: char) -> Action {
+        (self.decide)(c)
+    }
+
+    /// Return the length of the run of ASCII chars kept which `bytes` starts
+    /// with.
+    fn ascii_run(&self, bytes: &[u8]) -> usize {
+        if self.keeps_ascii_subset {
+            return ascii_subset_run(bytes);
+        }
+        bytes
+            .iter()
+            .take_while(|&&b| b.is_ascii() && self.keeps_ascii[b as usize])
+            .count()
+    }
+}
+
+impl Default for Predicate {
+    fn default() -> Self {
+        Self::new(Action::ascii_subset)
+    }
+}
+
+impl fmt::Debug for Predicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Predicate").finish_non_exhaustive()
+    }
+}
+
+/// Decides which valid chars the filter keeps.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CharFilter {
+    /// If false, keep all chars but those of `drop_categories`, and the
+    /// invisible ones if `strip_invisible`.
+    pub(crate) filtering: bool,
+    /// Decides the chars kept unless `allowed`, by default the ASCII subset.
+    pub(crate) predicate: Predicate,
+    /// Drop the invisible chars, even if kept otherwise.
+    pub(crate) strip_invisible: bool,
+    /// Keep CR too.
+    pub(crate) keep_cr: bool,
+    /// Keep NUL too.
+    pub(crate) keep_nul: bool,
+    /// If not `None`, keep these control chars too.
+    pub(crate) keep_controls: Option<CharSet>,
+    /// If not `None`, keep these chars instead of the ASCII subset.
+    pub(crate) allowed: Option<CharSet>,
+    /// Keep the printable chars of Latin-1 too.
+    pub(crate) keep_latin1: bool,
+    /// If not `None`, keep the chars encodable in this charset too.
+    pub(crate) encodable_in: Option<LegacyCharset>,
+    /// Keep the chars of these categories too.
+    #[cfg(feature = "unicode-category")]
+    pub(crate) keep_categories: Vec<GeneralCategory>,
+    /// Drop the chars of these categories, even if kept otherwise.
+    #[cfg(feature = "unicode-category")]
+    pub(crate) drop_categories: Vec<GeneralCategory>,
+    /// Keep the chars of these scripts too.
+    #[cfg(feature = "unicode-script")]
+    pub(crate) keep_scripts: Vec<Script>,
+}
+
+impl CharFilter {
+    pub(crate) fn new(opts: &Options) -> Self {
+        Self {
+            filtering: opts.filters_chars(),
+            predicate: Predicate::default(),
+            strip_invisible: opts.strip_invisible,
+            keep_cr: opts.keep_cr,
+            keep_nul: opts.null_data,
+            keep_controls: opts.keep_controls.clone(),
+            allowed: opts.allowed.clone(),
+            keep_latin1: opts.keep_latin1,
+            encodable_in: opts.encodable_in,
+            #[cfg(feature = "unicode-category")]
+            keep_categories: opts.keep_categories.clone(),
+            #[cfg(feature = "unicode-category")]
+            drop_categories: opts.drop_categories.clone(),
+            #[cfg(feature = "unicode-script")]
+            keep_scripts: opts.keep_scripts.clone(),
+        }
+    }
+
+    /// Return true if every valid char is kept.
+    pub(crate) fn keeps_all(&self) -> bool {
+        if self.strip_invisible {
+            return false;
+        }
+        #[cfg(feature = "unicode-category")]
+        if !self.drop_categories.is_empty() {
+            return false;
+        }
+        !self.filtering
+    }
+
+    /// Return the length of the run of ASCII chars kept which `bytes` starts
+    /// with, found without decoding them, or 0 if the ASCII chars kept are
+    /// not those of the predicate.
+    pub(crate) fn ascii_run(&self, bytes: &[u8]) -> usize {
+        if !self.filtering || self.allowed.is_some() || self.strip_invisible {
+            return 0;
+        }
+        #[cfg(feature = "unicode-category")]
+        if !self.drop_categories.is_empty() {
+            return 0;
+        }
+        self.predicate.ascii_run(bytes)
+    }
+
+    pub(crate) fn keeps(&self, c: char) -> bool {
+        self.action(c) == Action::Keep
+    }
+
+    /// Return the substitute of the dropped `c` the predicate gives, if any.
+    pub(crate) fn replacement(&self, c: char) -> Option<String> {
+        match self.action(c) {
+            Action::Replace(replacement) => Some(replacement),
+            _ => None,
+        }
+    }
+
+    /// Return what becomes of `c`, where a char kept by the options on top
+    /// of the predicate is kept whatever the predicate decides.
+    pub(crate) fn action(&self, c: char) -> Action {
+        if self.strip_invisible && is_invisible(c) {
+            return Action::Drop;
+        }
+        #[cfg(feature = "unicode-category")]
+        if !self.drop_categories.is_empty() || !self.keep_categories.is_empty()
+        {
+            let gc = general_category(c);
+            if self.drop_categories.contains(&gc) {
+                return Action::Drop;
+            }
+            if self.keep_categories.contains(&gc) {
+                return Action::Keep;
+            }
+        }
+        if !self.filtering
+            || (self.keep_cr && c == '\r')
+            || (self.keep_nul && c == '\0')
+            || self
+                .keep_controls
+                .as_ref()
+                .is_some_and(|set| set.contains(c))
+        {
+            return Action::Keep;
+        }
+        #[cfg(feature = "unicode-script")]
+        if !self.keep_scripts.is_empty()
+            && self.keep_scripts.contains(&script(c))
+        {
+            return Action::Keep;
+        }
+        if self.keep_latin1 && is_latin1_printable(c) {
+            return Action::Keep;
+        }
+        if !c.is_ascii()
+            && self.encodable_in.is_some_and(|charset| charset.encodes(c))
+        {
+            return Action::Keep;
+        }
+        match &self.allowed {
+            Some(allowed) if allowed.contains(c) => Action::Keep,
+            Some(_) => Action::Drop,
+            None => self.predicate.decide(c),
+        }
+    }
+}
+
+/// A set of chars, as sorted and disjoint inclusive ranges.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CharSet {
+    ranges: Vec<(char, char)>,
+}
+
+impl CharSet {
+    /// Create the empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the chars `first..=last`.
+    pub fn insert_range(&mut self, first: char, last: char) {
+        if first > last {
+            return;
+        }
+        let i = self
+            .ranges
+            .partition_point(|&(_, l)| (l as u32) + 1 < first as u32);
+        let mut first = first;
+        let mut last = last;
+        let mut j = i;
+        // merge the ranges overlapping or adjacent to `first..=last`
+        while j < self.ranges.len()
+            && self.ranges[j].0 as u32 <= last as u32 + 1
+        {
+            first = first.min(self.ranges[j].0);
+            last = last.max(self.ranges[j].1);
+            j += 1;
+        }
+        self.ranges.splice(i..j, [(first, last)]);
+    }
+
+    /// Add the char `c`.
+    pub fn insert(&mut self, c: char) {
+        self.insert_range(c, c);
+    }
+
+    /// Add the chars of `other`.
+    pub fn extend(&mut self, other: &CharSet) {
+        for &(first, last) in &other.ranges {
+            self.insert_range(first, last);
+        }
+    }
+
+    pub fn contains(&self, c: char) -> bool {
+        let i = self.ranges.partition_point(|&(_, last)| last < c);
+        self.ranges.get(i).is_some_and(|&(first, _)| first <= c)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Parse a string of control chars, given literally or as escapes
+    /// (`\a`, `\b`, `\t`, `\n`, `\v`, `\f`, `\r`, `\e`, `\0` or `\xNN`), e.g.
+    /// `\r\x0c\x07` for CR, form feed and bell.
+    pub fn parse_controls(s: &str) -> Result<CharSet, String> {
+        let mut set = CharSet::new();
+        let mut chars = s.chars();
+        while let Some(c) = chars.next() {
+            let c = match c {
+                '\\' => match chars.next() {
+                    Some('a') => '\x07',
+                    Some('b') => '\x08',
+                    Some('t') => '\t',
+                    Some('n') => '\n',
+                    Some('v') => '\x0b',
+                    Some('f') => '\x0c',
+                    Some('r') => '\r',
+                    Some('e') => '\x1b',
+                    Some('0') => '\0',
+                    Some('x') => {
+                        let hex: String = chars.by_ref().take(2).collect();
+                        u8::from_str_radix(&hex, 16)
+                            .ok()
+                            .filter(|_| hex.len() == 2)
+                            .map(char::from)
+                            .ok_or_else(|| {
+                                format!("invalid escape \\x{}", hex)
+                            })?
+                    }
+                    Some(c) => return Err(format!("unknown escape \\{}", c)),
+                    None => return Err("a trailing \\".to_string()),
+                },
+                c => c,
+            };
+            if !c.is_control() {
+                return Err(format!("{:?} is not a control char", c));
+            }
+            set.insert(c);
+        }
+        Ok(set)
+    }
+}
+
+impl FromIterator<char> for CharSet {
+    fn from_iter<I: IntoIterator<Item = char>>(iter: I) -> Self {
+        let mut set = CharSet::new();
+        for c in iter {
+            set.insert(c);
+        }
+        set
+    }
+}
+
+/// Parse a bound of a range at the start of `s`, returning the char and the
+/// rest of `s`.
+fn parse_bound(s: &str) -> Result<(char, &str), String> {
+    let hex = s
+        .strip_prefix("0x")
+        .or_else(|| s.strip_prefix("0X"))
+        .or_else(|| s.strip_prefix("U+"))
+        .or_else(|| s.strip_prefix("u+"));
+    if let Some(hex) = hex {
+        let len = hex
+            .find(|c: char| !c.is_ascii_hexdigit())
+            .unwrap_or(hex.len());
+        let c = u32::from_str_radix(&hex[..len], 16)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(|| {
+                format!(
+                    "invalid codepoint {:?}",
+                    &s[..s.len() - hex.len() + len]
+                )
+            })?;
+        return Ok((c, &hex[len..]));
+    }
+    let mut chars = s.chars();
+    match chars.next() {
+        Some('\\') => {
+            let c = match chars.next() {
+                Some('t') => '\t',
+                Some('n') => '\n',
+                Some('r') => '\r',
+                Some('0') => '\0',
+                Some(c @ ('\\' | ',' | '-')) => c,
+                Some(c) => return Err(format!("unknown escape \\{}", c)),
+                None => return Err("a trailing \\".to_string()),
+            };
+            Ok((c, chars.as_str()))
+        }
+        Some(c) => Ok((c, chars.as_str())),
+        None => Err("an empty item".to_string()),
+    }
+}
+
+/// Parse a comma-separated list of chars and ranges of chars, e.g.
+/// `0x20-0x7E,\t,\n,\r` or `a-z,U+00E9`. Chars are given literally, as
+/// escapes (`\t`, `\n`, `\r`, `\0`, `\\`, `\,`, `\-`), or as hex codepoints
+/// (`0x..` or `U+..`).
+impl FromStr for CharSet {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut set = CharSet::new();
+        let mut rest = s;
+        while !rest.is_empty() {
+            let (first, after) = parse_bound(rest)?;
+            // a '-' at the end of an item is the char itself
+            let (last, after) = match after.strip_prefix('-') {
+                Some(bound) if !bound.is_empty() && !bound.starts_with(',') => {
+                    parse_bound(bound)?
+                }
+                _ => (first, after),
+            };
+            if first > last {
+                return Err(format!(
+                    "empty range {:?}",
+                    &rest[..rest.len() - after.len()]
+                ));
+            }
+            set.insert_range(first, last);
+            rest = match after.strip_prefix(',') {
+                Some(after) => after,
+                None if after.is_empty() => after,
+                None => return Err(format!("expected a comma at {:?}", after)),
+            };
+        }
+        Ok(set)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Action, CharFilter, CharSet, Predicate};
+    use crate::Options;
+
+    #[test]
+    fn test_char_filter_ascii_run() {
+        let opts = Options {
+            ascii_only: true,
+            ..Options::default()
+        };
+        assert_eq!(CharFilter::new(&opts).ascii_run(b"ab\tc\n\rd"), 5);
+        assert_eq!(CharFilter::new(&opts).ascii_run("é".as_bytes()), 0);
+        let opts = Options {
+            allowed: Some("a".parse().unwrap()),
+            ..opts
+        };
+        assert_eq!(CharFilter::new(&opts).ascii_run(b"ab"), 0);
+        let chars = CharFilter {
+            filtering: true,
+            predicate: Predicate::new(|c| match c {
+                'b' => Action::Drop,
+                c => Action::ascii_subset(c),
+            }),
+            ..CharFilter::default()
+        };
+        assert_eq!(chars.ascii_run(b"aab"), 2);
+        assert!(!chars.predicate.keeps_ascii_subset);
+        assert!(Predicate::default().keeps_ascii_subset);
+        let predicate = Predicate::new(|c| match c {
+            'é' => Action::Replace("e".to_string()),
+            c => Action::ascii_subset(c),
+        });
+        assert!(predicate.keeps_ascii_subset);
+        assert_eq!(predicate.ascii_run(b"ab\tc\n\rd"), 5);
+    }
+
+    #[test]
+    fn test_charset_parse() {
+        let set: CharSet = "0x20-0x7E,\\t,\\n,U+00E9".parse().unwrap();
+        for c in [' ', 'a', '~', '\t', '\n', 'é'] {
+            assert!(set.contains(c), "{:?}", c);
+        }
+        for c in ['\r', '\x7f', 'è'] {
+            assert!(!set.contains(c), "{:?}", c);
+        }
+        let set: CharSet = "a-c,-,\\,".parse().unwrap();
+        assert_eq!(set, "abc-,".chars().collect());
+        assert!("z-a".parse::<CharSet>().is_err());
+        assert!("0x110000".parse::<CharSet>().is_err());
+        assert!("ab".parse::<CharSet>().is_err());
+    }
+
+    #[test]
+    fn test_charset_insert_range() {
+        let mut set = CharSet::new();
+        set.insert_range('m', 'p');
+        set.insert_range('a', 'c');
+        set.insert_range('d', 'l');
+        set.insert('x');
+        assert_eq!(set.ranges, vec![('a', 'p'), ('x', 'x')]);
+    }
+}
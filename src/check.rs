@@ -0,0 +1,436 @@
+//! Checks run alongside filtering, which catch the filter misbehaving on real
+//! data.
+
+use std::cell::RefCell;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead, Read, Write};
+
+use crate::{filter_writer, FilterWriter, Options};
+
+/// A failed check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CheckError {
+    /// The output diverges from the reference at output `offset`.
+    Diverged { offset: usize },
+    /// Filtering the output again changes it at output `offset`.
+    NotIdempotent { offset: usize },
+    /// The filter changes the input at input `offset`.
+    NotClean { offset: usize },
+    /// The filter drops or replaces `c` at `line` and `column` of the text
+    /// filtered, both from 1, in strict mode.
+    Disallowed { c: char, line: usize, column: usize },
+    /// The invalid utf-8 `bytes` are at `line` and `column` in strict mode.
+    InvalidUtf8 {
+        bytes: Vec<u8>,
+        line: usize,
+        column: usize,
+    },
+}
+
+impl fmt::Display for CheckError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckError::Diverged { offset } => write!(
+                f,
+                "the output diverges from the reference decoding at output \
+                 byte {}",
+                offset
+            ),
+            CheckError::NotIdempotent { offset } => write!(
+                f,
+                "filtering the output again changes it at output byte {}",
+                offset
+            ),
+            CheckError::NotClean { offset } => {
+                write!(f, "the input is not clean at byte {}", offset)
+            }
+            CheckError::Disallowed { c, line, column } => write!(
+                f,
+                "disallowed char U+{:04X} {:?} at {}:{}",
+                *c as u32, c, line, column
+            ),
+            CheckError::InvalidUtf8 {
+                bytes,
+                line,
+                column,
+            } => {
+                write!(f, "invalid utf-8 sequence ")?;
+                for b in bytes {
+                    write!(f, "\\x{:02X}", b)?;
+                }
+                write!(f, " at {}:{}", line, column)
+            }
+        }
+    }
+}
+
+impl Error for CheckError {}
+
+/// Two byte streams compared as they grow, so that memory stays bounded by
+/// the lag between them.
+#[derive(Default)]
+struct Comparison {
+    /// The expected bytes not yet compared.
+    expected: Vec<u8>,
+    /// The actual bytes not yet compared.
+    actual: Vec<u8>,
+    /// The number of bytes compared equal.
+    compared: usize,
+    /// The offset of the first difference.
+    diverged: Option<usize>,
+}
+
+impl Comparison {
+    fn expect(&mut self, bytes: &[u8]) {
+        if self.diverged.is_none() {
+            self.expected.extend_from_slice(bytes);
+            self.compare(false);
+        }
+    }
+
+    fn actual(&mut self, bytes: &[u8]) {
+        if self.diverged.is_none() {
+            self.actual.extend_from_slice(bytes);
+            self.compare(false);
+        }
+    }
+
+    /// Compare the common length, or everything at the end.
+    fn compare(&mut self, end: bool) {
+        let n = self.expected.len().min(self.actual.len());
+        let mismatch = (0..n).find(|&i| self.expected[i] != self.actual[i]);
+        if let Some(i) = mismatch {
+            self.diverged = Some(self.compared + i);
+        } else if end && self.expected.len() != self.actual.len() {
+            self.diverged = Some(self.compared + n);
+        } else {
+            self.expected.drain(..n);
+            self.actual.drain(..n);
+            self.compared += n;
+            return;
+        }
+        self.expected.clear();
+        self.actual.clear();
+    }
+}
+
+/// Decodes the input as `String::from_utf8_lossy` does but drops the invalid
+/// sequences, and passes the decoded chars through the same char filter, as
+/// the reference for the output.
+struct Reference {
+    /// The input not yet decoded, an incomplete sequence at most.
+    pending: Vec<u8>,
+    /// The char filter, into which the decoded chars are written.
+    filter: FilterWriter<Vec<u8>>,
+    cmp: Comparison,
+}
+
+impl Reference {
+    /// Decode `input` after the pending bytes into the reference output. At
+    /// the end of input, an incomplete sequence is dropped as invalid.
+    fn input(&mut self, input: &[u8], end: bool) {
+        self.pending.extend_from_slice(input);
+        let mut valid: Vec<u8> = Vec::with_capacity(self.pending.len());
+        let mut i: usize = 0;
+        while i < self.pending.len() {
+            match std::str::from_utf8(&self.pending[i..]) {
+                Ok(s) => {
+                    valid.extend_from_slice(s.as_bytes());
+                    i = self.pending.len();
+                }
+                Err(e) => {
+                    let j = i + e.valid_up_to();
+                    valid.extend_from_slice(&self.pending[i..j]);
+                    match e.error_len() {
+                        Some(n) => i = j + n,
+                        None if end => i = self.pending.len(),
+                        None => {
+                            // wait for the rest of the sequence
+                            i = j;
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+        self.pending.drain(..i);
+        // Writing to a `Vec` never fails.
+        self.filter.write_all(&valid).unwrap();
+        if end {
+            self.filter.flush().unwrap();
+        }
+        let expected = std::mem::take(self.filter.get_mut());
+        self.cmp.expect(&expected);
+    }
+}
+
+/// Filters the output a second time through the same pipeline, which must
+/// not change it.
+struct SecondPass {
+    filter: FilterWriter<Vec<u8>>,
+    cmp: Comparison,
+}
+
+impl SecondPass {
+    fn output(&mut self, output: &[u8], opts: &Options) {
+        self.cmp.expect(output);
+        // Writing to a `Vec` never fails.
+        self.filter.write_all(output).unwrap();
+        let refiltered = std::mem::take(self.filter.get_mut());
+        self.cmp.actual(&substitute_record_sep(&refiltered, opts));
+    }
+
+    fn finish(&mut self, opts: &Options) {
+        self.filter.flush().unwrap();
+        let refiltered = std::mem::take(self.filter.get_mut());
+        self.cmp.actual(&substitute_record_sep(&refiltered, opts));
+        self.cmp.compare(true);
+    }
+}
+
+fn record_sep(opts: &Options) -> Option<Vec<u8>> {
+    opts.out_record_sep.clone().map(String::into_bytes)
+}
+
+fn substitute_record_sep(bytes: &[u8], opts: &Options) -> Vec<u8> {
+    let mut out: Vec<u8> = Vec::with_capacity(bytes.len());
+    FilterWriter::new(&mut out, false)
+        .with_record_sep(record_sep(opts))
+        .write_all(bytes)
+        .unwrap();
+    out
+}
+
+/// Runs the checks enabled in the options, as the input is read and the
+/// output is written.
+pub(crate) struct Checker {
+    opts: Options,
+    reference: Option<Reference>,
+    second_pass: Option<SecondPass>,
+    /// The input as expected, and the output as actual.
+    passthrough: Option<Comparison>,
+}
+
+impl Checker {
+    pub(crate) fn new(opts: &Options) -> Self {
+        // the chars the checks drop were counted by the filter checked
+        let unrecorded = Options {
+            histogram: None,
+            ..opts.clone()
+        };
+        Self {
+            opts: opts.clone(),
+            reference: opts.verify.then(|| Reference {
+                pending: Vec::new(),
+                filter: filter_writer(Vec::new(), &unrecorded)
+                    .with_record_sep(record_sep(opts)),
+                cmp: Comparison::default(),
+            }),
+            second_pass: opts.check_idempotent.then(|| SecondPass {
+                filter: filter_writer(Vec::new(), &unrecorded),
+                cmp: Comparison::default(),
+            }),
+            passthrough: opts.assert_clean.then(Comparison::default),
+        }
+    }
+
+    /// Return true if any check is enabled in `opts`.
+    pub(crate) fn enabled(opts: &Options) -> bool {
+        opts.verify || opts.check_idempotent || opts.assert_clean
+    }
+
+    fn input(&mut self, input: &[u8]) {
+        if let Some(reference) = &mut self.reference {
+            reference.input(input, false);
+        }
+        if let Some(passthrough) = &mut self.passthrough {
+            passthrough.expect(input);
+        }
+    }
+
+    fn output(&mut self, output: &[u8]) {
+        if let Some(reference) = &mut self.reference {
+            reference.cmp.actual(output);
+        }
+        if let Some(second_pass) = &mut self.second_pass {
+            second_pass.output(output, &self.opts);
+        }
+        if let Some(passthrough) = &mut self.passthrough {
+            passthrough.actual(output);
+        }
+    }
+
+    /// Finish the checks at the end of input.
+    pub(crate) fn finish(&mut self) -> Result<(), CheckError> {
+        if let Some(reference) = &mut self.reference {
+            reference.input(&[], true);
+            reference.cmp.compare(true);
+            if let Some(offset) = reference.cmp.diverged {
+                return Err(CheckError::Diverged { offset });
+            }
+        }
+        if let Some(second_pass) = &mut self.second_pass {
+            second_pass.finish(&self.opts);
+            if let Some(offset) = second_pass.cmp.diverged {
+                return Err(CheckError::NotIdempotent { offset });
+            }
+        }
+        if let Some(passthrough) = &mut self.passthrough {
+            passthrough.compare(true);
+            if let Some(offset) = passthrough.diverged {
+                return Err(CheckError::NotClean { offset });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Feeds what is read from `inner` to the checker.
+pub(crate) struct CheckingReader<'a, R> {
+    pub(crate) inner: R,
+    pub(crate) checker: &'a RefCell<Checker>,
+}
+
+impl<'a, R: Read> Read for CheckingReader<'a, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.checker.borrow_mut().input(&buf[..n]);
+        Ok(n)
+    }
+}
+
+impl<'a, R: BufRead> BufRead for CheckingReader<'a, R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        // The bytes consumed are still in the buffer of `inner`.
+        if let Ok(buf) = self.inner.fill_buf() {
+            self.checker.borrow_mut().input(&buf[..amt]);
+        }
+        self.inner.consume(amt);
+    }
+}
+
+/// Feeds what is written to `inner` to the checker.
+pub(crate) struct CheckingWriter<'a, W> {
+    pub(crate) inner: W,
+    pub(crate) checker: &'a RefCell<Checker>,
+}
+
+impl<'a, W: Write> Write for CheckingWriter<'a, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.checker.borrow_mut().output(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CheckError, Checker};
+    use crate::{filter_bytes, EmojiMode, Mapping, Newlines, Options};
+    use regex::Regex;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    fn check(
+        input: &[u8],
+        output: &[u8],
+        opts: &Options,
+    ) -> Result<(), CheckError> {
+        let mut checker = Checker::new(opts);
+        for chunk in input.chunks(3) {
+            checker.input(chunk);
+        }
+        for chunk in output.chunks(2) {
+            checker.output(chunk);
+        }
+        checker.finish()
+    }
+
+    #[test]
+    fn test_verify() {
+        let opts = Options {
+            ascii_only: true,
+            verify: true,
+            ..Options::default()
+        };
+        let input = b"a\xc3\xa9\xe2\x82b\x80c\xf0\x9f\x98";
+        let output = filter_bytes(input, &opts);
+        assert_eq!(check(input, &output, &opts), Ok(()));
+        assert_eq!(
+            check(input, b"ab!", &opts),
+            Err(CheckError::Diverged { offset: 2 })
+        );
+    }
+
+    #[test]
+    fn test_assert_clean() {
+        let opts = Options {
+            ascii_only: true,
+            assert_clean: true,
+            ..Options::default()
+        };
+        let clean = b"abc\n";
+        assert_eq!(check(clean, &filter_bytes(clean, &opts), &opts), Ok(()));
+        let dirty = "ab\u{1b}é".as_bytes();
+        assert_eq!(
+            check(dirty, &filter_bytes(dirty, &opts), &opts),
+            Err(CheckError::NotClean { offset: 2 })
+        );
+    }
+
+    #[test]
+    fn test_check_idempotent() {
+        let mut opts = Options {
+            ascii_only: true,
+            check_idempotent: true,
+            ..Options::default()
+        };
+        let input = "aé€b".as_bytes();
+        assert_eq!(check(input, &filter_bytes(input, &opts), &opts), Ok(()));
+
+        // a rule emitting chars it then drops
+        let table: HashMap<char, String> = [('é', "è!".to_string())].into();
+        opts.mappings = vec![Mapping::Table(Arc::new(table))];
+        assert_eq!(
+            check(input, &filter_bytes(input, &opts), &opts),
+            Err(CheckError::NotIdempotent { offset: 1 })
+        );
+    }
+
+    #[test]
+    fn test_check_idempotent_pipeline() {
+        let opts = Options {
+            ascii_only: true,
+            check_idempotent: true,
+            ..Options::default()
+        };
+        let cases = [
+            Options {
+                emoji: Some(EmojiMode::Keep),
+                ..opts.clone()
+            },
+            Options {
+                newlines: Some(Newlines::Crlf),
+                ..opts.clone()
+            },
+            Options {
+                keep_regex: Some(Regex::new("é").unwrap()),
+                ..opts.clone()
+            },
+        ];
+        let input = "a👍\nb\naé\n".as_bytes();
+        for opts in &cases {
+            let output = filter_bytes(input, opts);
+            assert_eq!(check(input, &output, opts), Ok(()));
+        }
+    }
+}
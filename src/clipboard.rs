@@ -0,0 +1,151 @@
+//! The system clipboard as the input or the output, e.g. to sanitize text
+//! copied out of a word processor before pasting it into a terminal,
+//! through the clipboard tool of the platform.
+
+use std::io::{self, Read, Write};
+use std::process::{Command, Stdio};
+
+use ascii_filter::{filter, open_output, Error, Options, Stats};
+
+/// The commands printing the clipboard, in order of preference.
+#[cfg(target_os = "macos")]
+const PASTE: &[&[&str]] = &[&["pbpaste"]];
+#[cfg(windows)]
+const PASTE: &[&[&str]] = &[&[
+    "powershell",
+    "-NoProfile",
+    "-Command",
+    "[Console]::OutputEncoding = [Text.Encoding]::UTF8; Get-Clipboard -Raw",
+]];
+#[cfg(not(any(target_os = "macos", windows)))]
+const PASTE: &[&[&str]] = &[
+    &["wl-paste", "--no-newline"],
+    &["xclip", "-selection", "clipboard", "-out"],
+    &["xsel", "--clipboard", "--output"],
+];
+
+/// The commands copying their stdin to the clipboard, in order of
+/// preference.
+#[cfg(target_os = "macos")]
+const COPY: &[&[&str]] = &[&["pbcopy"]];
+#[cfg(windows)]
+const COPY: &[&[&str]] = &[&[
+    "powershell",
+    "-NoProfile",
+    "-Command",
+    "[Console]::InputEncoding = [Text.Encoding]::UTF8; \
+     Set-Clipboard -Value ([Console]::In.ReadToEnd())",
+]];
+#[cfg(not(any(target_os = "macos", windows)))]
+const COPY: &[&[&str]] = &[
+    &["wl-copy"],
+    &["xclip", "-selection", "clipboard", "-in"],
+    &["xsel", "--clipboard", "--input"],
+];
+
+/// Return the error of `command` exiting with `status`.
+fn failed(command: &[&str], status: std::process::ExitStatus) -> io::Error {
+    io::Error::other(format!("{}: {}", command.join(" "), status))
+}
+
+/// Return the error of none of `commands` being installed.
+fn not_found(commands: &[&[&str]]) -> io::Error {
+    let names: Vec<&str> = commands.iter().map(|command| command[0]).collect();
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("no clipboard tool found, tried {}", names.join(", ")),
+    )
+}
+
+/// Return the stdout of the first of `commands` installed.
+fn paste(commands: &[&[&str]]) -> io::Result<Vec<u8>> {
+    for command in commands {
+        let output = match Command::new(command[0])
+            .args(&command[1..])
+            .stdin(Stdio::null())
+            .stderr(Stdio::inherit())
+            .output()
+        {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            result => result?,
+        };
+        if !output.status.success() {
+            return Err(failed(command, output.status));
+        }
+        return Ok(output.stdout);
+    }
+    Err(not_found(commands))
+}
+
+/// Write `text` to the stdin of the first of `commands` installed.
+fn copy(commands: &[&[&str]], text: &[u8]) -> io::Result<()> {
+    for command in commands {
+        // The tools on X11 and Wayland stay in the background to serve the
+        // clipboard, so their stdout must not be a pipe waited on.
+        let mut child = match Command::new(command[0])
+            .args(&command[1..])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .spawn()
+        {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            result => result?,
+        };
+        let written = child
+            .stdin
+            .take()
+            .map_or(Ok(()), |mut stdin| stdin.write_all(text));
+        let status = child.wait()?;
+        written?;
+        if !status.success() {
+            return Err(failed(command, status));
+        }
+        return Ok(());
+    }
+    Err(not_found(commands))
+}
+
+/// Filter the clipboard if `from`, or stdin otherwise, per `opts`, to the
+/// clipboard if `to`, or the output per `opts` otherwise.
+pub(crate) fn filter_clipboard(
+    from: bool,
+    to: bool,
+    opts: &Options,
+) -> Result<Stats, Error> {
+    let input = match from {
+        true => paste(PASTE)?,
+        false => {
+            let mut input = Vec::new();
+            io::stdin().lock().read_to_end(&mut input)?;
+            input
+        }
+    };
+    if !to {
+        let mut output = open_output(opts)?;
+        let stats = filter(input.as_slice(), &mut output, opts)?;
+        output.commit()?;
+        return Ok(stats);
+    }
+    let mut output = Vec::new();
+    let stats = filter(input.as_slice(), &mut output, opts)?;
+    copy(COPY, &output)?;
+    Ok(stats)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::{copy, paste};
+    use std::io;
+
+    #[test]
+    fn test_clipboard_tools() {
+        // the first tool installed is used
+        let missing: &[&str] = &["ascii-filter-no-such-tool"];
+        let echo: &[&str] = &["echo", "naïve"];
+        assert_eq!(paste(&[missing, echo]).unwrap(), "naïve\n".as_bytes());
+        let e = paste(&[missing]).unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::NotFound);
+        copy(&[missing, &["cat"]], b"naive").unwrap();
+        assert!(copy(&[&["false"]], b"naive").is_err());
+    }
+}
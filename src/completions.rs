@@ -0,0 +1,397 @@
+//! The hidden `completions` subcommand, writing a completion script of the
+//! options, their values and the subcommands for a shell, e.g. with
+//!
+//! ```text
+//! ascii-filter completions bash > /etc/bash_completion.d/ascii-filter
+//! ```
+
+use std::io::{self, Write};
+
+use clap::ValueEnum;
+
+#[cfg(feature = "unicode-normalization")]
+use ascii_filter::Normalization;
+use ascii_filter::{
+    Compression, EmojiMode, Encoding, InvalidUtf8, Locale, Newlines,
+};
+
+/// The shells a completion script is written for.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+    Powershell,
+}
+
+/// An option as completed, with its value if it takes one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Opt {
+    short: Option<char>,
+    long: Option<String>,
+    /// The first sentence of the help.
+    help: String,
+    /// `None` if the option takes no value, else the values it may take, or
+    /// no values if any, which are completed as files.
+    values: Option<Vec<String>>,
+}
+
+/// Return the values of the option `long` which are parsed with `FromStr`,
+/// and so are unknown to clap.
+fn parsed_values(long: &str) -> Vec<String> {
+    fn names<T: ToString>(all: &[T]) -> Vec<String> {
+        all.iter().map(T::to_string).collect()
+    }
+    match long {
+        "newlines" => names(&Newlines::ALL),
+        "compress" => names(&Compression::ALL),
+        "from-encoding" | "to-encoding" => names(&Encoding::ALL),
+        "invalid-utf8" => names(&InvalidUtf8::ALL),
+        #[cfg(feature = "unicode-normalization")]
+        "normalize" => names(&Normalization::ALL),
+        "emoji" => names(&EmojiMode::ALL),
+        "translit-locale" => names(&Locale::ALL),
+        _ => Vec::new(),
+    }
+}
+
+/// Return the first sentence of `help`.
+fn summary(help: Option<&clap::builder::StyledStr>) -> String {
+    let help = help.map(ToString::to_string).unwrap_or_default();
+    let help = help.lines().next().unwrap_or_default();
+    match help.find(". ") {
+        Some(end) => help[..end].to_string(),
+        None => help.trim_end_matches('.').to_string(),
+    }
+}
+
+/// Return the options of `command` which are not hidden.
+fn options(command: &clap::Command) -> Vec<Opt> {
+    command
+        .get_arguments()
+        .filter(|arg| !arg.is_hide_set() && !arg.is_positional())
+        .map(|arg| {
+            let takes_value =
+                arg.get_num_args().is_some_and(|n| n.min_values() > 0);
+            let values = takes_value.then(|| {
+                let known: Vec<String> = arg
+                    .get_possible_values()
+                    .iter()
+                    .map(|value| value.get_name().to_string())
+                    .collect();
+                match (known.is_empty(), arg.get_long()) {
+                    (true, Some(long)) => parsed_values(long),
+                    _ => known,
+                }
+            });
+            Opt {
+                short: arg.get_short(),
+                long: arg.get_long().map(str::to_string),
+                help: summary(arg.get_help()),
+                values,
+            }
+        })
+        .collect()
+}
+
+/// Return the names and summaries of the subcommands of `command` which
+/// are not hidden.
+fn subcommands(command: &clap::Command) -> Vec<(String, String)> {
+    command
+        .get_subcommands()
+        .filter(|sub| !sub.is_hide_set())
+        .map(|sub| (sub.get_name().to_string(), summary(sub.get_about())))
+        .collect()
+}
+
+/// Write the completion script of `command` for `shell` to `out`.
+pub(crate) fn generate<W: Write>(
+    shell: Shell,
+    command: &mut clap::Command,
+    out: &mut W,
+) -> io::Result<()> {
+    command.build();
+    let name = command.get_name().to_string();
+    let opts = options(command);
+    let subs = subcommands(command);
+    match shell {
+        Shell::Bash => bash(&name, &opts, &subs, out),
+        Shell::Zsh => zsh(&name, &opts, &subs, out),
+        Shell::Fish => fish(&name, &opts, &subs, out),
+        Shell::Powershell => powershell(&name, &opts, &subs, out),
+    }
+}
+
+/// Return `name` as a shell function name.
+fn function(name: &str) -> String {
+    format!("_{}", name.replace('-', "_"))
+}
+
+/// Return the spellings of `opt` on the command line.
+fn flags(opt: &Opt) -> Vec<String> {
+    let short = opt.short.map(|c| format!("-{}", c));
+    let long = opt.long.as_ref().map(|long| format!("--{}", long));
+    short.into_iter().chain(long).collect()
+}
+
+fn bash<W: Write>(
+    name: &str,
+    opts: &[Opt],
+    subs: &[(String, String)],
+    out: &mut W,
+) -> io::Result<()> {
+    let words: Vec<String> = opts
+        .iter()
+        .flat_map(flags)
+        .chain(subs.iter().map(|(sub, _)| sub.clone()))
+        .collect();
+    writeln!(out, "{}() {{", function(name))?;
+    writeln!(out, "    local cur=${{COMP_WORDS[COMP_CWORD]}}")?;
+    writeln!(out, "    local prev=${{COMP_WORDS[COMP_CWORD-1]}}")?;
+    writeln!(out, "    case $prev in")?;
+    for opt in opts {
+        let Some(values) = &opt.values else {
+            continue;
+        };
+        writeln!(out, "        {})", flags(opt).join("|"))?;
+        match values.is_empty() {
+            true => writeln!(
+                out,
+                "            COMPREPLY=($(compgen -f -- \"$cur\"))"
+            )?,
+            false => writeln!(
+                out,
+                "            COMPREPLY=($(compgen -W '{}' -- \"$cur\"))",
+                values.join(" ")
+            )?,
+        }
+        writeln!(out, "            return")?;
+        writeln!(out, "            ;;")?;
+    }
+    writeln!(out, "    esac")?;
+    writeln!(out, "    case $cur in")?;
+    writeln!(out, "        -*)")?;
+    writeln!(
+        out,
+        "            COMPREPLY=($(compgen -W '{}' -- \"$cur\"))",
+        words.join(" ")
+    )?;
+    writeln!(out, "            ;;")?;
+    writeln!(out, "        *)")?;
+    writeln!(out, "            COMPREPLY=($(compgen -f -- \"$cur\"))")?;
+    writeln!(out, "            ;;")?;
+    writeln!(out, "    esac")?;
+    writeln!(out, "}}")?;
+    writeln!(out, "complete -o filenames -F {} {}", function(name), name)
+}
+
+/// Return `s` quoted for the shells which take `'\''` for a quote within
+/// single quotes.
+fn quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
+
+fn zsh<W: Write>(
+    name: &str,
+    opts: &[Opt],
+    subs: &[(String, String)],
+    out: &mut W,
+) -> io::Result<()> {
+    // the brackets of the specs of _arguments
+    let escape = |s: &str| {
+        s.replace('\\', "\\\\")
+            .replace('[', "\\[")
+            .replace(']', "\\]")
+    };
+    writeln!(out, "#compdef {}", name)?;
+    writeln!(out)?;
+    writeln!(out, "{}() {{", function(name))?;
+    writeln!(out, "    local -a subcommands")?;
+    writeln!(out, "    subcommands=(")?;
+    for (sub, about) in subs {
+        writeln!(
+            out,
+            "        {}",
+            quote(&format!("{}:{}", sub, about.replace(':', "\\:")))
+        )?;
+    }
+    writeln!(out, "    )")?;
+    writeln!(out, "    _arguments -s \\")?;
+    for opt in opts {
+        let value = match &opt.values {
+            None => String::new(),
+            Some(values) if values.is_empty() => ":value:_files".to_string(),
+            Some(values) => format!(":value:({})", values.join(" ")),
+        };
+        for flag in flags(opt) {
+            let eq = match (&opt.values, flag.starts_with("--")) {
+                (Some(_), true) => "=",
+                _ => "",
+            };
+            let spec =
+                format!("{}{}[{}]{}", flag, eq, escape(&opt.help), value);
+            writeln!(out, "        {} \\", quote(&spec))?;
+        }
+    }
+    writeln!(
+        out,
+        "        '1: :{{_describe command subcommands; _files}}' \\"
+    )?;
+    writeln!(out, "        '*:file:_files'")?;
+    writeln!(out, "}}")?;
+    writeln!(out)?;
+    writeln!(out, "{} \"$@\"", function(name))
+}
+
+fn fish<W: Write>(
+    name: &str,
+    opts: &[Opt],
+    subs: &[(String, String)],
+    out: &mut W,
+) -> io::Result<()> {
+    let quote =
+        |s: &str| format!("'{}'", s.replace('\\', "\\\\").replace('\'', "\\'"));
+    for (sub, about) in subs {
+        writeln!(
+            out,
+            "complete -c {} -n __fish_use_subcommand -a {} -d {}",
+            name,
+            sub,
+            quote(about)
+        )?;
+    }
+    for opt in opts {
+        write!(out, "complete -c {}", name)?;
+        if let Some(c) = opt.short {
+            write!(out, " -s {}", c)?;
+        }
+        if let Some(long) = &opt.long {
+            write!(out, " -l {}", long)?;
+        }
+        match &opt.values {
+            None => (),
+            Some(values) if values.is_empty() => write!(out, " -r -F")?,
+            Some(values) => write!(out, " -x -a {}", quote(&values.join(" ")))?,
+        }
+        match opt.help.is_empty() {
+            true => writeln!(out)?,
+            false => writeln!(out, " -d {}", quote(&opt.help))?,
+        }
+    }
+    Ok(())
+}
+
+fn powershell<W: Write>(
+    name: &str,
+    opts: &[Opt],
+    subs: &[(String, String)],
+    out: &mut W,
+) -> io::Result<()> {
+    let quote = |s: &str| format!("'{}'", s.replace('\'', "''"));
+    let results = opts
+        .iter()
+        .flat_map(|opt| flags(opt).into_iter().map(|flag| (flag, &opt.help)))
+        .map(|(flag, help)| (flag, "ParameterName", help))
+        .chain(
+            subs.iter()
+                .map(|(sub, about)| (sub.clone(), "Command", about)),
+        );
+    writeln!(out, "using namespace System.Management.Automation")?;
+    writeln!(out)?;
+    writeln!(
+        out,
+        "Register-ArgumentCompleter -Native -CommandName {} -ScriptBlock {{",
+        quote(name)
+    )?;
+    writeln!(
+        out,
+        "    param($wordToComplete, $commandAst, $cursorPosition)"
+    )?;
+    writeln!(out, "    @(")?;
+    for (text, kind, help) in results {
+        let help = match help.is_empty() {
+            true => text.clone(),
+            false => help.clone(),
+        };
+        writeln!(
+            out,
+            "        [CompletionResult]::new({}, {}, [CompletionResultType]::{}, {})",
+            quote(&text),
+            quote(&text),
+            kind,
+            quote(&help)
+        )?;
+    }
+    writeln!(out, "    ) | Where-Object {{ $_.CompletionText -like \"$wordToComplete*\" }}")?;
+    writeln!(out, "}}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{generate, options, summary, Shell};
+    use clap::{Arg, ArgAction, Command};
+
+    fn command() -> Command {
+        Command::new("tool")
+            .arg(
+                Arg::new("all")
+                    .short('a')
+                    .long("all")
+                    .action(ArgAction::SetTrue)
+                    .help("Keep all. And more"),
+            )
+            .arg(Arg::new("when").long("when").value_parser(["now", "never"]))
+            .arg(Arg::new("newlines").long("newlines"))
+            .arg(Arg::new("out").short('o').help("Write to [OUT]"))
+            .arg(Arg::new("secret").long("secret").hide(true))
+            .arg(Arg::new("files").num_args(0..))
+            .subcommand(Command::new("check").about("Check: it's fine."))
+            .subcommand(Command::new("hidden").hide(true))
+    }
+
+    #[test]
+    fn test_options() {
+        let mut command = command();
+        command.build();
+        let opts = options(&command);
+        let longs: Vec<_> =
+            opts.iter().map(|opt| opt.long.as_deref()).collect();
+        assert_eq!(
+            longs,
+            [
+                Some("all"),
+                Some("when"),
+                Some("newlines"),
+                None,
+                Some("help")
+            ]
+        );
+        assert_eq!(opts[0].help, "Keep all");
+        assert_eq!(opts[0].values, None);
+        assert_eq!(opts[1].values, Some(vec!["now".into(), "never".into()]));
+        assert_eq!(
+            opts[2].values,
+            Some(vec!["lf".into(), "crlf".into(), "keep".into()])
+        );
+        assert_eq!(opts[3].values, Some(vec![]));
+        assert_eq!(summary(None), "");
+    }
+
+    #[test]
+    fn test_generate() {
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish, Shell::Powershell] {
+            let mut out: Vec<u8> = Vec::new();
+            generate(shell, &mut command(), &mut out).unwrap();
+            let script = String::from_utf8(out).unwrap();
+            assert!(script.contains("when"), "{:?}: {}", shell, script);
+            assert!(script.contains("check"), "{:?}: {}", shell, script);
+            assert!(!script.contains("secret"), "{:?}: {}", shell, script);
+            assert!(!script.contains("hidden"), "{:?}: {}", shell, script);
+        }
+        let mut out: Vec<u8> = Vec::new();
+        generate(Shell::Zsh, &mut command(), &mut out).unwrap();
+        let script = String::from_utf8(out).unwrap();
+        assert!(script.contains("'-o[Write to \\[OUT\\]]:value:_files'"));
+        assert!(script.contains("'check:Check\\: it'\\''s fine'"));
+    }
+}
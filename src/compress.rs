@@ -0,0 +1,908 @@
+//! Decompression of the input, as a stage ahead of the filter, so that e.g.
+//! a rotated `app.log.gz` is filtered without `zcat`, and compression of the
+//! output, as a stage after it. Gzip is deflated and inflated here; zstd and
+//! xz are recognized, but not supported.
+
+use std::fmt;
+use std::io::{self, BufRead, Read, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+/// A compression format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    Gzip,
+    /// Recognized, but not supported.
+    Zstd,
+    /// Recognized, but not supported.
+    Xz,
+}
+
+impl Compression {
+    pub const ALL: [Compression; 3] =
+        [Compression::Gzip, Compression::Zstd, Compression::Xz];
+
+    /// Return the name of the format, e.g. "gzip".
+    pub fn name(&self) -> &'static str {
+        match self {
+            Compression::Gzip => "gzip",
+            Compression::Zstd => "zstd",
+            Compression::Xz => "xz",
+        }
+    }
+
+    /// Return the format of the file `path` by its extension, e.g. `.gz`,
+    /// if it is one of a compressed file.
+    pub fn from_extension(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "gz" => Some(Compression::Gzip),
+            "zst" => Some(Compression::Zstd),
+            "xz" => Some(Compression::Xz),
+            _ => None,
+        }
+    }
+
+    /// Return the format of the input starting with `head`, if compressed,
+    /// as recognized by its magic.
+    pub(crate) fn detect(head: &[u8]) -> Option<Self> {
+        if head.starts_with(b"\x1f\x8b") {
+            Some(Compression::Gzip)
+        } else if head.starts_with(b"\x28\xb5\x2f\xfd") {
+            Some(Compression::Zstd)
+        } else if head.starts_with(b"\xfd7zXZ\x00") {
+            Some(Compression::Xz)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for Compression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Compression {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Compression::ALL
+            .into_iter()
+            .find(|compression| compression.name() == s)
+            .ok_or_else(|| {
+                format!(
+                    "unknown compression {:?}, expected one of gzip, zstd, xz",
+                    s
+                )
+            })
+    }
+}
+
+/// The size of the window a distance of deflate reaches back into.
+const WINDOW_LEN: usize = 32 << 10;
+
+/// The lengths of the length codes 257 to 285, and their extra bits.
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59,
+    67, 83, 99, 115, 131, 163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5,
+    5, 5, 5, 0,
+];
+
+/// The distances of the distance codes 0 to 29, and their extra bits.
+const DIST_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513,
+    769, 1025, 1537, 2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DIST_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10,
+    11, 11, 12, 12, 13, 13,
+];
+
+/// The order in which the lengths of the code length code are given.
+const CODE_LENGTH_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+/// Return an error of kind [`io::ErrorKind::InvalidData`] about the gzip
+/// input.
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("gzip: {}", msg))
+}
+
+/// A canonical Huffman code, decoded a bit at a time.
+struct Huffman {
+    /// The number of codes of each length, from 0 to 15 bits.
+    counts: [u16; 16],
+    /// The symbols ordered by their codes.
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    /// Build the code of the symbols of code `lengths`, where 0 means the
+    /// symbol is unused.
+    fn new(lengths: &[u8]) -> io::Result<Self> {
+        let mut counts = [0u16; 16];
+        for &len in lengths {
+            counts[len as usize] += 1;
+        }
+        counts[0] = 0;
+        // no length may have more codes than are left
+        let mut left: i32 = 1;
+        for &count in &counts[1..] {
+            left = 2 * left - count as i32;
+            if left < 0 {
+                return Err(invalid("oversubscribed huffman code"));
+            }
+        }
+        let mut offsets = [0u16; 16];
+        for len in 1..15 {
+            offsets[len + 1] = offsets[len] + counts[len];
+        }
+        let mut symbols = vec![0u16; lengths.len()];
+        for (symbol, &len) in lengths.iter().enumerate() {
+            if len != 0 {
+                symbols[offsets[len as usize] as usize] = symbol as u16;
+                offsets[len as usize] += 1;
+            }
+        }
+        Ok(Self { counts, symbols })
+    }
+
+    /// The fixed code of the literals and lengths.
+    fn fixed_literals() -> Self {
+        let mut lengths = [0u8; 288];
+        lengths[..144].fill(8);
+        lengths[144..256].fill(9);
+        lengths[256..280].fill(7);
+        lengths[280..].fill(8);
+        Self::new(&lengths).unwrap()
+    }
+
+    /// The fixed code of the distances.
+    fn fixed_distances() -> Self {
+        Self::new(&[5; 30]).unwrap()
+    }
+}
+
+/// What the inflating is at.
+enum State {
+    /// The header of a gzip member, or the end of input.
+    Member,
+    /// The header of a deflate block.
+    Block,
+    /// A stored block with this many bytes left.
+    Stored(usize),
+    /// A block of Huffman codes.
+    Codes(Huffman, Huffman),
+    /// The trailer of a gzip member.
+    Trailer,
+    End,
+}
+
+/// Inflates the gzip input read from `inner`, of one or more members.
+pub(crate) struct GzipReader<R> {
+    inner: R,
+    /// The bits read from `inner` but not decoded yet, from the lowest.
+    bits: u64,
+    nbits: u32,
+    state: State,
+    /// If true, the current block is the last of the member.
+    last: bool,
+    /// The output, of which that from `pos` is not read yet, and that before
+    /// is kept as the window.
+    out: Vec<u8>,
+    pos: usize,
+    /// The checksum of the output of the member up to `out[checked..]`.
+    crc: u32,
+    checked: usize,
+    /// The length of the output of the member, modulo 2^32.
+    size: u32,
+    /// The number of members read.
+    members: usize,
+}
+
+impl<R: BufRead> GzipReader<R> {
+    pub(crate) fn new(inner: R) -> Self {
+        Self {
+            inner,
+            bits: 0,
+            nbits: 0,
+            state: State::Member,
+            last: false,
+            out: Vec::new(),
+            pos: 0,
+            crc: 0,
+            checked: 0,
+            size: 0,
+            members: 0,
+        }
+    }
+
+    /// Return the next `n` bits, of at most 32.
+    fn bits(&mut self, n: u32) -> io::Result<u32> {
+        while self.nbits < n {
+            let byte = match self.inner.fill_buf()?.first() {
+                Some(&byte) => byte,
+                // corrupt as any other, not a read which failed
+                None => return Err(invalid("truncated input")),
+            };
+            self.inner.consume(1);
+            self.bits |= (byte as u64) << self.nbits;
+            self.nbits += 8;
+        }
+        let value = (self.bits & ((1u64 << n) - 1)) as u32;
+        self.bits >>= n;
+        self.nbits -= n;
+        Ok(value)
+    }
+
+    /// Skip to the next byte boundary.
+    fn align(&mut self) {
+        let n = self.nbits % 8;
+        self.bits >>= n;
+        self.nbits -= n;
+    }
+
+    /// Return the next little-endian 16-bit or 32-bit number.
+    fn u16(&mut self) -> io::Result<u16> {
+        Ok(self.bits(16)? as u16)
+    }
+
+    fn u32(&mut self) -> io::Result<u32> {
+        Ok(self.u16()? as u32 | (self.u16()? as u32) << 16)
+    }
+
+    /// Decode a symbol of `code`.
+    fn decode(&mut self, code: &Huffman) -> io::Result<u16> {
+        // the first code of each length, and the index of its symbol
+        let (mut code_bits, mut first, mut index) = (0i32, 0i32, 0i32);
+        for &count in &code.counts[1..] {
+            code_bits |= self.bits(1)? as i32;
+            let count = count as i32;
+            if code_bits - count < first {
+                return Ok(code.symbols[(index + code_bits - first) as usize]);
+            }
+            index += count;
+            first = (first + count) << 1;
+            code_bits <<= 1;
+        }
+        Err(invalid("invalid huffman code"))
+    }
+
+    /// Read the header of a gzip member, returning false at the end of
+    /// input after a member.
+    fn member(&mut self) -> io::Result<bool> {
+        if self.members > 0 && self.inner.fill_buf()?.is_empty() {
+            return Ok(false);
+        }
+        if self.u16()? != 0x8b1f {
+            return Err(invalid("not in gzip format"));
+        }
+        if self.bits(8)? != 8 {
+            return Err(invalid("unknown compression method"));
+        }
+        let flags = self.bits(8)?;
+        // the modification time, extra flags and OS
+        for _ in 0..6 {
+            self.bits(8)?;
+        }
+        if flags & 0x04 != 0 {
+            for _ in 0..self.u16()? {
+                self.bits(8)?;
+            }
+        }
+        // the file name and the comment, each ended with a NUL
+        for flag in [0x08, 0x10] {
+            if flags & flag != 0 {
+                while self.bits(8)? != 0 {}
+            }
+        }
+        if flags & 0x02 != 0 {
+            self.u16()?;
+        }
+        self.members += 1;
+        self.crc = 0;
+        self.checked = self.out.len();
+        self.size = 0;
+        Ok(true)
+    }
+
+    /// Read the header of a deflate block.
+    fn block(&mut self) -> io::Result<State> {
+        self.last = self.bits(1)? == 1;
+        match self.bits(2)? {
+            0 => {
+                self.align();
+                let len = self.u16()?;
+                if self.u16()? != !len {
+                    return Err(invalid("invalid stored block length"));
+                }
+                Ok(State::Stored(len as usize))
+            }
+            1 => Ok(State::Codes(
+                Huffman::fixed_literals(),
+                Huffman::fixed_distances(),
+            )),
+            2 => self.dynamic(),
+            _ => Err(invalid("invalid block type")),
+        }
+    }
+
+    /// Read the codes of a block of dynamic Huffman codes.
+    fn dynamic(&mut self) -> io::Result<State> {
+        let nlen = self.bits(5)? as usize + 257;
+        let ndist = self.bits(5)? as usize + 1;
+        let ncode = self.bits(4)? as usize + 4;
+        if nlen > 286 || ndist > 30 {
+            return Err(invalid("too many length or distance codes"));
+        }
+        let mut lengths = [0u8; 19];
+        for &i in &CODE_LENGTH_ORDER[..ncode] {
+            lengths[i] = self.bits(3)? as u8;
+        }
+        let code = Huffman::new(&lengths)?;
+        let mut lengths = vec![0u8; nlen + ndist];
+        let mut i = 0;
+        while i < lengths.len() {
+            let symbol = self.decode(&code)?;
+            let (len, repeat) = match symbol {
+                0..=15 => (symbol as u8, 1),
+                16 if i == 0 => {
+                    return Err(invalid("repeated length without a first"))
+                }
+                16 => (lengths[i - 1], 3 + self.bits(2)? as usize),
+                17 => (0, 3 + self.bits(3)? as usize),
+                _ => (0, 11 + self.bits(7)? as usize),
+            };
+            if i + repeat > lengths.len() {
+                return Err(invalid("too many code lengths"));
+            }
+            lengths[i..i + repeat].fill(len);
+            i += repeat;
+        }
+        if lengths[256] == 0 {
+            return Err(invalid("no end-of-block code"));
+        }
+        Ok(State::Codes(
+            Huffman::new(&lengths[..nlen])?,
+            Huffman::new(&lengths[nlen..])?,
+        ))
+    }
+
+    /// Check the trailer of the member against its output.
+    fn trailer(&mut self) -> io::Result<()> {
+        self.align();
+        self.update_crc();
+        if self.u32()? != self.crc {
+            return Err(invalid("crc mismatch"));
+        }
+        if self.u32()? != self.size {
+            return Err(invalid("length mismatch"));
+        }
+        Ok(())
+    }
+
+    /// Add the output not checked yet to the checksum and the length.
+    fn update_crc(&mut self) {
+        let unchecked = &self.out[self.checked..];
+        self.crc = crc32(self.crc, unchecked);
+        self.size = self.size.wrapping_add(unchecked.len() as u32);
+        self.checked = self.out.len();
+    }
+
+    /// Inflate until at least a window of output is not read yet, or the
+    /// input ends.
+    fn inflate(&mut self) -> io::Result<()> {
+        while self.out.len() - self.pos < WINDOW_LEN {
+            match std::mem::replace(&mut self.state, State::End) {
+                State::Member => {
+                    if self.member()? {
+                        self.state = State::Block;
+                    }
+                }
+                State::Block => self.state = self.block()?,
+                State::Stored(len) => {
+                    let n = len.min(WINDOW_LEN);
+                    for _ in 0..n {
+                        let byte = self.bits(8)? as u8;
+                        self.out.push(byte);
+                    }
+                    self.state = match len - n {
+                        0 => self.end_block(),
+                        left => State::Stored(left),
+                    };
+                }
+                State::Codes(literals, distances) => {
+                    let symbol = self.decode(&literals)?;
+                    self.state = match symbol {
+                        0..=255 => {
+                            self.out.push(symbol as u8);
+                            State::Codes(literals, distances)
+                        }
+                        256 => self.end_block(),
+                        _ => {
+                            self.copy(symbol, &distances)?;
+                            State::Codes(literals, distances)
+                        }
+                    };
+                }
+                State::Trailer => {
+                    self.trailer()?;
+                    self.state = State::Member;
+                }
+                State::End => break,
+            }
+        }
+        Ok(())
+    }
+
+    /// Return the state after a block.
+    fn end_block(&self) -> State {
+        match self.last {
+            true => State::Trailer,
+            false => State::Block,
+        }
+    }
+
+    /// Copy the match of the length code `symbol` and a distance decoded
+    /// with `distances`.
+    fn copy(&mut self, symbol: u16, distances: &Huffman) -> io::Result<()> {
+        let i = symbol as usize - 257;
+        if i >= LENGTH_BASE.len() {
+            return Err(invalid("invalid length code"));
+        }
+        let len = LENGTH_BASE[i] as usize
+            + self.bits(LENGTH_EXTRA[i] as u32)? as usize;
+        let i = self.decode(distances)? as usize;
+        if i >= DIST_BASE.len() {
+            return Err(invalid("invalid distance code"));
+        }
+        let dist =
+            DIST_BASE[i] as usize + self.bits(DIST_EXTRA[i] as u32)? as usize;
+        if dist > self.out.len() {
+            return Err(invalid("distance too far back"));
+        }
+        for _ in 0..len {
+            self.out.push(self.out[self.out.len() - dist]);
+        }
+        Ok(())
+    }
+}
+
+impl<R: BufRead> Read for GzipReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let out = self.fill_buf()?;
+        let n = out.len().min(buf.len());
+        buf[..n].copy_from_slice(&out[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for GzipReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.pos == self.out.len() {
+            // keep only the window of the output read
+            let start = self.out.len().saturating_sub(WINDOW_LEN);
+            if start > 0 {
+                self.update_crc();
+                self.out.drain(..start);
+                self.pos -= start;
+                self.checked -= start;
+            }
+            self.inflate()?;
+        }
+        Ok(&self.out[self.pos..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos += amt;
+    }
+}
+
+/// The length of the input deflated into a block at a time.
+const BLOCK_LEN: usize = 64 << 10;
+
+/// The longest and shortest match of deflate.
+const MAX_MATCH: usize = 258;
+const MIN_MATCH: usize = 3;
+
+/// The number of earlier positions with the same hash tried for a match.
+const MAX_CHAIN: usize = 32;
+
+/// The number of bits of the hash of the 3 bytes at a position.
+const HASH_BITS: u32 = 15;
+
+/// Deflates what is written through it to `inner` as a gzip member, in
+/// blocks of fixed Huffman codes, matching a few earlier positions for each,
+/// or stored blocks if the codes do not shrink the input.
+/// The input is buffered into blocks, so that [`Write::flush`] writes only
+/// the blocks complete so far.
+pub(crate) struct GzipWriter<W> {
+    inner: W,
+    /// The window of the input deflated, then the input not deflated yet.
+    data: Vec<u8>,
+    /// The input offset of `data[0]`, and the length of the window.
+    base: usize,
+    window: usize,
+    /// The last input offset of each hash, plus 1, or 0 if none.
+    head: Vec<usize>,
+    /// The earlier offset with the same hash of each offset in the window,
+    /// by the offset modulo the window size.
+    prev: Vec<usize>,
+    /// The bits not written yet, from the lowest.
+    bits: u64,
+    nbits: u32,
+    /// The output not written yet.
+    out: Vec<u8>,
+    crc: u32,
+    size: u32,
+}
+
+impl<W: Write> GzipWriter<W> {
+    pub(crate) fn new(inner: W) -> Self {
+        Self {
+            inner,
+            data: Vec::new(),
+            base: 0,
+            window: 0,
+            head: vec![0; 1 << HASH_BITS],
+            prev: vec![0; WINDOW_LEN],
+            bits: 0,
+            nbits: 0,
+            // the header, with no name, time or flags, from an unknown OS
+            out: b"\x1f\x8b\x08\x00\x00\x00\x00\x00\x00\xff".to_vec(),
+            crc: 0,
+            size: 0,
+        }
+    }
+
+    /// Append the lowest `n` bits of `value` to the output.
+    fn put(&mut self, value: u32, n: u32) {
+        self.bits |= (value as u64) << self.nbits;
+        self.nbits += n;
+        while self.nbits >= 8 {
+            self.out.push(self.bits as u8);
+            self.bits >>= 8;
+            self.nbits -= 8;
+        }
+    }
+
+    /// Append the Huffman `code` of `n` bits, which is written from its
+    /// highest bit.
+    fn put_code(&mut self, code: u32, n: u32) {
+        self.put(code.reverse_bits() >> (32 - n), n);
+    }
+
+    /// Append the fixed code of the literal or length `symbol`.
+    fn put_symbol(&mut self, symbol: u32) {
+        match symbol {
+            0..=143 => self.put_code(0x30 + symbol, 8),
+            144..=255 => self.put_code(0x190 + symbol - 144, 9),
+            256..=279 => self.put_code(symbol - 256, 7),
+            _ => self.put_code(0xc0 + symbol - 280, 8),
+        }
+    }
+
+    /// Append the match of `len` bytes at `dist` bytes back.
+    fn put_match(&mut self, len: usize, dist: usize) {
+        let i = LENGTH_BASE.partition_point(|&base| base as usize <= len) - 1;
+        self.put_symbol(257 + i as u32);
+        self.put(
+            (len - LENGTH_BASE[i] as usize) as u32,
+            LENGTH_EXTRA[i] as u32,
+        );
+        let i = DIST_BASE.partition_point(|&base| base as usize <= dist) - 1;
+        self.put_code(i as u32, 5);
+        self.put((dist - DIST_BASE[i] as usize) as u32, DIST_EXTRA[i] as u32);
+    }
+
+    /// Return the hash of the 3 bytes at `data[i..]`.
+    fn hash(&self, i: usize) -> usize {
+        let b = &self.data[i..i + MIN_MATCH];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        (n.wrapping_mul(0x9e37_79b1) >> (32 - HASH_BITS)) as usize
+    }
+
+    /// Record the input offset `data[i]` is at under its hash, if 3 bytes
+    /// follow.
+    fn insert(&mut self, i: usize) {
+        if i + MIN_MATCH <= self.data.len() {
+            let h = self.hash(i);
+            let pos = self.base + i;
+            self.prev[pos % WINDOW_LEN] = self.head[h];
+            self.head[h] = pos + 1;
+        }
+    }
+
+    /// Return the longest match of `data[i..]`, of its length and distance,
+    /// among the earlier offsets with the same hash.
+    fn longest_match(&self, i: usize) -> Option<(usize, usize)> {
+        if i + MIN_MATCH > self.data.len() {
+            return None;
+        }
+        let pos = self.base + i;
+        let max = (self.data.len() - i).min(MAX_MATCH);
+        let mut best: Option<(usize, usize)> = None;
+        let mut candidate = self.head[self.hash(i)];
+        for _ in 0..MAX_CHAIN {
+            // past the window, or none
+            if candidate == 0 || pos - (candidate - 1) > WINDOW_LEN {
+                break;
+            }
+            let j = candidate - 1 - self.base;
+            let len = self.data[j..j + max]
+                .iter()
+                .zip(&self.data[i..i + max])
+                .take_while(|(a, b)| a == b)
+                .count();
+            if len >= MIN_MATCH && best.is_none_or(|(n, _)| len > n) {
+                best = Some((len, i - j));
+                if len == max {
+                    break;
+                }
+            }
+            let next = self.prev[(candidate - 1) % WINDOW_LEN];
+            // an offset overwritten by a later one is not earlier
+            if next >= candidate {
+                break;
+            }
+            candidate = next;
+        }
+        best
+    }
+
+    /// Deflate the input not deflated yet into a block, and write the output
+    /// so far.
+    fn deflate(&mut self, last: bool) -> io::Result<()> {
+        let start = (self.out.len(), self.bits, self.nbits);
+        // a block of fixed codes
+        self.put(last as u32 | 1 << 1, 3);
+        let mut i = self.window;
+        while i < self.data.len() {
+            match self.longest_match(i) {
+                Some((len, dist)) => {
+                    self.put_match(len, dist);
+                    for k in i..i + len {
+                        self.insert(k);
+                    }
+                    i += len;
+                }
+                None => {
+                    self.put_symbol(self.data[i] as u32);
+                    self.insert(i);
+                    i += 1;
+                }
+            }
+        }
+        self.put_symbol(256);
+        if self.out.len() - start.0 > self.data.len() - self.window + 5 {
+            // stored blocks instead, of input which the codes do not shrink
+            (self.bits, self.nbits) = (start.1, start.2);
+            self.out.truncate(start.0);
+            self.store(last);
+        }
+        // keep the window only
+        let start = self.data.len().saturating_sub(WINDOW_LEN);
+        self.data.drain(..start);
+        self.base += start;
+        self.window = self.data.len();
+        self.inner.write_all(&self.out)?;
+        self.out.clear();
+        Ok(())
+    }
+
+    /// Append the input not deflated yet as stored blocks, of at least one.
+    fn store(&mut self, last: bool) {
+        let mut start = self.window;
+        loop {
+            let end = (start + 0xffff).min(self.data.len());
+            self.put((last && end == self.data.len()) as u32, 3);
+            self.put(0, (8 - self.nbits % 8) % 8);
+            let len = (end - start) as u16;
+            self.out.extend(len.to_le_bytes());
+            self.out.extend((!len).to_le_bytes());
+            self.out.extend_from_slice(&self.data[start..end]);
+            if end == self.data.len() {
+                break;
+            }
+            start = end;
+        }
+    }
+
+    /// Deflate the rest of the input into the last block, and write it and
+    /// the trailer.
+    pub(crate) fn finish(&mut self) -> io::Result<()> {
+        self.deflate(true)?;
+        // to the byte boundary
+        self.put(0, (8 - self.nbits % 8) % 8);
+        let (crc, size) = (self.crc, self.size);
+        self.out.extend(crc.to_le_bytes());
+        self.out.extend(size.to_le_bytes());
+        self.inner.write_all(&self.out)?;
+        self.out.clear();
+        self.inner.flush()
+    }
+}
+
+impl<W: Write> Write for GzipWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.crc = crc32(self.crc, buf);
+        self.size = self.size.wrapping_add(buf.len() as u32);
+        self.data.extend_from_slice(buf);
+        if self.data.len() - self.window >= BLOCK_LEN {
+            self.deflate(false)?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Return the CRC-32 of `bytes` continued from `crc`, as gzip computes it.
+fn crc32(crc: u32, bytes: &[u8]) -> u32 {
+    const TABLE: [u32; 256] = {
+        let mut table = [0u32; 256];
+        let mut i = 0;
+        while i < 256 {
+            let mut c = i as u32;
+            let mut k = 0;
+            while k < 8 {
+                c = if c & 1 != 0 {
+                    0xedb88320 ^ (c >> 1)
+                } else {
+                    c >> 1
+                };
+                k += 1;
+            }
+            table[i] = c;
+            i += 1;
+        }
+        table
+    };
+    let mut c = !crc;
+    for &b in bytes {
+        c = TABLE[((c ^ b as u32) & 0xff) as usize] ^ (c >> 8);
+    }
+    !c
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{crc32, Compression, GzipReader, GzipWriter};
+    use std::io::{BufReader, Read, Write};
+    use std::path::Path;
+
+    /// "hello hello hello\n" in a block of fixed codes.
+    const HELLO: &[u8] = b"\x1f\x8b\x08\x00\x00\x00\x00\x00\x02\x03\xcb\x48\
+        \xcd\xc9\xc9\x57\xc8\x40\x90\x5c\x00\x3b\x7c\x8a\xdf\x12\x00\x00\x00";
+
+    /// The squares modulo 97 of 0 to 59, each followed by ',', in a block of
+    /// dynamic codes.
+    const SQUARES: &[u8] = b"\x1f\x8b\x08\x00\x00\x00\x00\x00\x02\x03\
+        \x1d\x8c\xc9\x0d\x04\x41\x08\xc4\x12\xf2\xa3\xb9\x21\xff\xc4\xb6\
+        \x76\x24\x84\x38\xec\x7a\x18\xc9\x61\x8d\x17\xd1\xe4\xd1\xc9\x1a\
+        \x81\x27\x39\x8c\xe3\x84\xd1\xce\x89\x08\xe6\x61\x4e\x05\x27\x5a\
+        \xae\x02\x34\x27\xf5\x58\xba\xf0\x61\x97\x4a\xdc\x59\xa5\x29\xaa\
+        \x30\x63\x9b\x96\xb1\x84\x63\x22\xb9\x60\x8b\x39\x46\x3d\xbe\xfa\
+        \x56\x1d\xf5\xea\x3f\x24\x54\x82\x34\xc9\x3f\x20\xee\x81\x27\xab\
+        \x00\x00\x00";
+
+    /// Inflate `input`, read through a buffer of `capacity` bytes.
+    fn inflate(input: &[u8], capacity: usize) -> std::io::Result<Vec<u8>> {
+        let inner = BufReader::with_capacity(capacity, input);
+        let mut out: Vec<u8> = Vec::new();
+        GzipReader::new(inner).read_to_end(&mut out)?;
+        Ok(out)
+    }
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(Compression::detect(HELLO), Some(Compression::Gzip));
+        assert_eq!(
+            Compression::detect(b"\x28\xb5\x2f\xfd\x00"),
+            Some(Compression::Zstd)
+        );
+        assert_eq!(
+            Compression::detect(b"\xfd7zXZ\x00\x00"),
+            Some(Compression::Xz)
+        );
+        assert_eq!(Compression::detect(b"\x1f"), None);
+    }
+
+    #[test]
+    fn test_compression_from_str() {
+        for compression in Compression::ALL {
+            assert_eq!(compression.to_string().parse(), Ok(compression));
+        }
+        assert!("gz".parse::<Compression>().is_err());
+        assert_eq!(
+            Compression::from_extension(Path::new("app.log.gz")),
+            Some(Compression::Gzip)
+        );
+        assert_eq!(Compression::from_extension(Path::new("app.log")), None);
+    }
+
+    #[test]
+    fn test_gzip_writer() {
+        let squares: String =
+            (0..60).map(|i| format!("{},", i * i % 97)).collect();
+        let repeated = "ascii-filter ".repeat(10000);
+        let random: Vec<u8> = (0..100_000u32)
+            .map(|i| (i.wrapping_mul(2654435761) >> 24) as u8)
+            .collect();
+        for input in [
+            b"".as_slice(),
+            squares.as_bytes(),
+            repeated.as_bytes(),
+            &random,
+        ] {
+            let mut w = GzipWriter::new(Vec::new());
+            // in pieces of several blocks
+            for piece in input.chunks(70000) {
+                w.write_all(piece).unwrap();
+            }
+            w.finish().unwrap();
+            if input == repeated.as_bytes() {
+                assert!(w.inner.len() < input.len() / 20);
+            }
+            // the random bytes are stored
+            assert!(w.inner.len() <= input.len() + 40);
+            assert_eq!(inflate(&w.inner, 64).unwrap(), input);
+        }
+    }
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(0, b"123456789"), 0xcbf43926);
+        assert_eq!(crc32(crc32(0, b"1234"), b"56789"), 0xcbf43926);
+    }
+
+    #[test]
+    fn test_gzip_reader() {
+        for capacity in [1, 7, 64] {
+            assert_eq!(
+                inflate(HELLO, capacity).unwrap(),
+                b"hello hello hello\n"
+            );
+        }
+        let squares: String =
+            (0..60).map(|i| format!("{},", i * i % 97)).collect();
+        assert_eq!(inflate(SQUARES, 3).unwrap(), squares.as_bytes());
+        // members concatenated, as by `cat a.gz b.gz`
+        let twice = [HELLO, HELLO].concat();
+        assert_eq!(
+            inflate(&twice, 64).unwrap(),
+            b"hello hello hello\n".repeat(2)
+        );
+
+        // a stored block, longer than a window
+        let text: Vec<u8> =
+            (0..40000u32).map(|i| b'a' + (i % 26) as u8).collect();
+        let mut stored = b"\x1f\x8b\x08\x00\x00\x00\x00\x00\x00\x03".to_vec();
+        for (i, block) in text.chunks(30000).enumerate() {
+            // the second block is the last
+            stored.push(i as u8);
+            stored.extend((block.len() as u16).to_le_bytes());
+            stored.extend((!(block.len() as u16)).to_le_bytes());
+            stored.extend(block);
+        }
+        stored.extend(crc32(0, &text).to_le_bytes());
+        stored.extend((text.len() as u32).to_le_bytes());
+        assert_eq!(inflate(&stored, 64).unwrap(), text);
+
+        let mut corrupt = HELLO.to_vec();
+        corrupt[HELLO.len() - 8] ^= 1;
+        let e = inflate(&corrupt, 64).unwrap_err();
+        assert_eq!(e.to_string(), "gzip: crc mismatch");
+        let e = inflate(&HELLO[..20], 64).unwrap_err();
+        assert_eq!(e.to_string(), "gzip: truncated input");
+        assert_eq!(e.kind(), std::io::ErrorKind::InvalidData);
+    }
+}
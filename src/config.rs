@@ -0,0 +1,743 @@
+//! The config file, in TOML, which sets defaults for the options by their
+//! long names. Named profiles, selected with `--profile`, override the
+//! top-level settings, e.g.
+//!
+//! ```toml
+//! buffer-size = 256
+//!
+//! [profiles.logs]
+//! allow = "0x20-0x7E,\\t,\\n"
+//! replace = "?"
+//! normalize = "nfkc"
+//! logfmt = true
+//!
+//! [profiles.filenames]
+//! ascii-only = true
+//! translit-locale = "de"
+//! ```
+//!
+//! In `--recursive` runs, the `types` table maps MIME types to profiles, or
+//! to settings of their own, applied to the files of those types, e.g.
+//!
+//! ```toml
+//! [types]
+//! "text/x-python" = "filenames"
+//! "text/csv" = { symbols = true }
+//! ```
+//!
+//! Options given on the command line, or by their `ASCII_FILTER_*`
+//! environment variables, override the config file.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+#[cfg(feature = "unicode-category")]
+use ascii_filter::tables::GeneralCategory;
+#[cfg(feature = "unicode-script")]
+use ascii_filter::tables::Script;
+use ascii_filter::CharSet;
+use clap::parser::ValueSource;
+use clap::ArgMatches;
+use toml::{Table, Value};
+
+use crate::{parse_columns, parse_duration, parse_rate, parse_size, App};
+
+/// Options of which at most one applies, so that one given on the command
+/// line overrides all of them in the config file.
+const EXCLUSIVE: [&[&str]; 4] = [
+    &[
+        "logfmt",
+        "syslog",
+        "journal-export",
+        "docker-json",
+        "json",
+        "csv",
+        "tsv",
+        "markdown",
+    ],
+    &["out-null", "out-record-sep"],
+    &["truncate", "wrap", "drop"],
+    &[
+        "replace",
+        "escape",
+        "collapse",
+        "word-sep",
+        "drop-lines",
+        "highlight",
+    ],
+];
+
+/// Return the default config file, `ascii-filter/config.toml` in the XDG
+/// config directory.
+fn default_path() -> Option<PathBuf> {
+    let dir = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(std::env::var_os("HOME")?).join(".config"),
+    };
+    Some(dir.join("ascii-filter").join("config.toml"))
+}
+
+/// Return the clap id of the option `key`.
+fn arg_id(key: &str) -> String {
+    match key {
+        "buffer-size" => "buf_size".to_string(),
+        key => key.replace('-', "_"),
+    }
+}
+
+/// Return true if the option `key` is given on the command line or by its
+/// environment variable, either of which overrides the config file.
+fn given(matches: &ArgMatches, key: &str) -> bool {
+    let id = arg_id(key);
+    // unknown keys are reported by `set`
+    matches.try_contains_id(&id).is_ok()
+        && matches!(
+            matches.value_source(&id),
+            Some(ValueSource::CommandLine | ValueSource::EnvVariable)
+        )
+}
+
+fn as_bool(key: &str, value: &Value) -> Result<bool, String> {
+    value
+        .as_bool()
+        .ok_or_else(|| format!("{}: expected a boolean", key))
+}
+
+fn as_str<'v>(key: &str, value: &'v Value) -> Result<&'v str, String> {
+    value
+        .as_str()
+        .ok_or_else(|| format!("{}: expected a string", key))
+}
+
+/// Return the path or array of paths `value`, relative to `dir`.
+fn as_paths(
+    key: &str,
+    value: &Value,
+    dir: &Path,
+) -> Result<Vec<PathBuf>, String> {
+    let paths: Vec<&Value> = match value {
+        Value::Array(values) => values.iter().collect(),
+        value => vec![value],
+    };
+    paths
+        .into_iter()
+        .map(|path| as_str(key, path).map(|path| dir.join(path)))
+        .collect()
+}
+
+/// Set the option `key` of `app` to `value`, where relative paths are
+/// relative to `dir`.
+fn set(
+    app: &mut App,
+    key: &str,
+    value: &Value,
+    dir: &Path,
+) -> Result<(), String> {
+    match key {
+        "buffer-size" => {
+            app.buf_size = match value {
+                Value::String(s) => parse_size(s),
+                _ => parse_size(&value.to_string()),
+            }
+            .map_err(|e| format!("{}: {}", key, e))?
+        }
+        "ascii-only" => app.ascii_only = as_bool(key, value)?,
+        "allow" => {
+            app.allow = Some(
+                as_str(key, value)?
+                    .parse()
+                    .map_err(|e| format!("{}: {}", key, e))?,
+            )
+        }
+        "allow-chars" => {
+            app.allow_chars = Some(as_str(key, value)?.to_string())
+        }
+        #[cfg(feature = "unicode-category")]
+        "keep-categories" => {
+            app.keep_categories = Some(
+                GeneralCategory::parse_list(as_str(key, value)?)
+                    .map_err(|e| format!("{}: {}", key, e))?,
+            )
+        }
+        #[cfg(feature = "unicode-category")]
+        "drop-categories" => {
+            app.drop_categories = Some(
+                GeneralCategory::parse_list(as_str(key, value)?)
+                    .map_err(|e| format!("{}: {}", key, e))?,
+            )
+        }
+        #[cfg(feature = "unicode-script")]
+        "keep-script" => {
+            app.keep_scripts = Some(
+                Script::parse_list(as_str(key, value)?)
+                    .map_err(|e| format!("{}: {}", key, e))?,
+            )
+        }
+        "emoji" => {
+            app.emoji = Some(
+                as_str(key, value)?
+                    .parse()
+                    .map_err(|e| format!("{}: {}", key, e))?,
+            )
+        }
+        #[cfg(feature = "unicode-segmentation")]
+        "graphemes" => app.graphemes = as_bool(key, value)?,
+        "strip-ansi" => app.strip_ansi = as_bool(key, value)?,
+        "drop-regex" => {
+            app.drop_regex = Some(
+                as_str(key, value)?
+                    .parse()
+                    .map_err(|e| format!("{}: {}", key, e))?,
+            )
+        }
+        "keep-regex" => {
+            app.keep_regex = Some(
+                as_str(key, value)?
+                    .parse()
+                    .map_err(|e| format!("{}: {}", key, e))?,
+            )
+        }
+        "punycode" => app.punycode = as_bool(key, value)?,
+        "keep-latin1" => app.keep_latin1 = as_bool(key, value)?,
+        "encodable-in" => {
+            app.encodable_in = Some(
+                as_str(key, value)?
+                    .parse()
+                    .map_err(|e| format!("{}: {}", key, e))?,
+            )
+        }
+        "strip-invisible" => app.strip_invisible = as_bool(key, value)?,
+        "keep-cr" => app.keep_cr = as_bool(key, value)?,
+        "keep-controls" => {
+            app.keep_controls = Some(
+                CharSet::parse_controls(as_str(key, value)?)
+                    .map_err(|e| format!("{}: {}", key, e))?,
+            )
+        }
+        "expand-tabs" => {
+            app.expand_tabs = Some(
+                value
+                    .as_integer()
+                    .and_then(|n| usize::try_from(n).ok())
+                    .filter(|&n| n > 0)
+                    .ok_or_else(|| {
+                        format!("{}: expected a positive integer", key)
+                    })?,
+            )
+        }
+        "max-line-length" => {
+            app.max_line_length = Some(
+                value
+                    .as_integer()
+                    .and_then(|n| usize::try_from(n).ok())
+                    .filter(|&n| n > 0)
+                    .ok_or_else(|| {
+                        format!("{}: expected a positive integer", key)
+                    })?,
+            )
+        }
+        "truncate" => app.truncate = as_bool(key, value)?,
+        "wrap" => app.wrap = as_bool(key, value)?,
+        "drop" => app.drop = as_bool(key, value)?,
+        "newlines" => {
+            app.newlines = Some(
+                as_str(key, value)?
+                    .parse()
+                    .map_err(|e| format!("{}: {}", key, e))?,
+            )
+        }
+        "decompress" => app.decompress = as_bool(key, value)?,
+        "compress" => {
+            app.compress = Some(
+                as_str(key, value)?
+                    .parse()
+                    .map_err(|e| format!("{}: {}", key, e))?,
+            )
+        }
+        "from-encoding" => {
+            app.from_encoding = Some(
+                as_str(key, value)?
+                    .parse()
+                    .map_err(|e| format!("{}: {}", key, e))?,
+            )
+        }
+        "to-encoding" => {
+            app.to_encoding = Some(
+                as_str(key, value)?
+                    .parse()
+                    .map_err(|e| format!("{}: {}", key, e))?,
+            )
+        }
+        "invalid-utf8" => {
+            app.invalid_utf8 = as_str(key, value)?
+                .parse()
+                .map_err(|e| format!("{}: {}", key, e))?
+        }
+        "invert" => app.invert = as_bool(key, value)?,
+        "write-bom" => app.write_bom = as_bool(key, value)?,
+        "strip-bom" => app.strip_bom = as_bool(key, value)?,
+        "out-null" => app.out_null = as_bool(key, value)?,
+        "null" => app.null = as_bool(key, value)?,
+        "out-record-sep" => {
+            app.out_record_sep = Some(as_str(key, value)?.to_string())
+        }
+        "logfmt" => app.logfmt = as_bool(key, value)?,
+        "syslog" => app.syslog = as_bool(key, value)?,
+        "journal-export" => app.journal_export = as_bool(key, value)?,
+        "docker-json" => app.docker_json = as_bool(key, value)?,
+        "json" => app.json = as_bool(key, value)?,
+        "json-keys" => app.json_keys = as_bool(key, value)?,
+        "csv" => app.csv = as_bool(key, value)?,
+        "tsv" => app.tsv = as_bool(key, value)?,
+        "markdown" => app.markdown = as_bool(key, value)?,
+        "columns" => app.columns = Some(parse_columns(as_str(key, value)?)?),
+        #[cfg(feature = "unicode-normalization")]
+        "normalize" => {
+            app.normalize = Some(
+                as_str(key, value)?
+                    .parse()
+                    .map_err(|e| format!("{}: {}", key, e))?,
+            )
+        }
+        "translit-locale" => {
+            app.translit_locale = Some(
+                as_str(key, value)?
+                    .parse()
+                    .map_err(|e| format!("{}: {}", key, e))?,
+            )
+        }
+        "translit-rules" => app.translit_rules = as_paths(key, value, dir)?,
+        "map" => app.map = as_paths(key, value, dir)?,
+        "greek" => app.greek = as_bool(key, value)?,
+        "units" => app.units = as_bool(key, value)?,
+        "symbols" => app.symbols = as_bool(key, value)?,
+        "arrows" => app.arrows = as_bool(key, value)?,
+        "box-drawing" => app.box_drawing = as_bool(key, value)?,
+        "bullets" => app.bullets = as_bool(key, value)?,
+        "spaces" => app.spaces = as_bool(key, value)?,
+        "roman" => app.roman = as_bool(key, value)?,
+        "enclosed" => app.enclosed = as_bool(key, value)?,
+        "full-width" => app.full_width = as_bool(key, value)?,
+        "math" => app.math = as_bool(key, value)?,
+        "punctuation" => app.punctuation = as_bool(key, value)?,
+        "transliterate" => app.transliterate = as_bool(key, value)?,
+        #[cfg(feature = "unicode-confusables")]
+        "confusables" => app.confusables = as_bool(key, value)?,
+        #[cfg(feature = "unicode-normalization")]
+        "strip-diacritics" => app.strip_diacritics = as_bool(key, value)?,
+        "replace" => app.replace = Some(as_str(key, value)?.to_string()),
+        "squeeze-replacements" => {
+            app.squeeze_replacements = Some(
+                value
+                    .as_integer()
+                    .and_then(|n| usize::try_from(n).ok())
+                    .filter(|&n| n > 0)
+                    .ok_or_else(|| {
+                        format!("{}: expected a positive integer", key)
+                    })?,
+            )
+        }
+        "collapse" => app.collapse = Some(as_str(key, value)?.to_string()),
+        "word-sep" => app.word_sep = Some(as_str(key, value)?.to_string()),
+        "drop-lines" => app.drop_lines = as_bool(key, value)?,
+        "line-numbers" => app.line_numbers = as_bool(key, value)?,
+        "with-filename" => app.with_filename = as_bool(key, value)?,
+        "no-filename" => app.no_filename = as_bool(key, value)?,
+        "highlight" => {
+            app.highlight = match as_str(key, value)? {
+                when @ ("auto" | "always") => Some(when.to_string()),
+                when => {
+                    return Err(format!("{}: unknown value {:?}", key, when))
+                }
+            }
+        }
+        "escape" => {
+            app.escape = Some(
+                as_str(key, value)?
+                    .parse()
+                    .map_err(|e| format!("{}: {}", key, e))?,
+            )
+        }
+        "escape-controls" => {
+            app.escape_controls = Some(
+                as_str(key, value)?
+                    .parse()
+                    .map_err(|e| format!("{}: {}", key, e))?,
+            )
+        }
+        "verify" => app.verify = as_bool(key, value)?,
+        "check-idempotent" => app.check_idempotent = as_bool(key, value)?,
+        "assert-clean" => app.assert_clean = as_bool(key, value)?,
+        "strict" => app.strict = as_bool(key, value)?,
+        "stats" => app.stats = as_bool(key, value)?,
+        "stats-top" => {
+            app.stats_top = Some(
+                value
+                    .as_integer()
+                    .and_then(|n| usize::try_from(n).ok())
+                    .filter(|&n| n > 0)
+                    .ok_or_else(|| {
+                        format!("{}: expected a positive integer", key)
+                    })?,
+            )
+        }
+        "verbose" => {
+            app.verbose = value
+                .as_integer()
+                .and_then(|n| u8::try_from(n).ok())
+                .ok_or_else(|| format!("{}: expected a level, e.g. 2", key))?
+        }
+        "rejects" => app.rejects = Some(dir.join(as_str(key, value)?)),
+        "reject-positions" => app.reject_positions = as_bool(key, value)?,
+        "rejects-hex" => app.rejects_hex = Some(dir.join(as_str(key, value)?)),
+        "report" => {
+            app.report = Some(
+                as_str(key, value)?
+                    .parse()
+                    .map_err(|e| format!("{}: {}", key, e))?,
+            )
+        }
+        "report-output" => {
+            app.report_output = Some(dir.join(as_str(key, value)?))
+        }
+        "undo-log" => app.undo_log = Some(dir.join(as_str(key, value)?)),
+        "source-map" => app.source_map = Some(dir.join(as_str(key, value)?)),
+        "no-skip-binary" => app.no_skip_binary = as_bool(key, value)?,
+        "no-ignore" => app.no_ignore = as_bool(key, value)?,
+        "hidden" => app.hidden = as_bool(key, value)?,
+        "output" => app.output = Some(dir.join(as_str(key, value)?)),
+        "threads" => {
+            app.threads = Some(
+                value
+                    .as_integer()
+                    .and_then(|n| usize::try_from(n).ok())
+                    .filter(|&n| n > 0)
+                    .ok_or_else(|| {
+                        format!("{}: expected a positive integer", key)
+                    })?,
+            )
+        }
+        "output-fd" => {
+            app.output_fd = Some(
+                value
+                    .as_integer()
+                    .and_then(|n| i32::try_from(n).ok())
+                    .ok_or_else(|| {
+                        format!("{}: expected a file descriptor", key)
+                    })?,
+            )
+        }
+        "timeout" => {
+            app.timeout = Some(
+                value
+                    .as_float()
+                    .or_else(|| value.as_integer().map(|n| n as f64))
+                    .filter(|&secs| secs > 0.0)
+                    .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+                    .ok_or_else(|| {
+                        format!(
+                            "{}: expected a positive number of seconds",
+                            key
+                        )
+                    })?,
+            )
+        }
+        "idle-timeout" => {
+            app.idle_timeout = Some(
+                match value {
+                    Value::String(s) => parse_duration(s),
+                    _ => parse_duration(&value.to_string()),
+                }
+                .map_err(|e| format!("{}: {}", key, e))?,
+            )
+        }
+        "line-buffered" => app.line_buffered = as_bool(key, value)?,
+        "interactive" => app.interactive = as_bool(key, value)?,
+        "follow" => app.follow = as_bool(key, value)?,
+        "progress" => app.progress = as_bool(key, value)?,
+        "rate-limit" => {
+            app.rate_limit = Some(
+                match value {
+                    Value::String(s) => parse_rate(s),
+                    _ => parse_rate(&value.to_string()),
+                }
+                .map_err(|e| format!("{}: {}", key, e))?,
+            )
+        }
+        "lines-per-sec" => {
+            app.lines_per_sec = Some(
+                value
+                    .as_integer()
+                    .and_then(|n| u64::try_from(n).ok())
+                    .filter(|&n| n > 0)
+                    .ok_or_else(|| {
+                        format!("{}: expected a positive integer", key)
+                    })?,
+            )
+        }
+        "follow-symlinks" => app.follow_symlinks = as_bool(key, value)?,
+        "hard-links" => {
+            app.hard_links = match as_str(key, value)? {
+                policy @ ("preserve" | "break") => policy.to_string(),
+                policy => {
+                    return Err(format!("{}: unknown policy {:?}", key, policy))
+                }
+            }
+        }
+        key => return Err(format!("unknown option {:?}", key)),
+    }
+    Ok(())
+}
+
+/// Apply the `settings` to the options of `app` not given on the command
+/// line.
+fn apply_table(
+    app: &mut App,
+    matches: &ArgMatches,
+    settings: &Table,
+    dir: &Path,
+) -> Result<(), String> {
+    for (key, value) in settings {
+        let overridden = given(matches, key)
+            || EXCLUSIVE.iter().any(|keys| {
+                keys.contains(&key.as_str())
+                    && keys.iter().any(|key| given(matches, key))
+            });
+        if !overridden {
+            set(app, key, value, dir)?;
+        }
+    }
+    Ok(())
+}
+
+/// Return the options of `app` for each MIME type in `types`, with their
+/// profiles in `profiles` or their own settings applied.
+fn apply_types(
+    app: &App,
+    matches: &ArgMatches,
+    types: &Table,
+    profiles: &Table,
+    dir: &Path,
+) -> Result<Vec<(String, App)>, String> {
+    let mut apps: Vec<(String, App)> = Vec::new();
+    for (mime, policy) in types {
+        let settings = match policy {
+            Value::String(name) => match profiles.get(name) {
+                Some(Value::Table(profile)) => profile,
+                Some(_) => {
+                    return Err(format!("profile {:?}: expected a table", name))
+                }
+                None => {
+                    return Err(format!(
+                        "types: {}: no profile {:?}",
+                        mime, name
+                    ))
+                }
+            },
+            Value::Table(settings) => settings,
+            _ => {
+                return Err(format!(
+                    "types: {}: expected a profile name or a table",
+                    mime
+                ))
+            }
+        };
+        let mut type_app = app.clone();
+        apply_table(&mut type_app, matches, settings, dir)
+            .map_err(|e| format!("types: {}: {}", mime, e))?;
+        apps.push((mime.clone(), type_app));
+    }
+    Ok(apps)
+}
+
+/// Apply the config file of `app`, or the default one if it exists, and the
+/// profile selected. Return the options for each MIME type in the `types`
+/// table.
+pub(crate) fn apply(
+    app: &mut App,
+    matches: &ArgMatches,
+) -> Result<Vec<(String, App)>, String> {
+    let path = match &app.config {
+        Some(path) => path.clone(),
+        None => match default_path() {
+            Some(path) if path.exists() => path,
+            _ => {
+                return match &app.profile {
+                    Some(name) => {
+                        Err(format!("profile {:?}: no config file found", name))
+                    }
+                    None => Ok(Vec::new()),
+                }
+            }
+        },
+    };
+    let err = |e: &dyn std::fmt::Display| format!("{}: {}", path.display(), e);
+    let config = std::fs::read_to_string(&path).map_err(|e| err(&e))?;
+    let mut config: Table = config.parse().map_err(|e| err(&e))?;
+    let profiles = match config.remove("profiles") {
+        None => Table::new(),
+        Some(Value::Table(profiles)) => profiles,
+        Some(_) => return Err(err(&"profiles: expected a table")),
+    };
+    let types = match config.remove("types") {
+        None => Table::new(),
+        Some(Value::Table(types)) => types,
+        Some(_) => return Err(err(&"types: expected a table")),
+    };
+    let dir = path.parent().unwrap_or(Path::new(".")).to_path_buf();
+    apply_table(app, matches, &config, &dir).map_err(|e| err(&e))?;
+    if let Some(name) = app.profile.clone() {
+        match profiles.get(&name) {
+            Some(Value::Table(profile)) => {
+                apply_table(app, matches, profile, &dir)
+                    .map_err(|e| err(&format!("profile {:?}: {}", name, e)))?
+            }
+            Some(_) => {
+                return Err(err(&format!(
+                    "profile {:?}: expected a table",
+                    name
+                )))
+            }
+            None => return Err(err(&format!("no profile {:?}", name))),
+        }
+    }
+    apply_types(app, matches, &types, &profiles, &dir).map_err(|e| err(&e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::apply;
+    use crate::{command, parse_rate, parse_size, App};
+    use clap::FromArgMatches;
+    use std::ffi::OsString;
+    use std::fs;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn parse_types(
+        config: &str,
+        args: &[&str],
+    ) -> Result<(App, Vec<(String, App)>), String> {
+        let dir = std::env::temp_dir()
+            .join(format!("ascii-filter-config-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        // a file per call, since the tests run in parallel
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        let n = CALLS.fetch_add(1, Ordering::Relaxed);
+        let path = dir.join(format!("{}.toml", n));
+        fs::write(&path, config).unwrap();
+        let mut argv = vec!["ascii-filter", "--config", path.to_str().unwrap()];
+        argv.extend(args);
+        let argv: Vec<OsString> =
+            argv.into_iter().map(OsString::from).collect();
+        let matches = command(&argv).get_matches_from(argv);
+        let mut app = App::from_arg_matches(&matches).unwrap();
+        let result = apply(&mut app, &matches);
+        fs::remove_file(&path).unwrap();
+        result.map(|types| (app, types))
+    }
+
+    fn parse(config: &str, args: &[&str]) -> Result<App, String> {
+        parse_types(config, args).map(|(app, _)| app)
+    }
+
+    #[test]
+    fn test_apply_profile() {
+        let config = "buffer-size = 64\nsymbols = true\n\
+                      [profiles.logs]\nascii-only = true\nlogfmt = true\n";
+        let app = parse(config, &["--profile", "logs", "--syslog"]).unwrap();
+        assert_eq!(app.buf_size, 64);
+        assert!(app.symbols && app.ascii_only && app.syslog && !app.logfmt);
+
+        let app = parse(config, &["-b", "32"]).unwrap();
+        assert_eq!(app.buf_size, 32);
+        assert!(!app.ascii_only);
+
+        let app = parse("buffer-size = \"64K\"\n", &["-a"]).unwrap();
+        assert_eq!(app.buf_size, 64 << 10);
+        let app = parse(config, &["--buffer-size", "4MiB"]).unwrap();
+        assert_eq!(app.buf_size, 4 << 20);
+        assert!(parse("buffer-size = 2\n", &[]).is_err());
+        assert_eq!(parse_size("1 m"), Ok(1 << 20));
+        assert!(parse_size("0").is_err() && parse_size("1X").is_err());
+
+        assert!(parse(config, &["--profile", "email"]).is_err());
+        assert!(parse("asci-only = true\n", &[]).is_err());
+    }
+
+    #[test]
+    fn test_parse_size_and_rate() {
+        assert_eq!(parse_size("4"), Ok(4));
+        assert_eq!(parse_size("64kib"), Ok(64 << 10));
+        assert!(parse_size("3").is_err());
+        assert!(parse_size("K").is_err());
+        assert_eq!(parse_rate("9600"), Ok(9600));
+        assert_eq!(parse_rate("1MB/s"), Ok(1 << 20));
+        assert_eq!(parse_rate("2 g/s"), Ok(2 << 30));
+        assert!(parse_rate("0/s").is_err());
+        // bad suffixes
+        for s in ["1X", "1 TB", "1K/m", "1/s/s", "-1"] {
+            assert!(parse_size(s).is_err() && parse_rate(s).is_err(), "{}", s);
+        }
+        // overflows
+        assert!(parse_rate("18446744073709551616").is_err());
+        assert!(parse_rate("17179869184G").is_err());
+        assert!(parse_size("18014398509481984K").is_err());
+        assert_eq!(
+            parse_size("1X"),
+            Err("\"1X\" is not a buffer size of at least 4 bytes, e.g. 64K \
+                 or 1M"
+                .to_string())
+        );
+    }
+
+    #[test]
+    fn test_apply_env() {
+        // the only test of these options, since the tests run in parallel
+        std::env::set_var("ASCII_FILTER_STRIP_ANSI", "true");
+        std::env::set_var("ASCII_FILTER_EXPAND_TABS", "2");
+        let config = "strip-ansi = false\nexpand-tabs = 4\n";
+        let app = parse(config, &[]).unwrap();
+        assert!(app.strip_ansi);
+        assert_eq!(app.expand_tabs, Some(2));
+        let app = parse(config, &["--expand-tabs=3"]).unwrap();
+        assert_eq!(app.expand_tabs, Some(3));
+        std::env::remove_var("ASCII_FILTER_STRIP_ANSI");
+        std::env::remove_var("ASCII_FILTER_EXPAND_TABS");
+    }
+
+    #[cfg(feature = "unicode-normalization")]
+    #[test]
+    fn test_apply_profile_filter() {
+        let config = "[profiles.logs]\nallow = \"0x20-0x7E,\\\\n\"\n\
+                      replace = \"?\"\nnormalize = \"nfkc\"\n";
+        let app = parse(config, &["--profile", "logs"]).unwrap();
+        let allow = app.allow.unwrap();
+        assert!(allow.contains('~') && allow.contains('\n'));
+        assert!(!allow.contains('\t'));
+        assert_eq!(app.replace.as_deref(), Some("?"));
+        assert_eq!(app.normalize.map(|n| n.name()), Some("nfkc"));
+
+        // the command line overrides the replacement policy as a whole
+        let app = parse(config, &["--profile", "logs", "--escape"]).unwrap();
+        assert!(app.replace.is_none() && app.escape.is_some());
+
+        assert!(parse("allow = \"z-a\"\n", &[]).is_err());
+    }
+
+    #[test]
+    fn test_apply_types() {
+        let config = "symbols = true\n\
+                      [profiles.code]\nascii-only = true\n\
+                      [types]\n\"text/x-python\" = \"code\"\n\
+                      \"text/csv\" = { symbols = false }\n";
+        let (app, types) = parse_types(config, &["-r", "-b", "32"]).unwrap();
+        assert!(app.symbols && !app.ascii_only);
+        let python =
+            &types.iter().find(|(t, _)| t == "text/x-python").unwrap().1;
+        assert!(python.symbols && python.ascii_only && python.buf_size == 32);
+        let csv = &types.iter().find(|(t, _)| t == "text/csv").unwrap().1;
+        assert!(!csv.symbols && !csv.ascii_only);
+
+        assert!(parse("[types]\n\"text/csv\" = \"none\"\n", &[]).is_err());
+    }
+}
@@ -0,0 +1,147 @@
+//! Output to a Windows console through the wide-char console API, which
+//! shows any text whatever the code page of the console, whereas the bytes
+//! of UTF-8 written to a console in a legacy code page show as mojibake.
+
+use std::convert::Infallible;
+use std::io::{self, Write};
+
+use ascii_filter_core::utf8::{Utf8Chunk, Utf8Decoder};
+
+/// Takes text as UTF-16, as a console does.
+pub(crate) trait WideWrite {
+    fn write_wide(&mut self, units: &[u16]) -> io::Result<()>;
+}
+
+/// Writes the UTF-8 written to it to the inner writer as UTF-16, carrying an
+/// incomplete char over to the next write, with U+FFFD for each invalid
+/// sequence rather than failing, e.g. on the output of `--invalid-utf8
+/// keep`.
+pub(crate) struct Console<W: WideWrite> {
+    inner: W,
+    utf8: Utf8Decoder,
+    units: Vec<u16>,
+}
+
+impl<W: WideWrite> Console<W> {
+    pub(crate) fn new(inner: W) -> Self {
+        Self {
+            inner,
+            utf8: Utf8Decoder::default(),
+            units: Vec::new(),
+        }
+    }
+}
+
+impl<W: WideWrite> Write for Console<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.units.clear();
+        let units = &mut self.units;
+        let _ = self.utf8.decode(buf, |chunk| {
+            match chunk {
+                Utf8Chunk::Valid(s) => units.extend(s.encode_utf16()),
+                Utf8Chunk::Invalid(_) => units.push(0xFFFD),
+            }
+            Ok::<(), Infallible>(())
+        });
+        self.inner.write_wide(&self.units)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<W: WideWrite> Drop for Console<W> {
+    /// Write the incomplete char left at the end, if any, as U+FFFD.
+    fn drop(&mut self) {
+        if !self.utf8.finish().is_empty() {
+            let _ = self.inner.write_wide(&[0xFFFD]);
+        }
+    }
+}
+
+/// The console of stdout.
+#[cfg(windows)]
+pub(crate) struct Stdout;
+
+#[cfg(windows)]
+impl WideWrite for Stdout {
+    fn write_wide(&mut self, mut units: &[u16]) -> io::Result<()> {
+        use std::ffi::c_void;
+        use std::os::windows::io::AsRawHandle;
+
+        #[link(name = "kernel32")]
+        extern "system" {
+            fn WriteConsoleW(
+                console: *mut c_void,
+                buf: *const c_void,
+                len: u32,
+                written: *mut u32,
+                reserved: *const c_void,
+            ) -> i32;
+        }
+
+        /// The most units written at once, as the console may take no more.
+        const MAX_UNITS: usize = 8 << 10;
+
+        let handle = io::stdout().as_raw_handle();
+        while !units.is_empty() {
+            let mut len = units.len().min(MAX_UNITS);
+            // keep a surrogate pair together
+            let high = 0xD800..0xDC00;
+            if len < units.len() && high.contains(&units[len - 1]) {
+                len -= 1;
+            }
+            let mut written = 0;
+            // SAFETY: `units[..len]` is valid for reads, and `written` for
+            // the write of the count.
+            let ok = unsafe {
+                WriteConsoleW(
+                    handle,
+                    units.as_ptr().cast(),
+                    len as u32,
+                    &mut written,
+                    std::ptr::null(),
+                )
+            };
+            if ok == 0 {
+                return Err(io::Error::last_os_error());
+            }
+            if written == 0 {
+                return Err(io::ErrorKind::WriteZero.into());
+            }
+            units = &units[written as usize..];
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Console, WideWrite};
+    use std::io::{self, Write};
+
+    impl WideWrite for &mut Vec<u16> {
+        fn write_wide(&mut self, units: &[u16]) -> io::Result<()> {
+            self.extend_from_slice(units);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_console() {
+        let mut units: Vec<u16> = Vec::new();
+        let mut console = Console::new(&mut units);
+        // a char split across writes, an invalid byte and an incomplete char
+        // at the end
+        for chunk in ["naï".as_bytes(), b"ve \xf0\x9f", b"\x91\x8d\xff \xc3"] {
+            console.write_all(chunk).unwrap();
+        }
+        drop(console);
+        assert_eq!(
+            String::from_utf16(&units).unwrap(),
+            "naïve 👍\u{fffd} \u{fffd}"
+        );
+    }
+}
@@ -0,0 +1,208 @@
+//! Filtering of CSV and TSV, where only the fields of the columns selected
+//! are filtered, and the delimiters, the line endings and the quoting are
+//! kept, so that e.g. the free-text fields of an export are sanitized without
+//! touching its IDs and codes.
+
+use std::io::{self, BufRead, Write};
+
+/// A field as read so far.
+#[derive(Default)]
+struct Field {
+    /// If true, the field is quoted.
+    quoted: bool,
+    /// If true, the field is quoted, and its closing quote is not read yet.
+    open: bool,
+    /// If true, the last byte read is a quote within the quoted field, which
+    /// either closes it or escapes the next.
+    quote: bool,
+    /// The content of the field, unescaped if quoted.
+    content: Vec<u8>,
+    /// What follows the closing quote, if any, e.g. the '\r' of a CRLF.
+    rest: Vec<u8>,
+}
+
+/// Write `content` quoted to `out`, with its quotes doubled.
+fn write_quoted(content: &[u8], closed: bool, out: &mut Vec<u8>) {
+    out.push(b'"');
+    for &b in content {
+        if b == b'"' {
+            out.push(b'"');
+        }
+        out.push(b);
+    }
+    if closed {
+        out.push(b'"');
+    }
+}
+
+impl Field {
+    /// Write the field to `out`, transformed by `filter` if `filtered`, and
+    /// quoted if it was, or if it has to be once transformed.
+    fn write<F>(
+        &self,
+        delimiter: u8,
+        filtered: bool,
+        filter: &mut F,
+        out: &mut Vec<u8>,
+    ) where
+        F: FnMut(&[u8]) -> Vec<u8>,
+    {
+        let content = match filtered {
+            true => filter(&self.content),
+            false => self.content.clone(),
+        };
+        let special =
+            |b: &u8| matches!(*b, b'"' | b'\n' | b'\r') || *b == delimiter;
+        if self.quoted || content.iter().any(special) {
+            write_quoted(&content, !self.open, out);
+        } else {
+            out.extend_from_slice(&content);
+        }
+        out.extend_from_slice(&self.rest);
+    }
+}
+
+/// Read CSV, or TSV with `delimiter` '\t', from `r`, and write it to `w` with
+/// each field of the `columns`, counted from 1, or of all the columns if
+/// `None`, transformed by `filter`. Quoted fields, which may span lines, are
+/// unescaped before, and escaped after. The input is not validated, so that
+/// e.g. a stray quote within an unquoted field is kept as is.
+pub(crate) fn filter<R, W, F>(
+    r: &mut R,
+    w: &mut W,
+    delimiter: u8,
+    columns: Option<&[usize]>,
+    filter: &mut F,
+) -> io::Result<()>
+where
+    R: BufRead,
+    W: Write,
+    F: FnMut(&[u8]) -> Vec<u8>,
+{
+    let selected =
+        |column: usize| columns.is_none_or(|columns| columns.contains(&column));
+    let mut field = Field::default();
+    // the column of `field`, counted from 1
+    let mut column = 1;
+    // If true, `field` is empty and not quoted, so that nothing is pending.
+    let mut empty = true;
+    let mut out: Vec<u8> = Vec::new();
+    loop {
+        let buf = r.fill_buf()?;
+        if buf.is_empty() {
+            break;
+        }
+        for &byte in buf {
+            if field.open {
+                if std::mem::take(&mut field.quote) {
+                    if byte == b'"' {
+                        field.content.push(b'"');
+                        continue;
+                    }
+                    field.open = false;
+                } else {
+                    match byte {
+                        b'"' => field.quote = true,
+                        byte => field.content.push(byte),
+                    }
+                    continue;
+                }
+            }
+            let line_end = byte == b'\n';
+            if byte != delimiter && !line_end {
+                if byte == b'"' && empty {
+                    field.quoted = true;
+                    field.open = true;
+                } else if field.quoted {
+                    field.rest.push(byte);
+                } else {
+                    field.content.push(byte);
+                }
+                empty = false;
+                continue;
+            }
+            // a CRLF ending an unquoted field is not part of it
+            let cr = line_end
+                && !field.quoted
+                && field.content.last() == Some(&b'\r');
+            if cr {
+                field.content.pop();
+            }
+            field.write(delimiter, selected(column), filter, &mut out);
+            if cr {
+                out.push(b'\r');
+            }
+            out.push(byte);
+            field = Field::default();
+            empty = true;
+            column = if line_end { 1 } else { column + 1 };
+        }
+        let len = buf.len();
+        r.consume(len);
+        w.write_all(&out)?;
+        out.clear();
+    }
+    // the last field, not followed by a line ending
+    if !empty || column > 1 {
+        field.open &= !field.quote;
+        field.write(delimiter, selected(column), filter, &mut out);
+        w.write_all(&out)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::filter;
+
+    fn drop_non_ascii(bytes: &[u8]) -> Vec<u8> {
+        bytes.iter().copied().filter(u8::is_ascii).collect()
+    }
+
+    /// Filter `input` in chunks of `len` bytes.
+    fn filter_chunked(
+        input: &str,
+        len: usize,
+        delimiter: u8,
+        columns: Option<&[usize]>,
+    ) -> String {
+        let mut r = std::io::BufReader::with_capacity(len, input.as_bytes());
+        let mut w: Vec<u8> = Vec::new();
+        filter(&mut r, &mut w, delimiter, columns, &mut drop_non_ascii)
+            .unwrap();
+        String::from_utf8(w).unwrap()
+    }
+
+    #[test]
+    fn test_filter() {
+        let input = "id,név,note\r\né1,\"a \"\"ü\"\"\nb\",x→y\r\n\
+                     é2,,\"\"\n";
+        for len in 1..=input.len() {
+            assert_eq!(
+                filter_chunked(input, len, b',', Some(&[2, 3])),
+                "id,nv,note\r\né1,\"a \"\"\"\"\nb\",xy\r\né2,,\"\"\n"
+            );
+        }
+        assert_eq!(
+            filter_chunked(input, 4, b',', None),
+            "id,nv,note\r\n1,\"a \"\"\"\"\nb\",xy\r\n2,,\"\"\n"
+        );
+        // no line ending at the end, and an unterminated quote
+        assert_eq!(
+            filter_chunked("é\té\t\"é", 4, b'\t', Some(&[1, 3])),
+            "\té\t\""
+        );
+        assert_eq!(filter_chunked("a,\"é\"", 4, b',', None), "a,\"\"");
+    }
+
+    #[test]
+    fn test_filter_requote() {
+        let mut comma = |bytes: &[u8]| {
+            let comma = |&b: &u8| if b == b';' { b',' } else { b };
+            bytes.iter().map(comma).collect::<Vec<u8>>()
+        };
+        let mut w: Vec<u8> = Vec::new();
+        filter(&mut &b"a;b,c\n"[..], &mut w, b',', None, &mut comma).unwrap();
+        assert_eq!(w, b"\"a,b\",c\n");
+    }
+}
@@ -0,0 +1,396 @@
+//! Reconnaissance of an input before filtering it: its likely encoding, its
+//! BOM, and the classes of the chars the filter would act on.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Locale, Mapping};
+
+/// A byte order mark.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bom {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Utf32Le,
+    Utf32Be,
+}
+
+impl Bom {
+    /// The BOMs by their bytes, the longer ones first, since the UTF-32LE
+    /// BOM starts with the UTF-16LE one.
+    const ALL: [(Bom, &'static [u8]); 5] = [
+        (Bom::Utf32Le, b"\xff\xfe\0\0"),
+        (Bom::Utf32Be, b"\0\0\xfe\xff"),
+        (Bom::Utf8, b"\xef\xbb\xbf"),
+        (Bom::Utf16Le, b"\xff\xfe"),
+        (Bom::Utf16Be, b"\xfe\xff"),
+    ];
+
+    fn encoding(self) -> Encoding {
+        match self {
+            Bom::Utf8 => Encoding::Utf8,
+            Bom::Utf16Le => Encoding::Utf16Le,
+            Bom::Utf16Be => Encoding::Utf16Be,
+            Bom::Utf32Le => Encoding::Utf32Le,
+            Bom::Utf32Be => Encoding::Utf32Be,
+        }
+    }
+}
+
+/// The likely encoding of an input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Ascii,
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Utf32Le,
+    Utf32Be,
+    /// ISO 8859-1, or Windows-1252, which cannot be told apart reliably.
+    Latin1,
+}
+
+impl Encoding {
+    pub const ALL: [Encoding; 7] = [
+        Encoding::Ascii,
+        Encoding::Utf8,
+        Encoding::Utf16Le,
+        Encoding::Utf16Be,
+        Encoding::Utf32Le,
+        Encoding::Utf32Be,
+        Encoding::Latin1,
+    ];
+
+    /// Return the name of the encoding as `iconv` knows it.
+    pub fn name(self) -> &'static str {
+        match self {
+            Encoding::Ascii => "ASCII",
+            Encoding::Utf8 => "UTF-8",
+            Encoding::Utf16Le => "UTF-16LE",
+            Encoding::Utf16Be => "UTF-16BE",
+            Encoding::Utf32Le => "UTF-32LE",
+            Encoding::Utf32Be => "UTF-32BE",
+            Encoding::Latin1 => "LATIN1",
+        }
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Encoding {
+    type Err = String;
+
+    /// Parse the name of an encoding, in any case, or one of its common
+    /// aliases, e.g. "utf16le", "iso-8859-1" or "cp1252".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.to_ascii_uppercase().replace('_', "-");
+        let encoding = match name.as_str() {
+            "US-ASCII" => Some(Encoding::Ascii),
+            "UTF8" => Some(Encoding::Utf8),
+            "UTF16LE" => Some(Encoding::Utf16Le),
+            "UTF16BE" => Some(Encoding::Utf16Be),
+            "UTF32LE" => Some(Encoding::Utf32Le),
+            "UTF32BE" => Some(Encoding::Utf32Be),
+            "LATIN-1" | "ISO-8859-1" | "ISO8859-1" | "WINDOWS-1252"
+            | "CP1252" => Some(Encoding::Latin1),
+            name => Encoding::ALL.into_iter().find(|e| e.name() == name),
+        };
+        encoding.ok_or_else(|| {
+            let names: Vec<&str> =
+                Encoding::ALL.iter().map(|e| e.name()).collect();
+            format!(
+                "unsupported encoding {:?}, expected one of {}; convert the \
+                 others, e.g. Shift_JIS, with iconv",
+                s,
+                names.join(", ")
+            )
+        })
+    }
+}
+
+/// The outcome of [`detect`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile {
+    pub encoding: Encoding,
+    pub bom: Option<Bom>,
+    /// The number of chars in the ASCII subset kept by `ascii_only`.
+    pub ascii: usize,
+    /// The number of C0 control chars other than tab and '\n', and DEL.
+    pub control: usize,
+    /// The number of C1 control chars.
+    pub c1: usize,
+    /// The number of CJK chars, including kana and Hangul.
+    pub cjk: usize,
+    pub emoji: usize,
+    /// The number of other non-ASCII chars.
+    pub other: usize,
+    /// The number of bytes invalid in the encoding.
+    pub invalid: usize,
+    /// The builtin mappings, and transliterations for each locale, which map
+    /// chars of the input, with the number of chars they map.
+    pub mappable: Vec<(Mapping, usize)>,
+}
+
+impl Profile {
+    /// Return true if filtering would leave the input as it is, even with
+    /// `ascii_only`.
+    pub fn is_clean(&self) -> bool {
+        self.encoding == Encoding::Ascii
+            && self.bom.is_none()
+            && self.control == 0
+    }
+}
+
+/// The mappings [`detect`] counts the chars of.
+fn builtin_mappings() -> Vec<Mapping> {
+    let mut mappings: Vec<Mapping> =
+        Locale::ALL.into_iter().map(Mapping::Translit).collect();
+    mappings.extend([
+        Mapping::Greek,
+        Mapping::Units,
+        Mapping::Symbols,
+        Mapping::Arrows,
+        Mapping::BoxDrawing,
+        Mapping::Bullets,
+        Mapping::Roman,
+        Mapping::Enclosed,
+        Mapping::FullWidth,
+        Mapping::Math,
+        Mapping::Punctuation,
+        Mapping::Spaces,
+        Mapping::Unidecode,
+    ]);
+    #[cfg(feature = "unicode-confusables")]
+    mappings.push(Mapping::Confusables);
+    mappings
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{1100}'..='\u{11ff}'
+        | '\u{2e80}'..='\u{2fdf}'
+        | '\u{3000}'..='\u{303f}'
+        | '\u{3040}'..='\u{31ff}'
+        | '\u{3400}'..='\u{4dbf}'
+        | '\u{4e00}'..='\u{9fff}'
+        | '\u{ac00}'..='\u{d7af}'
+        | '\u{f900}'..='\u{faff}'
+        | '\u{20000}'..='\u{3ffff}')
+}
+
+fn is_emoji(c: char) -> bool {
+    matches!(c, '\u{2600}'..='\u{27bf}' | '\u{1f000}'..='\u{1faff}')
+}
+
+/// Decode `bytes` as UTF-8, calling `f` with each char, and return the
+/// number of invalid bytes.
+fn decode_utf8<F: FnMut(char)>(bytes: &[u8], mut f: F) -> usize {
+    let mut invalid: usize = 0;
+    let mut rest = bytes;
+    while !rest.is_empty() {
+        let (valid, n) = match std::str::from_utf8(rest) {
+            Ok(s) => (s, rest.len()),
+            Err(e) => {
+                let valid = e.valid_up_to();
+                let len = e.error_len().unwrap_or(rest.len() - valid);
+                invalid += len;
+                (std::str::from_utf8(&rest[..valid]).unwrap(), valid + len)
+            }
+        };
+        valid.chars().for_each(&mut f);
+        rest = &rest[n..];
+    }
+    invalid
+}
+
+/// Decode `bytes` as UTF-16 or UTF-32 units of `width` bytes, calling `f`
+/// with each char, and return the number of invalid bytes.
+fn decode_wide<F: FnMut(char)>(
+    bytes: &[u8],
+    width: usize,
+    big_endian: bool,
+    mut f: F,
+) -> usize {
+    let units = bytes.chunks_exact(width).map(|unit| {
+        let unit = unit.iter().map(|&b| b as u32);
+        if big_endian {
+            unit.fold(0, |n, b| n << 8 | b)
+        } else {
+            unit.rev().fold(0, |n, b| n << 8 | b)
+        }
+    });
+    let mut invalid = bytes.len() % width;
+    if width == 2 {
+        for c in char::decode_utf16(units.map(|unit| unit as u16)) {
+            match c {
+                Ok(c) => f(c),
+                Err(_) => invalid += 2,
+            }
+        }
+    } else {
+        for unit in units {
+            match char::from_u32(unit) {
+                Some(c) => f(c),
+                None => invalid += 4,
+            }
+        }
+    }
+    invalid
+}
+
+/// Guess the encoding of `bytes` without a BOM.
+fn guess_encoding(bytes: &[u8]) -> Encoding {
+    if bytes.is_ascii() {
+        return Encoding::Ascii;
+    }
+    if std::str::from_utf8(bytes).is_ok() {
+        return Encoding::Utf8;
+    }
+    // text in UTF-16 has NULs in every other byte, mostly
+    let pairs = bytes.len() / 2;
+    let nul_at = |parity: usize| {
+        bytes
+            .iter()
+            .skip(parity)
+            .step_by(2)
+            .filter(|&&b| b == 0)
+            .count()
+    };
+    if pairs > 0 && nul_at(1) * 5 > pairs * 2 {
+        return Encoding::Utf16Le;
+    }
+    if pairs > 0 && nul_at(0) * 5 > pairs * 2 {
+        return Encoding::Utf16Be;
+    }
+    // Latin-1 text has mostly lone high bytes, which are invalid UTF-8,
+    // rather than multibyte sequences, and hardly any control chars, unlike
+    // corrupted UTF-8
+    let mut multibyte: usize = 0;
+    let invalid = decode_utf8(bytes, |c| multibyte += (!c.is_ascii()) as usize);
+    let control = bytes
+        .iter()
+        .filter(|&&b| b < 0x20 && !matches!(b, b'\t' | b'\n' | b'\r'))
+        .count();
+    if multibyte * 4 < invalid && control * 20 < invalid {
+        Encoding::Latin1
+    } else {
+        Encoding::Utf8
+    }
+}
+
+/// Profile `bytes`, the whole input.
+pub fn detect(bytes: &[u8]) -> Profile {
+    let found = Bom::ALL
+        .into_iter()
+        .find(|(_, bom_bytes)| bytes.starts_with(bom_bytes));
+    let (bom, encoding, text) = match found {
+        Some((bom, bom_bytes)) => {
+            (Some(bom), bom.encoding(), &bytes[bom_bytes.len()..])
+        }
+        None => (None, guess_encoding(bytes), bytes),
+    };
+    let mappings = builtin_mappings();
+    let mut profile = Profile {
+        encoding,
+        bom,
+        ascii: 0,
+        control: 0,
+        c1: 0,
+        cjk: 0,
+        emoji: 0,
+        other: 0,
+        invalid: 0,
+        mappable: mappings.iter().map(|m| (m.clone(), 0)).collect(),
+    };
+    let mut classify = |c: char| {
+        match c {
+            '\t' | '\n' | ' '..='~' => profile.ascii += 1,
+            '\0'..='\x1f' | '\x7f' => profile.control += 1,
+            '\u{80}'..='\u{9f}' => profile.c1 += 1,
+            c if is_cjk(c) => profile.cjk += 1,
+            c if is_emoji(c) => profile.emoji += 1,
+            _ => profile.other += 1,
+        }
+        if !c.is_ascii() {
+            for (mapping, n) in &mut profile.mappable {
+                *n += mapping.get(c).is_some() as usize;
+            }
+        }
+    };
+    let invalid = match encoding {
+        Encoding::Ascii | Encoding::Utf8 => decode_utf8(text, &mut classify),
+        Encoding::Latin1 => {
+            text.iter().for_each(|&b| classify(b as char));
+            0
+        }
+        Encoding::Utf16Le => decode_wide(text, 2, false, &mut classify),
+        Encoding::Utf16Be => decode_wide(text, 2, true, &mut classify),
+        Encoding::Utf32Le => decode_wide(text, 4, false, &mut classify),
+        Encoding::Utf32Be => decode_wide(text, 4, true, &mut classify),
+    };
+    profile.invalid = invalid;
+    profile.mappable.retain(|&(_, n)| n > 0);
+    profile
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{detect, Bom, Encoding};
+    use crate::{Locale, Mapping};
+
+    #[test]
+    fn test_detect() {
+        let profile = detect(b"plain text\n");
+        assert_eq!(profile.encoding, Encoding::Ascii);
+        assert!(profile.is_clean());
+
+        let profile = detect("\u{feff}a\u{1b}\u{85}ö→漢😀\n".as_bytes());
+        assert_eq!(profile.encoding, Encoding::Utf8);
+        assert_eq!(profile.bom, Some(Bom::Utf8));
+        assert_eq!((profile.ascii, profile.control, profile.c1), (2, 1, 1));
+        assert_eq!((profile.cjk, profile.emoji, profile.other), (1, 1, 2));
+        assert!(profile
+            .mappable
+            .contains(&(Mapping::Translit(Locale::De), 1)));
+        assert!(profile.mappable.contains(&(Mapping::Arrows, 1)));
+        assert!(!profile.is_clean());
+
+        let profile = detect(b"caf\xe9 cr\xe8me\n");
+        assert_eq!(profile.encoding, Encoding::Latin1);
+        assert_eq!((profile.other, profile.invalid), (2, 0));
+
+        let utf16: Vec<u8> =
+            "hé\n".encode_utf16().flat_map(u16::to_le_bytes).collect();
+        let profile = detect(&utf16);
+        assert_eq!(profile.encoding, Encoding::Utf16Le);
+        assert_eq!((profile.ascii, profile.other), (2, 1));
+
+        let profile = detect(b"ab\xffc\xc3\xa9\xc3\xa9");
+        assert_eq!(profile.encoding, Encoding::Utf8);
+        assert_eq!((profile.other, profile.invalid), (2, 1));
+    }
+
+    #[test]
+    fn test_encoding_from_str() {
+        for encoding in Encoding::ALL {
+            assert_eq!(encoding.to_string().parse(), Ok(encoding));
+        }
+        assert_eq!("utf16le".parse(), Ok(Encoding::Utf16Le));
+        assert_eq!("cp1252".parse(), Ok(Encoding::Latin1));
+        assert_eq!("iso_8859-1".parse(), Ok(Encoding::Latin1));
+        assert_eq!(
+            "shift_jis".parse::<Encoding>(),
+            Err(
+                "unsupported encoding \"shift_jis\", expected one of ASCII, \
+                 UTF-8, UTF-16LE, UTF-16BE, UTF-32LE, UTF-32BE, LATIN1; \
+                 convert the others, e.g. Shift_JIS, with iconv"
+                    .to_string()
+            )
+        );
+    }
+}
@@ -0,0 +1,95 @@
+//! Filtering of Docker/Kubernetes JSON log lines, as in
+//! `{"log":"...","stream":"stdout","time":"..."}`, where only the `log` field
+//! is filtered and then re-escaped, so that log collectors can still parse the
+//! result.
+
+use crate::json;
+
+/// Filter the `log` field of one JSON log `line` with `filter`, which maps raw
+/// bytes to filtered bytes. A line that cannot be parsed is filtered as a
+/// whole.
+pub(crate) fn filter_line<F: FnMut(&[u8]) -> Vec<u8>>(
+    line: &[u8],
+    filter: &mut F,
+) -> Vec<u8> {
+    match filter_log_field(line, filter) {
+        Some(out) => out,
+        None => filter(line),
+    }
+}
+
+fn filter_log_field<F: FnMut(&[u8]) -> Vec<u8>>(
+    line: &[u8],
+    filter: &mut F,
+) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(line.len());
+    let mut i = json::skip_ws(line, 0);
+    if line.get(i) != Some(&b'{') {
+        return None;
+    }
+    i += 1;
+    out.extend_from_slice(&line[..i]);
+    loop {
+        let key_start = json::skip_ws(line, i);
+        if line.get(key_start) == Some(&b'}') {
+            break;
+        }
+        let key_end = json::skip_string(line, key_start)?;
+        let colon = json::skip_ws(line, key_end);
+        if line.get(colon) != Some(&b':') {
+            return None;
+        }
+        let value_start = json::skip_ws(line, colon + 1);
+        let value_end = json::skip_value(line, value_start)?;
+        if &line[key_start..key_end] == b"\"log\"" && line[value_start] == b'"'
+        {
+            let log =
+                json::decode_string(&line[value_start + 1..value_end - 1])?;
+            let filtered = filter(log.as_bytes());
+            out.extend_from_slice(&line[i..=value_start]);
+            json::encode_string(&String::from_utf8_lossy(&filtered), &mut out);
+            out.push(b'"');
+        } else {
+            out.extend_from_slice(&line[i..value_end]);
+        }
+        i = json::skip_ws(line, value_end);
+        match line.get(i) {
+            Some(b',') => {
+                out.push(b',');
+                i += 1;
+            }
+            Some(b'}') => break,
+            _ => return None,
+        }
+    }
+    // the closing brace and the line ending
+    out.extend_from_slice(&line[i..]);
+
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::filter_line;
+
+    fn drop_non_ascii(bytes: &[u8]) -> Vec<u8> {
+        bytes.iter().copied().filter(u8::is_ascii).collect()
+    }
+
+    #[test]
+    fn test_filter_line() {
+        let line = r#"{"log":"héllo wörld\n","stream":"stdérr"}"#;
+        assert_eq!(
+            filter_line(line.as_bytes(), &mut drop_non_ascii),
+            r#"{"log":"hllo wrld\n","stream":"stdérr"}"#.as_bytes()
+        );
+    }
+
+    #[test]
+    fn test_filter_line_unparsable() {
+        assert_eq!(
+            filter_line("{\"log\"é".as_bytes(), &mut drop_non_ascii),
+            b"{\"log\""
+        );
+    }
+}
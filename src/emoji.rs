@@ -0,0 +1,282 @@
+//! Handling of emoji as whole sequences, e.g. 👍🏽 or 👩‍💻, rather than as the
+//! separate chars the filter would see.
+
+use std::fmt;
+use std::str::FromStr;
+
+use emojis::{Emoji, SkinTone};
+
+use crate::rules::Rule;
+
+/// What to do with emoji.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmojiMode {
+    /// Drop them, even if they would be kept otherwise.
+    Drop,
+    /// Keep them as is, even if they would be dropped otherwise.
+    Keep,
+    /// Replace them with their GitHub shortcodes, e.g. `:+1:` for 👍.
+    Shortcode,
+}
+
+impl EmojiMode {
+    pub const ALL: [EmojiMode; 3] =
+        [EmojiMode::Drop, EmojiMode::Keep, EmojiMode::Shortcode];
+
+    /// Return the name of the mode, e.g. "shortcode".
+    pub fn name(&self) -> &'static str {
+        match self {
+            EmojiMode::Drop => "drop",
+            EmojiMode::Keep => "keep",
+            EmojiMode::Shortcode => "shortcode",
+        }
+    }
+}
+
+impl fmt::Display for EmojiMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for EmojiMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        EmojiMode::ALL
+            .into_iter()
+            .find(|mode| mode.name() == s)
+            .ok_or_else(|| {
+                format!(
+                    "unknown emoji mode {:?}, expected one of drop, keep, \
+                     shortcode",
+                    s
+                )
+            })
+    }
+}
+
+/// Return true if `c` is a regional indicator, two of which make a flag.
+fn is_regional_indicator(c: char) -> bool {
+    matches!(c, '\u{1f1e6}'..='\u{1f1ff}')
+}
+
+/// Return true if `c` may start an emoji sequence.
+fn may_start(c: char) -> bool {
+    match c {
+        // the bases of keycaps, e.g. 1️⃣
+        '0'..='9' | '#' | '*' => true,
+        c if c.is_ascii() => false,
+        c => {
+            is_regional_indicator(c)
+                || emojis::get(c.encode_utf8(&mut [0; 4])).is_some()
+        }
+    }
+}
+
+/// Return the length of the sequence which `s` starts with, e.g. a base with
+/// a skin tone, a keycap or a ZWJ sequence, and true if the sequence may yet
+/// continue past the end of `s`.
+fn sequence_len(s: &str) -> (usize, bool) {
+    let mut chars = s.char_indices().peekable();
+    let Some((_, first)) = chars.next() else {
+        return (0, true);
+    };
+    let mut len = first.len_utf8();
+    if is_regional_indicator(first) {
+        return match chars.next() {
+            Some((i, c)) if is_regional_indicator(c) => {
+                (i + c.len_utf8(), false)
+            }
+            Some(_) => (len, false),
+            None => (len, true),
+        };
+    }
+    while let Some(&(i, c)) = chars.peek() {
+        match c {
+            // variation selectors, skin tones, keycaps and tags
+            '\u{fe0e}'
+            | '\u{fe0f}'
+            | '\u{1f3fb}'..='\u{1f3ff}'
+            | '\u{20e3}'
+            | '\u{e0020}'..='\u{e007f}' => {
+                chars.next();
+                len = i + c.len_utf8();
+            }
+            '\u{200d}' => {
+                chars.next();
+                match chars.next() {
+                    Some((j, c)) if may_start(c) && !c.is_ascii() => {
+                        len = j + c.len_utf8();
+                    }
+                    Some(_) => return (len, false),
+                    None => return (len, true),
+                }
+            }
+            _ => return (len, false),
+        }
+    }
+    (len, true)
+}
+
+/// Return the emoji `s`, if it is one. A single char which is text by
+/// default, e.g. ©, is one only with the emoji variation selector.
+fn lookup(s: &str) -> Option<&'static Emoji> {
+    let emoji = emojis::get(s)?;
+    let single = s.chars().nth(1).is_none();
+    (!single || emoji.as_str() == s).then_some(emoji)
+}
+
+/// Return the shortcode of `emoji`, e.g. `:+1:`, in the style of Slack for
+/// skin tones, e.g. `:+1::skin-tone-4:`, or made of its name if it has no
+/// shortcode of its own, e.g. `:grinning_face:`.
+fn shortcode(emoji: &Emoji) -> String {
+    if let Some(shortcode) = emoji.shortcode() {
+        return format!(":{}:", shortcode);
+    }
+    let base = emoji.skin_tones().and_then(|mut tones| tones.next());
+    let tone = match emoji.skin_tone() {
+        Some(SkinTone::Light) => Some(2),
+        Some(SkinTone::MediumLight) => Some(3),
+        Some(SkinTone::Medium) => Some(4),
+        Some(SkinTone::MediumDark) => Some(5),
+        Some(SkinTone::Dark) => Some(6),
+        _ => None,
+    };
+    match (base.and_then(Emoji::shortcode), tone) {
+        (Some(base), Some(tone)) => format!(":{}::skin-tone-{}:", base, tone),
+        (Some(base), None) => format!(":{}:", base),
+        (None, _) => {
+            let name: String = emoji
+                .name()
+                .chars()
+                .map(|c| match c {
+                    c if c.is_ascii_alphanumeric() => c.to_ascii_lowercase(),
+                    _ => '_',
+                })
+                .collect();
+            format!(":{}:", name)
+        }
+    }
+}
+
+/// Splits text pushed in pieces into emoji and the rest, holding back a
+/// sequence which may continue in the text pushed next.
+#[derive(Debug, Clone)]
+pub(crate) struct EmojiSplitter {
+    mode: EmojiMode,
+    /// The text not yet split, which starts a sequence.
+    held: String,
+}
+
+impl EmojiSplitter {
+    pub(crate) fn new(mode: EmojiMode) -> Self {
+        Self {
+            mode,
+            held: String::new(),
+        }
+    }
+
+    /// Split `s` after the text held back, returning the spans decided and
+    /// how each applies: emoji are kept, replaced by their shortcodes or
+    /// dropped, and the rest is subject to the char filter.
+    pub(crate) fn push(&mut self, s: &str) -> Vec<(Rule, String)> {
+        self.held.push_str(s);
+        self.split(false)
+    }
+
+    /// Split the text held back, e.g. at the end of input.
+    pub(crate) fn finish(&mut self) -> Vec<(Rule, String)> {
+        self.split(true)
+    }
+
+    fn split(&mut self, end: bool) -> Vec<(Rule, String)> {
+        let text = std::mem::take(&mut self.held);
+        let mut spans: Vec<(Rule, String)> = Vec::new();
+        let mut filtered: usize = 0;
+        let mut i: usize = 0;
+        while i < text.len() {
+            let c = text[i..].chars().next().unwrap();
+            if !may_start(c) {
+                i += c.len_utf8();
+                continue;
+            }
+            let (len, open) = sequence_len(&text[i..]);
+            if open && !end {
+                self.held = text[i..].to_string();
+                break;
+            }
+            // the longest prefix of the sequence which is an emoji
+            let found = text[i..i + len]
+                .char_indices()
+                .map(|(j, c)| i + j + c.len_utf8())
+                .rev()
+                .find_map(|j| lookup(&text[i..j]).map(|emoji| (j, emoji)));
+            let Some((j, emoji)) = found else {
+                i += c.len_utf8();
+                continue;
+            };
+            if filtered < i {
+                spans.push((Rule::Filter, text[filtered..i].to_string()));
+            }
+            spans.push(match self.mode {
+                EmojiMode::Drop => (Rule::Drop, text[i..j].to_string()),
+                EmojiMode::Keep => (Rule::Keep, text[i..j].to_string()),
+                EmojiMode::Shortcode => (Rule::Keep, shortcode(emoji)),
+            });
+            filtered = j;
+            i = j;
+        }
+        let decided = if self.held.is_empty() { text.len() } else { i };
+        if filtered < decided {
+            spans.push((Rule::Filter, text[filtered..decided].to_string()));
+        }
+        spans
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EmojiMode, EmojiSplitter};
+    use crate::rules::Rule;
+
+    fn shortcodes(pieces: &[&str]) -> String {
+        let mut emoji = EmojiSplitter::new(EmojiMode::Shortcode);
+        let mut spans = Vec::new();
+        for piece in pieces {
+            spans.extend(emoji.push(piece));
+        }
+        spans.extend(emoji.finish());
+        spans.into_iter().map(|(_, s)| s).collect()
+    }
+
+    #[test]
+    fn test_emoji_shortcode() {
+        assert_eq!(
+            shortcodes(&["ok 👍 1", "\u{fe0f}\u{20e3}!"]),
+            "ok :+1: :one:!"
+        );
+        // a sequence split across pushes
+        assert_eq!(
+            shortcodes(&["👍", "🏽 👩\u{200d}", "💻"]),
+            ":+1::skin-tone-4: :woman_technologist:"
+        );
+        assert_eq!(shortcodes(&["\u{1f1e9}", "\u{1f1ea}"]), ":de:");
+        // text by default without the variation selector
+        assert_eq!(shortcodes(&["© ©\u{fe0f} 12"]), "© :copyright: 12");
+    }
+
+    #[test]
+    fn test_emoji_spans() {
+        let mut emoji = EmojiSplitter::new(EmojiMode::Drop);
+        assert_eq!(
+            emoji.push("a😀b😀"),
+            [
+                (Rule::Filter, "a".to_string()),
+                (Rule::Drop, "😀".to_string()),
+                (Rule::Filter, "b".to_string()),
+            ]
+        );
+        assert_eq!(emoji.finish(), [(Rule::Drop, "😀".to_string())]);
+    }
+}
@@ -0,0 +1,87 @@
+//! The error of [`filter`](crate::filter) and
+//! [`stdin_stdout_buffer_filter`](crate::stdin_stdout_buffer_filter).
+
+use std::fmt;
+use std::io;
+use std::time::Duration;
+
+use crate::CheckError;
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    /// A check enabled in the options failed.
+    Check(CheckError),
+    /// No input arrived within this timeout, after the output so far was
+    /// flushed.
+    Timeout(Duration),
+}
+
+/// The class of an [`Error`], on which e.g. the exit status of a program
+/// can depend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// A check enabled in the options failed, e.g. the input is not clean.
+    Check,
+    /// Reading or writing failed, e.g. a file was not found.
+    Io,
+    /// Data could not be decoded, e.g. invalid utf-8 with
+    /// [`InvalidUtf8::Error`](crate::InvalidUtf8::Error) or a corrupt gzip
+    /// stream, an error of kind [`io::ErrorKind::InvalidData`].
+    Encoding,
+    /// No input arrived within the timeout.
+    Timeout,
+}
+
+impl Error {
+    /// Return the class of the error.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Error::Io(e) if e.kind() == io::ErrorKind::InvalidData => {
+                ErrorKind::Encoding
+            }
+            Error::Io(_) => ErrorKind::Io,
+            Error::Check(_) => ErrorKind::Check,
+            Error::Timeout(_) => ErrorKind::Timeout,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(e) => e.fmt(f),
+            Error::Check(e) => e.fmt(f),
+            Error::Timeout(timeout) => {
+                write!(f, "no input within {} seconds", timeout.as_secs_f64())
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            Error::Check(e) => Some(e),
+            Error::Timeout(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        // a check failing within a write, e.g. of a strict `FilterWriter`
+        let check = e.get_ref().and_then(|e| e.downcast_ref::<CheckError>());
+        match check {
+            Some(check) => Error::Check(check.clone()),
+            None => Error::Io(e),
+        }
+    }
+}
+
+impl From<CheckError> for Error {
+    fn from(e: CheckError) -> Self {
+        Error::Check(e)
+    }
+}
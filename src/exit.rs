@@ -0,0 +1,27 @@
+//! The exit statuses of the binary, stable so that scripts can tell e.g. an
+//! input with chars the filter drops from a file not found. A signal exits
+//! with 128 plus its number, as the shells do.
+
+use ascii_filter::ErrorKind;
+
+/// A check failed, e.g. `check` found chars the filter changes.
+pub(crate) const VIOLATIONS: i32 = 1;
+/// The command line or the config is invalid, as clap exits with too.
+pub(crate) const USAGE: i32 = 2;
+/// Reading or writing failed, e.g. a file was not found.
+pub(crate) const IO: i32 = 3;
+/// The input could not be decoded, e.g. invalid utf-8 with
+/// `--invalid-utf8 error`, or a corrupt or truncated gzip stream.
+pub(crate) const ENCODING: i32 = 4;
+/// No input arrived within `--timeout`.
+pub(crate) const TIMEOUT: i32 = 5;
+
+/// Return the exit status of an error of `kind`.
+pub(crate) fn status(kind: ErrorKind) -> i32 {
+    match kind {
+        ErrorKind::Check => VIOLATIONS,
+        ErrorKind::Io => IO,
+        ErrorKind::Encoding => ENCODING,
+        ErrorKind::Timeout => TIMEOUT,
+    }
+}
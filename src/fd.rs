@@ -0,0 +1,132 @@
+//! File descriptors handed over by the parent process, as with `3>file` in
+//! the shell, and the standard streams as files.
+
+use std::fs::File;
+use std::io::{self, Seek};
+
+/// Return a duplicate of the inherited file descriptor `fd` as a file, Unix
+/// only, leaving `fd` itself open, e.g. for another option given the same
+/// fd, or for stdout given as 1.
+#[cfg(unix)]
+pub(crate) fn inherited(fd: i32) -> io::Result<File> {
+    use std::os::fd::BorrowedFd;
+    if fd < 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("bad file descriptor {}", fd),
+        ));
+    }
+    // Fail on a closed fd here rather than on the first write.
+    // SAFETY: the fd is only borrowed for the duplication, which fails if it
+    // is not open.
+    let owned = unsafe { BorrowedFd::borrow_raw(fd) }
+        .try_clone_to_owned()
+        .map_err(|e| io::Error::new(e.kind(), format!("fd {}: {}", fd, e)))?;
+    Ok(owned.into())
+}
+
+#[cfg(not(unix))]
+pub(crate) fn inherited(_fd: i32) -> io::Result<File> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "file descriptors are only supported on unix",
+    ))
+}
+
+/// Return a duplicate of stdin as a file.
+#[cfg(any(unix, target_os = "wasi"))]
+pub(crate) fn stdin_file() -> io::Result<File> {
+    use std::os::fd::AsFd;
+    Ok(io::stdin().as_fd().try_clone_to_owned()?.into())
+}
+
+#[cfg(windows)]
+pub(crate) fn stdin_file() -> io::Result<File> {
+    use std::os::windows::io::AsHandle;
+    Ok(io::stdin().as_handle().try_clone_to_owned()?.into())
+}
+
+#[cfg(not(any(unix, windows, target_os = "wasi")))]
+pub(crate) fn stdin_file() -> io::Result<File> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "stdin is not a file on this platform",
+    ))
+}
+
+/// Return a duplicate of stdout as a file.
+#[cfg(any(unix, target_os = "wasi"))]
+pub(crate) fn stdout_file() -> io::Result<File> {
+    use std::os::fd::AsFd;
+    Ok(io::stdout().as_fd().try_clone_to_owned()?.into())
+}
+
+#[cfg(windows)]
+pub(crate) fn stdout_file() -> io::Result<File> {
+    use std::os::windows::io::AsHandle;
+    Ok(io::stdout().as_handle().try_clone_to_owned()?.into())
+}
+
+#[cfg(not(any(unix, windows, target_os = "wasi")))]
+pub(crate) fn stdout_file() -> io::Result<File> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "stdout is not a file on this platform",
+    ))
+}
+
+/// Return the number of bytes left to read from `file` if it is a regular
+/// file, or `None` if it is a pipe, a terminal or the like.
+pub(crate) fn remaining_size(mut file: &File) -> Option<u64> {
+    let metadata = file.metadata().ok()?;
+    if !metadata.is_file() {
+        return None;
+    }
+    let pos = file.stream_position().ok()?;
+    Some(metadata.len().saturating_sub(pos))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::remaining_size;
+    use std::fs::{self, File};
+    #[cfg(unix)]
+    use std::io::{Read, Write};
+    use std::io::{Seek, SeekFrom};
+
+    #[test]
+    fn test_remaining_size() {
+        let path = std::env::temp_dir()
+            .join(format!("ascii-filter-fd-{}", std::process::id()));
+        fs::write(&path, b"0123456789").unwrap();
+        let mut file = File::open(&path).unwrap();
+        assert_eq!(remaining_size(&file), Some(10));
+        file.seek(SeekFrom::Start(4)).unwrap();
+        assert_eq!(remaining_size(&file), Some(6));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_inherited_twice() {
+        use super::inherited;
+        use std::os::fd::AsRawFd;
+        let path = std::env::temp_dir()
+            .join(format!("ascii-filter-inherited-{}", std::process::id()));
+        let mut file = File::create(&path).unwrap();
+        let fd = file.as_raw_fd();
+        // each a duplicate, which leaves the fd open
+        inherited(fd).unwrap().write_all(b"a").unwrap();
+        inherited(fd).unwrap().write_all(b"b").unwrap();
+        file.write_all(b"c").unwrap();
+        drop(file);
+        let mut written = String::new();
+        File::open(&path)
+            .unwrap()
+            .read_to_string(&mut written)
+            .unwrap();
+        assert_eq!(written, "abc");
+        assert!(inherited(-1).is_err());
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,109 @@
+//! Reading on past the end of input, as `tail -f` does, e.g. of a growing
+//! log file or of a FIFO whose writers come and go.
+
+use std::io::{self, Read};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::caught_signal;
+
+/// The time between polls at the end of input.
+pub(crate) const INTERVAL: Duration = Duration::from_millis(200);
+
+/// Reads from `inner`, polling it every `interval` at its end until more
+/// input arrives, so that the input ends only on a signal handled by
+/// [`end_input_on_signals`](crate::end_input_on_signals), or once idle for
+/// the idle timeout, if any.
+pub(crate) struct FollowReader<R> {
+    inner: R,
+    interval: Duration,
+    idle_timeout: Option<Duration>,
+    /// The time input last arrived, or reading started.
+    last_input: Instant,
+}
+
+impl<R: Read> FollowReader<R> {
+    pub(crate) fn new(inner: R, interval: Duration) -> Self {
+        Self {
+            inner,
+            interval,
+            idle_timeout: None,
+            last_input: Instant::now(),
+        }
+    }
+
+    /// End the input once no input arrives within `idle_timeout`.
+    pub(crate) fn with_idle_timeout(
+        mut self,
+        idle_timeout: Option<Duration>,
+    ) -> Self {
+        self.idle_timeout = idle_timeout;
+        self
+    }
+}
+
+impl<R: Read> Read for FollowReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        // The input ends only once interrupted, or idle.
+        while caught_signal().is_none() {
+            match self.inner.read(buf) {
+                Ok(0) => {
+                    let idle = self.last_input.elapsed();
+                    if self.idle_timeout.is_some_and(|timeout| idle >= timeout)
+                    {
+                        break;
+                    }
+                    thread::sleep(self.interval);
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                result => {
+                    self.last_input = Instant::now();
+                    return result;
+                }
+            }
+        }
+        Ok(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FollowReader;
+    use std::io::{self, Read};
+    use std::time::Duration;
+
+    /// Yields its chunks, where an empty one is an end of input.
+    struct Growing(Vec<&'static [u8]>);
+
+    impl Read for Growing {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let chunk = self.0.remove(0);
+            buf[..chunk.len()].copy_from_slice(chunk);
+            Ok(chunk.len())
+        }
+    }
+
+    #[test]
+    fn test_follow_reader() {
+        let mut r = FollowReader::new(
+            Growing(vec![b"ab", b"", b"", b"c"]),
+            Duration::from_millis(1),
+        );
+        let mut out = [0u8; 3];
+        r.read_exact(&mut out).unwrap();
+        assert_eq!(&out, b"abc");
+        assert_eq!(r.read(&mut []).unwrap(), 0);
+
+        let mut r = FollowReader::new(
+            Growing(vec![b"ab", b"", b"", b"", b"c"]),
+            Duration::from_millis(20),
+        )
+        .with_idle_timeout(Some(Duration::from_millis(30)));
+        let mut out: Vec<u8> = Vec::new();
+        r.read_to_end(&mut out).unwrap();
+        assert_eq!(out, b"ab");
+    }
+}
@@ -0,0 +1,236 @@
+//! The `git-filter` subcommand, a clean filter speaking the long-running
+//! filter process protocol of git, so that one process filters all the
+//! files staged, e.g. with
+//!
+//! ```text
+//! git config filter.ascii.process "ascii-filter -a git-filter"
+//! echo "*.txt filter=ascii" >> .gitattributes
+//! ```
+
+use std::io::{self, BufRead, Write};
+use std::process;
+
+use ascii_filter::{filter, Options};
+
+use crate::exit;
+
+/// The largest payload of a packet.
+const MAX_PAYLOAD: usize = 65516;
+
+/// Read a packet, returning `None` for a flush packet, or fail with
+/// [`io::ErrorKind::UnexpectedEof`] at the end of input.
+fn read_packet<R: BufRead>(r: &mut R) -> io::Result<Option<Vec<u8>>> {
+    let mut len = [0u8; 4];
+    r.read_exact(&mut len)?;
+    let len = std::str::from_utf8(&len)
+        .ok()
+        .and_then(|len| usize::from_str_radix(len, 16).ok())
+        .ok_or_else(|| invalid("invalid packet length"))?;
+    match len {
+        0 => Ok(None),
+        1..=4 => Err(invalid("invalid packet length")),
+        len => {
+            let mut payload = vec![0u8; len - 4];
+            r.read_exact(&mut payload)?;
+            Ok(Some(payload))
+        }
+    }
+}
+
+/// Read the text packets up to a flush packet, without their '\n'.
+fn read_lines<R: BufRead>(r: &mut R) -> io::Result<Vec<String>> {
+    let mut lines: Vec<String> = Vec::new();
+    while let Some(packet) = read_packet(r)? {
+        let line = String::from_utf8(packet)
+            .map_err(|_| invalid("invalid utf-8 in a text packet"))?;
+        lines.push(line.strip_suffix('\n').unwrap_or(&line).to_string());
+    }
+    Ok(lines)
+}
+
+/// Read the content packets up to a flush packet.
+fn read_content<R: BufRead>(r: &mut R) -> io::Result<Vec<u8>> {
+    let mut content: Vec<u8> = Vec::new();
+    while let Some(packet) = read_packet(r)? {
+        content.extend_from_slice(&packet);
+    }
+    Ok(content)
+}
+
+fn write_packet<W: Write>(w: &mut W, payload: &[u8]) -> io::Result<()> {
+    write!(w, "{:04x}", payload.len() + 4)?;
+    w.write_all(payload)
+}
+
+fn write_flush<W: Write>(w: &mut W) -> io::Result<()> {
+    w.write_all(b"0000")
+}
+
+/// Write the text packets `lines` and a flush packet.
+fn write_lines<W: Write>(w: &mut W, lines: &[&str]) -> io::Result<()> {
+    for line in lines {
+        write_packet(w, format!("{}\n", line).as_bytes())?;
+    }
+    write_flush(w)
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_string())
+}
+
+/// Speak the protocol on `r` and `w` until `r` ends between requests,
+/// cleaning the content of each per `opts`, and reporting the files which
+/// fail to with `on_error`, for which git fails or keeps them unfiltered,
+/// per `filter.<driver>.required`.
+fn serve<R, W, F>(
+    r: &mut R,
+    w: &mut W,
+    opts: &Options,
+    mut on_error: F,
+) -> io::Result<()>
+where
+    R: BufRead,
+    W: Write,
+    F: FnMut(&str, &ascii_filter::Error),
+{
+    let welcome = read_lines(r)?;
+    if welcome.first().map(String::as_str) != Some("git-filter-client")
+        || !welcome.iter().any(|line| line == "version=2")
+    {
+        return Err(invalid("expected a git-filter-client of version 2"));
+    }
+    write_lines(w, &["git-filter-server", "version=2"])?;
+    w.flush()?;
+    let capabilities = read_lines(r)?;
+    if !capabilities.iter().any(|line| line == "capability=clean") {
+        return Err(invalid("expected the clean capability"));
+    }
+    write_lines(w, &["capability=clean"])?;
+    w.flush()?;
+    loop {
+        let request = match read_lines(r) {
+            Ok(request) => request,
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        let value = |key: &str| {
+            request
+                .iter()
+                .find_map(|line| line.strip_prefix(key)?.strip_prefix('='))
+                .unwrap_or_default()
+        };
+        let content = read_content(r)?;
+        if value("command") != "clean" {
+            write_lines(w, &["status=error"])?;
+            w.flush()?;
+            continue;
+        }
+        let mut cleaned: Vec<u8> = Vec::new();
+        match filter(&content[..], &mut cleaned, opts) {
+            Ok(_) => {
+                write_lines(w, &["status=success"])?;
+                for chunk in cleaned.chunks(MAX_PAYLOAD) {
+                    write_packet(w, chunk)?;
+                }
+                write_flush(w)?;
+                // the status is unchanged
+                write_flush(w)?;
+            }
+            Err(e) => {
+                on_error(value("pathname"), &e);
+                write_lines(w, &["status=error"])?;
+            }
+        }
+        w.flush()?;
+    }
+}
+
+/// Run the `git-filter` subcommand on stdin and stdout, exiting with 3 on
+/// failure.
+pub(crate) fn run(opts: &Options) {
+    let result = serve(
+        &mut io::stdin().lock(),
+        &mut io::stdout().lock(),
+        opts,
+        |path, e| eprintln!("ascii-filter: {}: {}", path, e),
+    );
+    if let Err(e) = result {
+        eprintln!("ascii-filter: git-filter: {}", e);
+        process::exit(exit::IO);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{serve, write_flush, write_lines, write_packet};
+    use ascii_filter::Options;
+
+    /// Return the packets of `lines`, where `None` is a flush packet.
+    fn packets(lines: &[Option<&str>]) -> Vec<u8> {
+        let mut out: Vec<u8> = Vec::new();
+        for line in lines {
+            match line {
+                Some(line) => write_packet(&mut out, line.as_bytes()).unwrap(),
+                None => write_flush(&mut out).unwrap(),
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_serve() {
+        let mut input = packets(&[
+            Some("git-filter-client\n"),
+            Some("version=2\n"),
+            None,
+            Some("capability=clean\n"),
+            Some("capability=smudge\n"),
+            None,
+            Some("command=clean\n"),
+            Some("pathname=a.txt\n"),
+            None,
+            Some("caf\u{e9}\n"),
+            Some("ok\n"),
+            None,
+            Some("command=smudge\n"),
+            Some("pathname=a.txt\n"),
+            None,
+            None,
+        ]);
+        // a second file in one packet per byte
+        write_lines(&mut input, &["command=clean", "pathname=b.txt"]).unwrap();
+        for b in "\u{2192}x".bytes() {
+            write_packet(&mut input, &[b]).unwrap();
+        }
+        write_flush(&mut input).unwrap();
+        let opts = Options {
+            ascii_only: true,
+            ..Options::default()
+        };
+        let mut output: Vec<u8> = Vec::new();
+        serve(&mut &input[..], &mut output, &opts, |_, _| ()).unwrap();
+        let expected = packets(&[
+            Some("git-filter-server\n"),
+            Some("version=2\n"),
+            None,
+            Some("capability=clean\n"),
+            None,
+            Some("status=success\n"),
+            None,
+            Some("caf\nok\n"),
+            None,
+            None,
+            Some("status=error\n"),
+            None,
+            Some("status=success\n"),
+            None,
+            Some("x"),
+            None,
+            None,
+        ]);
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            String::from_utf8(expected).unwrap()
+        );
+    }
+}
@@ -0,0 +1,124 @@
+//! What becomes of the invalid utf-8 sequences of the input.
+
+use std::fmt;
+use std::io;
+use std::str::FromStr;
+
+/// How the invalid utf-8 sequences of the input end up in the output, each
+/// sequence being what [`String::from_utf8_lossy`] replaces with one U+FFFD.
+/// The text they are decoded as, if any, is filtered like the rest of the
+/// input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidUtf8 {
+    /// Drop them.
+    Drop,
+    /// Decode each as U+FFFD, as [`String::from_utf8_lossy`] does, e.g.
+    /// replaced with `?` by the replacement `?`.
+    Replace,
+    /// Decode each byte as `\xNN`, e.g. `\xFF`.
+    Escape,
+    /// Fail at the first with an error of kind
+    /// [`io::ErrorKind::InvalidData`], giving its input offset.
+    Error,
+}
+
+impl InvalidUtf8 {
+    pub const ALL: [InvalidUtf8; 4] = [
+        InvalidUtf8::Drop,
+        InvalidUtf8::Replace,
+        InvalidUtf8::Escape,
+        InvalidUtf8::Error,
+    ];
+
+    /// Return the name of the policy, e.g. "escape".
+    pub fn name(&self) -> &'static str {
+        match self {
+            InvalidUtf8::Drop => "drop",
+            InvalidUtf8::Replace => "replace",
+            InvalidUtf8::Escape => "escape",
+            InvalidUtf8::Error => "error",
+        }
+    }
+
+    /// Return the text the invalid `bytes` at input `offset` are decoded as,
+    /// or `None` if they are dropped.
+    pub(crate) fn decode(
+        &self,
+        bytes: &[u8],
+        offset: u64,
+    ) -> io::Result<Option<String>> {
+        match self {
+            InvalidUtf8::Drop => Ok(None),
+            InvalidUtf8::Replace => Ok(Some('\u{FFFD}'.to_string())),
+            InvalidUtf8::Escape => Ok(Some(escape(bytes))),
+            InvalidUtf8::Error => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "invalid utf-8 sequence {} at byte offset {}",
+                    escape(bytes),
+                    offset
+                ),
+            )),
+        }
+    }
+}
+
+/// Return `bytes` as `\xNN` escapes.
+fn escape(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("\\x{:02X}", b)).collect()
+}
+
+impl fmt::Display for InvalidUtf8 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for InvalidUtf8 {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        InvalidUtf8::ALL
+            .into_iter()
+            .find(|policy| policy.name() == s)
+            .ok_or_else(|| {
+                format!(
+                    "unknown invalid utf-8 policy {:?}, expected one of drop, \
+                     replace, escape, error",
+                    s
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InvalidUtf8;
+
+    #[test]
+    fn test_invalid_utf8_from_str() {
+        for policy in InvalidUtf8::ALL {
+            assert_eq!(policy.to_string().parse(), Ok(policy));
+        }
+        assert!("Drop".parse::<InvalidUtf8>().is_err());
+    }
+
+    #[test]
+    fn test_decode() {
+        let bytes = b"\xe4\xbd";
+        assert_eq!(InvalidUtf8::Drop.decode(bytes, 0).unwrap(), None);
+        assert_eq!(
+            InvalidUtf8::Replace.decode(bytes, 0).unwrap().as_deref(),
+            Some("\u{FFFD}")
+        );
+        assert_eq!(
+            InvalidUtf8::Escape.decode(bytes, 0).unwrap().as_deref(),
+            Some("\\xE4\\xBD")
+        );
+        let e = InvalidUtf8::Error.decode(bytes, 7).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "invalid utf-8 sequence \\xE4\\xBD at byte offset 7"
+        );
+    }
+}
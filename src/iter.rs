@@ -0,0 +1,131 @@
+//! The filter as an adapter of char iterators, e.g. to filter in iterator
+//! chains without any I/O types involved.
+
+use std::collections::VecDeque;
+use std::str::Chars;
+
+use crate::charset::CharFilter;
+use crate::mapping::MappingReplacer;
+use crate::{replacer, Options, Replacer};
+
+/// The chars of an iterator filtered lazily, see
+/// [`AsciiFilterExt::ascii_filtered`].
+#[derive(Debug, Clone)]
+pub struct AsciiFiltered<I> {
+    chars: I,
+    /// Decides the chars kept.
+    filter: CharFilter,
+    /// If true, keep the chars dropped otherwise, and drop the rest.
+    invert: bool,
+    /// If not `None`, substitute dropped chars with it.
+    replacer: Option<MappingReplacer>,
+    /// If not `None`, substitute each run of dropped chars left
+    /// unsubstituted with this once, at its first char.
+    collapse: Option<String>,
+    /// If true, the current run of dropped chars is substituted already.
+    collapsing: bool,
+    /// The rest of the substitute of the char dropped last.
+    substitute: VecDeque<char>,
+}
+
+impl<I: Iterator<Item = char>> AsciiFiltered<I> {
+    fn new(chars: I, opts: &Options) -> Self {
+        Self {
+            chars,
+            filter: CharFilter::new(opts),
+            invert: opts.invert,
+            replacer: replacer(opts).filter(|_| !opts.invert),
+            collapse: opts.collapse.clone().filter(|_| !opts.invert),
+            collapsing: false,
+            substitute: VecDeque::new(),
+        }
+    }
+}
+
+impl<I: Iterator<Item = char>> Iterator for AsciiFiltered<I> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        loop {
+            if let Some(c) = self.substitute.pop_front() {
+                return Some(c);
+            }
+            let c = self.chars.next()?;
+            if self.filter.keeps(c) != self.invert {
+                self.collapsing = false;
+                return Some(c);
+            }
+            let mut replacement: Vec<u8> = Vec::new();
+            if let Some(replacer) = &mut self.replacer {
+                // Writing to a `Vec` never fails.
+                replacer.replace(c, &mut replacement).unwrap();
+            }
+            if !replacement.is_empty() {
+                self.collapsing = false;
+                // The substitutes are strings.
+                let replacement = String::from_utf8_lossy(&replacement);
+                self.substitute.extend(replacement.chars());
+            } else if let Some(separator) = &self.collapse {
+                if !std::mem::replace(&mut self.collapsing, true) {
+                    self.substitute.extend(separator.chars());
+                }
+            }
+        }
+    }
+}
+
+/// Filtering of char iterators per [`Options`], of which the char filter,
+/// inverting, the mappings and the other substitutions, and collapsing
+/// apply, as for [`FilterMachine`](crate::FilterMachine).
+pub trait AsciiFilterExt: Iterator<Item = char> + Sized {
+    /// Return the chars of `self` filtered per `opts` lazily, e.g.
+    /// `text.chars().ascii_filtered(&opts).take(80).collect::<String>()`.
+    fn ascii_filtered(self, opts: &Options) -> AsciiFiltered<Self> {
+        AsciiFiltered::new(self, opts)
+    }
+}
+
+impl<I: Iterator<Item = char>> AsciiFilterExt for I {}
+
+/// Filtering of strings as [`AsciiFilterExt`] does their chars.
+pub trait AsciiFilterStrExt {
+    /// Return the chars of `self` filtered per `opts` lazily, e.g.
+    /// `"naïve".ascii_filtered(&opts)`.
+    fn ascii_filtered(&self, opts: &Options) -> AsciiFiltered<Chars<'_>>;
+}
+
+impl AsciiFilterStrExt for str {
+    fn ascii_filtered(&self, opts: &Options) -> AsciiFiltered<Chars<'_>> {
+        AsciiFiltered::new(self.chars(), opts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AsciiFilterExt, AsciiFilterStrExt};
+    use crate::{filter_str, Mapping, Options};
+
+    #[test]
+    fn test_ascii_filtered() {
+        let opts = Options {
+            ascii_only: true,
+            mappings: vec![Mapping::Punctuation],
+            collapse: Some("_".to_string()),
+            ..Options::default()
+        };
+        let input = "“naïve” 你好, 世界";
+        let filtered: String = input.ascii_filtered(&opts).collect();
+        assert_eq!(filtered, "\"na_ve\" _, _");
+        assert_eq!(filtered, filter_str(input, &opts));
+        // lazy, and in iterator chains
+        let mut chars = "ab".chars().cycle().ascii_filtered(&opts);
+        assert_eq!(chars.nth(3), Some('b'));
+        let opts = Options {
+            invert: true,
+            ..opts
+        };
+        let inverted: String =
+            input.chars().rev().ascii_filtered(&opts).collect();
+        assert_eq!(inverted, "界世好你”ï“");
+    }
+}
@@ -0,0 +1,76 @@
+//! Filtering of the systemd journal export format, where only textual field
+//! values are filtered. Binary fields (a field name line followed by a 64-bit
+//! little-endian size and the raw data) and the framing are kept as-is, so
+//! that the sanitized export can be imported losslessly.
+
+use std::io::{self, BufRead, Read, Write};
+
+/// Read the journal export stream from `r`, and write it to `w` with each
+/// textual field value transformed by `filter`.
+pub(crate) fn filter<R, W, F>(
+    r: &mut R,
+    w: &mut W,
+    filter: &mut F,
+) -> io::Result<()>
+where
+    R: BufRead,
+    W: Write,
+    F: FnMut(&[u8]) -> Vec<u8>,
+{
+    let mut line: Vec<u8> = Vec::new();
+    while r.read_until(b'\n', &mut line)? > 0 {
+        let content = line.strip_suffix(b"\n").unwrap_or(&line);
+        if let Some(eq) = content.iter().position(|&b| b == b'=') {
+            // FIELD=value
+            w.write_all(&content[..=eq])?;
+            w.write_all(&filter(&content[eq + 1..]))?;
+            w.write_all(&line[content.len()..])?;
+        } else if content.is_empty() {
+            // the separator between entries
+            w.write_all(&line)?;
+        } else {
+            // FIELD, then the binary value
+            w.write_all(&line)?;
+            let mut size = [0u8; 8];
+            r.read_exact(&mut size)?;
+            w.write_all(&size)?;
+            let size = u64::from_le_bytes(size);
+            // the data and the trailing '\n'
+            let copied = io::copy(&mut (&mut *r).take(size + 1), w)?;
+            if copied < size + 1 {
+                // truncated input, nothing more to do
+                break;
+            }
+        }
+        line.clear();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::filter;
+
+    fn drop_non_ascii(bytes: &[u8]) -> Vec<u8> {
+        bytes.iter().copied().filter(u8::is_ascii).collect()
+    }
+
+    #[test]
+    fn test_filter() {
+        let mut input: Vec<u8> = Vec::new();
+        input.extend("__CURSOR=s=1\nMESSAGE=héllo\nDATA\n".as_bytes());
+        input.extend(4u64.to_le_bytes());
+        input.extend(b"\xff\n=\xfe\n\n");
+        input.extend("MESSAGE=wörld\n".as_bytes());
+
+        let mut expected: Vec<u8> = Vec::new();
+        expected.extend(b"__CURSOR=s=1\nMESSAGE=hllo\nDATA\n");
+        expected.extend(4u64.to_le_bytes());
+        expected.extend(b"\xff\n=\xfe\n\n");
+        expected.extend(b"MESSAGE=wrld\n");
+
+        let mut w: Vec<u8> = Vec::new();
+        filter(&mut &input[..], &mut w, &mut drop_non_ascii).unwrap();
+        assert_eq!(w, expected);
+    }
+}
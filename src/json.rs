@@ -0,0 +1,325 @@
+//! Minimal JSON scanning, just enough to rewrite string values in place while
+//! keeping the rest of a document byte-exact.
+
+use std::io::{self, BufRead, Write};
+
+/// Return the offset just past the JSON whitespace starting at `i`.
+pub(crate) fn skip_ws(bytes: &[u8], mut i: usize) -> usize {
+    while i < bytes.len() && matches!(bytes[i], b' ' | b'\t' | b'\n' | b'\r') {
+        i += 1;
+    }
+    i
+}
+
+/// Return the offset just past the string whose opening quote is at `i`.
+pub(crate) fn skip_string(bytes: &[u8], mut i: usize) -> Option<usize> {
+    if bytes.get(i) != Some(&b'"') {
+        return None;
+    }
+    i += 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => return Some(i + 1),
+            b'\\' => i += 2,
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+/// Return the offset just past the value starting at `i`. Only strings and
+/// brackets are checked, since other tokens may be copied verbatim.
+pub(crate) fn skip_value(bytes: &[u8], i: usize) -> Option<usize> {
+    match bytes.get(i)? {
+        b'"' => skip_string(bytes, i),
+        b'{' | b'[' => {
+            let mut depth: usize = 0;
+            let mut i = i;
+            while i < bytes.len() {
+                match bytes[i] {
+                    b'"' => {
+                        i = skip_string(bytes, i)?;
+                        continue;
+                    }
+                    b'{' | b'[' => depth += 1,
+                    b'}' | b']' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return Some(i + 1);
+                        }
+                    }
+                    _ => (),
+                }
+                i += 1;
+            }
+            None
+        }
+        _ => {
+            let mut i = i;
+            while i < bytes.len()
+                && !matches!(
+                    bytes[i],
+                    b',' | b'}' | b']' | b' ' | b'\t' | b'\n' | b'\r'
+                )
+            {
+                i += 1;
+            }
+            Some(i)
+        }
+    }
+}
+
+/// Decode the content of a JSON string (without the quotes). Lone surrogates
+/// are decoded as U+FFFD.
+pub(crate) fn decode_string(raw: &[u8]) -> Option<String> {
+    let raw = std::str::from_utf8(raw).ok()?;
+    let mut out = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        let decoded = match chars.next()? {
+            '"' => '"',
+            '\\' => '\\',
+            '/' => '/',
+            'b' => '\u{8}',
+            'f' => '\u{c}',
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            'u' => {
+                let hi = decode_hex4(&mut chars)?;
+                if (0xD800..0xDC00).contains(&hi) {
+                    if let Some(rest) = chars.as_str().strip_prefix("\\u") {
+                        let mut lookahead = rest.chars();
+                        let lo = decode_hex4(&mut lookahead)?;
+                        if (0xDC00..0xE000).contains(&lo) {
+                            chars = lookahead;
+                            let c =
+                                0x10000 + ((hi - 0xD800) << 10) + lo - 0xDC00;
+                            out.push(char::from_u32(c)?);
+                            continue;
+                        }
+                    }
+                }
+                char::from_u32(hi).unwrap_or('\u{FFFD}')
+            }
+            _ => return None,
+        };
+        out.push(decoded);
+    }
+
+    Some(out)
+}
+
+fn decode_hex4(chars: &mut std::str::Chars) -> Option<u32> {
+    let mut n: u32 = 0;
+    for _ in 0..4 {
+        n = n * 16 + chars.next()?.to_digit(16)?;
+    }
+    Some(n)
+}
+
+/// Encode `s` as the content of a JSON string (without the quotes).
+pub(crate) fn encode_string(s: &str, out: &mut Vec<u8>) {
+    for c in s.chars() {
+        match c {
+            '"' => out.extend_from_slice(b"\\\""),
+            '\\' => out.extend_from_slice(b"\\\\"),
+            '\n' => out.extend_from_slice(b"\\n"),
+            '\r' => out.extend_from_slice(b"\\r"),
+            '\t' => out.extend_from_slice(b"\\t"),
+            c if (c as u32) < 0x20 => {
+                out.extend_from_slice(format!("\\u{:04x}", c as u32).as_bytes())
+            }
+            c => {
+                let mut buf = [0u8; 4];
+                out.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            }
+        }
+    }
+}
+
+/// Where the scan of a JSON stream is, outside of strings.
+struct Scan {
+    /// For each container entered, true if it is an object.
+    objects: Vec<bool>,
+    /// If true, the next string in the innermost object is a key.
+    key_next: bool,
+}
+
+impl Scan {
+    /// Scan the structural `byte`.
+    fn step(&mut self, byte: u8) {
+        match byte {
+            b'{' => {
+                self.objects.push(true);
+                self.key_next = true;
+            }
+            b'[' => self.objects.push(false),
+            b'}' | b']' => {
+                self.objects.pop();
+            }
+            b',' => self.key_next = self.in_object(),
+            b':' => self.key_next = false,
+            _ => (),
+        }
+    }
+
+    fn in_object(&self) -> bool {
+        self.objects.last() == Some(&true)
+    }
+}
+
+/// Write the content of the string `raw` (without the quotes) to `out`, with
+/// its value transformed by `filter`, re-escaped, or as is if unchanged. A
+/// string which cannot be decoded is transformed raw.
+fn filter_string<F: FnMut(&[u8]) -> Vec<u8>>(
+    raw: &[u8],
+    filter: &mut F,
+    out: &mut Vec<u8>,
+) {
+    let Some(value) = decode_string(raw) else {
+        out.extend_from_slice(&filter(raw));
+        return;
+    };
+    let filtered = filter(value.as_bytes());
+    if filtered == value.as_bytes() {
+        out.extend_from_slice(raw);
+    } else {
+        encode_string(&String::from_utf8_lossy(&filtered), out);
+    }
+}
+
+/// Read a stream of JSON values from `r`, e.g. a document or JSON lines, and
+/// write it to `w` with each string value transformed by `filter`, and each
+/// key too if `keys`. The rest is kept byte-exact, and so is a string which
+/// is left unchanged, escapes and all. The input is not validated, so that
+/// what is not JSON is copied as is, but for its quoted strings.
+pub(crate) fn filter<R, W, F>(
+    r: &mut R,
+    w: &mut W,
+    keys: bool,
+    filter: &mut F,
+) -> io::Result<()>
+where
+    R: BufRead,
+    W: Write,
+    F: FnMut(&[u8]) -> Vec<u8>,
+{
+    let mut scan = Scan {
+        objects: Vec::new(),
+        key_next: false,
+    };
+    // The raw content of the string being read, if any, whether it is to be
+    // filtered, and whether its last byte is an escaping backslash.
+    let mut string: Option<(Vec<u8>, bool)> = None;
+    let mut escaped = false;
+    let mut out: Vec<u8> = Vec::new();
+    loop {
+        let buf = r.fill_buf()?;
+        if buf.is_empty() {
+            break;
+        }
+        for &byte in buf {
+            let Some((raw, filtered)) = &mut string else {
+                if byte == b'"' {
+                    let key = scan.in_object() && scan.key_next;
+                    string = Some((Vec::new(), keys || !key));
+                }
+                scan.step(byte);
+                out.push(byte);
+                continue;
+            };
+            if byte != b'"' || escaped {
+                escaped = byte == b'\\' && !escaped;
+                raw.push(byte);
+                continue;
+            }
+            match filtered {
+                true => filter_string(raw, filter, &mut out),
+                false => out.extend_from_slice(raw),
+            }
+            out.push(b'"');
+            string = None;
+        }
+        let len = buf.len();
+        r.consume(len);
+        w.write_all(&out)?;
+        out.clear();
+    }
+    // an unterminated string at the end
+    if let Some((raw, filtered)) = string {
+        match filtered {
+            true => filter_string(&raw, filter, &mut out),
+            false => out.extend_from_slice(&raw),
+        }
+        w.write_all(&out)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_string, encode_string, filter, skip_value};
+
+    fn drop_non_ascii(bytes: &[u8]) -> Vec<u8> {
+        bytes.iter().copied().filter(u8::is_ascii).collect()
+    }
+
+    /// Filter the JSON stream `input` in chunks of `len` bytes.
+    fn filter_chunked(input: &str, len: usize, keys: bool) -> String {
+        let mut r = std::io::BufReader::with_capacity(len, input.as_bytes());
+        let mut w: Vec<u8> = Vec::new();
+        filter(&mut r, &mut w, keys, &mut drop_non_ascii).unwrap();
+        String::from_utf8(w).unwrap()
+    }
+
+    #[test]
+    fn test_filter() {
+        let input = "{\"kéy\": [\"café\", 1.5e3, {\"é\": \"\\/\"}],\n \
+                     \"né\": \"a\\\"b→\"}\n[\"xé\", null]\n";
+        for len in 1..=input.len() {
+            assert_eq!(
+                filter_chunked(input, len, false),
+                "{\"kéy\": [\"caf\", 1.5e3, {\"é\": \"\\/\"}],\n \
+                 \"né\": \"a\\\"b\"}\n[\"x\", null]\n"
+            );
+        }
+        assert_eq!(
+            filter_chunked(input, 8, true),
+            "{\"ky\": [\"caf\", 1.5e3, {\"\": \"\\/\"}],\n \
+             \"n\": \"a\\\"b\"}\n[\"x\", null]\n"
+        );
+        // a string changed is re-escaped, and one unterminated is left open
+        assert_eq!(
+            filter_chunked(r#"["\u00e9\/", "bé"#, 4, false),
+            r#"["/", "b"#
+        );
+    }
+
+    #[test]
+    fn test_decode_string() {
+        assert_eq!(
+            decode_string(r#"a\"\né\ud83d\ude00\ud800"#.as_bytes()).unwrap(),
+            "a\"\n\u{e9}\u{1F600}\u{FFFD}"
+        );
+        assert_eq!(decode_string(br"\x"), None);
+    }
+
+    #[test]
+    fn test_encode_string() {
+        let mut out: Vec<u8> = Vec::new();
+        encode_string("a\"\n\u{1b}é", &mut out);
+        assert_eq!(out, "a\\\"\\n\\u001bé".as_bytes());
+    }
+
+    #[test]
+    fn test_skip_value() {
+        let doc = br#"{"a":["}",1]},2"#;
+        assert_eq!(skip_value(doc, 0), Some(13));
+        assert_eq!(skip_value(doc, 14), Some(15));
+    }
+}
@@ -0,0 +1,74 @@
+//! Filtering of `serde_json` values, so that structured payloads can be
+//! sanitized without serializing them to text first.
+
+use std::io::Read;
+
+use serde_json::{Map, Value};
+
+use crate::{filter_string, Options};
+
+/// Return `value` with every string in it, including object keys, filtered
+/// per `opts`. If two keys of an object become equal after filtering, the
+/// latter one wins.
+pub fn filter_json_value(value: Value, opts: &Options) -> Value {
+    match value {
+        Value::String(s) => Value::String(filter_string(&s, opts)),
+        Value::Array(values) => Value::Array(
+            values
+                .into_iter()
+                .map(|v| filter_json_value(v, opts))
+                .collect(),
+        ),
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(k, v)| {
+                    (filter_string(&k, opts), filter_json_value(v, opts))
+                })
+                .collect::<Map<String, Value>>(),
+        ),
+        other => other,
+    }
+}
+
+/// Deserialize a stream of JSON values from `r`, yielding each value filtered
+/// by [`filter_json_value`].
+pub fn filter_json_stream<'a, R: Read + 'a>(
+    r: R,
+    opts: &'a Options,
+) -> impl Iterator<Item = serde_json::Result<Value>> + 'a {
+    serde_json::Deserializer::from_reader(r)
+        .into_iter::<Value>()
+        .map(move |v| v.map(|v| filter_json_value(v, opts)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{filter_json_stream, filter_json_value};
+    use crate::Options;
+    use serde_json::json;
+
+    #[test]
+    fn test_filter_json_value() {
+        let opts = Options {
+            ascii_only: true,
+            ..Options::default()
+        };
+        assert_eq!(
+            filter_json_value(json!({"kéy": ["vä", 1, null]}), &opts),
+            json!({"ky": ["v", 1, null]})
+        );
+    }
+
+    #[test]
+    fn test_filter_json_stream() {
+        let opts = Options {
+            ascii_only: true,
+            ..Options::default()
+        };
+        let values: Vec<_> =
+            filter_json_stream(r#""é" {"a":"bü"}"#.as_bytes(), &opts)
+                .map(Result::unwrap)
+                .collect();
+        assert_eq!(values, vec![json!(""), json!({"a": "b"})]);
+    }
+}
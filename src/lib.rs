@@ -1,182 +1,3935 @@
-use std::io::{self, Read, Write};
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, Read, Seek, SeekFrom, Write};
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
-/// When writing bytes, all bytes are assumed valid utf-8 char(s).
-struct FilterWriter<'a, W: Write> {
-    /// If true, write only ASCII letters, ASCII punctuations, ASCII digits,
-    /// space, tab, and '\n'.
-    ascii_only: bool,
-    backend: &'a mut W,
+use ansi::AnsiStripper;
+use ascii_filter_core::class::is_ascii_subset;
+use ascii_filter_core::utf8::{Utf8Chunk, Utf8Decoder};
+use changes::ChangeLog;
+use charset::{CharFilter, Predicate};
+use check::{Checker, CheckingReader, CheckingWriter};
+use compress::{GzipReader, GzipWriter};
+use emoji::EmojiSplitter;
+use follow::FollowReader;
+use limit::LineLimiter;
+use mapping::MappingReplacer;
+use mmap::Mmap;
+#[cfg(feature = "unicode-normalization")]
+use normalize::Normalizer;
+use progress::{Progress, ProgressReader};
+use qp::QpWriter;
+use regex::Regex;
+use rejects::{HexRejects, Record, Rejects};
+use rules::{LineRules, Rule};
+use stats::{CountingReader, CountingWriter};
+#[cfg(feature = "unicode-category")]
+use tables::GeneralCategory;
+#[cfg(feature = "unicode-script")]
+use tables::Script;
+use tabs::TabExpander;
+use throttle::Throttle;
+use timeout::TimeoutReader;
+use transcode::{DecodingReader, EncodingWriter};
+use watch::Watcher;
+
+mod ansi;
+mod builder;
+#[cfg(feature = "capi")]
+pub mod capi;
+mod changes;
+mod charset;
+mod check;
+mod compress;
+#[cfg(any(windows, test))]
+mod console;
+mod csv;
+mod detect;
+mod docker;
+mod emoji;
+mod error;
+mod fd;
+mod follow;
+mod invalid;
+mod iter;
+mod journal;
+mod json;
+#[cfg(feature = "serde_json")]
+mod json_value;
+mod legacy;
+mod limit;
+mod logfmt;
+#[cfg(feature = "log")]
+mod logger;
+mod machine;
+pub mod mapping;
+mod markdown;
+mod mmap;
+mod newlines;
+#[cfg(feature = "unicode-normalization")]
+mod normalize;
+mod output;
+mod parallel;
+mod progress;
+mod punycode;
+mod qp;
+mod reader;
+mod rejects;
+mod replace;
+mod resume;
+mod rules;
+mod selftest;
+#[cfg(feature = "serde")]
+mod ser;
+mod serve;
+mod signal;
+mod split;
+mod stages;
+mod stats;
+mod syslog;
+pub mod tables;
+mod tabs;
+mod throttle;
+mod timeout;
+mod transcode;
+pub mod transform;
+mod walk;
+mod watch;
+
+pub use ascii_filter_core::utf8;
+pub use builder::FilterBuilder;
+pub use changes::{restore, SourceMap};
+pub use charset::{Action, CharSet};
+pub use check::CheckError;
+pub use compress::Compression;
+pub use detect::{detect, Bom, Encoding, Profile};
+pub use emoji::EmojiMode;
+pub use error::{Error, ErrorKind};
+pub use invalid::InvalidUtf8;
+pub use iter::{AsciiFilterExt, AsciiFilterStrExt, AsciiFiltered};
+pub use legacy::LegacyCharset;
+pub use limit::Overlong;
+pub use machine::{Event, FilterMachine, Output};
+pub use mapping::{Locale, Mapping};
+pub use newlines::Newlines;
+#[cfg(feature = "unicode-normalization")]
+pub use normalize::Normalization;
+pub use output::Destination;
+pub use reader::FilterReader;
+pub use rejects::ReportFormat;
+pub use replace::{ControlEscape, Escape, Replacer};
+pub use selftest::{selftest, SelftestFailure, SelftestReport};
+pub use serve::{serve, Endpoint};
+pub use signal::{caught_signal, end_input_on_signals};
+pub use stats::{Histogram, Stats};
+pub use transform::{Pipeline, Transform, TransformWriter};
+pub use walk::{
+    filter_tree, filter_tree_in_place, sniff_type, HardLinks, Skipped,
+};
+
+#[cfg(feature = "serde_json")]
+pub use json_value::{filter_json_stream, filter_json_value};
+#[cfg(feature = "log")]
+pub use logger::SanitizingLogger;
+#[cfg(feature = "serde")]
+pub use ser::{Compound, Sanitized, SanitizingSerializer};
+
+/// The largest output of a `FilterWriter::write` call gathered before it is
+/// written to the backend, so that it takes few writes however much is
+/// dropped in between.
+const BATCH_LEN: usize = 8 << 10;
+
+/// A `Write` adapter which filters what is written through it to `backend`.
+/// Invalid sequences are dropped where [`String::from_utf8_lossy`] would
+/// replace them, or handled per [`FilterWriter::with_invalid_utf8`], and a
+/// multibyte char split across `write` calls is kept until the rest of it
+/// arrives, so any bytes can be written in any chunks. An incomplete char
+/// left at the end is dropped as invalid.
+///
+/// With a normalization, the text which may yet combine with what is written
+/// next is held back until then, or until `flush`. Likewise with regex rules,
+/// the text of a line is held back until its end, and with emoji handling, a
+/// possibly incomplete emoji sequence, until then or until `flush`, as is the
+/// last grapheme cluster when filtering by clusters. With line ending
+/// conversion, a CR written last is held back until it is known
+/// whether LF follows.
+pub struct FilterWriter<W: Write> {
+    /// Decides the chars written, by default ASCII letters, ASCII
+    /// punctuations, ASCII digits, space, tab, and '\n' if filtering, or
+    /// per the predicate given.
+    chars: CharFilter,
+    /// If true, write only the chars dropped otherwise, unsubstituted.
+    invert: bool,
+    /// If not `None`, write this instead of each '\n'.
+    record_sep: Option<Vec<u8>>,
+    /// If not `None`, substitute dropped chars with it.
+    replacer: Option<Box<dyn Replacer + Send>>,
+    /// If not `None`, substitute each run of dropped chars left
+    /// unsubstituted with this once.
+    collapse: Option<String>,
+    /// If true, `collapse` was written last for the current run.
+    collapsing: bool,
+    /// If not `None`, write this replacement at most the given times for
+    /// each run of chars replaced with it.
+    squeeze: Option<(Vec<u8>, usize)>,
+    /// The number of times `squeeze` was written for the current run.
+    squeezed: usize,
+    /// If not `None`, write this where dropping chars unsubstituted would
+    /// join two alphanumeric chars.
+    word_sep: Option<String>,
+    /// If true, the output ends with an alphanumeric char.
+    after_alnum: bool,
+    /// If true, chars were dropped unsubstituted after an alphanumeric char
+    /// last written.
+    word_gap: bool,
+    /// If not `None`, strip the ANSI escape sequences first.
+    ansi: Option<AnsiStripper>,
+    /// If not `None`, normalize the text before filtering it.
+    #[cfg(feature = "unicode-normalization")]
+    normalizer: Option<Normalizer>,
+    /// If not `None`, drop or keep what the regexes match in each line,
+    /// after normalizing.
+    rules: Option<LineRules>,
+    /// If not `None`, handle the emoji in what the regexes leave to the char
+    /// filter.
+    emoji: Option<EmojiSplitter>,
+    /// If not `None`, filter by extended grapheme clusters, holding back the
+    /// last cluster, which may continue, here.
+    #[cfg(feature = "unicode-segmentation")]
+    graphemes: Option<String>,
+    /// If not `None`, convert the line endings per this.
+    newlines: Option<Newlines>,
+    /// If true, a CR ended the text filtered last, which is part of a CRLF
+    /// if LF follows.
+    held_cr: bool,
+    /// If not `None`, expand the tabs written.
+    tabs: Option<TabExpander>,
+    /// If not `None`, the output of the line not yet ended, held back since
+    /// the line is omitted if it drops any char left unsubstituted.
+    held_line: Option<Vec<u8>>,
+    /// If true, the line held back is omitted.
+    line_dropped: bool,
+    /// If not `None`, the number of the line of input which the output line
+    /// next is of, prefixed to it.
+    line_number: Option<usize>,
+    /// If true, the prefix and the number of the current output line are
+    /// written.
+    line_started: bool,
+    /// If not `None`, prefixed to each output line, before its number.
+    line_prefix: Option<Vec<u8>>,
+    /// If not `None`, bound the length of the output lines.
+    limiter: Option<LineLimiter>,
+    /// If not `None`, record the chars dropped here.
+    rejects: Option<Rejects>,
+    /// If not `None`, dump the bytes dropped here.
+    hex_rejects: Option<HexRejects>,
+    /// If not `None`, report the chars dropped here.
+    report: Option<Rejects>,
+    /// If not `None`, fail at the first char dropped or invalid sequence, at
+    /// the position tracked here.
+    strict: Option<Rejects>,
+    /// If not `None`, record the changes to the text here.
+    undo: Option<ChangeLog>,
+    /// If not `None`, map the output offsets to the input here.
+    source_map: Option<ChangeLog>,
+    /// Called with each dropped or replaced char and its input offset.
+    on_drop: Option<Box<dyn FnMut(char, usize) + Send>>,
+    /// If not `None`, count the chars dropped or replaced here.
+    histogram: Option<Arc<Mutex<Histogram>>>,
+    /// The input offset of the text filtered next, not counting the CR held
+    /// back.
+    offset: usize,
+    /// Decodes the bytes written, carrying over the start of a multibyte
+    /// char written last.
+    utf8: Utf8Decoder,
+    /// What becomes of the invalid sequences written.
+    invalid_utf8: InvalidUtf8,
+    /// If true, drop a BOM at the start of input.
+    strip_bom: bool,
+    /// If true, the bytes written last end with `record_end`.
+    ends_line: bool,
+    /// The byte ending a line, '\n', or NUL for NUL-delimited records.
+    record_end: u8,
+    /// The output of the current `write` call not yet written to the
+    /// backend.
+    batch: Vec<u8>,
+    /// The totals so far.
+    stats: Stats,
+    backend: W,
 }
 
-impl<'a, W: Write> FilterWriter<'a, W> {
-    fn new(backend: &'a mut W, ascii_only: bool) -> Self {
+impl<W: Write> FilterWriter<W> {
+    /// Create the writer to `backend`, which keeps only the ASCII subset if
+    /// `ascii_only`, per [`Action::ascii_subset`], and passes valid utf-8
+    /// through otherwise.
+    pub fn new(backend: W, ascii_only: bool) -> Self {
+        let mut fw = Self::from_predicate(backend, Predicate::default());
+        fw.chars.filtering = ascii_only;
+        fw
+    }
+
+    /// Create the writer to `backend`, which does with each char what
+    /// `predicate` returns, e.g. to express a policy no built-in mode does.
+    /// The chars it drops are substituted by the replacer, if any, and the
+    /// options keeping chars too, e.g. [`with_keep_cr`](Self::with_keep_cr),
+    /// apply on top of it, while [`with_allowed`](Self::with_allowed)
+    /// replaces it. It may be called several times for a char, and ahead of
+    /// time for the ASCII chars.
+    pub fn with_predicate<F>(backend: W, predicate: F) -> Self
+    where
+        F: Fn(char) -> Action + Send + Sync + 'static,
+    {
+        Self::from_predicate(backend, Predicate::new(predicate))
+    }
+
+    fn from_predicate(backend: W, predicate: Predicate) -> Self {
         Self {
-            ascii_only,
+            chars: CharFilter {
+                filtering: true,
+                predicate,
+                ..CharFilter::default()
+            },
+            invert: false,
+            record_sep: None,
+            replacer: None,
+            collapse: None,
+            collapsing: false,
+            squeeze: None,
+            squeezed: 0,
+            word_sep: None,
+            after_alnum: false,
+            word_gap: false,
+            ansi: None,
+            #[cfg(feature = "unicode-normalization")]
+            normalizer: None,
+            rules: None,
+            emoji: None,
+            #[cfg(feature = "unicode-segmentation")]
+            graphemes: None,
+            newlines: None,
+            held_cr: false,
+            tabs: None,
+            held_line: None,
+            line_dropped: false,
+            line_number: None,
+            line_started: false,
+            line_prefix: None,
+            limiter: None,
+            rejects: None,
+            hex_rejects: None,
+            report: None,
+            strict: None,
+            undo: None,
+            source_map: None,
+            on_drop: None,
+            histogram: None,
+            offset: 0,
+            utf8: Utf8Decoder::default(),
+            invalid_utf8: InvalidUtf8::Drop,
+            strip_bom: false,
+            ends_line: false,
+            record_end: b'\n',
+            batch: Vec::new(),
+            stats: Stats::default(),
             backend,
         }
     }
-}
 
-impl<'a, W: Write> Write for FilterWriter<'a, W> {
-    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        if self.ascii_only {
-            let utf8_buf = unsafe { std::str::from_utf8_unchecked(buf) };
-            let mut written: usize = 0;
-            for (j, c) in utf8_buf.char_indices() {
-                let c_len = c.len_utf8();
-                if c_len > 1 {
-                    // `c` is not ASCII. Drop directly.
-                    written += c_len;
-                } else {
-                    let c_byte = buf[j];
-                    if (c_byte < 11 && c_byte >= 9)
-                        || (c_byte < 127 && c_byte >= 32)
-                    {
-                        self.backend.write_all(&buf[j..j + 1]).unwrap();
-                        written += 1;
-                    } else {
-                        // `c` is not in the ASCII subset. Drop directly.
-                        written += 1;
-                    }
-                }
+    /// Create the writer to `backend` per `opts`, of which the char filter,
+    /// the mappings, the record separator, the line numbers, the line prefix
+    /// and the maximum line length apply.
+    pub fn from_options(backend: W, opts: &Options) -> Self {
+        let mut fw = filter_writer(backend, opts);
+        if let Some(max) = opts.max_line_length {
+            fw = fw.with_max_line_length(max, opts.overlong);
+        }
+        fw.with_line_numbers(opts.line_numbers)
+            .with_line_prefix(opts.line_prefix.clone().map(String::into_bytes))
+            .with_record_sep(
+                opts.out_record_sep.clone().map(String::into_bytes),
+            )
+    }
+
+    /// Substitute each dropped char with what `replacer` writes.
+    pub fn with_replacer<R: Replacer + Send + 'static>(
+        mut self,
+        replacer: R,
+    ) -> Self {
+        self.replacer = Some(Box::new(replacer));
+        self
+    }
+
+    /// Substitute each run of dropped chars which are left unsubstituted,
+    /// e.g. by the replacer, with `separator` once, so that the words on
+    /// either side of it stay apart.
+    pub fn with_collapse(mut self, separator: String) -> Self {
+        self.collapse = Some(separator);
+        self
+    }
+
+    /// Write `replacement` at most `max` times for each run of chars replaced
+    /// with it, e.g. `?` once rather than `????????` for a word in another
+    /// script.
+    pub fn with_squeeze(mut self, replacement: String, max: usize) -> Self {
+        self.squeeze = Some((replacement.into_bytes(), max));
+        self
+    }
+
+    /// Write `separator`, e.g. a space or `-`, where dropping chars without
+    /// substitution would join two alphanumeric chars, e.g. `naïve` into
+    /// `na ve` rather than `nave` with `-a`.
+    pub fn with_word_sep(mut self, separator: String) -> Self {
+        self.word_sep = Some(separator);
+        self
+    }
+
+    /// Write only the chars in `allowed` instead of the ASCII subset, whether
+    /// or not `ascii_only`.
+    pub fn with_allowed(mut self, allowed: CharSet) -> Self {
+        self.chars.filtering = true;
+        self.chars.allowed = Some(allowed);
+        self
+    }
+
+    /// Write the chars of the general categories `categories` too, whether
+    /// or not `ascii_only`.
+    #[cfg(feature = "unicode-category")]
+    pub fn with_keep_categories(
+        mut self,
+        categories: Vec<GeneralCategory>,
+    ) -> Self {
+        self.chars.filtering = true;
+        self.chars.keep_categories = categories;
+        self
+    }
+
+    /// Drop the chars of the general categories `categories`, even if they
+    /// would be written otherwise, whether or not `ascii_only`.
+    #[cfg(feature = "unicode-category")]
+    pub fn with_drop_categories(
+        mut self,
+        categories: Vec<GeneralCategory>,
+    ) -> Self {
+        self.chars.drop_categories = categories;
+        self
+    }
+
+    /// Write the chars of the scripts `scripts` too, whether or not
+    /// `ascii_only`.
+    #[cfg(feature = "unicode-script")]
+    pub fn with_keep_scripts(mut self, scripts: Vec<Script>) -> Self {
+        self.chars.filtering = true;
+        self.chars.keep_scripts = scripts;
+        self
+    }
+
+    /// Write the printable chars of ISO 8859-1 and ISO 8859-15 too, e.g. 'é'
+    /// and '€', whether or not `ascii_only`.
+    pub fn with_keep_latin1(mut self) -> Self {
+        self.chars.filtering = true;
+        self.chars.keep_latin1 = true;
+        self
+    }
+
+    /// Write the chars encodable in `charset` too, e.g. 'Ł' in ISO 8859-2,
+    /// whether or not `ascii_only`, without encoding them.
+    pub fn with_encodable_in(mut self, charset: LegacyCharset) -> Self {
+        self.chars.filtering = true;
+        self.chars.encodable_in = Some(charset);
+        self
+    }
+
+    /// Drop the invisible chars, e.g. ZWSP, ZWJ, a BOM, the soft hyphen and
+    /// bidi controls, even if they would be written otherwise, whether or not
+    /// `ascii_only`.
+    pub fn with_strip_invisible(mut self) -> Self {
+        self.chars.strip_invisible = true;
+        self
+    }
+
+    /// Drop a BOM, i.e. U+FEFF, at the start of input if `strip_bom`, even if
+    /// it would be written otherwise. It is not counted or reported as a
+    /// dropped char.
+    pub fn with_strip_bom(mut self, strip_bom: bool) -> Self {
+        self.strip_bom = strip_bom;
+        self
+    }
+
+    /// Prefix each output line with the number of its line of input, from 1,
+    /// right-aligned and followed by a tab, as `cat -n` does, if
+    /// `line_numbers`. The lines omitted count too, so that the numbers are
+    /// those of the input as long as '\n' is kept.
+    pub fn with_line_numbers(mut self, line_numbers: bool) -> Self {
+        self.line_number = line_numbers.then_some(1);
+        self
+    }
+
+    /// Bound the output lines, with their prefixes and numbers, if any, to
+    /// `max` chars each, treating a longer one per `overlong`. A line which
+    /// may be omitted is held back until its end, or until `flush`.
+    pub fn with_max_line_length(
+        mut self,
+        max: usize,
+        overlong: Overlong,
+    ) -> Self {
+        self.limiter = Some(LineLimiter::new(max, overlong));
+        self
+    }
+
+    /// Take NUL as the end of a line instead of '\n' if `null_data`, for the
+    /// regex rules, dropping lines, the line numbers and the line prefix,
+    /// e.g. for the output of `find -print0`. The char filter keeps NUL
+    /// then, as [`Options::null_data`] does, only if set up to.
+    pub fn with_null_data(mut self, null_data: bool) -> Self {
+        self.record_end = if null_data { b'\0' } else { b'\n' };
+        self
+    }
+
+    /// Prefix each output line with `prefix`, if not `None`, e.g. the name of
+    /// its file, before its number, if any.
+    pub fn with_line_prefix(mut self, prefix: Option<Vec<u8>>) -> Self {
+        self.line_prefix = prefix;
+        self
+    }
+
+    /// Write only the chars dropped otherwise, e.g. to audit what the filter
+    /// removes, instead of the chars kept. Invalid sequences are still
+    /// dropped, and nothing is substituted.
+    pub fn with_invert(mut self, invert: bool) -> Self {
+        self.invert = invert;
+        self
+    }
+
+    /// Strip the ANSI escape sequences, e.g. `ESC [ 31 m`, as whole units
+    /// before anything else.
+    pub fn with_strip_ansi(mut self) -> Self {
+        self.ansi = Some(AnsiStripper::new());
+        self
+    }
+
+    /// Normalize the text to `form` before filtering it.
+    #[cfg(feature = "unicode-normalization")]
+    pub fn with_normalization(mut self, form: Normalization) -> Self {
+        self.normalizer = Some(Normalizer::new(form));
+        self
+    }
+
+    /// Drop what `drop` matches in each line, and write what `keep` matches
+    /// as is unless dropped, instead of filtering it. A line is matched
+    /// without its '\n'.
+    pub fn with_regex_rules(
+        mut self,
+        drop: Option<Regex>,
+        keep: Option<Regex>,
+    ) -> Self {
+        self.rules = (drop.is_some() || keep.is_some())
+            .then(|| LineRules::new(drop, keep));
+        self
+    }
+
+    /// Convert the hostnames with labels which are not ASCII in each line,
+    /// unless kept or dropped by a regex rule, to punycode, e.g. bücher.de to
+    /// xn--bcher-kva.de, instead of filtering them.
+    pub fn with_punycode(mut self) -> Self {
+        let rules = self.rules.take();
+        let rules = rules.unwrap_or_else(|| LineRules::new(None, None));
+        self.rules = Some(rules.with_hostnames());
+        self
+    }
+
+    /// Drop emoji, keep them or replace them with their shortcodes per
+    /// `mode`, as whole sequences, e.g. 👍🏽, rather than char by char.
+    pub fn with_emoji(mut self, mode: EmojiMode) -> Self {
+        self.emoji = Some(EmojiSplitter::new(mode));
+        self
+    }
+
+    /// Filter by extended grapheme clusters: a cluster of several chars,
+    /// e.g. 👩‍💻, e plus a combining accent or a flag, is kept if the char
+    /// filter keeps all of its chars, and dropped and substituted as a whole
+    /// otherwise, as its first char dropped, so that no ZWJ or combining
+    /// mark is left dangling. A CRLF is filtered char by char still.
+    #[cfg(feature = "unicode-segmentation")]
+    pub fn with_graphemes(mut self) -> Self {
+        self.graphemes = Some(String::new());
+        self
+    }
+
+    /// Handle the invalid sequences written per `policy` instead of dropping
+    /// them.
+    pub fn with_invalid_utf8(mut self, policy: InvalidUtf8) -> Self {
+        self.invalid_utf8 = policy;
+        self
+    }
+
+    /// Write the control chars in `controls` too, e.g. form feed and bell,
+    /// whether or not `ascii_only`.
+    pub fn with_keep_controls(mut self, controls: CharSet) -> Self {
+        self.chars.keep_controls = Some(controls);
+        self
+    }
+
+    /// Write CR too, whether or not `ascii_only`.
+    pub fn with_keep_cr(mut self) -> Self {
+        self.chars.keep_cr = true;
+        self
+    }
+
+    /// Convert the line endings per `newlines`. The record separator, if
+    /// any, takes precedence over [`Newlines::Crlf`].
+    pub fn with_newlines(mut self, newlines: Newlines) -> Self {
+        self.newlines = Some(newlines);
+        self
+    }
+
+    /// Expand the tabs written to spaces up to the next multiple of `width`
+    /// columns, where each char takes one column.
+    pub fn with_expand_tabs(mut self, width: usize) -> Self {
+        self.tabs = Some(TabExpander::new(width));
+        self
+    }
+
+    /// Omit each line which drops any char, unless substituted, e.g. by the
+    /// replacer, instead of dropping the char alone. The output of a line is
+    /// held back until its end, or until `flush`.
+    pub fn with_drop_lines(mut self) -> Self {
+        self.held_line = Some(Vec::new());
+        self
+    }
+
+    /// Write each char dropped to `rejects` too, by the char filter, the
+    /// regex rules or emoji handling, on a line `LINE:COLUMN: U+XXXX c` of
+    /// its position in the text filtered if `positions`, or as is otherwise.
+    pub fn with_rejects<R: Write + Send + 'static>(
+        mut self,
+        rejects: R,
+        positions: bool,
+    ) -> Self {
+        let record = match positions {
+            true => Record::Positions,
+            false => Record::Chars,
+        };
+        self.rejects = Some(Rejects::new(Box::new(rejects), record));
+        self
+    }
+
+    /// Write a diagnostic per `format` to `report` for each char dropped, as
+    /// [`with_rejects`](Self::with_rejects) records them, with its position
+    /// in the text filtered, its byte offset in the input, and whether it was
+    /// removed or replaced, naming `file`, if not `None`.
+    pub fn with_report<R: Write + Send + 'static>(
+        mut self,
+        report: R,
+        format: ReportFormat,
+        file: Option<String>,
+    ) -> Self {
+        let record = match format {
+            ReportFormat::Jsonl => Record::Jsonl(file),
+        };
+        self.report = Some(Rejects::new(Box::new(report), record));
+        self
+    }
+
+    /// Write a hex dump line `OFFSET: XX XX  U+XXXX c` to `rejects` for each
+    /// char dropped, as [`with_on_drop`](Self::with_on_drop) reports them,
+    /// and `OFFSET: XX XX  invalid` for each invalid sequence, whatever
+    /// becomes of it, at its offset in the raw input.
+    pub fn with_hex_rejects<R: Write + Send + 'static>(
+        mut self,
+        rejects: R,
+    ) -> Self {
+        self.hex_rejects = Some(HexRejects::new(Box::new(rejects)));
+        self
+    }
+
+    /// Call `on_drop(c, offset)` for each char `c` dropped or replaced at
+    /// input `offset`, by the char filter, the regex rules or emoji handling,
+    /// e.g. to audit removals as they happen. The offset counts the bytes
+    /// written before, and is that in the text after normalization, ANSI
+    /// stripping and emoji shortcodes, if any. Invalid bytes count once
+    /// written, even before the text held back ahead of them.
+    pub fn with_on_drop<F: FnMut(char, usize) + Send + 'static>(
+        mut self,
+        on_drop: F,
+    ) -> Self {
+        self.on_drop = Some(Box::new(on_drop));
+        self
+    }
+
+    /// Fail with [`CheckError::Disallowed`] at the first char dropped or
+    /// replaced, or [`CheckError::InvalidUtf8`] at the first invalid
+    /// sequence, whatever becomes of it, e.g. to validate machine-generated
+    /// files rather than repair them. The error is an [`io::Error`] wrapping
+    /// the [`CheckError`], which [`Error`] takes out.
+    pub fn with_strict(mut self) -> Self {
+        // only the position is tracked
+        let position = Rejects::new(Box::new(io::sink()), Record::Positions);
+        self.strict = Some(position);
+        self
+    }
+
+    /// Write an undo log of the changes to the text to `log`, a line
+    /// `OFFSET LEN HEX` per run of chars dropped or replaced, invalid bytes
+    /// dropped or BOM stripped, whose `LEN` bytes at `OFFSET` of the output
+    /// stand for the original bytes in hex, from which [`restore`] restores
+    /// the input. The other transformations are not undone, and must be off
+    /// for the log to apply.
+    pub fn with_undo_log<L: Write + Send + 'static>(mut self, log: L) -> Self {
+        self.undo = Some(ChangeLog::undo(Box::new(log)));
+        self
+    }
+
+    /// Write a source map of the output to `map`, a line per segment of the
+    /// output in order, `N` for `N` bytes output as they are input, and
+    /// `OUT IN` for `OUT` bytes, none if removed, standing for `IN` bytes of
+    /// the input, from which [`SourceMap`] translates the output offsets to
+    /// the input. The segments are of the same changes as
+    /// [`with_undo_log`](Self::with_undo_log) records, and the other
+    /// transformations must be off for the map to apply.
+    pub fn with_source_map<M: Write + Send + 'static>(
+        mut self,
+        map: M,
+    ) -> Self {
+        self.source_map = Some(ChangeLog::source_map(Box::new(map)));
+        self
+    }
+
+    /// Count each char dropped or replaced in `histogram`, as
+    /// [`with_on_drop`](Self::with_on_drop) reports them, e.g. shared by the
+    /// writers of several files for a summary of the run.
+    pub fn with_histogram(mut self, histogram: Arc<Mutex<Histogram>>) -> Self {
+        self.histogram = Some(histogram);
+        self
+    }
+
+    /// Write `record_sep` instead of each '\n', if not `None`.
+    pub fn with_record_sep(mut self, record_sep: Option<Vec<u8>>) -> Self {
+        self.record_sep = record_sep;
+        self
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.backend
+    }
+
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.backend
+    }
+
+    /// End the input: handle an incomplete char written last as invalid, and
+    /// flush the text held back. Writing to a `FilterWriter<Vec<u8>>` and
+    /// taking its output with [`get_mut`](Self::get_mut) filters a stream
+    /// chunk by chunk without blocking, e.g. in async code.
+    pub fn finish(&mut self) -> io::Result<()> {
+        self.end_input()?;
+        self.flush()
+    }
+
+    /// Return the backend, dropping an incomplete char written last, and the
+    /// text held back for normalization unless flushed.
+    pub fn into_inner(self) -> W {
+        self.backend
+    }
+
+    /// Return the totals so far, where the bytes written are those passed
+    /// to the backend, and the text held back is not yet filtered.
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    /// Return true if the text written last ends with a kept '\n', after
+    /// which nothing held back combines with what is written next, so that
+    /// flushing there leaves the output the same.
+    pub(crate) fn at_line_end(&self) -> bool {
+        self.ends_line
+            && self.utf8.is_empty()
+            && self.chars.keeps(self.record_end as char) != self.invert
+    }
+
+    /// Return true if `buf` is kept as is, being a run of the ASCII chars kept
+    /// which nothing held back before it or transforming text applies to.
+    fn passes(&self, buf: &[u8]) -> bool {
+        #[cfg(feature = "unicode-normalization")]
+        if self.normalizer.is_some() {
+            return false;
+        }
+        #[cfg(feature = "unicode-segmentation")]
+        if self.graphemes.is_some() {
+            return false;
+        }
+        !self.invert
+            && self.utf8.is_empty()
+            && !self.held_cr
+            && self.ansi.is_none()
+            && self.rules.is_none()
+            && self.emoji.is_none()
+            && self.chars.ascii_run(buf) == buf.len()
+    }
+
+    /// Handle an incomplete char written last as invalid, at the end of
+    /// input.
+    pub(crate) fn end_input(&mut self) -> io::Result<()> {
+        let rest = self.utf8.finish().to_vec();
+        if rest.is_empty() {
+            return Ok(());
+        }
+        let offset = self.stats.bytes_read - rest.len() as u64;
+        self.write_invalid(&rest, offset)?;
+        self.write_batch()
+    }
+
+    /// Handle the invalid `bytes` at input `offset` per the policy.
+    fn write_invalid(&mut self, bytes: &[u8], offset: u64) -> io::Result<()> {
+        self.stats.invalid_bytes += bytes.len() as u64;
+        log::trace!("invalid utf-8 {:02x?} at {}", bytes, offset);
+        if let Some(hex_rejects) = &mut self.hex_rejects {
+            hex_rejects.reject_invalid(bytes, offset)?;
+        }
+        if let Some(strict) = &self.strict {
+            let (line, column) = strict.position();
+            return Err(io::Error::other(CheckError::InvalidUtf8 {
+                bytes: bytes.to_vec(),
+                line,
+                column,
+            }));
+        }
+        match self.invalid_utf8.decode(bytes, offset)? {
+            Some(text) => self.write_str(&text),
+            None => {
+                self.offset += bytes.len();
+                if let Some(report) = &mut self.report {
+                    report.skip(bytes.len());
+                }
+                for log in
+                    [&mut self.undo, &mut self.source_map].into_iter().flatten()
+                {
+                    log.drop_invalid(bytes, offset)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Report the char `c` dropped at `offset` to the callback and the hex
+    /// dump, if any.
+    fn report_drop(&mut self, c: char, offset: usize) -> io::Result<()> {
+        log::trace!("dropped {:?} U+{:04X} at {}", c, c as u32, offset);
+        if let Some(on_drop) = &mut self.on_drop {
+            on_drop(c, offset);
+        }
+        if let Some(histogram) = &self.histogram {
+            histogram.lock().unwrap().record(c);
+        }
+        if let Some(hex_rejects) = &mut self.hex_rejects {
+            hex_rejects.reject(c, offset as u64)?;
+        }
+        match &self.strict {
+            Some(strict) => {
+                let (line, column) = strict.position();
+                Err(io::Error::other(CheckError::Disallowed {
+                    c,
+                    line,
+                    column,
+                }))
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Write kept bytes to the backend, expanding tabs, holding back the line
+    /// if lines may be dropped, and substituting the record separator.
+    fn emit(&mut self, bytes: &[u8]) -> io::Result<()> {
+        if !bytes.is_empty() {
+            self.collapsing = false;
+            self.squeezed = 0;
+            if std::mem::take(&mut self.word_gap)
+                && first_char(bytes).is_some_and(char::is_alphanumeric)
+            {
+                if let Some(separator) = self.word_sep.clone() {
+                    self.emit(separator.as_bytes())?;
+                }
+            }
+            self.after_alnum =
+                last_char(bytes).is_some_and(char::is_alphanumeric);
+        }
+        let bytes = match &mut self.tabs {
+            Some(tabs) => tabs.expand(bytes),
+            None => Cow::Borrowed(bytes),
+        };
+        if self.held_line.is_none() {
+            return self.emit_records(&bytes);
+        }
+        let end = self.record_end;
+        for piece in bytes.split_inclusive(|&b| b == end) {
+            let line = self.held_line.as_mut().unwrap();
+            line.extend_from_slice(piece);
+            if piece.ends_with(&[end]) {
+                self.finish_line()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the line held back, unless dropped.
+    fn finish_line(&mut self) -> io::Result<()> {
+        let Some(line) = self.held_line.take() else {
+            return Ok(());
+        };
+        let ends_line = line.ends_with(&[self.record_end]);
+        let result = if std::mem::take(&mut self.line_dropped) {
+            if let (Some(number), true) = (&mut self.line_number, ends_line) {
+                *number += 1;
+            }
+            Ok(())
+        } else {
+            self.emit_records(&line)
+        };
+        self.held_line = Some(Vec::new());
+        result
+    }
+
+    /// Write `bytes` to the backend, prefixing each line with the prefix and
+    /// its number, if any.
+    fn emit_records(&mut self, bytes: &[u8]) -> io::Result<()> {
+        if self.line_number.is_none() && self.line_prefix.is_none() {
+            return self.emit_limited(bytes);
+        }
+        let end = self.record_end;
+        for piece in bytes.split_inclusive(|&b| b == end) {
+            if !self.line_started {
+                self.start_line()?;
+            }
+            self.emit_limited(piece)?;
+            if piece.ends_with(&[end]) {
+                if let Some(number) = &mut self.line_number {
+                    *number += 1;
+                }
+                self.line_started = false;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the prefix and the number of the output line started.
+    fn start_line(&mut self) -> io::Result<()> {
+        self.line_started = true;
+        // taken out of `self` while `emit_limited` borrows it
+        let prefix = self.line_prefix.take();
+        let result = match &prefix {
+            Some(prefix) => self.emit_limited(prefix),
+            None => Ok(()),
+        };
+        self.line_prefix = prefix;
+        result?;
+        match self.line_number {
+            Some(number) => {
+                self.emit_limited(format!("{:>6}\t", number).as_bytes())
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Write `bytes` to the backend with the lines bounded, if at all.
+    fn emit_limited(&mut self, bytes: &[u8]) -> io::Result<()> {
+        match &mut self.limiter {
+            Some(limiter) => {
+                let limited = limiter.limit(bytes, self.record_end);
+                self.emit_separated(&limited)
+            }
+            None => self.emit_separated(bytes),
+        }
+    }
+
+    /// Write `bytes` to the backend, substituting the record separator.
+    fn emit_separated(&mut self, bytes: &[u8]) -> io::Result<()> {
+        // taken out of `self` while `put` borrows it
+        let own_sep = self.record_sep.take();
+        let record_sep = match (&own_sep, self.newlines) {
+            (Some(sep), _) => Some(sep.as_slice()),
+            (None, Some(Newlines::Crlf)) => Some(b"\r\n".as_slice()),
+            (None, _) => None,
+        };
+        let result = match record_sep {
+            None => self.put(bytes),
+            Some(sep) => self.put_records(bytes, sep),
+        };
+        self.record_sep = own_sep;
+        result
+    }
+
+    /// Add `bytes` to the batch with `sep` instead of each '\n'.
+    fn put_records(&mut self, bytes: &[u8], sep: &[u8]) -> io::Result<()> {
+        let mut records = bytes.split(|&b| b == b'\n');
+        if let Some(first) = records.next() {
+            self.put(first)?;
+        }
+        for record in records {
+            self.put(sep)?;
+            self.put(record)?;
+        }
+        Ok(())
+    }
+
+    /// Add `bytes` to the batch written to the backend at the end of the
+    /// `write` call, writing the batch early if it would grow past
+    /// `BATCH_LEN`, and `bytes` directly if that large themselves.
+    fn put(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.stats.bytes_written += bytes.len() as u64;
+        if self.batch.len() + bytes.len() > BATCH_LEN {
+            self.write_batch()?;
+        }
+        if bytes.len() >= BATCH_LEN {
+            return self.backend.write_all(bytes);
+        }
+        self.batch.extend_from_slice(bytes);
+        Ok(())
+    }
+
+    /// Write the batch to the backend in one go.
+    fn write_batch(&mut self) -> io::Result<()> {
+        if self.batch.is_empty() {
+            return Ok(());
+        }
+        let result = self.backend.write_all(&self.batch);
+        self.batch.clear();
+        result
+    }
+
+    /// Normalize and filter the valid utf-8 `s` to the backend.
+    fn write_str(&mut self, s: &str) -> io::Result<()> {
+        let s = match &mut self.ansi {
+            Some(ansi) => ansi.strip(s),
+            None => Cow::Borrowed(s),
+        };
+        #[cfg(feature = "unicode-normalization")]
+        if let Some(normalizer) = &mut self.normalizer {
+            let normalized = normalizer.push(&s);
+            return self.write_normalized(&normalized);
+        }
+        self.write_normalized(&s)
+    }
+
+    /// Apply the regex rules to the normalized `s`, filtering the rest to the
+    /// backend.
+    fn write_normalized(&mut self, s: &str) -> io::Result<()> {
+        match &mut self.rules {
+            Some(rules) => match rules.push(s, self.record_end as char) {
+                Some(lines) => self.write_lines(&lines),
+                None => Ok(()),
+            },
+            None => self.write_chars(s),
+        }
+    }
+
+    /// Apply the regex rules to the whole `lines`.
+    fn write_lines(&mut self, lines: &str) -> io::Result<()> {
+        let Some(rules) = &self.rules else {
+            return self.write_chars(lines);
+        };
+        for (rule, span) in rules.split(lines, self.record_end as char) {
+            match rule {
+                Rule::Filter => {
+                    // no emoji continues past the span
+                    self.write_chars(span)?;
+                    self.finish_emoji()?;
+                }
+                Rule::Punycode => self.write_hostname(span)?,
+                rule => self.write_ruled(rule, span)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the hostname `span` converted to punycode, or filter it if
+    /// inverted, or if it does not convert.
+    fn write_hostname(&mut self, span: &str) -> io::Result<()> {
+        let ascii = match self.invert {
+            true => None,
+            false => punycode::host_to_ascii(span),
+        };
+        let Some(ascii) = ascii else {
+            self.write_chars(span)?;
+            return self.finish_emoji();
+        };
+        self.finish_graphemes()?;
+        self.finish_cr()?;
+        self.offset += span.len();
+        self.advance_rejects(span);
+        let replaced = span.chars().filter(|c| !c.is_ascii()).count();
+        self.stats.chars_replaced += replaced as u64;
+        self.emit(ascii.as_bytes())
+    }
+
+    /// Write `span`, which a rule keeps or drops, if kept, or only if dropped
+    /// when inverted.
+    fn write_ruled(&mut self, rule: Rule, span: &str) -> io::Result<()> {
+        // no LF follows the CR held back
+        self.finish_graphemes()?;
+        self.finish_cr()?;
+        let kept = matches!(
+            (rule, self.invert),
+            (Rule::Keep, false) | (Rule::Drop, true)
+        );
+        let offset = self.offset;
+        self.offset += span.len();
+        if kept {
+            self.advance_rejects(span);
+            return self.emit(span.as_bytes());
+        }
+        for (i, c) in span.char_indices() {
+            self.report_drop(c, offset + i)?;
+        }
+        span.chars().try_for_each(|c| self.reject(c, None))
+    }
+
+    /// Advance the position of the chars dropped over `s`.
+    fn advance_rejects(&mut self, s: &str) {
+        let positions = [&mut self.rejects, &mut self.report, &mut self.strict];
+        for rejects in positions.into_iter().flatten() {
+            rejects.advance(s);
+        }
+        for log in [&mut self.undo, &mut self.source_map].into_iter().flatten()
+        {
+            log.advance(s.len());
+        }
+    }
+
+    /// Record the dropped `c`, substituted by `replacement` if not `None`.
+    fn reject(
+        &mut self,
+        c: char,
+        replacement: Option<&[u8]>,
+    ) -> io::Result<()> {
+        for rejects in
+            [&mut self.rejects, &mut self.report].into_iter().flatten()
+        {
+            rejects.reject(c, replacement)?;
+        }
+        let len = replacement.map_or(0, <[u8]>::len);
+        for log in [&mut self.undo, &mut self.source_map].into_iter().flatten()
+        {
+            log.change(c.encode_utf8(&mut [0; 4]).as_bytes(), len)?;
+        }
+        Ok(())
+    }
+
+    /// Handle the emoji in `s`, filtering the rest to the backend.
+    fn write_chars(&mut self, s: &str) -> io::Result<()> {
+        match &mut self.emoji {
+            Some(emoji) => {
+                let spans = emoji.push(s);
+                self.write_emoji_spans(spans)
+            }
+            None => self.filter_chars(s),
+        }
+    }
+
+    /// Write the text held back as a possible emoji sequence.
+    fn finish_emoji(&mut self) -> io::Result<()> {
+        match &mut self.emoji {
+            Some(emoji) => {
+                let spans = emoji.finish();
+                self.write_emoji_spans(spans)
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn write_emoji_spans(
+        &mut self,
+        spans: Vec<(Rule, String)>,
+    ) -> io::Result<()> {
+        for (rule, span) in spans {
+            match rule {
+                Rule::Filter => self.filter_chars(&span)?,
+                rule => self.write_ruled(rule, &span)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Filter the valid utf-8 `s` to the backend, by grapheme clusters if
+    /// enabled.
+    fn filter_chars(&mut self, s: &str) -> io::Result<()> {
+        #[cfg(feature = "unicode-segmentation")]
+        if self.graphemes.is_some() {
+            return self.filter_graphemes(s, false);
+        }
+        self.filter_scalars(s)
+    }
+
+    /// Filter `s` after the cluster held back by extended grapheme clusters,
+    /// holding back the last cluster unless at the `end` of the text.
+    #[cfg(feature = "unicode-segmentation")]
+    fn filter_graphemes(&mut self, s: &str, end: bool) -> io::Result<()> {
+        use unicode_segmentation::UnicodeSegmentation;
+
+        let Some(held) = &mut self.graphemes else {
+            return self.filter_scalars(s);
+        };
+        held.push_str(s);
+        let text = std::mem::take(held);
+        // the start of the run of clusters filtered char by char
+        let mut run: usize = 0;
+        let mut rest = text.len();
+        for (i, cluster) in text.grapheme_indices(true) {
+            if i + cluster.len() == text.len() && !end {
+                rest = i;
+                break;
+            }
+            let whole = cluster.chars().nth(1).is_none()
+                || cluster == "\r\n"
+                || cluster.chars().all(|c| self.chars.keeps(c));
+            if !whole {
+                self.filter_scalars(&text[run..i])?;
+                self.drop_cluster(cluster)?;
+                run = i + cluster.len();
+            }
+        }
+        self.filter_scalars(&text[run..rest])?;
+        if let Some(held) = &mut self.graphemes {
+            held.push_str(&text[rest..]);
+        }
+        Ok(())
+    }
+
+    /// Drop the `cluster` of several chars, some dropped by the char filter,
+    /// as a whole, substituting it as its first char dropped, or write it
+    /// whole if inverted.
+    #[cfg(feature = "unicode-segmentation")]
+    fn drop_cluster(&mut self, cluster: &str) -> io::Result<()> {
+        let offset = self.offset;
+        self.offset += cluster.len();
+        if self.invert {
+            self.advance_rejects(cluster);
+            return self.emit(cluster.as_bytes());
+        }
+        for (i, c) in cluster.char_indices() {
+            self.report_drop(c, offset + i)?;
+        }
+        let first = cluster.chars().position(|c| !self.chars.keeps(c));
+        for (i, c) in cluster.chars().enumerate() {
+            if Some(i) == first {
+                continue;
+            }
+            self.reject(c, None)?;
+            self.stats.chars_removed += 1;
+        }
+        match first.and_then(|i| cluster.chars().nth(i)) {
+            Some(c) => self.replace_char(c),
+            None => Ok(()),
+        }
+    }
+
+    /// Filter the cluster held back, e.g. before a span decided otherwise.
+    fn finish_graphemes(&mut self) -> io::Result<()> {
+        #[cfg(feature = "unicode-segmentation")]
+        if self.graphemes.is_some() {
+            return self.filter_graphemes("", true);
+        }
+        Ok(())
+    }
+
+    /// Filter the valid utf-8 `s` to the backend char by char.
+    fn filter_scalars(&mut self, s: &str) -> io::Result<()> {
+        let joined: String;
+        let s = if std::mem::take(&mut self.held_cr) {
+            joined = format!("\r{}", s);
+            &joined
+        } else {
+            s
+        };
+        let offset = self.offset;
+        if self.chars.keeps_all() && self.newlines.is_none() {
+            self.offset += s.len();
+            if self.invert {
+                for (i, c) in s.char_indices() {
+                    self.report_drop(c, offset + i)?;
+                }
+                return s.chars().try_for_each(|c| self.reject(c, None));
+            }
+            self.advance_rejects(s);
+            return self.emit(s.as_bytes());
+        }
+        let mut kept: usize = 0;
+        // the end of the text the position of the rejects is advanced over
+        let mut seen: usize = 0;
+        let mut j: usize = 0;
+        while j < s.len() {
+            if !self.invert {
+                // skip the ASCII chars kept without decoding them
+                j += self.chars.ascii_run(&s.as_bytes()[j..]);
+            }
+            let Some(c) = s[j..].chars().next() else {
+                break;
+            };
+            let next = j + c.len_utf8();
+            if let (Some(newlines), '\r') = (self.newlines, c) {
+                match s[next..].chars().next() {
+                    // wait for what follows
+                    None => {
+                        self.emit(&s.as_bytes()[kept..j])?;
+                        self.advance_rejects(&s[seen..j]);
+                        self.held_cr = true;
+                        self.offset = offset + j;
+                        return Ok(());
+                    }
+                    Some('\n') => {
+                        // the CR of a CRLF, kept or converted as a whole
+                        if newlines != Newlines::Keep || self.invert {
+                            self.emit(&s.as_bytes()[kept..j])?;
+                            kept = next;
+                        }
+                        j = next;
+                        continue;
+                    }
+                    Some(_) => (),
+                }
+            }
+            if self.chars.keeps(c) != self.invert {
+                j = next;
+                continue;
+            }
+            // Emit the run of kept chars before `c`, and drop `c`.
+            self.emit(&s.as_bytes()[kept..j])?;
+            kept = next;
+            self.advance_rejects(&s[seen..j]);
+            seen = kept;
+            self.report_drop(c, offset + j)?;
+            self.replace_char(c)?;
+            j = next;
+        }
+        self.offset = offset + s.len();
+        self.advance_rejects(&s[seen..]);
+        self.emit(&s.as_bytes()[kept..])
+    }
+
+    /// Substitute the dropped `c`, unless inverted.
+    fn replace_char(&mut self, c: char) -> io::Result<()> {
+        if self.invert {
+            return self.reject(c, None);
+        }
+        let mut replacement: Vec<u8> = Vec::new();
+        match (self.chars.replacement(c), &mut self.replacer) {
+            (Some(s), _) => replacement = s.into_bytes(),
+            (None, Some(replacer)) => replacer.replace(c, &mut replacement)?,
+            (None, None) => (),
+        }
+        let replaced = Some(replacement.as_slice()).filter(|r| !r.is_empty());
+        self.reject(c, replaced)?;
+        if replacement.is_empty() {
+            self.stats.chars_removed += 1;
+            self.word_gap |= self.after_alnum && self.word_sep.is_some();
+        } else {
+            self.stats.chars_replaced += 1;
+        }
+        if self.held_line.is_some() && replacement.is_empty() {
+            self.line_dropped = true;
+            return Ok(());
+        }
+        match &self.collapse {
+            Some(separator) if replacement.is_empty() => {
+                if self.collapsing {
+                    return Ok(());
+                }
+                let separator = separator.clone();
+                self.emit(separator.as_bytes())?;
+                self.collapsing = true;
+                Ok(())
+            }
+            _ => match &self.squeeze {
+                Some((squeeze, max)) if replacement == *squeeze => {
+                    let squeezed = self.squeezed;
+                    if squeezed < *max {
+                        self.emit(&replacement)?;
+                        self.squeezed = squeezed + 1;
+                    }
+                    Ok(())
+                }
+                _ => self.emit(&replacement),
+            },
+        }
+    }
+
+    /// Filter the CR held back, which ends no line.
+    fn finish_cr(&mut self) -> io::Result<()> {
+        if !std::mem::take(&mut self.held_cr) {
+            return Ok(());
+        }
+        self.offset += 1;
+        if self.chars.keeps('\r') != self.invert {
+            self.advance_rejects("\r");
+            self.emit(b"\r")
+        } else {
+            self.report_drop('\r', self.offset - 1)?;
+            self.replace_char('\r')
+        }
+    }
+}
+
+impl<W: Write> FilterWriter<W> {
+    /// Filter `buf` into the batch.
+    fn write_unbatched(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.stats.bytes_read += buf.len() as u64;
+        if let Some(&last) = buf.last() {
+            self.ends_line = last == self.record_end;
+        }
+        if self.passes(buf) {
+            // skip the decoding and the per-char work
+            self.offset += buf.len();
+            // `buf` is ASCII.
+            self.advance_rejects(std::str::from_utf8(buf).unwrap());
+            return self.emit(buf);
+        }
+        let mut utf8 = self.utf8;
+        // the input offset of the next chunk, which may start with the bytes
+        // carried over
+        let mut offset =
+            self.stats.bytes_read - (buf.len() + utf8.len()) as u64;
+        let decoded = utf8.decode(buf, |chunk| match chunk {
+            Utf8Chunk::Valid(mut s) => {
+                if offset == 0 && self.strip_bom {
+                    if let Some(rest) = s.strip_prefix('\u{feff}') {
+                        // The positions of the rejects are of the text
+                        // after it.
+                        let len = s.len() - rest.len();
+                        self.offset += len;
+                        if let Some(report) = &mut self.report {
+                            report.skip(len);
+                        }
+                        for log in [&mut self.undo, &mut self.source_map]
+                            .into_iter()
+                            .flatten()
+                        {
+                            log.change(&s.as_bytes()[..len], 0)?;
+                        }
+                        offset += len as u64;
+                        s = rest;
+                    }
+                }
+                offset += s.len() as u64;
+                self.write_str(s)
+            }
+            Utf8Chunk::Invalid(bytes) => {
+                offset += bytes.len() as u64;
+                self.write_invalid(bytes, offset - bytes.len() as u64)
+            }
+        });
+        self.utf8 = utf8;
+        decoded
+    }
+}
+
+impl<W: Write> Write for FilterWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.write_unbatched(buf)?;
+        self.write_batch()?;
+        Ok(buf.len())
+    }
+
+    /// Filter all of `bufs` in turn, writing what is kept of them to the
+    /// backend at once.
+    fn write_vectored(
+        &mut self,
+        bufs: &[io::IoSlice<'_>],
+    ) -> io::Result<usize> {
+        let mut n: usize = 0;
+        for buf in bufs {
+            self.write_unbatched(buf)?;
+            n += buf.len();
+        }
+        self.write_batch()?;
+        Ok(n)
+    }
+
+    /// Flush the text held back for normalization, regex rules, emoji and
+    /// line endings too, which then no longer combines with what is written
+    /// next.
+    fn flush(&mut self) -> io::Result<()> {
+        #[cfg(feature = "unicode-normalization")]
+        if let Some(normalizer) = &mut self.normalizer {
+            let held = normalizer.finish();
+            self.write_normalized(&held)?;
+        }
+        if let Some(rules) = &mut self.rules {
+            let line = rules.finish();
+            self.write_lines(&line)?;
+        }
+        self.finish_emoji()?;
+        self.finish_graphemes()?;
+        self.finish_cr()?;
+        self.finish_line()?;
+        if let Some(limiter) = &mut self.limiter {
+            let held = limiter.finish();
+            self.emit_separated(&held)?;
+        }
+        for rejects in
+            [&mut self.rejects, &mut self.report].into_iter().flatten()
+        {
+            rejects.flush()?;
+        }
+        if let Some(hex_rejects) = &mut self.hex_rejects {
+            hex_rejects.flush()?;
+        }
+        for log in [&mut self.undo, &mut self.source_map].into_iter().flatten()
+        {
+            log.flush()?;
+        }
+        self.write_batch()?;
+        self.backend.flush()
+    }
+}
+
+/// Return the first char of the UTF-8 in `bytes`, if valid.
+fn first_char(bytes: &[u8]) -> Option<char> {
+    let len = bytes.len().min(4);
+    (1..=len)
+        .find_map(|n| std::str::from_utf8(&bytes[..n]).ok())
+        .and_then(|s| s.chars().next())
+}
+
+/// Return the last char of the UTF-8 in `bytes`, if valid.
+fn last_char(bytes: &[u8]) -> Option<char> {
+    let start = bytes.iter().rposition(|&b| b & 0xC0 != 0x80)?;
+    std::str::from_utf8(&bytes[start..]).ok()?.chars().next()
+}
+
+/// Filter `r` to `w` straight from the buffer of `r`, in chunks of at most
+/// `buf_size` bytes, and flush `w`.
+fn buffer_filter<R: BufRead, W: Write>(
+    buf_size: usize,
+    r: &mut R,
+    w: &mut FilterWriter<W>,
+) -> io::Result<()> {
+    buffer_filter_checkpointed(buf_size, r, w, |_, _| Ok(()))?;
+    w.flush()
+}
+
+/// Like `buffer_filter`, but call `checkpoint(n, w)` after each chunk, where
+/// `n` is the number of input bytes in the chunk, and leave `w` unflushed.
+/// No input before a checkpoint affects the output after it.
+fn buffer_filter_checkpointed<R, W, C>(
+    buf_size: usize,
+    r: &mut R,
+    w: &mut FilterWriter<W>,
+    mut checkpoint: C,
+) -> io::Result<()>
+where
+    R: BufRead,
+    W: Write,
+    C: FnMut(usize, &mut FilterWriter<W>) -> io::Result<()>,
+{
+    loop {
+        let buf = match r.fill_buf() {
+            Ok([]) => break,
+            Ok(buf) => buf,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {
+                if caught_signal().is_some() {
+                    break;
+                }
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+        let n = buf.len().min(buf_size.max(1));
+        w.write_all(&buf[..n])?;
+        r.consume(n);
+        checkpoint(n, w)?;
+        if caught_signal().is_some() {
+            break;
+        }
+    }
+    w.end_input()
+}
+
+/// Filter `r` to `w` per `opts` like `buffer_filter`, flushing `w` after
+/// each line if `opts.line_buffered` or `opts.follow`.
+fn filter_text<R: BufRead, W: Write>(
+    r: &mut R,
+    w: &mut FilterWriter<W>,
+    opts: &Options,
+) -> io::Result<()> {
+    if !opts.flushes_lines() {
+        return buffer_filter(opts.buf_size, r, w);
+    }
+    let end = if opts.null_data { b'\0' } else { b'\n' };
+    let mut r = LineChunks(r, end);
+    buffer_filter_checkpointed(opts.buf_size, &mut r, w, |_, w| {
+        // Flushing elsewhere would end the text held back early.
+        if w.at_line_end() {
+            w.flush()?;
+        }
+        Ok(())
+    })?;
+    w.flush()
+}
+
+/// A reader of `inner` whose buffer ends after its first line end, '\n' or
+/// NUL, so that each line is filtered apart.
+struct LineChunks<R>(R, u8);
+
+impl<R: Read> Read for LineChunks<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+impl<R: BufRead> BufRead for LineChunks<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        let buf = self.0.fill_buf()?;
+        let end = self.1;
+        Ok(match buf.iter().position(|&b| b == end) {
+            Some(i) => &buf[..=i],
+            None => buf,
+        })
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.0.consume(amt)
+    }
+}
+
+/// Return the replacer of the chars dropped per `opts`, or `None` if they are
+/// dropped without substitution.
+fn replacer(opts: &Options) -> Option<MappingReplacer> {
+    if opts.mappings.is_empty()
+        && !opts.strips_diacritics()
+        && opts.escape.is_none()
+        && opts.control_escape.is_none()
+        && opts.replacement.is_none()
+        && !opts.highlight
+    {
+        return None;
+    }
+    let mut replacer = MappingReplacer::new(opts.mappings.clone());
+    #[cfg(feature = "unicode-normalization")]
+    if opts.strip_diacritics {
+        replacer = replacer.with_strip_diacritics();
+    }
+    if opts.highlight {
+        replacer = replacer.with_highlight();
+    }
+    if let Some(escape) = opts.control_escape {
+        replacer = replacer.with_control_escape(escape);
+    }
+    if let Some(escape) = opts.escape {
+        replacer = replacer.with_escape(escape);
+    }
+    if let Some(replacement) = &opts.replacement {
+        replacer = replacer.with_replacement(replacement.clone());
+    }
+    Some(replacer)
+}
+
+/// Create the `FilterWriter` per `opts`, without the record separator.
+fn filter_writer<W: Write>(backend: W, opts: &Options) -> FilterWriter<W> {
+    let mut fw = FilterWriter::new(backend, false)
+        .with_invert(opts.invert)
+        .with_null_data(opts.null_data);
+    fw.chars = CharFilter::new(opts);
+    if opts.strip_ansi {
+        fw = fw.with_strip_ansi();
+    }
+    #[cfg(feature = "unicode-normalization")]
+    if let Some(form) = opts.normalization {
+        fw = fw.with_normalization(form);
+    }
+    fw = fw.with_regex_rules(opts.drop_regex.clone(), opts.keep_regex.clone());
+    if opts.punycode {
+        fw = fw.with_punycode();
+    }
+    if let Some(mode) = opts.emoji {
+        fw = fw.with_emoji(mode);
+    }
+    #[cfg(feature = "unicode-segmentation")]
+    if opts.graphemes {
+        fw = fw.with_graphemes();
+    }
+    if let Some(newlines) = opts.newlines {
+        fw = fw.with_newlines(newlines);
+    }
+    fw = fw
+        .with_invalid_utf8(opts.invalid_utf8)
+        .with_strip_bom(opts.strip_bom);
+    if let Some(separator) = &opts.collapse {
+        fw = fw.with_collapse(separator.clone());
+    }
+    if let (Some(replacement), Some(max)) =
+        (&opts.replacement, opts.squeeze_replacements)
+    {
+        fw = fw.with_squeeze(replacement.clone(), max);
+    }
+    if let Some(separator) = &opts.word_sep {
+        fw = fw.with_word_sep(separator.clone());
+    }
+    if let Some(width) = opts.expand_tabs {
+        fw = fw.with_expand_tabs(width);
+    }
+    if opts.drop_lines {
+        fw = fw.with_drop_lines();
+    }
+    if let Some(histogram) = &opts.histogram {
+        fw = fw.with_histogram(Arc::clone(histogram));
+    }
+    match replacer(opts) {
+        Some(replacer) => fw.with_replacer(replacer),
+        None => fw,
+    }
+}
+
+/// Write the chars `fw` drops to the file `opts.rejects` too, if any, and
+/// their hex dump to `opts.rejects_hex`, appending to them, make `fw` strict
+/// per `opts.strict`, and write its undo log to `opts.undo_log` and its
+/// source map to `opts.source_map`.
+fn with_rejects<W: Write>(
+    mut fw: FilterWriter<W>,
+    opts: &Options,
+) -> io::Result<FilterWriter<W>> {
+    let append = |path| OpenOptions::new().create(true).append(true).open(path);
+    if let Some(path) = &opts.rejects {
+        let file = io::BufWriter::new(append(path)?);
+        fw = fw.with_rejects(file, opts.reject_positions);
+    }
+    if let Some(path) = &opts.rejects_hex {
+        fw = fw.with_hex_rejects(io::BufWriter::new(append(path)?));
+    }
+    if let Some(format) = opts.report {
+        let report: Box<dyn Write + Send> = match &opts.report_output {
+            Some(path) => Box::new(io::BufWriter::new(append(path)?)),
+            None => Box::new(io::stderr()),
+        };
+        fw = fw.with_report(report, format, opts.report_name.clone());
+    }
+    if opts.strict {
+        fw = fw.with_strict();
+    }
+    let conflict = |log| match opts.change_log_conflict() {
+        Some(option) => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("the {} does not apply with {}", log, option),
+        )),
+        None => Ok(()),
+    };
+    if let Some(path) = &opts.undo_log {
+        conflict("undo log")?;
+        fw = fw.with_undo_log(io::BufWriter::new(File::create(path)?));
+    }
+    if let Some(path) = &opts.source_map {
+        conflict("source map")?;
+        fw = fw.with_source_map(io::BufWriter::new(File::create(path)?));
+    }
+    Ok(fw)
+}
+
+/// Filter `bytes` in memory per `opts`, of which the char filter and the
+/// mappings apply, returning the filtered bytes. The invalid sequences are
+/// handled per `opts.invalid_utf8` as by [`filter`], but dropped rather than
+/// failing with [`InvalidUtf8::Error`].
+pub fn filter_bytes(bytes: &[u8], opts: &Options) -> Vec<u8> {
+    filter_bytes_counted(bytes, opts, &mut Stats::default())
+}
+
+/// Like [`filter_bytes`], but add the stats of the filtering to `stats`.
+fn filter_bytes_counted(
+    bytes: &[u8],
+    opts: &Options,
+    stats: &mut Stats,
+) -> Vec<u8> {
+    let mut out: Vec<u8> = Vec::with_capacity(bytes.len());
+    let mut fw = filter_writer(&mut out, opts);
+    if opts.invalid_utf8 == InvalidUtf8::Error {
+        fw = fw.with_invalid_utf8(InvalidUtf8::Drop);
+    }
+    // `bytes` may be a part of the input, e.g. a field of a line.
+    fw = fw.with_strip_bom(false);
+    let mut r = bytes;
+    // Reading from a slice and writing to a `Vec` never fail.
+    buffer_filter(opts.buf_size, &mut r, &mut fw).unwrap();
+    *stats += fw.stats();
+    out
+}
+
+/// Filter the string `s` in memory per `opts`.
+fn filter_string(s: &str, opts: &Options) -> String {
+    let filtered = filter_bytes(s.as_bytes(), opts);
+    // `s` is valid utf-8, and so is whatever `FilterWriter` keeps from it.
+    String::from_utf8(filtered).unwrap()
+}
+
+/// Filter `input` in memory per `opts`, of which the char filter and the
+/// mappings apply, borrowing `input` if the filter would leave it as it is.
+pub fn filter_str<'a>(input: &'a str, opts: &Options) -> Cow<'a, str> {
+    // kept chars are left as they are, unless normalized, which leaves the
+    // ASCII subset as it is
+    let chars = CharFilter::new(opts);
+    let unchanged = !opts.invert
+        && !opts.has_regex_rules()
+        && opts.emoji.is_none()
+        && match opts.newlines {
+            Some(Newlines::Crlf) => !input.contains('\n'),
+            Some(_) => !input.contains("\r\n"),
+            None => true,
+        }
+        && (opts.expand_tabs.is_none() || !input.contains('\t'))
+        && (!opts.strip_ansi || !ansi::has_escapes(input))
+        && (!opts.normalizes() || input.chars().all(is_ascii_subset))
+        && (chars.keeps_all() || input.chars().all(|c| chars.keeps(c)));
+    if unchanged {
+        Cow::Borrowed(input)
+    } else {
+        Cow::Owned(filter_string(input, opts))
+    }
+}
+
+/// Read lines from `r`, and write each line transformed by `f` to `w`.
+fn line_filter<R, W, F>(r: &mut R, w: &mut W, mut f: F) -> io::Result<()>
+where
+    R: BufRead,
+    W: Write,
+    F: FnMut(&[u8]) -> Vec<u8>,
+{
+    let mut line: Vec<u8> = Vec::new();
+    while r.read_until(b'\n', &mut line)? > 0 {
+        w.write_all(&f(&line))?;
+        line.clear();
+    }
+    Ok(())
+}
+
+/// The input format, which determines which parts of the input are subject
+/// to filtering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    /// Filter everything.
+    #[default]
+    Text,
+    /// Filter only the values of logfmt `key=value` pairs.
+    Logfmt,
+    /// Filter only the MSG part of RFC 3164/5424 syslog messages.
+    Syslog,
+    /// Filter only the textual field values of the systemd journal export
+    /// format.
+    JournalExport,
+    /// Filter only the `log` field of Docker/Kubernetes JSON log lines.
+    DockerJson,
+    /// Filter only the string values of a stream of JSON values, and the
+    /// keys of objects too if `keys`, re-escaping them, and keeping the rest
+    /// byte-exact.
+    Json {
+        /// If true, filter the keys of objects too.
+        keys: bool,
+    },
+    /// Filter only the fields of [`Options::columns`] of CSV, or of TSV with
+    /// `delimiter` '\t', keeping the delimiters and the quoting.
+    Csv {
+        /// The byte between the fields of a record.
+        delimiter: u8,
+    },
+    /// Filter only the prose of Markdown, keeping the fenced code blocks and
+    /// the inline code spans byte for byte.
+    Markdown,
+}
+
+/// Filters one line with a filter of raw bytes.
+type FilterLine<F> = fn(&[u8], &mut F) -> Vec<u8>;
+
+/// Return the `FilterLine` of `format`, or `None` if `format` is not
+/// line-based.
+fn format_filter_line<F: FnMut(&[u8]) -> Vec<u8>>(
+    format: Format,
+) -> Option<FilterLine<F>> {
+    match format {
+        Format::Text => Some(|line, filter| filter(line)),
+        Format::JournalExport
+        | Format::Json { .. }
+        | Format::Csv { .. }
+        | Format::Markdown => None,
+        Format::Logfmt => Some(logfmt::filter_line),
+        Format::Syslog => Some(syslog::filter_line),
+        Format::DockerJson => Some(docker::filter_line),
+    }
+}
+
+/// Where [`Options::checkpoint`] emits the checkpoints.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Checkpoint {
+    /// A line per checkpoint to this file descriptor, Unix only.
+    Fd(i32),
+    /// A file replaced with the latest checkpoint.
+    File(PathBuf),
+}
+
+/// The byte order mark of utf-8, the only output encoding.
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Options controlling [`filter`] and [`stdin_stdout_buffer_filter`].
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// The size of the chunks the input is read in.
+    pub buf_size: usize,
+    /// See `FilterWriter::ascii_only`.
+    pub ascii_only: bool,
+    /// If not `None`, keep the chars in this set instead of the ASCII subset,
+    /// whether or not `ascii_only`.
+    pub allowed: Option<CharSet>,
+    /// If true, keep the printable chars of ISO 8859-1 and ISO 8859-15 too,
+    /// i.e. U+00A0 to U+00FF, and '€', 'Š', 'š', 'Ž', 'ž', 'Œ', 'œ' and 'Ÿ',
+    /// whether or not `ascii_only`, so that Western European text is kept.
+    pub keep_latin1: bool,
+    /// If not `None`, keep the chars encodable in this charset too, but the
+    /// control chars, whether or not `ascii_only`, e.g. for a printing
+    /// system which takes ISO 8859-15 only. The output is not encoded.
+    pub encodable_in: Option<LegacyCharset>,
+    /// Keep the chars of these general categories too, whether or not
+    /// `ascii_only`, e.g. `L` to keep the letters of any script.
+    #[cfg(feature = "unicode-category")]
+    pub keep_categories: Vec<GeneralCategory>,
+    /// Drop the chars of these general categories, even if kept otherwise,
+    /// whether or not `ascii_only`, e.g. `Cf` and `Co`. Note that tab and
+    /// '\n' are of `Cc`.
+    #[cfg(feature = "unicode-category")]
+    pub drop_categories: Vec<GeneralCategory>,
+    /// Keep the chars of these scripts too, whether or not `ascii_only`,
+    /// e.g. `Latin` and `Greek`. Digits and most punctuation are of the
+    /// script `Common`.
+    #[cfg(feature = "unicode-script")]
+    pub keep_scripts: Vec<Script>,
+    /// If true, drop the invisible chars, e.g. ZWSP, ZWJ, a BOM, the soft
+    /// hyphen and bidi controls, even if kept otherwise, whether or not
+    /// `ascii_only`.
+    pub strip_invisible: bool,
+    /// If true, keep CR too, whether or not `ascii_only`.
+    pub keep_cr: bool,
+    /// If not `None`, keep these control chars too, e.g. form feed and bell,
+    /// see [`CharSet::parse_controls`].
+    pub keep_controls: Option<CharSet>,
+    /// If not `None`, convert the line endings of the output per this, e.g.
+    /// CRLF to LF. `out_record_sep` takes precedence over
+    /// [`Newlines::Crlf`]. Does not apply to [`FilterMachine`].
+    pub newlines: Option<Newlines>,
+    /// If true, decompress the input before filtering if it is gzip, as
+    /// recognized by its magic, and fail if it is zstd or xz, which are not
+    /// supported. The stats are of the decompressed input, but for
+    /// `bytes_read`. Does not apply to [`FilterMachine`].
+    pub decompress: bool,
+    /// If not `None`, compress the output in this format, of which only
+    /// [`Compression::Gzip`] is supported, and the others fail. The output is
+    /// compressed in blocks, so that a flush, e.g. after a line, writes the
+    /// blocks complete so far only. The stats are of the output before
+    /// compression, but for `bytes_written`. Does not apply to
+    /// [`FilterMachine`].
+    pub compress: Option<Compression>,
+    /// If not `None`, decode the input from this encoding before filtering,
+    /// e.g. from [`Encoding::Utf16Le`], dropping a BOM at its start and
+    /// decoding the units which are no char as U+FFFD. Input in utf-8 or
+    /// ASCII is filtered as it is. The stats are of the decoded text, but
+    /// for `bytes_read`. Does not apply to [`FilterMachine`].
+    pub from_encoding: Option<Encoding>,
+    /// If not `None`, encode the output in this encoding, e.g. in
+    /// [`Encoding::Latin1`], which is taken as Windows-1252. The chars it has
+    /// no code for are encoded as `?`, so that e.g. with `ascii_only` none
+    /// are. `write_bom` writes the BOM of a utf-16 or utf-32 encoding, and
+    /// none of the others. The stats are of the text before encoding, but
+    /// for `bytes_written`. Does not apply to [`FilterMachine`].
+    pub to_encoding: Option<Encoding>,
+    /// What becomes of the invalid utf-8 sequences of the input, by default
+    /// [`InvalidUtf8::Drop`]. Does not apply to [`FilterMachine`], which
+    /// reports them as [`Event::Invalid`].
+    pub invalid_utf8: InvalidUtf8,
+    /// If not `None`, expand the tabs of the output to spaces up to the next
+    /// multiple of this many columns, as `expand` does. Does not apply to
+    /// [`FilterMachine`].
+    pub expand_tabs: Option<usize>,
+    /// If not `None`, bound the output lines of [`Format::Text`] to this
+    /// many chars, treating a longer one per `overlong`, see
+    /// [`FilterWriter::with_max_line_length`].
+    pub max_line_length: Option<usize>,
+    /// What becomes of an output line longer than `max_line_length`.
+    pub overlong: Overlong,
+    /// If true, output only the chars dropped otherwise, see
+    /// [`FilterWriter::with_invert`].
+    pub invert: bool,
+    /// If true, prepend a BOM to the output. Some Windows tools (e.g. Excel
+    /// CSV import) misdetect the encoding without one.
+    pub write_bom: bool,
+    /// If true, drop a BOM at the start of the input, which is otherwise
+    /// kept or dropped as the char U+FEFF, e.g. dropped if `ascii_only`. It
+    /// is not counted as a dropped char. Applies to [`Format::Text`] only,
+    /// and not to [`filter_bytes`] or [`FilterMachine`].
+    pub strip_bom: bool,
+    /// If not `None`, terminate output records with this instead of '\n',
+    /// e.g. "\0" for `xargs -0`.
+    pub out_record_sep: Option<String>,
+    /// If true, take NUL as the end of a line of [`Format::Text`] instead of
+    /// '\n', see [`FilterWriter::with_null_data`], and keep it, e.g. to
+    /// filter the output of `find -print0` for `xargs -0`.
+    pub null_data: bool,
+    /// The input format.
+    pub format: Format,
+    /// If not `None`, the columns of [`Format::Csv`] to filter, counted from
+    /// 1, instead of all of them.
+    pub columns: Option<Vec<usize>>,
+    /// If not `None`, normalize the input to this form before filtering it,
+    /// e.g. NFKC, which folds ﬁ to fi. Does not apply to [`FilterMachine`],
+    /// whose events are per input char.
+    #[cfg(feature = "unicode-normalization")]
+    pub normalization: Option<Normalization>,
+    /// If true, strip the ANSI escape sequences, e.g. color codes, as whole
+    /// units before anything else. Does not apply to [`FilterMachine`].
+    pub strip_ansi: bool,
+    /// If not `None`, drop what this matches in each line of the input,
+    /// after normalizing, e.g. a range of emoji. Does not apply to
+    /// [`FilterMachine`].
+    pub drop_regex: Option<Regex>,
+    /// If not `None`, keep what this matches in each line of the input as
+    /// is, unless matched by `drop_regex`, instead of filtering it. Does not
+    /// apply to [`FilterMachine`].
+    pub keep_regex: Option<Regex>,
+    /// If true, convert the hostnames with labels which are not ASCII in each
+    /// line of the input, unless matched by `drop_regex` or `keep_regex`, to
+    /// punycode, e.g. bücher.de to xn--bcher-kva.de, instead of filtering
+    /// them. Does not apply to [`FilterMachine`].
+    pub punycode: bool,
+    /// If true, filter by extended grapheme clusters, see
+    /// [`FilterWriter::with_graphemes`]. Does not apply to [`FilterMachine`].
+    #[cfg(feature = "unicode-segmentation")]
+    pub graphemes: bool,
+    /// If not `None`, drop emoji, keep them or replace them with their
+    /// shortcodes, as whole sequences. Does not apply to [`FilterMachine`].
+    pub emoji: Option<EmojiMode>,
+    /// The mappings consulted, in order, for each char that would otherwise
+    /// be dropped.
+    pub mappings: Vec<Mapping>,
+    /// If true, substitute each dropped char found in none of the mappings
+    /// with its canonical decomposition without the combining marks, if that
+    /// is in the ASCII subset, so that e.g. café becomes cafe, not caf.
+    #[cfg(feature = "unicode-normalization")]
+    pub strip_diacritics: bool,
+    /// If not `None`, substitute each dropped char found in none of the
+    /// mappings with this, e.g. "?", so that the columns stay aligned.
+    pub replacement: Option<String>,
+    /// If not `None`, substitute each dropped char found in none of the
+    /// mappings with its escape in this style instead, e.g. `\u{4F60}`, so
+    /// that the output stays reversible. Takes precedence over
+    /// `replacement`.
+    pub escape: Option<Escape>,
+    /// If not `None`, substitute each run of dropped chars found in none of
+    /// the mappings with this once, e.g. " ", so that the words on either
+    /// side of it are not glued together.
+    pub collapse: Option<String>,
+    /// If not `None`, write `replacement` at most this many times for each
+    /// run of chars replaced with it, e.g. `?` once rather than `????????`.
+    /// Does not apply to [`FilterMachine`].
+    pub squeeze_replacements: Option<usize>,
+    /// If not `None`, write this, e.g. " " or "-", where dropping chars found
+    /// in none of the mappings would join two alphanumeric chars, so that
+    /// e.g. naïve becomes na ve rather than nave. Does not apply to
+    /// [`FilterMachine`].
+    pub word_sep: Option<String>,
+    /// If true, omit each line which drops any char found in none of the
+    /// mappings, instead of dropping the char alone. Does not apply to
+    /// [`FilterMachine`].
+    pub drop_lines: bool,
+    /// If true, prefix each output line of [`Format::Text`] with the number
+    /// of its line of input, see [`FilterWriter::with_line_numbers`]. They
+    /// count from where the input resumes with `resume_state`.
+    pub line_numbers: bool,
+    /// If not `None`, prefix each output line of [`Format::Text`] with this,
+    /// see [`FilterWriter::with_line_prefix`].
+    pub line_prefix: Option<String>,
+    /// If `Some(true)`, set `line_prefix` to the path of each file and ':' in
+    /// [`filter_files`] and [`filter_tree`], as `grep -H` does; if
+    /// `Some(false)`, leave the path out of the lines of the reports too.
+    pub filenames: Option<bool>,
+    /// If not `None`, substitute each dropped control char, i.e. a C0
+    /// control or DEL, with its escape in this style, e.g. `^[` for ESC,
+    /// before consulting the mappings.
+    pub control_escape: Option<ControlEscape>,
+    /// If true, keep each dropped char instead, wrapped in the escapes of
+    /// reverse video, e.g. to audit on a terminal what the filter would
+    /// drop. Takes precedence over the other substitutions.
+    pub highlight: bool,
+    /// If true, compare the output of [`Format::Text`] with a reference
+    /// filter while filtering, failing with [`CheckError::Diverged`].
+    pub verify: bool,
+    /// If true, filter the output of [`Format::Text`] a second time while
+    /// filtering, failing with [`CheckError::NotIdempotent`] if that changes
+    /// it, e.g. when a mapping emits chars that are then dropped.
+    pub check_idempotent: bool,
+    /// If true, fail with [`CheckError::NotClean`] if the filter changes any
+    /// of the input of [`Format::Text`], e.g. to verify already clean files.
+    pub assert_clean: bool,
+    /// If true, fail at the first char dropped or replaced from
+    /// [`Format::Text`], or invalid sequence, naming it and its position,
+    /// see [`FilterWriter::with_strict`].
+    pub strict: bool,
+    /// If not `None`, append each char dropped from [`Format::Text`] to this
+    /// file, see [`FilterWriter::with_rejects`].
+    pub rejects: Option<PathBuf>,
+    /// If true, write each char to `rejects` with its position.
+    pub reject_positions: bool,
+    /// If not `None`, append a hex dump of each char dropped from
+    /// [`Format::Text`], and of each invalid sequence, to this file, see
+    /// [`FilterWriter::with_hex_rejects`].
+    pub rejects_hex: Option<PathBuf>,
+    /// If not `None`, count each char dropped or replaced from
+    /// [`Format::Text`] here, see [`FilterWriter::with_histogram`].
+    pub histogram: Option<Arc<Mutex<Histogram>>>,
+    /// If not `None`, write a diagnostic in this format for each char
+    /// dropped from [`Format::Text`], see [`FilterWriter::with_report`], to
+    /// `report_output`, appending to it, or to stderr if `None`.
+    pub report: Option<ReportFormat>,
+    pub report_output: Option<PathBuf>,
+    /// The file named in the diagnostics of `report`, set to the path of
+    /// each file by [`filter_files`], [`filter_tree`] and
+    /// [`filter_in_place`], where `None` is stdin.
+    pub report_name: Option<String>,
+    /// If not `None`, write the undo log of [`Format::Text`] to this file,
+    /// replacing it, see [`FilterWriter::with_undo_log`] and [`restore`].
+    /// Fails with [`io::ErrorKind::InvalidInput`] if
+    /// [`change_log_conflict`](Self::change_log_conflict) is not `None`.
+    pub undo_log: Option<PathBuf>,
+    /// If not `None`, write the source map of [`Format::Text`] to this file,
+    /// replacing it, see [`FilterWriter::with_source_map`] and
+    /// [`SourceMap`]. Fails with [`io::ErrorKind::InvalidInput`] if
+    /// [`change_log_conflict`](Self::change_log_conflict) is not `None`.
+    pub source_map: Option<PathBuf>,
+    /// If not `None`, filter [`Format::Text`] resumably, recording the
+    /// progress in this state file every so often, and resuming from it if
+    /// it exists. See `--resume-state`.
+    pub resume_state: Option<PathBuf>,
+    /// If not `None`, emit the input offset decided so far here every so
+    /// often, once the output before it is flushed.
+    pub checkpoint: Option<Checkpoint>,
+    /// If either is not `None`, write the lines the filter leaves unchanged
+    /// to `clean_output`, and the filtered lines it changes to
+    /// `dirty_output`, or to stdout if `None`. Requires a line-based format.
+    pub clean_output: Option<PathBuf>,
+    pub dirty_output: Option<PathBuf>,
+    /// If true, skip the files that look binary in [`filter_tree`].
+    pub skip_binary: bool,
+    /// If true, leave out the files ignored by `.gitignore`, `.ignore` and
+    /// the like in [`filter_tree`].
+    pub ignore: bool,
+    /// If true, filter hidden files and the files under hidden directories,
+    /// e.g. `.git`, too in [`filter_tree`], which are left out otherwise
+    /// whether or not `ignore`.
+    pub hidden: bool,
+    /// If true, follow symbolic links in [`filter_tree`], within the paths
+    /// given only.
+    pub follow_symlinks: bool,
+    /// How [`filter_tree`] treats a file reachable by several paths.
+    pub hard_links: HardLinks,
+    /// If not `None`, write the output to this inherited file descriptor
+    /// instead of stdout, Unix only.
+    pub output_fd: Option<i32>,
+    /// If not `None`, write the output to a temporary file next to this file
+    /// instead of stdout, and rename it to this file once done, so that it
+    /// is never left truncated. Takes precedence over `output_fd`, and does
+    /// not apply with `resume_state` or `checkpoint`.
+    pub output: Option<PathBuf>,
+    /// If not `None`, filter a large regular file of [`Format::Text`] on this
+    /// many threads instead of one per CPU, where 1 filters it serially.
+    /// Files are filtered serially anyway if `invert`, `rejects`,
+    /// `rejects_hex`, `report`, `line_numbers`, or if the char filter drops
+    /// '\n'. With 1, [`stdin_stdout_buffer_filter`] also reads, filters and
+    /// writes stdin from a pipe on one thread rather than three.
+    pub threads: Option<usize>,
+    /// If not `None`, end the input if nothing arrives on stdin within this
+    /// timeout, and fail with [`Error::Timeout`] once the output so far is
+    /// flushed.
+    pub timeout: Option<Duration>,
+    /// If not `None`, end the input likewise if nothing arrives on stdin
+    /// within this timeout, also with `follow`, but succeed, as at the end
+    /// of input, e.g. of a pipe whose writer stalls.
+    pub idle_timeout: Option<Duration>,
+    /// If true, flush the output after each line of a text, e.g. to filter
+    /// the output of a REPL interactively.
+    pub line_buffered: bool,
+    /// If true, keep reading stdin at its end for more input, as `tail -f`
+    /// does, and flush the output after each line as with `line_buffered`.
+    /// The input ends only on an error then.
+    pub follow: bool,
+    /// If true, draw a progress bar of the files read by [`filter_files`]
+    /// on stderr, which is assumed to be a terminal.
+    pub progress: bool,
+    /// If not `None`, write at most this many bytes a second to stdout or
+    /// `output_fd`, e.g. to relay the output to a slow serial console.
+    pub rate_limit: Option<u64>,
+    /// If not `None`, write at most this many lines a second likewise.
+    pub lines_per_sec: Option<u64>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            buf_size: 128,
+            ascii_only: false,
+            allowed: None,
+            keep_latin1: false,
+            encodable_in: None,
+            #[cfg(feature = "unicode-category")]
+            keep_categories: Vec::new(),
+            #[cfg(feature = "unicode-category")]
+            drop_categories: Vec::new(),
+            #[cfg(feature = "unicode-script")]
+            keep_scripts: Vec::new(),
+            strip_invisible: false,
+            keep_cr: false,
+            keep_controls: None,
+            newlines: None,
+            decompress: false,
+            compress: None,
+            from_encoding: None,
+            to_encoding: None,
+            invalid_utf8: InvalidUtf8::Drop,
+            expand_tabs: None,
+            max_line_length: None,
+            overlong: Overlong::Truncate,
+            invert: false,
+            write_bom: false,
+            strip_bom: false,
+            out_record_sep: None,
+            null_data: false,
+            format: Format::Text,
+            columns: None,
+            #[cfg(feature = "unicode-normalization")]
+            normalization: None,
+            strip_ansi: false,
+            drop_regex: None,
+            keep_regex: None,
+            punycode: false,
+            #[cfg(feature = "unicode-segmentation")]
+            graphemes: false,
+            emoji: None,
+            mappings: Vec::new(),
+            #[cfg(feature = "unicode-normalization")]
+            strip_diacritics: false,
+            replacement: None,
+            escape: None,
+            control_escape: None,
+            highlight: false,
+            collapse: None,
+            squeeze_replacements: None,
+            word_sep: None,
+            drop_lines: false,
+            line_numbers: false,
+            line_prefix: None,
+            filenames: None,
+            verify: false,
+            check_idempotent: false,
+            assert_clean: false,
+            strict: false,
+            rejects: None,
+            reject_positions: false,
+            rejects_hex: None,
+            histogram: None,
+            report: None,
+            report_output: None,
+            report_name: None,
+            undo_log: None,
+            source_map: None,
+            resume_state: None,
+            checkpoint: None,
+            clean_output: None,
+            dirty_output: None,
+            skip_binary: true,
+            ignore: true,
+            hidden: false,
+            follow_symlinks: false,
+            hard_links: HardLinks::Preserve,
+            output_fd: None,
+            output: None,
+            threads: None,
+            timeout: None,
+            idle_timeout: None,
+            line_buffered: false,
+            follow: false,
+            progress: false,
+            rate_limit: None,
+            lines_per_sec: None,
+        }
+    }
+}
+
+impl Options {
+    /// Return true if the char filter drops any valid chars.
+    pub(crate) fn filters_chars(&self) -> bool {
+        #[cfg(feature = "unicode-category")]
+        if !self.keep_categories.is_empty() {
+            return true;
+        }
+        #[cfg(feature = "unicode-script")]
+        if !self.keep_scripts.is_empty() {
+            return true;
+        }
+        self.ascii_only
+            || self.allowed.is_some()
+            || self.keep_latin1
+            || self.encodable_in.is_some()
+    }
+
+    /// Return true if a regex rule, or the punycode conversion, applies to
+    /// the lines.
+    pub(crate) fn has_regex_rules(&self) -> bool {
+        self.drop_regex.is_some() || self.keep_regex.is_some() || self.punycode
+    }
+
+    /// Return true if the filter leaves valid utf-8 as it is, dropping only
+    /// the invalid bytes, so that the input can be copied straight through.
+    pub(crate) fn passes_utf8(&self) -> bool {
+        !self.invert
+            && CharFilter::new(self).keeps_all()
+            && self.newlines.is_none()
+            && self.expand_tabs.is_none()
+            && self.out_record_sep.is_none()
+            && !self.normalizes()
+            && !self.strip_ansi
+            && !self.has_regex_rules()
+            && self.emoji.is_none()
+            && !self.drop_lines
+            && self.rejects.is_none()
+            && self.rejects_hex.is_none()
+            && self.report.is_none()
+            && !self.line_numbers
+            && self.line_prefix.is_none()
+            && self.max_line_length.is_none()
+            && !self.flushes_lines()
+            && self.invalid_utf8 == InvalidUtf8::Drop
+            && !self.strip_bom
+            && !self.strict
+            && self.undo_log.is_none()
+            && self.source_map.is_none()
+    }
+
+    /// Return the option which changes the text otherwise than an undo log
+    /// or a source map records, e.g. "normalization", if any, so that the
+    /// log would not restore the input, nor the map its offsets.
+    pub fn change_log_conflict(&self) -> Option<&'static str> {
+        let conflicts = [
+            (self.format != Format::Text, "a format other than text"),
+            (self.invert, "invert"),
+            (self.normalizes(), "normalization"),
+            (self.strips_diacritics(), "strip_diacritics"),
+            (self.splits_graphemes(), "graphemes"),
+            (self.newlines.is_some(), "newlines"),
+            (self.expand_tabs.is_some(), "expand_tabs"),
+            (self.out_record_sep.is_some(), "out_record_sep"),
+            (self.line_numbers, "line_numbers"),
+            (self.line_prefix.is_some(), "line_prefix"),
+            (self.max_line_length.is_some(), "max_line_length"),
+            (self.drop_lines, "drop_lines"),
+            (self.collapse.is_some(), "collapse"),
+            (self.squeeze_replacements.is_some(), "squeeze_replacements"),
+            (self.word_sep.is_some(), "word_sep"),
+            (self.punycode, "punycode"),
+            (self.emoji.is_some(), "emoji"),
+            (self.strip_ansi, "strip_ansi"),
+            (self.write_bom, "write_bom"),
+            (
+                !matches!(
+                    self.invalid_utf8,
+                    InvalidUtf8::Drop | InvalidUtf8::Error
+                ),
+                "invalid_utf8",
+            ),
+            (self.from_encoding.is_some(), "from_encoding"),
+            (self.to_encoding.is_some(), "to_encoding"),
+            (self.decompress, "decompress"),
+            (self.compress.is_some(), "compress"),
+            (self.resume_state.is_some(), "resume_state"),
+        ];
+        conflicts
+            .into_iter()
+            .find_map(|(conflicts, name)| conflicts.then_some(name))
+    }
+
+    /// Return true if the output is flushed after each line.
+    pub(crate) fn flushes_lines(&self) -> bool {
+        self.line_buffered || self.follow
+    }
+
+    /// Return true if the input is normalized.
+    pub(crate) fn normalizes(&self) -> bool {
+        #[cfg(feature = "unicode-normalization")]
+        return self.normalization.is_some();
+        #[cfg(not(feature = "unicode-normalization"))]
+        false
+    }
+
+    /// Return true if the diacritics of the dropped chars are stripped.
+    pub(crate) fn strips_diacritics(&self) -> bool {
+        #[cfg(feature = "unicode-normalization")]
+        return self.strip_diacritics;
+        #[cfg(not(feature = "unicode-normalization"))]
+        false
+    }
+
+    /// Return true if the text is filtered by grapheme clusters.
+    pub(crate) fn splits_graphemes(&self) -> bool {
+        #[cfg(feature = "unicode-segmentation")]
+        return self.graphemes;
+        #[cfg(not(feature = "unicode-segmentation"))]
+        false
+    }
+}
+
+/// Filter `reader` to `writer` per `opts`, and flush `writer`, returning the
+/// stats of the filtering. The options specific to the standard streams,
+/// i.e. `resume_state`, `checkpoint`, `clean_output`, `dirty_output`,
+/// `output_fd`, `output`, `timeout` and `follow`, do not apply.
+pub fn filter<R: Read, W: Write>(
+    reader: R,
+    writer: W,
+    opts: &Options,
+) -> Result<Stats, Error> {
+    filter_bufread(io::BufReader::new(reader), writer, opts)
+}
+
+/// Like [`filter`], but filter straight from the buffer of `reader`, which
+/// saves a copy if `reader` is already buffered, e.g. an [`io::BufReader`]
+/// or an [`io::StdinLock`].
+pub fn filter_bufread<R: BufRead, W: Write>(
+    mut reader: R,
+    mut writer: W,
+    opts: &Options,
+) -> Result<Stats, Error> {
+    let stats = filter_stream(&mut reader, &mut writer, opts)?;
+    writer.flush()?;
+    Ok(stats)
+}
+
+/// Filter the files `paths` in sequence to `writer` per `opts`, as `cat`
+/// concatenates them, where `-` is stdin, and flush `writer`, returning the
+/// stats of the filtering summed. Each line is prefixed with the path of its
+/// file per `opts.filenames`. The BOM, if any, starts the output, not
+/// each file. A file which cannot be opened, or is a directory, is reported
+/// with `on_error` and skipped, as `cat` does. The options specific to the
+/// standard streams do not apply, as with [`filter`].
+pub fn filter_files<W, F>(
+    paths: &[PathBuf],
+    mut writer: W,
+    opts: &Options,
+    mut on_error: F,
+) -> Result<Stats, Error>
+where
+    W: Write,
+    F: FnMut(&Path, &io::Error),
+{
+    let mut file_opts = opts.clone();
+    let mut stats = Stats::default();
+    let mut progress = opts.progress.then(|| Progress::new(input_size(paths)));
+    for path in paths {
+        file_opts.line_prefix = file_line_prefix(path, opts);
+        file_opts.report_name = report_name(path);
+        let file_stats = if path.as_os_str() == "-" {
+            let mut stdin = io::stdin().lock();
+            match &mut progress {
+                Some(progress) => filter_stream(
+                    &mut ProgressReader {
+                        inner: stdin,
+                        progress,
+                    },
+                    &mut writer,
+                    &file_opts,
+                )?,
+                None => filter_stream(&mut stdin, &mut writer, &file_opts)?,
+            }
+        } else {
+            let opened = File::open(path).and_then(|file| {
+                if file.metadata()?.is_dir() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::IsADirectory,
+                        "is a directory",
+                    ));
+                }
+                Ok(file)
+            });
+            match (opened, &mut progress) {
+                // The progress is of the reads, so the file is not mapped.
+                (Ok(file), Some(progress)) => filter_stream(
+                    &mut ProgressReader {
+                        inner: file_reader(file),
+                        progress,
+                    },
+                    &mut writer,
+                    &file_opts,
+                )?,
+                (Ok(file), None) => filter_file(file, &mut writer, &file_opts)?,
+                (Err(e), _) => {
+                    on_error(path, &e);
+                    continue;
+                }
+            }
+        };
+        log::info!("{}: {}", path.display(), file_stats);
+        stats += file_stats;
+        file_opts.write_bom = false;
+    }
+    writer.flush()?;
+    Ok(stats)
+}
+
+/// Return the total size of the files `paths`, or `None` if unknown, e.g.
+/// of stdin as `-` or of a pipe.
+fn input_size(paths: &[PathBuf]) -> Option<u64> {
+    paths.iter().try_fold(0u64, |total, path| {
+        if path.as_os_str() == "-" {
+            return None;
+        }
+        let size = match fs::metadata(path) {
+            Ok(metadata) if metadata.is_file() => metadata.len(),
+            // skipped as failing
+            Ok(metadata) if metadata.is_dir() => 0,
+            Err(_) => 0,
+            Ok(_) => return None,
+        };
+        Some(total + size)
+    })
+}
+
+/// Return the name of the file `path` in the diagnostics, where `-` is
+/// stdin.
+pub(crate) fn report_name(path: &Path) -> Option<String> {
+    (path.as_os_str() != "-").then(|| path.display().to_string())
+}
+
+/// Return the line prefix of the file `path`, where `-` is stdin, per
+/// `opts.filenames`.
+pub(crate) fn file_line_prefix(path: &Path, opts: &Options) -> Option<String> {
+    if opts.filenames != Some(true) {
+        return opts.line_prefix.clone();
+    }
+    Some(match path.as_os_str() == "-" {
+        true => "<stdin>:".to_string(),
+        false => format!("{}:", path.display()),
+    })
+}
+
+/// Filter the file `path` in place per `opts`, as `sed -i` does, returning
+/// the stats of the filtering. The file is replaced atomically once filtered,
+/// keeping its permissions, and copied to its path with `backup_suffix`
+/// appended first, if not `None`. A symbolic link is followed, and the file
+/// it leads to is replaced. The options specific to the standard streams do
+/// not apply, as with [`filter`].
+pub fn filter_in_place(
+    path: &Path,
+    backup_suffix: Option<&str>,
+    opts: &Options,
+) -> Result<Stats, Error> {
+    rewrite(path, backup_suffix, opts, HardLinks::Break)
+}
+
+/// Filter the file `path` in place as [`filter_in_place`] does, replacing it
+/// per [`HardLinks::Break`], or rewriting it, so that its other hard links see
+/// the result too, per [`HardLinks::Preserve`].
+pub(crate) fn rewrite(
+    path: &Path,
+    backup_suffix: Option<&str>,
+    opts: &Options,
+    links: HardLinks,
+) -> Result<Stats, Error> {
+    let with_path = |e: io::Error| {
+        io::Error::new(e.kind(), format!("{}: {}", path.display(), e))
+    };
+    let opts = &Options {
+        report_name: report_name(path),
+        ..opts.clone()
+    };
+    let path = fs::canonicalize(path).map_err(with_path)?;
+    let file = File::open(&path).map_err(with_path)?;
+    let mut output = Destination::replace(&path)?;
+    let stats = filter_file(file, &mut output, opts)?;
+    if let Some(suffix) = backup_suffix {
+        let mut backup = path.clone().into_os_string();
+        backup.push(suffix);
+        let backup = PathBuf::from(backup);
+        match fs::remove_file(&backup) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => {
+                return Err(e.into())
+            }
+            _ => (),
+        }
+        // A link to a file rewritten in place would be rewritten too.
+        if links == HardLinks::Preserve
+            || fs::hard_link(&path, &backup).is_err()
+        {
+            fs::copy(&path, &backup)?;
+        }
+    }
+    match links {
+        HardLinks::Break => output.commit()?,
+        HardLinks::Preserve => output.commit_in_place()?,
+    }
+    Ok(stats)
+}
+
+/// Filter the file `path` to the file `opts.output` per `opts`, and again
+/// each time `path` changes, e.g. to mirror a log into a clean copy, calling
+/// `on_update` with the result of each run until it breaks. The output is
+/// replaced atomically by each run. Changes are watched with inotify on
+/// Linux, and polled for elsewhere.
+pub fn watch_file<F>(
+    path: &Path,
+    opts: &Options,
+    mut on_update: F,
+) -> Result<(), Error>
+where
+    F: FnMut(Result<Stats, Error>) -> ControlFlow<()>,
+{
+    if opts.output.is_none() {
+        return Err(Error::Io(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "watching a file requires an output file",
+        )));
+    }
+    loop {
+        // Watch before filtering, so that no change is missed in between.
+        let watcher = Watcher::new(path)?;
+        let result = File::open(path).map_err(Error::from).and_then(|file| {
+            let mut output = open_output(opts)?;
+            let stats = filter_file(file, &mut output, opts)?;
+            output.commit()?;
+            Ok(stats)
+        });
+        if on_update(result).is_break() {
+            return Ok(());
+        }
+        watcher.wait()?;
+    }
+}
+
+/// Filter stdin to stdout per `opts`, returning the stats of the filtering,
+/// or of the run resumed with `opts.resume_state`.
+pub fn stdin_stdout_buffer_filter(opts: &Options) -> Result<Stats, Error> {
+    if opts.resume_state.is_some() || opts.checkpoint.is_some() {
+        return Ok(resume::filter(opts)?);
+    }
+    if opts.follow {
+        let stdin = FollowReader::new(io::stdin(), follow::INTERVAL)
+            .with_idle_timeout(opts.idle_timeout);
+        return filter_input(&mut io::BufReader::new(stdin), opts);
+    }
+    match opts.timeout.or(opts.idle_timeout) {
+        Some(timeout) => {
+            let mut stdin =
+                io::BufReader::new(TimeoutReader::new(io::stdin(), timeout));
+            let stats = filter_input(&mut stdin, opts)?;
+            // an idle timeout ends the input as its end does
+            if stdin.get_ref().timed_out && opts.timeout.is_some() {
+                return Err(Error::Timeout(timeout));
+            }
+            Ok(stats)
+        }
+        None => match stdin_regular_file() {
+            Some(file)
+                if opts.clean_output.is_none()
+                    && opts.dirty_output.is_none() =>
+            {
+                let mut output = open_output(opts)?;
+                let stats = filter_file(file, &mut output, opts)?;
+                output.commit()?;
+                Ok(stats)
+            }
+            Some(file) => {
+                let mut stdin = file_reader(file);
+                filter_input(&mut stdin, opts)
+            }
+            None if stages::enabled(opts) => {
+                stages::filter(&mut io::stdin().lock(), opts)
+            }
+            None => filter_input(&mut io::stdin().lock(), opts),
+        },
+    }
+}
+
+/// Return a reader of the rest of the regular `file`, with a larger buffer
+/// than stdin's for a larger file.
+fn file_reader(file: File) -> io::BufReader<File> {
+    let size = fd::remaining_size(&file).unwrap_or(0);
+    let capacity = usize::try_from(size)
+        .unwrap_or(usize::MAX)
+        .clamp(PIPE_READ_LEN, FILE_READ_LEN);
+    io::BufReader::with_capacity(capacity, file)
+}
+
+/// Filter the rest of `file` to `stdout` per `opts`, from a memory mapping
+/// if it is a regular file filtered as a text, leaving it at its end.
+fn filter_file<W: Write>(
+    mut file: File,
+    stdout: &mut W,
+    opts: &Options,
+) -> Result<Stats, Error> {
+    if let Some((map, start)) = map_file(&mut file, opts) {
+        log::debug!("filtering the file mapped from {}", start);
+        let stats = filter_mapped(&map[start..], stdout, opts)?;
+        file.seek(SeekFrom::End(0))?;
+        return Ok(stats);
+    }
+    filter_stream(&mut file_reader(file), stdout, opts)
+}
+
+/// Map the regular `file`, returning the mapping and the current position
+/// in it, or `None` if the mapping fails, or `opts` filter the input as a
+/// stream, e.g. to check or decode it while reading it.
+fn map_file(file: &mut File, opts: &Options) -> Option<(Mmap, usize)> {
+    if opts.format != Format::Text
+        || Checker::enabled(opts)
+        || opts.decompress
+        || opts.compress.is_some()
+        || opts.from_encoding.is_some_and(transcode::transcodes)
+        || opts.to_encoding.is_some_and(transcode::encodes)
+        || opts.escape == Some(Escape::Qp)
+    {
+        return None;
+    }
+    if !file.metadata().ok()?.is_file() {
+        return None;
+    }
+    let start = usize::try_from(file.stream_position().ok()?).ok()?;
+    let map = Mmap::map(file).ok()??;
+    (start <= map.len()).then_some((map, start))
+}
+
+/// The size of the pieces of a mapped input written to the filter at a
+/// time.
+const MAPPED_CHUNK_LEN: usize = 1 << 20;
+
+/// Filter the text `input`, e.g. a mapped file, to `stdout` per `opts`,
+/// returning the stats of the filtering. This decides as
+/// [`filter_stream`] does, without copying the input into chunks, and on
+/// several threads if it is large.
+fn filter_mapped<W: Write>(
+    input: &[u8],
+    stdout: &mut W,
+    opts: &Options,
+) -> Result<Stats, Error> {
+    let mut stats = Stats::default();
+    if opts.write_bom {
+        stdout.write_all(UTF8_BOM)?;
+        stats.bytes_written += UTF8_BOM.len() as u64;
+    }
+    if opts.passes_utf8() {
+        log::debug!("copying the valid utf-8 of {} bytes through", input.len());
+        stats += copy_utf8(&mut &input[..], stdout)?;
+        return Ok(stats);
+    }
+    let threads = parallel::threads(input, opts);
+    log::debug!("filtering {} bytes on {} threads", input.len(), threads);
+    if threads > 1 {
+        stats += parallel::filter(
+            input,
+            stdout,
+            opts,
+            threads,
+            parallel::CHUNK_LEN,
+        )?;
+        return Ok(stats);
+    }
+    let fw = FilterWriter::from_options(&mut *stdout, opts);
+    stats += write_through(with_rejects(fw, opts)?, input)?;
+    Ok(stats)
+}
+
+/// Copy `r` to `w` straight from the buffer of `r` but for the invalid bytes,
+/// and flush `w`, returning the stats of the copy.
+fn copy_utf8<R: BufRead, W: Write>(r: &mut R, w: &mut W) -> io::Result<Stats> {
+    let mut stats = Stats::default();
+    let mut utf8 = Utf8Decoder::default();
+    loop {
+        let buf = match r.fill_buf() {
+            Ok([]) => break,
+            Ok(buf) => buf,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {
+                if caught_signal().is_some() {
+                    break;
+                }
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+        let n = buf.len();
+        utf8.decode(buf, |chunk| match chunk {
+            Utf8Chunk::Valid(s) => {
+                stats.bytes_written += s.len() as u64;
+                w.write_all(s.as_bytes())
+            }
+            Utf8Chunk::Invalid(bytes) => {
+                stats.invalid_bytes += bytes.len() as u64;
+                Ok(())
+            }
+        })?;
+        r.consume(n);
+        stats.bytes_read += n as u64;
+    }
+    stats.invalid_bytes += utf8.finish().len() as u64;
+    w.flush()?;
+    Ok(stats)
+}
+
+/// Write `input` through `fw` in pieces, and flush it, returning its stats,
+/// where an incomplete char at the end counts as invalid.
+fn write_through<W: Write>(
+    mut fw: FilterWriter<W>,
+    input: &[u8],
+) -> io::Result<Stats> {
+    for chunk in input.chunks(MAPPED_CHUNK_LEN) {
+        fw.write_all(chunk)?;
+    }
+    fw.end_input()?;
+    fw.flush()?;
+    Ok(fw.stats())
+}
+
+/// The read size of stdin when it is a pipe or the like.
+const PIPE_READ_LEN: usize = 8 << 10;
+
+/// The largest read size of stdin when it is a regular file, which is known
+/// not to block, so that it is read in fewer and larger chunks.
+const FILE_READ_LEN: usize = 1 << 20;
+
+/// Return stdin as a file, if it is a regular file.
+fn stdin_regular_file() -> Option<File> {
+    let file = fd::stdin_file().ok()?;
+    fd::remaining_size(&file)?;
+    Some(file)
+}
+
+/// Filter `stdin` to the outputs per `opts`.
+fn filter_input<R: BufRead>(
+    stdin: &mut R,
+    opts: &Options,
+) -> Result<Stats, Error> {
+    if opts.clean_output.is_some() || opts.dirty_output.is_some() {
+        let mut stats = Stats::default();
+        let mut filter =
+            |bytes: &[u8]| filter_bytes_counted(bytes, opts, &mut stats);
+        let Some(filter_line) = format_filter_line(opts.format) else {
+            return Err(Error::Io(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "clean and dirty outputs require a line-based format",
+            )));
+        };
+        let (read, written) =
+            split::filter(stdin, opts, |line| filter_line(line, &mut filter))?;
+        stats.bytes_read = read;
+        stats.bytes_written = written;
+        return Ok(stats);
+    }
+    let mut output = open_output(opts)?;
+    let stats = filter_stream(stdin, &mut output, opts)?;
+    output.commit()?;
+    Ok(stats)
+}
+
+/// Open the output, the file `opts.output` replaced once committed, the file
+/// descriptor `opts.output_fd` if given, or stdout, written through the
+/// wide-char API if a Windows console and UTF-8, throttled per
+/// `opts.rate_limit` and `opts.lines_per_sec`.
+pub fn open_output(opts: &Options) -> io::Result<Destination> {
+    if let Some(path) = &opts.output {
+        return Destination::replace(path);
+    }
+    let stream: Box<dyn Write + Send> = match opts.output_fd {
+        Some(n) => Box::new(io::BufWriter::new(fd::inherited(n)?)),
+        // text in another encoding goes out as is
+        #[cfg(windows)]
+        None if !opts.to_encoding.is_some_and(transcode::encodes)
+            && io::IsTerminal::is_terminal(&io::stdout()) =>
+        {
+            Box::new(io::LineWriter::new(console::Console::new(
+                console::Stdout,
+            )))
+        }
+        None => Box::new(io::stdout()),
+    };
+    if opts.rate_limit.is_none() && opts.lines_per_sec.is_none() {
+        return Ok(Destination::stream(stream));
+    }
+    let end = if opts.null_data { b'\0' } else { b'\n' };
+    Ok(Destination::stream(Box::new(Throttle::new(
+        stream,
+        opts.rate_limit,
+        opts.lines_per_sec,
+        end,
+    ))))
+}
+
+/// Filter `stdin` to `stdout` per `opts`, apart from the options specific to
+/// the standard streams, returning the stats of the filtering.
+fn filter_stream<R: BufRead, W: Write>(
+    stdin: &mut R,
+    stdout: &mut W,
+    opts: &Options,
+) -> Result<Stats, Error> {
+    if opts.decompress {
+        let compression = Compression::detect(stdin.fill_buf()?);
+        match compression {
+            Some(compression) => log::debug!("{} input detected", compression),
+            None => log::debug!("uncompressed input, filtered as it is"),
+        }
+        let opts = Options {
+            decompress: false,
+            ..opts.clone()
+        };
+        return match compression {
+            Some(Compression::Gzip) => {
+                // The decompressed input is filtered, but the bytes read
+                // are those of the input. The reader is `dyn` so that the
+                // recursion instantiates no further readers.
+                let mut input = CountingReader {
+                    inner: stdin,
+                    count: 0,
+                };
+                let mut decompressed =
+                    GzipReader::new(&mut input as &mut dyn BufRead);
+                let mut stats =
+                    filter_stream(&mut decompressed, stdout, &opts)?;
+                stats.bytes_read = input.count;
+                Ok(stats)
+            }
+            Some(compression) => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "{} input is not supported, decompress it first, e.g. \
+                     with `{} -dc`",
+                    compression, compression
+                ),
+            )
+            .into()),
+            None => filter_stream(stdin, stdout, &opts),
+        };
+    }
+    if let Some(compression) = opts.compress {
+        if compression != Compression::Gzip {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!(
+                    "{} output is not supported, compress it afterwards, \
+                     e.g. with `{} -c`",
+                    compression, compression
+                ),
+            )
+            .into());
+        }
+        // Likewise, the bytes written are those compressed.
+        let mut output = CountingWriter {
+            inner: stdout,
+            count: 0,
+        };
+        let mut compressed = GzipWriter::new(&mut output as &mut dyn Write);
+        let opts = Options {
+            compress: None,
+            ..opts.clone()
+        };
+        let mut stats = filter_stream(stdin, &mut compressed, &opts)?;
+        compressed.finish()?;
+        stats.bytes_written = output.count;
+        return Ok(stats);
+    }
+    if let Some(encoding) =
+        opts.from_encoding.filter(|&e| transcode::transcodes(e))
+    {
+        // Likewise, the decoded input is filtered as utf-8.
+        log::debug!("decoding the input from {}", encoding);
+        let mut input = CountingReader {
+            inner: stdin,
+            count: 0,
+        };
+        let mut decoded =
+            DecodingReader::new(&mut input as &mut dyn BufRead, encoding);
+        let opts = Options {
+            from_encoding: None,
+            ..opts.clone()
+        };
+        let mut stats = filter_stream(&mut decoded, stdout, &opts)?;
+        stats.bytes_read = input.count;
+        return Ok(stats);
+    }
+    if let Some(encoding) = opts.to_encoding.filter(|&e| transcode::encodes(e))
+    {
+        // Likewise, the filtered text is encoded as it is written.
+        log::debug!("encoding the output in {}", encoding);
+        let mut output = CountingWriter {
+            inner: stdout,
+            count: 0,
+        };
+        let mut encoded =
+            EncodingWriter::new(&mut output as &mut dyn Write, encoding);
+        let opts = Options {
+            to_encoding: None,
+            // U+FEFF is encoded as the BOM, if the encoding has one.
+            write_bom: opts.write_bom && transcode::has_bom(encoding),
+            ..opts.clone()
+        };
+        let mut stats = filter_stream(stdin, &mut encoded, &opts)?;
+        encoded.finish()?;
+        stats.bytes_written = output.count;
+        return Ok(stats);
+    }
+    if opts.escape == Some(Escape::Qp) {
+        // Likewise, the bytes written are those encoded, and the chars which
+        // the escape keeps are encoded with the rest.
+        let mut output = CountingWriter {
+            inner: stdout,
+            count: 0,
+        };
+        let mut encoded = QpWriter::new(&mut output as &mut dyn Write);
+        let mut stats = filter_format(stdin, &mut encoded, opts)?;
+        encoded.finish()?;
+        stats.bytes_written = output.count;
+        return Ok(stats);
+    }
+    filter_format(stdin, stdout, opts)
+}
+
+/// Filter `stdin` to `stdout` per the format of `opts`, once decompressed,
+/// decoded and so on, as [`filter_stream`] does.
+fn filter_format<R: BufRead, W: Write>(
+    stdin: &mut R,
+    stdout: &mut W,
+    opts: &Options,
+) -> Result<Stats, Error> {
+    // The bytes read and written are counted here, since those of the
+    // line-based formats are not all filtered.
+    let mut input = CountingReader {
+        inner: stdin,
+        count: 0,
+    };
+    let mut output = CountingWriter {
+        inner: stdout,
+        count: 0,
+    };
+    let mut stats = Stats::default();
+    if opts.write_bom {
+        // The BOM is not subject to filtering, so bypass `FilterWriter`.
+        output.write_all(UTF8_BOM)?;
+    }
+    let record_sep = opts.out_record_sep.clone().map(String::into_bytes);
+    match opts.format {
+        Format::Text if Checker::enabled(opts) => {
+            let checker = RefCell::new(Checker::new(opts));
+            let mut output = CheckingWriter {
+                inner: &mut output,
+                checker: &checker,
+            };
+            let fw = FilterWriter::from_options(&mut output, opts);
+            let mut fw = with_rejects(fw, opts)?;
+            filter_text(
+                &mut CheckingReader {
+                    inner: &mut input,
+                    checker: &checker,
+                },
+                &mut fw,
+                opts,
+            )?;
+            stats = fw.stats();
+            checker.borrow_mut().finish()?;
+        }
+        Format::Text if opts.passes_utf8() => {
+            stats = copy_utf8(&mut input, &mut output)?;
+        }
+        Format::Text => {
+            let fw = FilterWriter::from_options(&mut output, opts);
+            let mut fw = with_rejects(fw, opts)?;
+            filter_text(&mut input, &mut fw, opts)?;
+            stats = fw.stats();
+        }
+        Format::JournalExport => {
+            // The record separator does not apply, since '\n' is part of the
+            // framing.
+            let mut filter =
+                |bytes: &[u8]| filter_bytes_counted(bytes, opts, &mut stats);
+            journal::filter(&mut input, &mut output, &mut filter)?;
+        }
+        Format::Json { keys } => {
+            // The record separator does not apply, since the structure is
+            // kept as is.
+            let mut filter =
+                |bytes: &[u8]| filter_bytes_counted(bytes, opts, &mut stats);
+            json::filter(&mut input, &mut output, keys, &mut filter)?;
+        }
+        Format::Csv { delimiter } => {
+            // The record separator does not apply, since a quoted field may
+            // span lines.
+            let mut filter =
+                |bytes: &[u8]| filter_bytes_counted(bytes, opts, &mut stats);
+            let columns = opts.columns.as_deref();
+            csv::filter(
+                &mut input,
+                &mut output,
+                delimiter,
+                columns,
+                &mut filter,
+            )?;
+        }
+        Format::Markdown => {
+            // The record separator does not apply, since the code blocks are
+            // kept as they are.
+            let mut filter =
+                |bytes: &[u8]| filter_bytes_counted(bytes, opts, &mut stats);
+            markdown::filter(&mut input, &mut output, &mut filter)?;
+        }
+        line_based => {
+            // The structure is preserved by the per-format `filter_line`, so
+            // only pass through here.
+            let mut fw = FilterWriter::new(&mut output, false)
+                .with_record_sep(record_sep);
+            let mut filter =
+                |bytes: &[u8]| filter_bytes_counted(bytes, opts, &mut stats);
+            let filter_line = format_filter_line(line_based).unwrap();
+            line_filter(&mut input, &mut fw, |line| {
+                filter_line(line, &mut filter)
+            })?;
+        }
+    }
+    stats.bytes_read = input.count;
+    stats.bytes_written = output.count;
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        buffer_filter, buffer_filter_checkpointed, copy_utf8, filter,
+        filter_bufread, filter_bytes, filter_files, filter_in_place,
+        filter_mapped, filter_str, filter_writer, restore, watch_file, Action,
+        CharSet, CheckError, Compression, ControlEscape, EmojiMode, Encoding,
+        Error, ErrorKind, Escape, FilterWriter, Histogram, InvalidUtf8,
+        LegacyCharset, Mapping, Newlines, Options, Overlong, ReportFormat,
+        SourceMap,
+    };
+    #[cfg(feature = "unicode-normalization")]
+    use crate::{Normalization, Stats};
+    use regex::Regex;
+    use std::borrow::Cow;
+    use std::io::{self, Cursor, Write};
+    use std::ops::ControlFlow;
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_filter() {
+        let opts = Options {
+            ascii_only: true,
+            write_bom: true,
+            ..Options::default()
+        };
+        let mut out: Vec<u8> = Vec::new();
+        filter(&b"a\xe4\xbd\xa0\xffb\n"[..], &mut out, &opts).unwrap();
+        assert_eq!(out, b"\xef\xbb\xbfab\n");
+    }
+
+    #[test]
+    fn test_filter_bufread() {
+        let opts = Options {
+            ascii_only: true,
+            ..Options::default()
+        };
+        let input = "a你\nb".repeat(100);
+        // a char split across the buffer fills
+        let r = io::BufReader::with_capacity(2, Cursor::new(&input));
+        let mut out: Vec<u8> = Vec::new();
+        let stats = filter_bufread(r, &mut out, &opts).unwrap();
+        assert_eq!(out, "a\nb".repeat(100).as_bytes());
+        assert_eq!(stats.bytes_read, input.len() as u64);
+        assert_eq!(stats.chars_removed, 100);
+    }
+
+    #[test]
+    fn test_filter_decompress() {
+        let opts = Options {
+            ascii_only: true,
+            decompress: true,
+            ..Options::default()
+        };
+        // "héllo wörld\n" as gzip compresses it
+        let input = b"\x1f\x8b\x08\x00\x00\x00\x00\x00\x02\x03\xcb\x38\xbc\x32\
+            \x27\x27\x5f\xa1\xfc\xf0\xb6\xa2\x9c\x14\x2e\x00\xe4\x10\x7f\x80\
+            \x0e\x00\x00\x00";
+        let mut out: Vec<u8> = Vec::new();
+        let stats = filter(&input[..], &mut out, &opts).unwrap();
+        assert_eq!(out, b"hllo wrld\n");
+        assert_eq!(stats.bytes_read, input.len() as u64);
+        assert_eq!(stats.chars_removed, 2);
+
+        // other input is filtered as it is
+        let mut out: Vec<u8> = Vec::new();
+        filter(&b"h\xc3\xa9llo"[..], &mut out, &opts).unwrap();
+        assert_eq!(out, b"hllo");
+
+        let e = filter(&b"\xfd7zXZ\x00"[..], io::sink(), &opts).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "xz input is not supported, decompress it first, e.g. with `xz -dc`"
+        );
+    }
+
+    #[test]
+    fn test_filter_compress() {
+        let opts = Options {
+            ascii_only: true,
+            compress: Some(Compression::Gzip),
+            ..Options::default()
+        };
+        let input = "héllo wörld\n".repeat(100);
+        let mut compressed: Vec<u8> = Vec::new();
+        let stats = filter(input.as_bytes(), &mut compressed, &opts).unwrap();
+        assert_eq!(stats.bytes_written, compressed.len() as u64);
+        assert!(compressed.len() < 100);
+
+        let opts = Options {
+            decompress: true,
+            ..Options::default()
+        };
+        let mut out: Vec<u8> = Vec::new();
+        filter(&compressed[..], &mut out, &opts).unwrap();
+        assert_eq!(out, "hllo wrld\n".repeat(100).as_bytes());
+
+        let opts = Options {
+            compress: Some(Compression::Zstd),
+            ..Options::default()
+        };
+        assert!(filter(input.as_bytes(), io::sink(), &opts).is_err());
+    }
+
+    #[test]
+    fn test_filter_from_encoding() {
+        let opts = Options {
+            ascii_only: true,
+            from_encoding: Some(Encoding::Utf16Le),
+            ..Options::default()
+        };
+        let input: Vec<u8> = "\u{feff}a你\r\nb"
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect();
+        let r = io::BufReader::with_capacity(3, &input[..]);
+        let mut out: Vec<u8> = Vec::new();
+        let stats = filter_bufread(r, &mut out, &opts).unwrap();
+        assert_eq!(out, b"a\nb");
+        assert_eq!(stats.bytes_read, input.len() as u64);
+        assert_eq!(stats.chars_removed, 2);
+
+        // utf-8 is filtered as it is
+        let opts = Options {
+            from_encoding: Some(Encoding::Utf8),
+            ..opts
+        };
+        let mut out: Vec<u8> = Vec::new();
+        filter(&b"a\xe9b"[..], &mut out, &opts).unwrap();
+        assert_eq!(out, b"ab");
+    }
+
+    #[test]
+    fn test_filter_to_encoding() {
+        let opts = Options {
+            write_bom: true,
+            to_encoding: Some(Encoding::Latin1),
+            ..Options::default()
+        };
+        let mut out: Vec<u8> = Vec::new();
+        let stats = filter("café—ж😀\n".as_bytes(), &mut out, &opts).unwrap();
+        assert_eq!(out, b"caf\xe9\x97??\n");
+        assert_eq!(stats.bytes_written, out.len() as u64);
+        assert_eq!(stats.bytes_read, "café—ж😀\n".len() as u64);
+
+        let opts = Options {
+            to_encoding: Some(Encoding::Utf16Le),
+            from_encoding: Some(Encoding::Latin1),
+            ..opts
+        };
+        let mut out: Vec<u8> = Vec::new();
+        filter(&b"caf\xe9"[..], &mut out, &opts).unwrap();
+        let utf16le: Vec<u8> = "\u{feff}café"
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect();
+        assert_eq!(out, utf16le);
+    }
+
+    #[test]
+    fn test_filter_escape_qp() {
+        let opts = Options {
+            ascii_only: true,
+            escape: Some(Escape::Qp),
+            ..Options::default()
+        };
+        let mut out: Vec<u8> = Vec::new();
+        let input = "a=你 \n\u{7}b";
+        let stats = filter(input.as_bytes(), &mut out, &opts).unwrap();
+        assert_eq!(out, b"a=3D=E4=BD=A0=20\n=07b");
+        assert_eq!(stats.chars_replaced, 2);
+        assert_eq!(stats.bytes_written, out.len() as u64);
+    }
+
+    #[test]
+    fn test_filter_strip_bom() {
+        let opts = Options {
+            strip_bom: true,
+            ..Options::default()
+        };
+        let input = "\u{feff}a\u{feff}b\n";
+        // the BOM split across the buffer fills
+        let r = io::BufReader::with_capacity(1, input.as_bytes());
+        let mut out: Vec<u8> = Vec::new();
+        let stats = filter_bufread(r, &mut out, &opts).unwrap();
+        assert_eq!(out, "a\u{feff}b\n".as_bytes());
+        assert_eq!(stats.bytes_read, input.len() as u64);
+        assert_eq!(stats.chars_removed, 0);
+
+        // a BOM is kept otherwise
+        let mut out: Vec<u8> = Vec::new();
+        filter(input.as_bytes(), &mut out, &Options::default()).unwrap();
+        assert_eq!(out, input.as_bytes());
+    }
+
+    #[test]
+    fn test_filter_invalid_utf8() {
+        let input = b"a\xffb\xe4\xbd\xa0c\xe4";
+        let filtered = |policy, ascii_only| {
+            let opts = Options {
+                ascii_only,
+                invalid_utf8: policy,
+                replacement: Some("?".to_string()),
+                buf_size: 4,
+                ..Options::default()
+            };
+            let mut out: Vec<u8> = Vec::new();
+            filter(&input[..], &mut out, &opts).map(|_| out)
+        };
+        assert_eq!(
+            filtered(InvalidUtf8::Drop, false).unwrap(),
+            "ab你c".as_bytes()
+        );
+        assert_eq!(
+            filtered(InvalidUtf8::Replace, false).unwrap(),
+            "a\u{FFFD}b你c\u{FFFD}".as_bytes()
+        );
+        // the replacement is filtered too
+        assert_eq!(filtered(InvalidUtf8::Replace, true).unwrap(), b"a?b?c?");
+        assert_eq!(
+            filtered(InvalidUtf8::Escape, true).unwrap(),
+            b"a\\xFFb?c\\xE4"
+        );
+        let e = filtered(InvalidUtf8::Error, false).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "invalid utf-8 sequence \\xFF at byte offset 1"
+        );
+        assert_eq!(e.kind(), ErrorKind::Encoding);
+        // the incomplete char at the end, read in another chunk
+        let opts = Options {
+            invalid_utf8: InvalidUtf8::Error,
+            buf_size: 4,
+            ..Options::default()
+        };
+        let mut out: Vec<u8> = Vec::new();
+        let e = filter(&input[2..], &mut out, &opts).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "invalid utf-8 sequence \\xE4 at byte offset 5"
+        );
+        assert_eq!(out, "b你c".as_bytes());
+    }
+
+    /// A reader and writer failing every call.
+    struct Failing;
+
+    impl io::Read for Failing {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::other("read failed"))
+        }
+    }
+
+    impl Write for Failing {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::Error::other("write failed"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Err(io::Error::other("flush failed"))
+        }
+    }
+
+    #[test]
+    fn test_filter_io_errors() {
+        let opts = Options {
+            ascii_only: true,
+            ..Options::default()
+        };
+        let mut out: Vec<u8> = Vec::new();
+        let e = filter(Failing, &mut out, &opts).unwrap_err();
+        assert_eq!(e.to_string(), "read failed");
+        assert_eq!(e.kind(), ErrorKind::Io);
+        let e = filter(&b"a\xc3\xa4b\n"[..], Failing, &opts).unwrap_err();
+        assert_eq!(e.to_string(), "write failed");
+
+        let mut fw = filter_writer(Failing, &opts);
+        let e = fw.write_all(b"ab\n").and_then(|()| fw.flush()).unwrap_err();
+        assert_eq!(e.to_string(), "write failed");
+    }
+
+    #[cfg(feature = "unicode-normalization")]
+    #[test]
+    fn test_filter_stats() {
+        let opts = Options {
+            ascii_only: true,
+            strip_diacritics: true,
+            ..Options::default()
+        };
+        let mut out: Vec<u8> = Vec::new();
+        let stats = filter("aé你b\n".as_bytes(), &mut out, &opts).unwrap();
+        assert_eq!(out, b"aeb\n");
+        assert_eq!(
+            stats,
+            Stats {
+                bytes_read: 8,
+                bytes_written: 4,
+                chars_removed: 1,
+                chars_replaced: 1,
+                invalid_bytes: 0,
+            }
+        );
+        let stats = filter(&b"a\xffb"[..], &mut Vec::new(), &opts).unwrap();
+        assert_eq!((stats.bytes_read, stats.invalid_bytes), (3, 1));
+    }
+
+    #[test]
+    fn test_filter_mapped() {
+        let inputs: [&[u8]; 4] = [
+            "a\u{e9}\r\nb\u{4f60}\r".as_bytes(),
+            b"\xe4\xbd\xffx\xf0\x9f\x98",
+            "e\u{301}\u{fb01} \t\n".as_bytes(),
+            b"",
+        ];
+        for opts in [
+            Options {
+                ascii_only: true,
+                ..Options::default()
+            },
+            Options {
+                ascii_only: true,
+                newlines: Some(Newlines::Crlf),
+                #[cfg(feature = "unicode-normalization")]
+                normalization: Some(Normalization::Nfkc),
+                write_bom: true,
+                ..Options::default()
+            },
+            Options {
+                invert: true,
+                ascii_only: true,
+                ..Options::default()
+            },
+            Options::default(),
+        ] {
+            for input in inputs {
+                let mut expected: Vec<u8> = Vec::new();
+                let stats = filter(input, &mut expected, &opts).unwrap();
+                let mut out: Vec<u8> = Vec::new();
+                assert_eq!(
+                    filter_mapped(input, &mut out, &opts).unwrap(),
+                    stats,
+                    "{:?}",
+                    input
+                );
+                assert_eq!(out, expected, "{:?}", input);
             }
-            Ok(written)
-        } else {
-            self.backend.write(buf)
         }
     }
 
-    fn flush(&mut self) -> io::Result<()> {
-        self.backend.flush()
+    /// A writer counting the calls writing to it, and recording the length
+    /// of what is written at each flush.
+    #[derive(Default)]
+    struct Calls {
+        bytes: Vec<u8>,
+        writes: usize,
+        flushed: Vec<usize>,
     }
-}
 
-/// Attempt to group bytes into valid utf-8 chars and write them to writer.
-/// `taken_limit` is used to upper bound the bytes taken. Return the number of
-/// bytes actually taken, which is larger than or equal to `taken_limit`.
-///
-/// Dynamic programming is used to find the solution.
-///
-/// Arguments:
-///
-/// - `cbuf`: buffer
-/// - `m`: data size, where m <= cbuf.len()
-/// - `taken_limit`: the index of the last char taken <= this
-/// - `w`: writer to write utf-8 chars
-fn take_from_buffer<W: Write>(
-    cbuf: &[u8],
-    m: usize,
-    taken_limit: usize,
-    w: &mut W,
-) -> usize {
-    let mut cost: Vec<usize> = vec![0; m + 1];
-    let mut backtrack: Vec<usize> = vec![0; m];
-    // valid_utf8[(m + 1) * i + j - (i + 2) * (i + 1) / 2] = true if cbuf[i..j]
-    // is a valid utf-8 char(s).
-    let mut valid_utf8: Vec<bool> = vec![false; (m + 1) * m - (m + 1) * m / 2];
-    for i in (0..m).rev() {
-        let mut min_cost_i = usize::MAX;
-        for j in i + 1..=m {
-            // check if cbuf[i..j] is valid utf-8 char(s)
-            let valid_utf8_ij = std::str::from_utf8(&cbuf[i..j]).is_ok();
-            valid_utf8[(m + 1) * i + j - (i + 2) * (i + 1) / 2] = valid_utf8_ij;
-            // update min_cost_i & backtrack_i
-            let cost_ij = if valid_utf8_ij { 0 } else { j - i };
-            let cost_j = cost[j];
-            if cost_ij + cost_j < min_cost_i {
-                min_cost_i = cost_ij + cost_j;
-                backtrack[i] = j;
-            }
-        }
-        cost[i] = min_cost_i;
-    }
-
-    let mut i: usize = 0;
-    while i <= taken_limit && i < m {
-        let j = backtrack[i];
-        let valid_utf8_ij = valid_utf8[(m + 1) * i + j - (i + 2) * (i + 1) / 2];
-        if valid_utf8_ij {
-            w.write_all(&cbuf[i..j]).unwrap();
-        }
-        i = j;
-    }
-
-    i
-}
-
-/// Return `Ok(())` if `buf` is filled. Return `Err(n)` if EOF is reached the
-/// `buf` is not filled -- only `n` bytes are read in.
-fn fill_buf<R: Read>(buf: &mut [u8], r: &mut R) -> Result<(), usize> {
-    let byte = buf.len();
-    let mut in_bytes_total: usize = 0;
-    while in_bytes_total < byte {
-        let in_bytes = r.read(&mut buf[in_bytes_total..]).unwrap();
-        if in_bytes == 0 {
-            return Err(in_bytes_total);
-        }
-        in_bytes_total += in_bytes;
+    impl Write for Calls {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.bytes.extend_from_slice(buf);
+            self.writes += 1;
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            self.flushed.push(self.bytes.len());
+            Ok(())
+        }
     }
 
-    Ok(())
-}
+    #[test]
+    fn test_filter_line_buffered() {
+        let opts = Options {
+            ascii_only: true,
+            line_buffered: true,
+            ..Options::default()
+        };
+        let input = "ab\nc你d\nef";
+        let mut out = Calls::default();
+        filter_bufread(input.as_bytes(), &mut out, &opts).unwrap();
+        assert_eq!(out.bytes, b"ab\ncd\nef");
+        out.flushed.dedup();
+        assert_eq!(out.flushed, [3, 6, 8]);
+    }
 
-fn buffer_filter<R: Read, W: Write>(
-    buf_size: usize,
-    mut taken_limit: usize,
-    r: &mut R,
-    w: &mut W,
-) {
-    let mut buf = vec![0u8; buf_size];
-    let mut m = match fill_buf(&mut buf, r) {
-        Ok(()) => buf_size,
-        Err(n) => {
-            taken_limit = n;
-            n
+    #[test]
+    fn test_filter_writer_batches() {
+        let opts = Options {
+            ascii_only: true,
+            ..Options::default()
+        };
+        let mut fw = filter_writer(Calls::default(), &opts)
+            .with_record_sep(Some(b"\r\n".to_vec()));
+        fw.write_all("a你b好c\nd".as_bytes()).unwrap();
+        assert_eq!(fw.get_ref().writes, 1);
+        let bufs = ["é1\n".as_bytes(), b"2", "ü3".as_bytes()];
+        let bufs: Vec<io::IoSlice> =
+            bufs.iter().map(|buf| io::IoSlice::new(buf)).collect();
+        assert_eq!(fw.write_vectored(&bufs).unwrap(), 8);
+        assert_eq!(fw.get_ref().writes, 2);
+        assert_eq!(fw.get_ref().bytes, b"abc\r\nd1\r\n23");
+    }
+
+    #[test]
+    fn test_copy_utf8() {
+        let input = include_bytes!("../corrupted_lipsum.txt");
+        let opts = Options::default();
+        assert!(opts.passes_utf8());
+        let mut expected: Vec<u8> = Vec::new();
+        let mut fw = filter_writer(&mut expected, &opts);
+        buffer_filter(opts.buf_size, &mut &input[..], &mut fw).unwrap();
+        let expected_stats = fw.stats();
+        // in pieces splitting the chars
+        let mut r = io::BufReader::with_capacity(5, &input[..]);
+        let mut out: Vec<u8> = Vec::new();
+        assert_eq!(copy_utf8(&mut r, &mut out).unwrap(), expected_stats);
+        assert_eq!(out, expected);
+        assert!(!Options {
+            ascii_only: true,
+            ..opts
         }
-    };
-    while m > 0 {
-        let taken = take_from_buffer(&buf, m, taken_limit, w);
-        buf.copy_within(taken..m, 0);
-        m = match fill_buf(&mut buf[m - taken..], r) {
-            Ok(()) => buf_size,
-            Err(n) => {
-                taken_limit = m - taken + n;
-                taken_limit
+        .passes_utf8());
+    }
+
+    #[test]
+    fn test_filter_files() {
+        let dir = std::env::temp_dir()
+            .join(format!("ascii-filter-files-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("a"), "aé\n").unwrap();
+        std::fs::write(dir.join("b"), "b你").unwrap();
+        let opts = Options {
+            ascii_only: true,
+            write_bom: true,
+            ..Options::default()
+        };
+        let mut out: Vec<u8> = Vec::new();
+        let paths = [dir.join("c"), dir.join("a"), dir.clone(), dir.join("b")];
+        let mut failed: Vec<PathBuf> = Vec::new();
+        let stats = filter_files(&paths, &mut out, &opts, |path, _| {
+            failed.push(path.to_path_buf())
+        })
+        .unwrap();
+        assert_eq!(out, b"\xef\xbb\xbfa\nb");
+        assert_eq!(stats.chars_removed, 2);
+        assert_eq!(failed, [dir.join("c"), dir.clone()]);
+
+        let opts = Options {
+            filenames: Some(true),
+            ..opts
+        };
+        let mut out: Vec<u8> = Vec::new();
+        let paths = [dir.join("a"), dir.join("b")];
+        filter_files(&paths, &mut out, &opts, |_, _| ()).unwrap();
+        let expected = format!(
+            "\u{feff}{}:a\n{}:b",
+            paths[0].display(),
+            paths[1].display()
+        );
+        assert_eq!(String::from_utf8(out).unwrap(), expected);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_filter_in_place() {
+        let dir = std::env::temp_dir()
+            .join(format!("ascii-filter-in-place-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("a");
+        std::fs::write(&path, "aé\n").unwrap();
+        let opts = Options {
+            ascii_only: true,
+            ..Options::default()
+        };
+        let stats = filter_in_place(&path, Some(".bak"), &opts).unwrap();
+        assert_eq!(stats.chars_removed, 1);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "a\n");
+        assert_eq!(std::fs::read_to_string(dir.join("a.bak")).unwrap(), "aé\n");
+        assert!(filter_in_place(&dir.join("b"), None, &opts).is_err());
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 2);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_watch_file() {
+        let dir = std::env::temp_dir()
+            .join(format!("ascii-filter-watch-file-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("log");
+        std::fs::write(&path, "aé\n").unwrap();
+        let opts = Options {
+            ascii_only: true,
+            output: Some(dir.join("clean")),
+            ..Options::default()
+        };
+        let mut outputs: Vec<String> = Vec::new();
+        watch_file(&path, &opts, |result| {
+            result.unwrap();
+            outputs.push(std::fs::read_to_string(dir.join("clean")).unwrap());
+            if outputs.len() == 2 {
+                return ControlFlow::Break(());
             }
+            std::fs::write(&path, "aé\nbè\n").unwrap();
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+        assert_eq!(outputs, ["a\n", "a\nb\n"]);
+        let no_output = Options::default();
+        assert!(
+            watch_file(&path, &no_output, |_| ControlFlow::Break(())).is_err()
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_filter_rejects() {
+        let path = std::env::temp_dir()
+            .join(format!("ascii-filter-rejects-{}", std::process::id()));
+        let mut opts = Options {
+            ascii_only: true,
+            drop_regex: Some(Regex::new("#.*").unwrap()),
+            rejects: Some(path.clone()),
+            ..Options::default()
         };
+        let mut out: Vec<u8> = Vec::new();
+        filter("aé\nb你 #x\n".as_bytes(), &mut out, &opts).unwrap();
+        assert_eq!(out, b"a\nb \n");
+        opts.reject_positions = true;
+        filter("aé\n".as_bytes(), &mut Vec::new(), &opts).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "é你#x1:2: U+00E9 é\n"
+        );
+        std::fs::remove_file(&path).unwrap();
     }
-}
 
-pub fn stdin_stdout_buffer_filter(buf_size: usize, ascii_only: bool) {
-    let mut stdin = io::stdin();
-    let mut stdout = io::stdout();
-    let mut fw = FilterWriter::new(&mut stdout, ascii_only);
-    buffer_filter(buf_size, buf_size / 2, &mut stdin, &mut fw);
-}
+    #[test]
+    fn test_filter_rejects_hex() {
+        let path = std::env::temp_dir()
+            .join(format!("ascii-filter-rejects-hex-{}", std::process::id()));
+        let opts = Options {
+            ascii_only: true,
+            rejects_hex: Some(path.clone()),
+            ..Options::default()
+        };
+        let mut out: Vec<u8> = Vec::new();
+        filter(&b"a\xff\xfe\xc3\xa9b\n"[..], &mut out, &opts).unwrap();
+        assert_eq!(out, b"ab\n");
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "00000001: ff  invalid\n\
+             00000002: fe  invalid\n\
+             00000003: c3 a9  U+00E9 é\n"
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
 
-#[cfg(test)]
-mod tests {
-    use crate::{fill_buf, take_from_buffer, FilterWriter};
-    use std::io::{Cursor, Write};
+    #[test]
+    fn test_filter_undo_log() {
+        let dir = std::env::temp_dir()
+            .join(format!("ascii-filter-undo-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("a.txt");
+        let original: &[u8] =
+            b"\xef\xbb\xbfa\xff\xc3\xa9b # \xe2\x80\x9cc\xe2\x80\x9d\n\xe2\x80\x9cd\n";
+        std::fs::write(&path, original).unwrap();
+        let log = dir.join("undo.log");
+        let opts = Options {
+            ascii_only: true,
+            mappings: vec![Mapping::Punctuation],
+            strip_bom: true,
+            drop_regex: Some(Regex::new(" #.*").unwrap()),
+            undo_log: Some(log.clone()),
+            ..Options::default()
+        };
+        filter_in_place(&path, None, &opts).unwrap();
+        let filtered = std::fs::read(&path).unwrap();
+        assert_eq!(filtered, b"ab\n\"d\n");
+        assert_eq!(
+            std::fs::read_to_string(&log).unwrap(),
+            "0 0 efbbbf\n1 0 ffc3a9\n2 0 202320e2809c63e2809d\n\
+             3 1 e2809c\n"
+        );
+        let mut restored: Vec<u8> = Vec::new();
+        let log_file = io::BufReader::new(std::fs::File::open(&log).unwrap());
+        restore(filtered.as_slice(), log_file, &mut restored).unwrap();
+        assert_eq!(restored, original);
+        // the other transformations are not undone
+        let opts = Options {
+            expand_tabs: Some(4),
+            ..opts
+        };
+        let e = filter_in_place(&path, None, &opts).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "the undo log does not apply with expand_tabs"
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 
     #[test]
-    fn test_take_from_buffer() {
-        let mut w: Vec<u8> = Vec::new();
-        assert_eq!(take_from_buffer(b"abcdef", 5, 2, &mut w), 3);
-        assert_eq!(w, vec![b'a', b'b', b'c']);
+    fn test_filter_source_map() {
+        let dir = std::env::temp_dir()
+            .join(format!("ascii-filter-source-map-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("a.txt");
+        std::fs::write(
+            &path,
+            b"\xef\xbb\xbfa\xff\xc3\xa9b # \xe2\x80\x9cc\xe2\x80\x9d\n\xe2\x80\x9cd\n",
+        )
+        .unwrap();
+        let map = dir.join("a.map");
+        let opts = Options {
+            ascii_only: true,
+            mappings: vec![Mapping::Punctuation],
+            strip_bom: true,
+            drop_regex: Some(Regex::new(" #.*").unwrap()),
+            source_map: Some(map.clone()),
+            ..Options::default()
+        };
+        filter_in_place(&path, None, &opts).unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"ab\n\"d\n");
+        assert_eq!(
+            std::fs::read_to_string(&map).unwrap(),
+            "0 3\n1\n0 3\n1\n0 10\n1\n1 3\n2\n"
+        );
+        let map = SourceMap::read(io::BufReader::new(
+            std::fs::File::open(&map).unwrap(),
+        ))
+        .unwrap();
+        let offsets: Vec<u64> = (0..7).map(|i| map.input_offset(i)).collect();
+        assert_eq!(offsets, [3, 7, 18, 19, 22, 23, 24]);
+        let opts = Options {
+            collapse: Some(" ".to_string()),
+            ..opts
+        };
+        let e = filter_in_place(&path, None, &opts).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "the source map does not apply with collapse"
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_filter_report() {
+        let dir = std::env::temp_dir()
+            .join(format!("ascii-filter-report-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("in.txt");
+        std::fs::write(&input, b"a\xff\xc3\xa9b\n\xe2\x80\x9cc\n").unwrap();
+        let report = dir.join("report.jsonl");
+        let opts = Options {
+            ascii_only: true,
+            mappings: vec![Mapping::Punctuation],
+            report: Some(ReportFormat::Jsonl),
+            report_output: Some(report.clone()),
+            ..Options::default()
+        };
+        let mut out: Vec<u8> = Vec::new();
+        let paths = [input.clone()];
+        filter_files(&paths, &mut out, &opts, |_, e| panic!("{}", e)).unwrap();
+        assert_eq!(out, b"ab\n\"c\n");
+        let file = input.display().to_string().replace('\\', "\\\\");
+        assert_eq!(
+            std::fs::read_to_string(&report).unwrap(),
+            format!(
+                "{{\"file\":\"{0}\",\"line\":1,\"column\":2,\"offset\":2,\
+                 \"codepoint\":\"U+00E9\",\"char\":\"é\",\
+                 \"action\":\"removed\"}}\n\
+                 {{\"file\":\"{0}\",\"line\":2,\"column\":1,\"offset\":6,\
+                 \"codepoint\":\"U+201C\",\"char\":\"“\",\
+                 \"action\":\"replaced\",\"replacement\":\"\\\"\"}}\n",
+                file
+            )
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_flush_at_line_end() {
+        let opts = Options {
+            ascii_only: true,
+            ..Options::default()
+        };
+        let cases = [
+            Options {
+                drop_lines: true,
+                ..opts.clone()
+            },
+            Options {
+                keep_regex: Some(Regex::new("é+").unwrap()),
+                ..opts.clone()
+            },
+            Options {
+                #[cfg(feature = "unicode-normalization")]
+                normalization: Some(Normalization::Nfc),
+                ..opts.clone()
+            },
+            Options {
+                newlines: Some(Newlines::Lf),
+                ..opts.clone()
+            },
+        ];
+        let input = "a\ne\u{301}a\n\taé\r\nééé\n\rx".repeat(4);
+        for opts in &cases {
+            let mut expected: Vec<u8> = Vec::new();
+            let mut fw = filter_writer(&mut expected, opts);
+            buffer_filter(8, &mut input.as_bytes(), &mut fw).unwrap();
+            // flushing at every line end changes nothing
+            let mut out: Vec<u8> = Vec::new();
+            let mut fw = filter_writer(&mut out, opts);
+            buffer_filter_checkpointed(
+                8,
+                &mut input.as_bytes(),
+                &mut fw,
+                |_, fw| match fw.at_line_end() {
+                    true => fw.flush(),
+                    false => Ok(()),
+                },
+            )
+            .unwrap();
+            fw.flush().unwrap();
+            assert_eq!(out, expected);
+        }
+
+        let mut fw = filter_writer(Vec::new(), &cases[0]);
+        fw.write_all("aé".as_bytes()).unwrap();
+        assert!(!fw.at_line_end());
+        fw.write_all(b"\n").unwrap();
+        assert!(fw.at_line_end());
+    }
+
+    #[test]
+    fn test_filter_writer_on_drop() {
+        let opts = Options {
+            ascii_only: true,
+            drop_regex: Some(Regex::new("#.*").unwrap()),
+            newlines: Some(Newlines::Lf),
+            ..Options::default()
+        };
+        let dropped = Arc::new(Mutex::new(Vec::new()));
+        let dropped_clone = Arc::clone(&dropped);
+        let mut fw =
+            filter_writer(Vec::new(), &opts).with_on_drop(move |c, offset| {
+                dropped_clone.lock().unwrap().push((c, offset))
+            });
+        for piece in [&b"a\xe4\xbd"[..], b"\xa0\r", b"\n\xffb\r#\xc3\xa9\n"] {
+            fw.write_all(piece).unwrap();
+        }
+        fw.flush().unwrap();
+        assert_eq!(fw.into_inner(), b"a\nb\n");
+        assert_eq!(
+            *dropped.lock().unwrap(),
+            vec![('你', 1), ('\r', 8), ('#', 9), ('é', 10)]
+        );
+    }
+
+    #[test]
+    fn test_buffer_filter_checkpointed() {
+        let opts = Options {
+            ascii_only: true,
+            ..Options::default()
+        };
+        let input = "hé\nllo wörld\n".repeat(3);
+        let mut r = io::BufReader::with_capacity(7, Cursor::new(&input));
+        let mut out: Vec<u8> = Vec::new();
+        let mut fw = filter_writer(&mut out, &opts);
+        let mut chunks: Vec<usize> = Vec::new();
+        buffer_filter_checkpointed(4, &mut r, &mut fw, |n, _| {
+            chunks.push(n);
+            Ok(())
+        })
+        .unwrap();
+        fw.flush().unwrap();
+        // at most `buf_size` bytes of what the reader buffers at a time
+        assert_eq!(chunks.iter().sum::<usize>(), input.len());
+        assert!(chunks.iter().all(|&n| n <= 4));
+        assert_eq!(out, "h\nllo wrld\n".repeat(3).as_bytes());
     }
 
     #[test]
-    fn test_fill_buf() {
-        let mut buf = vec![0u8; 5];
-        let data = vec![b'h', b'e', b'l'];
-        let mut r = Cursor::new(data);
-        assert_eq!(fill_buf(&mut buf, &mut r), Err(3));
+    fn test_filter_bytes() {
+        let opts = Options::default();
+        let bytes = b"caf\xc3\xa9\xff \xe4\xbd\n";
+        assert_eq!(filter_bytes(bytes, &opts), b"caf\xc3\xa9 \n");
+        let opts = Options {
+            ascii_only: true,
+            ..opts
+        };
+        assert_eq!(filter_bytes(bytes, &opts), b"caf \n");
+    }
 
-        let mut buf = vec![0u8; 3];
-        let data = vec![b'h', b'e', b'l', b'l'];
-        let mut r = Cursor::new(data);
-        assert_eq!(fill_buf(&mut buf, &mut r), Ok(()));
+    #[test]
+    fn test_filter_str() {
+        let opts = Options {
+            ascii_only: true,
+            ..Options::default()
+        };
+        assert!(matches!(filter_str("plain\n", &opts), Cow::Borrowed(_)));
+        assert_eq!(filter_str("a→b\n", &opts), "ab\n");
+        let opts = Options {
+            mappings: vec![Mapping::Arrows],
+            ..opts
+        };
+        assert_eq!(filter_str("a→b\n", &opts), "a->b\n");
+        #[cfg(feature = "unicode-normalization")]
+        let opts = Options {
+            strip_diacritics: true,
+            ..opts
+        };
+        #[cfg(feature = "unicode-normalization")]
+        assert_eq!(filter_str("café→\n", &opts), "cafe->\n");
+        #[cfg(feature = "unicode-normalization")]
+        assert_eq!(filter_str("cafe\u{301}\n", &opts), "cafe\n");
+        #[cfg(feature = "unicode-normalization")]
+        let opts = Options {
+            normalization: Some(Normalization::Nfkc),
+            ..opts
+        };
+        #[cfg(feature = "unicode-normalization")]
+        assert_eq!(filter_str("ﬁ１²\n", &opts), "fi12\n");
+        let opts = Options {
+            replacement: Some("?".to_string()),
+            ..opts
+        };
+        assert_eq!(filter_str("a→b你\n", &opts), "a->b?\n");
+        let opts = Options {
+            escape: Some(Escape::Unicode),
+            ..opts
+        };
+        assert_eq!(filter_str("a→b你\n", &opts), "a->b\\u{4F60}\n");
+        assert!(matches!(
+            filter_str("a→b\n", &Options::default()),
+            Cow::Borrowed(_)
+        ));
     }
 
     #[test]
@@ -186,4 +3939,546 @@ mod tests {
         write!(fw, "abc你好 wor").unwrap();
         assert_eq!(w, vec![b'a', b'b', b'c', b' ', b'w', b'o', b'r']);
     }
+
+    #[test]
+    fn test_filter_writer_finish() {
+        let mut fw = FilterWriter::new(Vec::new(), true)
+            .with_invalid_utf8(InvalidUtf8::Escape);
+        let mut out = Vec::new();
+        for chunk in [&b"a\xe4"[..], b"\xbd\xa0b\xe4"] {
+            fw.write_all(chunk).unwrap();
+            out.append(fw.get_mut());
+        }
+        fw.finish().unwrap();
+        out.append(fw.get_mut());
+        assert_eq!(out, b"ab\\xE4");
+    }
+
+    #[test]
+    fn test_filter_writer_split_utf8() {
+        let mut fw = FilterWriter::new(Vec::new(), false);
+        for chunk in "a你😀".as_bytes().chunks(1) {
+            fw.write_all(chunk).unwrap();
+        }
+        // invalid bytes, and an incomplete char at the end
+        fw.write_all(b"\xff\xe4b\xe4\xbd").unwrap();
+        assert_eq!(fw.into_inner(), "a你😀b".as_bytes());
+    }
+
+    #[test]
+    fn test_filter_writer_allowed() {
+        let mut w: Vec<u8> = Vec::new();
+        let allowed: CharSet = "a-c,\\n,é".parse().unwrap();
+        let mut fw = FilterWriter::new(&mut w, false).with_allowed(allowed);
+        writeln!(fw, "abcdé你").unwrap();
+        assert_eq!(w, "abcé\n".as_bytes());
+    }
+
+    #[test]
+    fn test_send() {
+        // to be held across `.await` in a spawned task
+        fn assert_send<T: Send>() {}
+        assert_send::<FilterWriter<Vec<u8>>>();
+        assert_send::<crate::FilterReader<&[u8]>>();
+        assert_send::<crate::FilterMachine>();
+    }
+
+    #[test]
+    fn test_filter_writer_predicate() {
+        let mut w: Vec<u8> = Vec::new();
+        let mut fw = FilterWriter::with_predicate(&mut w, |c| match c {
+            'é' => Action::Replace("e".to_string()),
+            '#' => Action::Replace(String::new()),
+            '你' => Action::Keep,
+            c => Action::ascii_subset(c),
+        })
+        .with_keep_cr()
+        .with_replacer(|_: char, out: &mut dyn Write| out.write_all(b"?"));
+        writeln!(fw, "#café 你好\r").unwrap();
+        let stats = fw.stats();
+        assert_eq!((stats.chars_removed, stats.chars_replaced), (1, 2));
+        assert_eq!(w, "cafe 你?\r\n".as_bytes());
+    }
+
+    #[test]
+    fn test_filter_writer_invert() {
+        let mut w: Vec<u8> = Vec::new();
+        let mut fw = FilterWriter::new(&mut w, true)
+            .with_invert(true)
+            .with_replacer(|_: char, out: &mut dyn Write| out.write_all(b"?"));
+        fw.write_all(b"a\xe4\xbd\xa0\xffb\xe5\xa5\xbd\n").unwrap();
+        assert_eq!(w, "你好".as_bytes());
+    }
+
+    #[cfg(feature = "unicode-category")]
+    #[test]
+    fn test_filter_writer_categories() {
+        use crate::tables::GeneralCategory;
+
+        let mut w: Vec<u8> = Vec::new();
+        let mut fw = FilterWriter::new(&mut w, false).with_drop_categories(
+            GeneralCategory::parse_list("Cf,Co").unwrap(),
+        );
+        writeln!(fw, "a\u{200b}你\u{e000}").unwrap();
+        assert_eq!(w, "a你\n".as_bytes());
+
+        let mut w: Vec<u8> = Vec::new();
+        let mut fw = FilterWriter::new(&mut w, true)
+            .with_keep_categories(GeneralCategory::parse_list("L").unwrap());
+        write!(fw, "é, 你！").unwrap();
+        assert_eq!(w, "é, 你".as_bytes());
+    }
+
+    #[test]
+    fn test_filter_writer_keep_latin1() {
+        let mut w: Vec<u8> = Vec::new();
+        let mut fw = FilterWriter::new(&mut w, true)
+            .with_keep_latin1()
+            .with_replacer(|_: char, out: &mut dyn Write| out.write_all(b"?"));
+        writeln!(fw, "Ça coûte 5 € ½ — Œuvre\u{85} ĳ").unwrap();
+        assert_eq!(w, "Ça coûte 5 € ½ ? Œuvre? ?\n".as_bytes());
+    }
+
+    #[test]
+    fn test_filter_strict() {
+        let opts = Options {
+            ascii_only: true,
+            mappings: vec![Mapping::Punctuation],
+            strict: true,
+            ..Options::default()
+        };
+        let mut out: Vec<u8> = Vec::new();
+        filter(&b"ab\ncd\n"[..], &mut out, &opts).unwrap();
+        let e =
+            filter("ab\nc “d”\n".as_bytes(), Vec::new(), &opts).unwrap_err();
+        assert!(matches!(
+            e,
+            Error::Check(CheckError::Disallowed {
+                c: '“',
+                line: 2,
+                column: 3
+            })
+        ));
+        assert_eq!(e.to_string(), "disallowed char U+201C '“' at 2:3");
+        let e = filter(&b"a\n\t\xff"[..], Vec::new(), &opts).unwrap_err();
+        assert_eq!(e.kind(), ErrorKind::Check);
+        assert_eq!(e.to_string(), "invalid utf-8 sequence \\xFF at 2:2");
+    }
+
+    #[test]
+    fn test_filter_histogram() {
+        let histogram = Arc::new(Mutex::new(Histogram::new()));
+        let opts = Options {
+            ascii_only: true,
+            mappings: vec![Mapping::Punctuation],
+            histogram: Some(Arc::clone(&histogram)),
+            verify: true,
+            ..Options::default()
+        };
+        let mut out: Vec<u8> = Vec::new();
+        filter("a\u{a0}b\u{a0}“c”\n".as_bytes(), &mut out, &opts).unwrap();
+        assert_eq!(out, b"a b \"c\"\n");
+        let histogram = histogram.lock().unwrap();
+        assert_eq!(histogram.top(3), [('\u{a0}', 2), ('“', 1), ('”', 1)]);
+    }
+
+    #[test]
+    fn test_filter_keep_controls() {
+        let opts = Options {
+            ascii_only: true,
+            keep_controls: Some(CharSet::parse_controls("\\f\\x07").unwrap()),
+            ..Options::default()
+        };
+        assert_eq!(filter_str("a\x0cb\x07\x1bc\r\n", &opts), "a\x0cb\x07c\n");
+        let mut out: Vec<u8> = Vec::new();
+        filter(&b"\x0c\x0b\xe4"[..], &mut out, &opts).unwrap();
+        assert_eq!(out, b"\x0c");
+        assert_eq!(
+            CharSet::parse_controls("\\r\\x85x"),
+            Err("'x' is not a control char".to_string())
+        );
+        assert!(CharSet::parse_controls("\\x0").is_err());
+    }
+
+    #[test]
+    fn test_filter_encodable_in() {
+        let opts = Options {
+            encodable_in: Some(LegacyCharset::Iso8859_2),
+            replacement: Some("?".to_string()),
+            ..Options::default()
+        };
+        assert_eq!(
+            filter_str("Łódź: 5 € ¤\u{85} Ž\n", &opts),
+            "Łódź: 5 ? ¤? Ž\n"
+        );
+    }
+
+    #[cfg(feature = "unicode-script")]
+    #[test]
+    fn test_filter_writer_scripts() {
+        use crate::tables::Script;
+
+        let mut w: Vec<u8> = Vec::new();
+        let mut fw = FilterWriter::new(&mut w, true)
+            .with_keep_scripts(Script::parse_list("Latin,Greek").unwrap())
+            .with_replacer(|_: char, out: &mut dyn Write| out.write_all(b"?"));
+        writeln!(fw, "Café αβγ Привет 你").unwrap();
+        assert_eq!(w, "Café αβγ ?????? ?\n".as_bytes());
+    }
+
+    #[test]
+    fn test_filter_writer_regex_rules() {
+        let mut w: Vec<u8> = Vec::new();
+        let mut fw = FilterWriter::new(&mut w, true).with_regex_rules(
+            Some(Regex::new("[\\x{1F300}-\\x{1FAFF}]|ERR").unwrap()),
+            Some(Regex::new("\\p{Greek}+").unwrap()),
+        );
+        // the lines are matched whole, however they are written
+        for chunk in ["αβ😀 你", "好 E", "RR\nγ", "δ OK"] {
+            fw.write_all(chunk.as_bytes()).unwrap();
+        }
+        assert_eq!(fw.get_ref().as_slice(), "αβ  \n".as_bytes());
+        fw.flush().unwrap();
+        assert_eq!(w, "αβ  \nγδ OK".as_bytes());
+
+        let opts = Options {
+            drop_regex: Some(Regex::new("^#.*").unwrap()),
+            ..Options::default()
+        };
+        assert_eq!(
+            filter_str("# 注释\n代码 # 注释\n", &opts),
+            "\n代码 # 注释\n"
+        );
+    }
+
+    #[test]
+    fn test_filter_punycode() {
+        let opts = Options {
+            ascii_only: true,
+            punycode: true,
+            keep_regex: Some(Regex::new("例子").unwrap()),
+            ..Options::default()
+        };
+        assert_eq!(
+            filter_str("Café: www.Bücher.de, 例子.测试\n", &opts),
+            "Caf: www.xn--bcher-kva.de, 例子.xn--0zwm56d\n"
+        );
+        let mut out: Vec<u8> = Vec::new();
+        let stats = filter("bücher.de".as_bytes(), &mut out, &opts).unwrap();
+        assert_eq!(
+            (out.as_slice(), stats.chars_replaced),
+            (&b"xn--bcher-kva.de"[..], 1)
+        );
+    }
+
+    #[cfg(feature = "unicode-segmentation")]
+    #[test]
+    fn test_filter_writer_graphemes() {
+        let opts = Options {
+            allowed: Some(CharSet::from_iter("abe\u{301}\r\n".chars())),
+            replacement: Some("?".to_string()),
+            graphemes: true,
+            ..Options::default()
+        };
+        let mut w: Vec<u8> = Vec::new();
+        let mut fw = filter_writer(&mut w, &opts);
+        // a ZWJ sequence and a combining sequence split across writes
+        fw.write_all("a👩\u{200d}".as_bytes()).unwrap();
+        fw.write_all("💻be".as_bytes()).unwrap();
+        fw.write_all("\u{301}o\u{301}\r".as_bytes()).unwrap();
+        fw.write_all(b"\n").unwrap();
+        fw.flush().unwrap();
+        let stats = fw.stats();
+        assert_eq!(w, "a?be\u{301}?\r\n".as_bytes());
+        assert_eq!((stats.chars_removed, stats.chars_replaced), (3, 2));
+    }
+
+    #[test]
+    fn test_filter_squeeze_replacements() {
+        let mut opts = Options {
+            ascii_only: true,
+            mappings: vec![Mapping::Symbols],
+            replacement: Some("?".to_string()),
+            squeeze_replacements: Some(1),
+            ..Options::default()
+        };
+        // a run ends at a char kept or converted otherwise
+        assert_eq!(
+            filter_str("Hi 你好世界! 你©好\n世界", &opts),
+            "Hi ?! ?(c)?\n?"
+        );
+        opts.squeeze_replacements = Some(2);
+        let mut w: Vec<u8> = Vec::new();
+        let mut fw = filter_writer(&mut w, &opts);
+        // a run split across writes
+        fw.write_all("a你好".as_bytes()).unwrap();
+        fw.write_all("世界b".as_bytes()).unwrap();
+        fw.flush().unwrap();
+        assert_eq!(fw.stats().chars_replaced, 4);
+        assert_eq!(w, b"a??b");
+    }
+
+    #[test]
+    fn test_filter_word_sep() {
+        let opts = Options {
+            ascii_only: true,
+            word_sep: Some(" ".to_string()),
+            ..Options::default()
+        };
+        // not where either side is a space, punctuation or the end
+        assert_eq!(
+            filter_str("naïve foo—bar «x» 1€2 a€", &opts),
+            "na ve foo bar x 1 2 a"
+        );
+        let mut w: Vec<u8> = Vec::new();
+        let mut fw = filter_writer(&mut w, &opts);
+        // a run of dropped chars split across writes
+        fw.write_all("foo—".as_bytes()).unwrap();
+        fw.write_all("—bar".as_bytes()).unwrap();
+        fw.flush().unwrap();
+        assert_eq!(w, b"foo bar");
+    }
+
+    #[test]
+    fn test_filter_writer_strip_ansi() {
+        let mut w: Vec<u8> = Vec::new();
+        let mut fw = FilterWriter::new(&mut w, true).with_strip_ansi();
+        // a sequence split across writes is stripped as a whole
+        for chunk in [&b"\x1b[1;3"[..], b"1mcaf\xc3", b"\xa9\x1b[0m\n"] {
+            fw.write_all(chunk).unwrap();
+        }
+        assert_eq!(w, b"caf\n");
+
+        let opts = Options {
+            strip_ansi: true,
+            ..Options::default()
+        };
+        assert_eq!(filter_str("\x1b[32mok\x1b[0m", &opts), "ok");
+        assert!(matches!(filter_str("ok", &opts), Cow::Borrowed("ok")));
+    }
+
+    #[test]
+    fn test_filter_writer_strip_invisible() {
+        let mut w: Vec<u8> = Vec::new();
+        let mut fw = FilterWriter::new(&mut w, false).with_strip_invisible();
+        write!(fw, "\u{feff}zero\u{200b}width\u{ad} \u{202e}你好").unwrap();
+        assert_eq!(w, "zerowidth 你好".as_bytes());
+    }
+
+    #[cfg(feature = "unicode-confusables")]
+    #[test]
+    fn test_filter_str_confusables() {
+        let opts = Options {
+            ascii_only: true,
+            mappings: vec![Mapping::Confusables],
+            ..Options::default()
+        };
+        // Cyrillic а and о, Greek ο, full-width Ａ
+        assert_eq!(
+            filter_str("pаypal.cоm gοοgle Ａ", &opts),
+            "paypal.com google A"
+        );
+    }
+
+    #[test]
+    fn test_filter_writer_emoji() {
+        let mut w: Vec<u8> = Vec::new();
+        let mut fw =
+            FilterWriter::new(&mut w, true).with_emoji(EmojiMode::Keep);
+        fw.write_all("好 👍".as_bytes()).unwrap();
+        fw.write_all("🏽!\n".as_bytes()).unwrap();
+        assert_eq!(w, " 👍🏽!\n".as_bytes());
+
+        let opts = Options {
+            emoji: Some(EmojiMode::Shortcode),
+            ..Options::default()
+        };
+        assert_eq!(filter_str("ship it 🚀", &opts), "ship it :rocket:");
+    }
+
+    #[test]
+    fn test_filter_str_control_escape() {
+        let opts = Options {
+            ascii_only: true,
+            control_escape: Some(ControlEscape::Caret),
+            replacement: Some("?".to_string()),
+            ..Options::default()
+        };
+        assert_eq!(filter_str("a\x01\x1b[0m你\tb\n", &opts), "a^A^[[0m?\tb\n");
+        let opts = Options {
+            ascii_only: true,
+            control_escape: Some(ControlEscape::Hex),
+            ..Options::default()
+        };
+        assert_eq!(filter_str("\x1b\x7f", &opts), "\\x1B\\x7F");
+    }
+
+    #[test]
+    fn test_filter_writer_replacer() {
+        let mut w: Vec<u8> = Vec::new();
+        let mut fw = FilterWriter::new(&mut w, true).with_replacer(
+            |c: char, out: &mut dyn Write| -> io::Result<()> {
+                write!(out, "<{}>", c as u32)
+            },
+        );
+        write!(fw, "a你b").unwrap();
+        assert_eq!(w, b"a<20320>b");
+    }
+
+    #[test]
+    fn test_filter_str_collapse() {
+        let opts = Options {
+            ascii_only: true,
+            mappings: vec![Mapping::Symbols],
+            collapse: Some(" ".to_string()),
+            ..Options::default()
+        };
+        assert_eq!(filter_str("abc你好def", &opts), "abc def");
+        assert_eq!(filter_str("你©好", &opts), " (c) ");
+
+        let mut w: Vec<u8> = Vec::new();
+        let mut fw = FilterWriter::new(&mut w, true).with_collapse("_".into());
+        fw.write_all("a你".as_bytes()).unwrap();
+        fw.write_all("好b".as_bytes()).unwrap();
+        assert_eq!(w, b"a_b");
+    }
+
+    #[test]
+    fn test_filter_str_expand_tabs() {
+        let opts = Options {
+            ascii_only: true,
+            expand_tabs: Some(4),
+            ..Options::default()
+        };
+        assert_eq!(filter_str("a\tb你\tc\n\td", &opts), "a   b   c\n    d");
+        assert!(matches!(filter_str("a b", &opts), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_filter_str_drop_lines() {
+        let opts = Options {
+            ascii_only: true,
+            mappings: vec![Mapping::Symbols],
+            drop_lines: true,
+            ..Options::default()
+        };
+        assert_eq!(filter_str("ok\n你好\n©\nend 你", &opts), "ok\n(c)\n");
+
+        // a line split across writes
+        let mut w: Vec<u8> = Vec::new();
+        let mut fw = FilterWriter::new(&mut w, true).with_drop_lines();
+        fw.write_all("a\nb".as_bytes()).unwrap();
+        assert_eq!(fw.get_ref().as_slice(), b"a\n");
+        fw.write_all("你\nc".as_bytes()).unwrap();
+        fw.flush().unwrap();
+        assert_eq!(w, b"a\nc");
+    }
+
+    #[test]
+    fn test_filter_max_line_length() {
+        let opts = Options {
+            ascii_only: true,
+            line_numbers: true,
+            max_line_length: Some(10),
+            overlong: Overlong::Drop,
+            ..Options::default()
+        };
+        let mut out: Vec<u8> = Vec::new();
+        filter("abcé\nabcd\nx".as_bytes(), &mut out, &opts).unwrap();
+        assert_eq!(out, b"     1\tabc\n     3\tx");
+    }
+
+    #[test]
+    fn test_filter_null_data() {
+        let opts = Options {
+            ascii_only: true,
+            null_data: true,
+            drop_lines: true,
+            drop_regex: Some(Regex::new("^tmp/.*").unwrap()),
+            line_numbers: true,
+            ..Options::default()
+        };
+        let mut out: Vec<u8> = Vec::new();
+        let input = "a b\nc\0tmp/x\0caf\u{e9}\0d\u{7}";
+        filter(input.as_bytes(), &mut out, &opts).unwrap();
+        assert_eq!(out, b"     1\ta b\nc\0     2\t\0");
+    }
+
+    #[test]
+    fn test_filter_writer_line_numbers() {
+        let mut w: Vec<u8> = Vec::new();
+        let mut fw = FilterWriter::new(&mut w, true)
+            .with_drop_lines()
+            .with_line_numbers(true);
+        for chunk in ["a\n你\nb", "c\n\nd"] {
+            fw.write_all(chunk.as_bytes()).unwrap();
+        }
+        fw.flush().unwrap();
+        fw.write_all(b"e\n").unwrap();
+        assert_eq!(w, b"     1\ta\n     3\tbc\n     4\t\n     5\tde\n");
+
+        let opts = Options {
+            line_numbers: true,
+            out_record_sep: Some("\0".to_string()),
+            ..Options::default()
+        };
+        let mut out: Vec<u8> = Vec::new();
+        filter(&b"x\ny"[..], &mut out, &opts).unwrap();
+        assert_eq!(out, b"     1\tx\0     2\ty");
+    }
+
+    #[test]
+    fn test_filter_str_highlight() {
+        let opts = Options {
+            ascii_only: true,
+            mappings: vec![Mapping::Symbols],
+            highlight: true,
+            ..Options::default()
+        };
+        assert_eq!(
+            filter_str("a©你", &opts),
+            "a\x1b[7m©\x1b[27m\x1b[7m你\x1b[27m"
+        );
+    }
+
+    #[test]
+    fn test_filter_writer_newlines() {
+        let mut w: Vec<u8> = Vec::new();
+        let mut fw =
+            FilterWriter::new(&mut w, true).with_newlines(Newlines::Keep);
+        // a CRLF split across writes
+        fw.write_all(b"a\r").unwrap();
+        fw.write_all(b"\nb\rc\r").unwrap();
+        fw.flush().unwrap();
+        assert_eq!(w, b"a\r\nbc");
+
+        let opts = Options {
+            ascii_only: true,
+            keep_cr: true,
+            newlines: Some(Newlines::Lf),
+            ..Options::default()
+        };
+        assert_eq!(filter_str("a\r\nb\rc\n", &opts), "a\nb\rc\n");
+        let opts = Options {
+            newlines: Some(Newlines::Crlf),
+            ..Options::default()
+        };
+        assert_eq!(filter_str("a\r\nb\n", &opts), "a\r\nb\r\n");
+        assert!(matches!(filter_str("a\rb", &opts), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_filter_writer_record_sep() {
+        let mut w: Vec<u8> = Vec::new();
+        let mut fw =
+            FilterWriter::new(&mut w, true).with_record_sep(Some(vec![0]));
+        write!(fw, "a\nb你\n").unwrap();
+        assert_eq!(w, b"a\0b\0");
+
+        let mut w: Vec<u8> = Vec::new();
+        let mut fw = FilterWriter::new(&mut w, false)
+            .with_record_sep(Some(b"\r\n".to_vec()));
+        fw.write_all("你\n".as_bytes()).unwrap();
+        assert_eq!(w, "你\r\n".as_bytes());
+    }
 }
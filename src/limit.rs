@@ -0,0 +1,155 @@
+//! Bounding of the length of the output lines, e.g. for legacy consumers of
+//! logs which cannot take longer ones.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// What becomes of an output line longer than the maximum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Overlong {
+    /// Cut it at the maximum.
+    #[default]
+    Truncate,
+    /// Break it into lines of the maximum each.
+    Wrap,
+    /// Omit it, line end and all.
+    Drop,
+}
+
+impl Overlong {
+    pub const ALL: [Overlong; 3] =
+        [Overlong::Truncate, Overlong::Wrap, Overlong::Drop];
+
+    /// Return the name of the treatment, e.g. "wrap".
+    pub fn name(&self) -> &'static str {
+        match self {
+            Overlong::Truncate => "truncate",
+            Overlong::Wrap => "wrap",
+            Overlong::Drop => "drop",
+        }
+    }
+}
+
+impl fmt::Display for Overlong {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Overlong {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Overlong::ALL
+            .into_iter()
+            .find(|overlong| overlong.name() == s)
+            .ok_or_else(|| {
+                format!(
+                    "unknown overlong line treatment {:?}, expected one of \
+                     truncate, wrap, drop",
+                    s
+                )
+            })
+    }
+}
+
+/// Bounds the length of the lines of the output written in pieces, keeping
+/// track of the line across them. Each char takes one column, and the line
+/// end none.
+#[derive(Debug, Clone)]
+pub(crate) struct LineLimiter {
+    /// The most chars of a line.
+    max: usize,
+    overlong: Overlong,
+    /// The chars of the current line so far.
+    column: usize,
+    /// If true, the char the continuation bytes next belong to is kept.
+    kept: bool,
+    /// With [`Overlong::Drop`], the current line, held back until it ends or
+    /// runs too long.
+    held: Vec<u8>,
+    /// With [`Overlong::Drop`], if true, the current line runs too long, and
+    /// is omitted.
+    dropping: bool,
+}
+
+impl LineLimiter {
+    pub(crate) fn new(max: usize, overlong: Overlong) -> Self {
+        Self {
+            max: max.max(1),
+            overlong,
+            column: 0,
+            kept: true,
+            held: Vec::new(),
+            dropping: false,
+        }
+    }
+
+    /// Return the utf-8 `bytes`, whose lines end with `end`, with their lines
+    /// bounded, holding back a line which may be omitted.
+    pub(crate) fn limit(&mut self, bytes: &[u8], end: u8) -> Vec<u8> {
+        let mut out: Vec<u8> = Vec::with_capacity(bytes.len());
+        for &b in bytes {
+            if b == end {
+                if !std::mem::take(&mut self.dropping) {
+                    out.append(&mut self.held);
+                    out.push(end);
+                }
+                self.held.clear();
+                self.column = 0;
+                self.kept = true;
+                continue;
+            }
+            // the continuation bytes of a multibyte char
+            if !(0x80..=0xbf).contains(&b) {
+                self.column += 1;
+                self.kept = self.column <= self.max;
+                if !self.kept && self.overlong == Overlong::Wrap {
+                    out.push(end);
+                    self.column = 1;
+                    self.kept = true;
+                }
+                if !self.kept && self.overlong == Overlong::Drop {
+                    self.dropping = true;
+                    self.held.clear();
+                }
+            }
+            match (self.kept, self.overlong) {
+                (false, _) => (),
+                (true, Overlong::Drop) => self.held.push(b),
+                (true, _) => out.push(b),
+            }
+        }
+        out
+    }
+
+    /// Return the line held back, e.g. at the end of input, unless omitted.
+    pub(crate) fn finish(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.held)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LineLimiter, Overlong};
+
+    fn limit(pieces: &[&str], overlong: Overlong) -> String {
+        let mut limiter = LineLimiter::new(3, overlong);
+        let mut out: Vec<u8> = Vec::new();
+        for piece in pieces {
+            out.extend(limiter.limit(piece.as_bytes(), b'\n'));
+        }
+        out.extend(limiter.finish());
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_line_limiter() {
+        let pieces = ["ab", "cdé\nxy", "é\n\nwxyz"];
+        assert_eq!(limit(&pieces, Overlong::Truncate), "abc\nxyé\n\nwxy");
+        assert_eq!(limit(&pieces, Overlong::Wrap), "abc\ndé\nxyé\n\nwxy\nz");
+        assert_eq!(limit(&pieces, Overlong::Drop), "xyé\n\n");
+        assert_eq!(limit(&["ab"], Overlong::Drop), "ab");
+        assert_eq!("wrap".parse(), Ok(Overlong::Wrap));
+    }
+}
@@ -0,0 +1,332 @@
+//! The `check`, `report` and `hook` subcommands, which lint the inputs against the
+//! filter instead of filtering them, e.g. in pre-commit hooks.
+
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+
+use ascii_filter::{filter, sniff_type, Error, Event, FilterMachine, Options};
+
+use crate::exit;
+
+/// Read each of `files`, or stdin if none, with its name, exiting on
+/// failure.
+fn read_inputs(files: &[PathBuf]) -> Vec<(String, Vec<u8>)> {
+    let read = |name: String, bytes: std::io::Result<Vec<u8>>| {
+        let bytes = bytes.unwrap_or_else(|e| {
+            eprintln!("ascii-filter: {}: {}", name, e);
+            process::exit(exit::IO);
+        });
+        (name, bytes)
+    };
+    if files.is_empty() {
+        let mut bytes: Vec<u8> = Vec::new();
+        let result = std::io::stdin().read_to_end(&mut bytes).map(|_| bytes);
+        return vec![read("<stdin>".to_string(), result)];
+    }
+    files
+        .iter()
+        .map(|file| read(file.display().to_string(), std::fs::read(file)))
+        .collect()
+}
+
+/// Locates byte offsets of an input as lines and columns, from 1, where
+/// each char takes one column. The offsets are located in increasing order.
+struct Locator<'a> {
+    bytes: &'a [u8],
+    /// The offset located last.
+    offset: usize,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Locator<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            offset: 0,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    /// Return the line and the column of the byte `offset`.
+    fn locate(&mut self, offset: usize) -> (usize, usize) {
+        for &b in &self.bytes[self.offset..offset] {
+            match b {
+                b'\n' => {
+                    self.line += 1;
+                    self.column = 1;
+                }
+                // the continuation bytes of a multibyte char
+                0x80..=0xbf => (),
+                _ => self.column += 1,
+            }
+        }
+        self.offset = offset;
+        (self.line, self.column)
+    }
+}
+
+/// Return the line reporting `what` at `line` and `column` of the input
+/// `name`, which is left out if `opts.filenames` is `Some(false)`.
+fn locate(
+    name: &str,
+    line: usize,
+    column: usize,
+    what: &str,
+    opts: &Options,
+) -> String {
+    match opts.filenames {
+        Some(false) => format!("{}:{}: {}", line, column, what),
+        _ => format!("{}:{}:{}: {}", name, line, column, what),
+    }
+}
+
+/// Describe `c`, e.g. `U+00A0 NO-BREAK SPACE`.
+pub(crate) fn describe_char(c: char) -> String {
+    #[cfg(feature = "unicode-names")]
+    if let Some(name) = ascii_filter::tables::name(c) {
+        return format!("U+{:04X} {}", c as u32, name);
+    }
+    format!("U+{:04X} {:?}", c as u32, c)
+}
+
+/// Describe what the filter changes at the start of `bytes`.
+fn describe_change(bytes: &[u8]) -> String {
+    let valid = match std::str::from_utf8(bytes) {
+        Ok(s) => s,
+        Err(e) => std::str::from_utf8(&bytes[..e.valid_up_to()]).unwrap(),
+    };
+    match (valid.chars().next(), bytes.first()) {
+        (Some(c), _) => describe_char(c),
+        (None, Some(b)) => format!("invalid byte {:#04x}", b),
+        (None, None) => "the end of input".to_string(),
+    }
+}
+
+/// Return the lines reporting where the filter per `opts` changes each of
+/// `inputs` first, if anywhere, and the exit status, 1 if it changes any of
+/// them, or 0, or the name of the input and the error filtering it.
+fn check(
+    inputs: &[(String, Vec<u8>)],
+    opts: &Options,
+) -> Result<(Vec<String>, i32), (String, Error)> {
+    let mut lines: Vec<String> = Vec::new();
+    for (name, bytes) in inputs {
+        let mut output: Vec<u8> = Vec::new();
+        filter(bytes.as_slice(), &mut output, opts)
+            .map_err(|e| (name.clone(), e))?;
+        let Some(offset) =
+            (0..=bytes.len()).find(|&i| bytes.get(i) != output.get(i))
+        else {
+            continue;
+        };
+        let (line, column) = Locator::new(bytes).locate(offset);
+        let what = describe_change(&bytes[offset..]);
+        lines.push(locate(name, line, column, &what, opts));
+    }
+    let status = if lines.is_empty() {
+        0
+    } else {
+        exit::VIOLATIONS
+    };
+    Ok((lines, status))
+}
+
+/// Exit with 1 if the filter per `opts` changes any of `files`, or stdin if
+/// none, reporting where it changes each first.
+pub fn run_check(files: &[PathBuf], opts: &Options) {
+    match check(&read_inputs(files), opts) {
+        Ok((lines, status)) => {
+            for line in lines {
+                eprintln!("{}", line);
+            }
+            if status != 0 {
+                process::exit(status);
+            }
+        }
+        Err((name, e)) => {
+            eprintln!("ascii-filter: {}: {}", name, e);
+            process::exit(exit::status(e.kind()));
+        }
+    }
+}
+
+/// Return the lines reporting each char the char filter per `opts` drops or
+/// replaces, and each invalid byte, in `bytes` named `name`.
+fn report(name: &str, bytes: &[u8], opts: &Options) -> Vec<String> {
+    let mut machine = FilterMachine::new(opts);
+    // The replacer per the options writes to a `Vec`, which never fails.
+    let mut events = machine.push(bytes).unwrap().events;
+    events.extend(machine.finish().unwrap().events);
+    let mut locator = Locator::new(bytes);
+    let mut lines: Vec<String> = Vec::new();
+    for event in events {
+        let (offset, what) = match event {
+            Event::Kept { .. } => continue,
+            Event::Dropped { offset, c }
+            | Event::Replaced { offset, c, .. } => (offset, describe_char(c)),
+            Event::Invalid { offset, bytes } => {
+                (offset, format!("invalid byte {:#04x}", bytes[0]))
+            }
+        };
+        let (line, column) = locator.locate(offset);
+        lines.push(locate(name, line, column, &what, opts));
+    }
+    lines
+}
+
+/// Print the lines reporting each char the char filter per `opts` drops or
+/// replaces in `inputs`, and exit with 1 if there is any.
+fn print_reports(inputs: Vec<(String, Vec<u8>)>, opts: &Options) {
+    let mut clean = true;
+    let mut stdout = io::stdout().lock();
+    'inputs: for (name, bytes) in inputs {
+        for line in report(&name, &bytes, opts) {
+            clean = false;
+            match writeln!(stdout, "{}", line) {
+                Ok(()) => {}
+                // e.g. piped to `head`
+                Err(e) if e.kind() == io::ErrorKind::BrokenPipe => {
+                    break 'inputs
+                }
+                Err(e) => {
+                    eprintln!("ascii-filter: {}", e);
+                    process::exit(exit::IO);
+                }
+            }
+        }
+    }
+    if !clean {
+        process::exit(exit::VIOLATIONS);
+    }
+}
+
+/// Print each char the char filter per `opts` drops or replaces in `files`,
+/// or stdin if none, with its location, and exit with 1 if there is any.
+pub fn run_report(files: &[PathBuf], opts: &Options) {
+    print_reports(read_inputs(files), opts)
+}
+
+/// Run `git` with `args`, returning its stdout.
+fn git(args: &[&str]) -> io::Result<Vec<u8>> {
+    let output = Command::new("git").args(args).output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(io::Error::other(format!(
+            "git {}: {}",
+            args.join(" "),
+            stderr.lines().next().unwrap_or_default()
+        )));
+    }
+    Ok(output.stdout)
+}
+
+/// Return the paths in the NUL-terminated list `output`.
+fn split_paths(output: &[u8]) -> Vec<String> {
+    output
+        .split(|&b| b == 0)
+        .filter(|path| !path.is_empty())
+        .map(|path| String::from_utf8_lossy(path).into_owned())
+        .collect()
+}
+
+/// Return the files added, copied, modified or renamed in the git index,
+/// with their staged content, leaving out those which look binary.
+fn read_staged() -> io::Result<Vec<(String, Vec<u8>)>> {
+    // fails outside of a repository, where `git diff` would not
+    git(&["rev-parse", "--git-dir"])?;
+    let paths = git(&[
+        "diff",
+        "--cached",
+        "--name-only",
+        "-z",
+        "--diff-filter=ACMR",
+    ])?;
+    let mut inputs: Vec<(String, Vec<u8>)> = Vec::new();
+    for path in split_paths(&paths) {
+        let bytes = git(&["cat-file", "blob", &format!(":{}", path)])?;
+        let head = &bytes[..bytes.len().min(8192)];
+        // without an extension, only binary content is not text
+        if sniff_type(Path::new(""), head).starts_with("text/") {
+            inputs.push((path, bytes));
+        }
+    }
+    Ok(inputs)
+}
+
+/// Print each char the char filter per `opts` drops or replaces in the
+/// files staged in git, with its location, and exit with 1 if there is
+/// any, e.g. as a pre-commit hook.
+pub fn run_hook(opts: &Options) {
+    match read_staged() {
+        Ok(inputs) => print_reports(inputs, opts),
+        Err(e) => {
+            eprintln!("ascii-filter: {}", e);
+            process::exit(exit::IO);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check, report, split_paths, Locator};
+    use ascii_filter::Options;
+
+    #[test]
+    fn test_locator() {
+        let bytes = "ab\nçd\ne".as_bytes();
+        let mut locator = Locator::new(bytes);
+        assert_eq!(locator.locate(1), (1, 2));
+        assert_eq!(locator.locate(6), (2, 3));
+        assert_eq!(locator.locate(7), (3, 1));
+    }
+
+    #[test]
+    fn test_report() {
+        let opts = Options {
+            ascii_only: true,
+            ..Options::default()
+        };
+        let lines = report("a.txt", "ok\n1\u{a0}000".as_bytes(), &opts);
+        assert_eq!(lines.len(), 1);
+        #[cfg(feature = "unicode-names")]
+        assert_eq!(lines[0], "a.txt:2:2: U+00A0 NO-BREAK SPACE");
+        let lines = report("-", b"\x1b\xff", &opts);
+        assert_eq!(lines[1], "-:1:2: invalid byte 0xff");
+        let opts = Options {
+            filenames: Some(false),
+            ..opts
+        };
+        let lines = report("-", b"\x1b\xff", &opts);
+        assert_eq!(lines[1], "1:2: invalid byte 0xff");
+    }
+
+    #[test]
+    fn test_check() {
+        let opts = Options {
+            ascii_only: true,
+            ..Options::default()
+        };
+        let clean = ("a.txt".to_string(), b"ok\n".to_vec());
+        let (lines, status) =
+            check(std::slice::from_ref(&clean), &opts).unwrap();
+        assert!(lines.is_empty());
+        assert_eq!(status, 0);
+
+        let dirty = ("b.txt".to_string(), b"ok\nx\xff".to_vec());
+        let (lines, status) = check(&[clean, dirty], &opts).unwrap();
+        assert_eq!(lines, ["b.txt:2:2: invalid byte 0xff"]);
+        assert_eq!(status, 1);
+    }
+
+    #[test]
+    fn test_split_paths() {
+        assert_eq!(
+            split_paths(b"a.txt\0dir/b c.md\0"),
+            ["a.txt", "dir/b c.md"]
+        );
+        assert!(split_paths(b"").is_empty());
+    }
+}
@@ -0,0 +1,125 @@
+//! logfmt-aware filtering, where only the values of `key=value` pairs are
+//! filtered. Keys, separators, quoting and escapes are kept as-is, so that the
+//! output remains valid logfmt.
+
+/// Filter the values in one logfmt `line` with `filter`, which maps raw bytes
+/// to filtered bytes.
+pub(crate) fn filter_line<F: FnMut(&[u8]) -> Vec<u8>>(
+    line: &[u8],
+    filter: &mut F,
+) -> Vec<u8> {
+    let mut out = Vec::with_capacity(line.len());
+    let mut i: usize = 0;
+    while i < line.len() {
+        // separators between pairs
+        let start = i;
+        while i < line.len() && is_space(line[i]) {
+            i += 1;
+        }
+        out.extend_from_slice(&line[start..i]);
+        // the key, up to '=' or the next separator
+        let start = i;
+        while i < line.len() && line[i] != b'=' && !is_space(line[i]) {
+            i += 1;
+        }
+        out.extend_from_slice(&line[start..i]);
+        if i == line.len() || line[i] != b'=' {
+            // a bare key
+            continue;
+        }
+        out.push(b'=');
+        i += 1;
+        if i < line.len() && line[i] == b'"' {
+            out.push(b'"');
+            i += 1;
+            let start = i;
+            while i < line.len() && line[i] != b'"' {
+                i += if line[i] == b'\\' { 2 } else { 1 };
+            }
+            let end = i.min(line.len());
+            out.extend(filter_quoted(&line[start..end], filter));
+            if i < line.len() {
+                out.push(b'"');
+                i += 1;
+            }
+        } else {
+            let start = i;
+            while i < line.len() && !is_space(line[i]) {
+                i += 1;
+            }
+            out.extend(filter(&line[start..i]));
+        }
+    }
+
+    out
+}
+
+fn is_space(b: u8) -> bool {
+    b == b' ' || b == b'\t' || b == b'\n' || b == b'\r'
+}
+
+/// Filter the content of a quoted value. An escape whose escaped char is
+/// dropped by `filter` is dropped as a whole, so that the backslash never
+/// binds to the closing quote.
+fn filter_quoted<F: FnMut(&[u8]) -> Vec<u8>>(
+    content: &[u8],
+    filter: &mut F,
+) -> Vec<u8> {
+    let mut out = Vec::with_capacity(content.len());
+    let mut i: usize = 0;
+    while i < content.len() {
+        let start = i;
+        while i < content.len() && content[i] != b'\\' {
+            i += 1;
+        }
+        out.extend(filter(&content[start..i]));
+        if i == content.len() {
+            break;
+        }
+        // `content[i]` is a backslash
+        if i + 1 < content.len() && content[i + 1].is_ascii() {
+            out.extend_from_slice(&content[i..i + 2]);
+            i += 2;
+        } else {
+            let start = i + 1;
+            i = start;
+            while i < content.len() && !content[i].is_ascii() {
+                i += 1;
+            }
+            let escaped = filter(&content[start..i]);
+            if !escaped.is_empty() {
+                out.push(b'\\');
+                out.extend(escaped);
+            }
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::filter_line;
+
+    fn drop_non_ascii(bytes: &[u8]) -> Vec<u8> {
+        bytes.iter().copied().filter(u8::is_ascii).collect()
+    }
+
+    #[test]
+    fn test_filter_line() {
+        let line = "lvl=info msg=\"héllo \\\"wörld\\\"\" ké=vé flag\n";
+        assert_eq!(
+            filter_line(line.as_bytes(), &mut drop_non_ascii),
+            "lvl=info msg=\"hllo \\\"wrld\\\"\" ké=v flag\n".as_bytes()
+        );
+    }
+
+    #[test]
+    fn test_filter_line_dropped_escape() {
+        let line = "msg=\"a\\é\"";
+        assert_eq!(
+            filter_line(line.as_bytes(), &mut drop_non_ascii),
+            b"msg=\"a\""
+        );
+    }
+}
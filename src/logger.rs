@@ -0,0 +1,144 @@
+//! A `log::Log` adapter that filters the formatted message and the
+//! key-values of every record before delegating, so that applications using
+//! the `log` facade get clean output without touching call sites.
+
+use log::kv::{self, Key, Source, Value, VisitSource};
+use log::{Log, Metadata, Record};
+
+use crate::{filter_string, Options};
+
+/// Wrap `inner`, filtering each record per `opts` before delegating.
+pub struct SanitizingLogger<L> {
+    inner: L,
+    opts: Options,
+}
+
+impl<L: Log> SanitizingLogger<L> {
+    pub fn new(inner: L, opts: Options) -> Self {
+        Self { inner, opts }
+    }
+}
+
+/// Collect the key-values of a record as filtered strings.
+struct Collect<'a> {
+    opts: &'a Options,
+    pairs: Vec<(String, String)>,
+}
+
+impl<'a, 'kvs> VisitSource<'kvs> for Collect<'a> {
+    fn visit_pair(
+        &mut self,
+        key: Key<'kvs>,
+        value: Value<'kvs>,
+    ) -> Result<(), kv::Error> {
+        self.pairs.push((
+            filter_string(key.as_str(), self.opts),
+            filter_string(&value.to_string(), self.opts),
+        ));
+        Ok(())
+    }
+}
+
+/// The filtered key-values.
+struct Pairs(Vec<(String, String)>);
+
+impl Source for Pairs {
+    fn visit<'kvs>(
+        &'kvs self,
+        visitor: &mut dyn VisitSource<'kvs>,
+    ) -> Result<(), kv::Error> {
+        for (k, v) in &self.0 {
+            visitor.visit_pair(Key::from_str(k), Value::from(v.as_str()))?;
+        }
+        Ok(())
+    }
+}
+
+impl<L: Log> Log for SanitizingLogger<L> {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.inner.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        let message = filter_string(&record.args().to_string(), &self.opts);
+        let mut collect = Collect {
+            opts: &self.opts,
+            pairs: Vec::new(),
+        };
+        // Collecting into a `Vec` never fails.
+        record.key_values().visit(&mut collect).unwrap();
+        let pairs = Pairs(collect.pairs);
+        self.inner.log(
+            &Record::builder()
+                .metadata(record.metadata().clone())
+                .args(format_args!("{}", message))
+                .module_path(record.module_path())
+                .file(record.file())
+                .line(record.line())
+                .key_values(&pairs)
+                .build(),
+        );
+    }
+
+    fn flush(&self) {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SanitizingLogger;
+    use crate::Options;
+    use log::kv::{self, Key, Value, VisitSource};
+    use log::{Level, Log, Metadata, Record};
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct Capture(Mutex<Vec<String>>);
+
+    struct Line(String);
+
+    impl<'kvs> VisitSource<'kvs> for Line {
+        fn visit_pair(
+            &mut self,
+            key: Key<'kvs>,
+            value: Value<'kvs>,
+        ) -> Result<(), kv::Error> {
+            self.0.push_str(&format!(" {}={}", key, value));
+            Ok(())
+        }
+    }
+
+    impl Log for &Capture {
+        fn enabled(&self, _: &Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &Record) {
+            let mut line = Line(record.args().to_string());
+            record.key_values().visit(&mut line).unwrap();
+            self.0.lock().unwrap().push(line.0);
+        }
+
+        fn flush(&self) {}
+    }
+
+    #[test]
+    fn test_sanitizing_logger() {
+        let capture = Capture::default();
+        let opts = Options {
+            ascii_only: true,
+            ..Options::default()
+        };
+        let logger = SanitizingLogger::new(&capture, opts);
+        let kvs = [("usér", "nämé")];
+        logger.log(
+            &Record::builder()
+                .level(Level::Info)
+                .args(format_args!("héllo {}", "wörld"))
+                .key_values(&kvs)
+                .build(),
+        );
+        assert_eq!(*capture.0.lock().unwrap(), vec!["hllo wrld usr=nm"]);
+    }
+}
@@ -0,0 +1,394 @@
+//! A sans-IO filter, which never performs I/O itself but reports what it does
+//! to the pushed bytes as events. This is the integration point for custom
+//! runtimes and foreign interfaces.
+
+use std::io;
+
+use ascii_filter_core::utf8::{Utf8Chunk, Utf8Decoder};
+
+use crate::charset::CharFilter;
+use crate::{replacer, Options, Replacer, Stats};
+
+/// What happened to a span of the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// `bytes` at input `offset` are kept as-is.
+    Kept { offset: usize, bytes: Vec<u8> },
+    /// The char `c` at input `offset` is dropped.
+    Dropped { offset: usize, c: char },
+    /// The char `c` at input `offset` is replaced with `bytes`.
+    Replaced {
+        offset: usize,
+        c: char,
+        bytes: Vec<u8>,
+    },
+    /// `bytes` at input `offset` are not valid utf-8, and are dropped.
+    Invalid { offset: usize, bytes: Vec<u8> },
+}
+
+/// The events produced by one [`FilterMachine::push`] or
+/// [`FilterMachine::finish`], in input order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Output {
+    pub events: Vec<Event>,
+}
+
+impl Output {
+    /// Return the concatenation of all kept and replacement bytes, i.e. the
+    /// filtered output.
+    pub fn kept(&self) -> Vec<u8> {
+        let mut out: Vec<u8> = Vec::new();
+        for event in &self.events {
+            match event {
+                Event::Kept { bytes, .. } | Event::Replaced { bytes, .. } => {
+                    out.extend_from_slice(bytes)
+                }
+                _ => (),
+            }
+        }
+        out
+    }
+
+    /// Append the kept span `bytes` at `offset`, merging it into the previous
+    /// event if that is a contiguous kept span.
+    fn keep(&mut self, offset: usize, bytes: &[u8]) {
+        if let Some(Event::Kept {
+            offset: prev_offset,
+            bytes: prev_bytes,
+        }) = self.events.last_mut()
+        {
+            if *prev_offset + prev_bytes.len() == offset {
+                prev_bytes.extend_from_slice(bytes);
+                return;
+            }
+        }
+        self.events.push(Event::Kept {
+            offset,
+            bytes: bytes.to_vec(),
+        });
+    }
+}
+
+/// The streaming filter state, which decides each input char as the
+/// `Read`/`Write` based filter does with the same [`Options`], except for
+/// the options documented as not applying to it: line ending conversion, tab
+/// expansion, normalization, ANSI stripping, regex rules, emoji handling and
+/// dropping lines. The buffer size does not apply either, since the bytes
+/// are decided as they are pushed.
+pub struct FilterMachine {
+    /// Decides the chars kept.
+    chars: CharFilter,
+    /// If true, keep the chars dropped otherwise, and drop the rest.
+    invert: bool,
+    /// Decodes the bytes pushed, carrying over the start of a multibyte
+    /// char pushed last.
+    utf8: Utf8Decoder,
+    /// The input offset of the first byte not yet decided.
+    offset: usize,
+    /// Called with each dropped or replaced char and its input offset.
+    on_drop: Option<Box<dyn FnMut(char, usize) + Send>>,
+    /// If not `None`, substitute dropped chars with it.
+    replacer: Option<Box<dyn Replacer + Send>>,
+    /// If not `None`, substitute each run of dropped chars left
+    /// unsubstituted with this once, at its first char.
+    collapse: Option<Vec<u8>>,
+    /// If true, the current run of dropped chars is substituted already.
+    collapsing: bool,
+    /// The totals of the bytes decided so far.
+    stats: Stats,
+}
+
+impl FilterMachine {
+    pub fn new(opts: &Options) -> Self {
+        Self {
+            chars: CharFilter::new(opts),
+            invert: opts.invert,
+            utf8: Utf8Decoder::default(),
+            offset: 0,
+            on_drop: None,
+            replacer: replacer(opts)
+                .map(|replacer| Box::new(replacer) as Box<dyn Replacer + Send>),
+            collapse: opts.collapse.clone().map(String::into_bytes),
+            collapsing: false,
+            stats: Stats::default(),
+        }
+    }
+
+    /// Call `on_drop(c, offset)` for each char `c` dropped or replaced at
+    /// input `offset`, in addition to reporting it in the [`Output`].
+    pub fn with_on_drop<F: FnMut(char, usize) + Send + 'static>(
+        mut self,
+        on_drop: F,
+    ) -> Self {
+        self.on_drop = Some(Box::new(on_drop));
+        self
+    }
+
+    /// Substitute each dropped char with what `replacer` writes, reporting it
+    /// as [`Event::Replaced`].
+    pub fn with_replacer<R: Replacer + Send + 'static>(
+        mut self,
+        replacer: R,
+    ) -> Self {
+        self.replacer = Some(Box::new(replacer));
+        self
+    }
+
+    /// Return the totals of the bytes decided so far, as reported in the
+    /// events.
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    /// Feed `input` into the machine. All of it is decided at once, except
+    /// a multibyte char left incomplete at its end, which is decided with the
+    /// next input. Return the first error of the replacer, if any.
+    pub fn push(&mut self, input: &[u8]) -> io::Result<Output> {
+        let mut out = Output::default();
+        let mut utf8 = self.utf8;
+        let decided = utf8.decode(input, |chunk| self.decide(chunk, &mut out));
+        self.utf8 = utf8;
+        decided?;
+        Ok(out)
+    }
+
+    /// Decide the incomplete char left, if any, at the end of input.
+    pub fn finish(&mut self) -> io::Result<Output> {
+        let mut out = Output::default();
+        let rest = self.utf8.finish().to_vec();
+        if !rest.is_empty() {
+            self.decide(Utf8Chunk::Invalid(&rest), &mut out)?;
+        }
+        Ok(out)
+    }
+
+    /// Decide the span `chunk` of the input at `self.offset`.
+    fn decide(&mut self, chunk: Utf8Chunk, out: &mut Output) -> io::Result<()> {
+        let offset = self.offset;
+        let s = match chunk {
+            Utf8Chunk::Valid(s) => s,
+            Utf8Chunk::Invalid(bytes) => {
+                self.stats.bytes_read += bytes.len() as u64;
+                self.stats.invalid_bytes += bytes.len() as u64;
+                for (i, &b) in bytes.iter().enumerate() {
+                    out.events.push(Event::Invalid {
+                        offset: offset + i,
+                        bytes: vec![b],
+                    });
+                }
+                self.offset += bytes.len();
+                return Ok(());
+            }
+        };
+        self.stats.bytes_read += s.len() as u64;
+        self.offset += s.len();
+        let collapse = self.collapse.as_ref().filter(|_| !self.invert);
+        for (k, c) in s.char_indices() {
+            let c_offset = offset + k;
+            if self.chars.keeps(c) != self.invert {
+                self.stats.bytes_written += c.len_utf8() as u64;
+                out.keep(c_offset, &s.as_bytes()[k..k + c.len_utf8()]);
+                self.collapsing = false;
+                continue;
+            }
+            if let Some(on_drop) = &mut self.on_drop {
+                on_drop(c, c_offset);
+            }
+            let mut bytes: Option<Vec<u8>> = None;
+            if let Some(replacer) =
+                self.replacer.as_mut().filter(|_| !self.invert)
+            {
+                let mut replacement: Vec<u8> = Vec::new();
+                // A custom replacer may fail on its own.
+                replacer.replace(c, &mut replacement)?;
+                bytes = Some(replacement);
+            }
+            let bytes = match bytes {
+                Some(bytes) if !bytes.is_empty() => {
+                    self.collapsing = false;
+                    Some(bytes)
+                }
+                bytes => match collapse {
+                    // the run is substituted at its first char
+                    Some(_) if self.collapsing => None,
+                    Some(separator) => {
+                        self.collapsing = true;
+                        Some(separator.clone())
+                    }
+                    None => bytes,
+                },
+            };
+            match bytes {
+                Some(bytes) => {
+                    self.stats.chars_replaced += 1;
+                    self.stats.bytes_written += bytes.len() as u64;
+                    out.events.push(Event::Replaced {
+                        offset: c_offset,
+                        c,
+                        bytes,
+                    })
+                }
+                None => {
+                    self.stats.chars_removed += 1;
+                    out.events.push(Event::Dropped {
+                        offset: c_offset,
+                        c,
+                    })
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Event, FilterMachine};
+    use crate::{Options, Stats};
+    use std::io::{self, Write};
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_filter_machine() {
+        let opts = Options {
+            buf_size: 4,
+            ascii_only: true,
+            ..Options::default()
+        };
+        let mut fm = FilterMachine::new(&opts);
+        // decided at once, but for the start of 你
+        let mut events = fm.push(b"ab\xff\xe4").unwrap();
+        assert_eq!(events.events.len(), 2);
+        events.events.extend(fm.push(b"\xbd\xa0c").unwrap().events);
+        events.events.extend(fm.finish().unwrap().events);
+        assert_eq!(
+            events.events,
+            vec![
+                Event::Kept {
+                    offset: 0,
+                    bytes: b"ab".to_vec()
+                },
+                Event::Invalid {
+                    offset: 2,
+                    bytes: b"\xff".to_vec()
+                },
+                Event::Dropped {
+                    offset: 3, c: '你'
+                },
+                Event::Kept {
+                    offset: 6,
+                    bytes: b"c".to_vec()
+                },
+            ]
+        );
+        assert_eq!(events.kept(), b"abc");
+        assert_eq!(
+            fm.stats(),
+            Stats {
+                bytes_read: 7,
+                bytes_written: 3,
+                chars_removed: 1,
+                chars_replaced: 0,
+                invalid_bytes: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_filter_machine_small_buf_size() {
+        let opts = Options {
+            buf_size: 0,
+            ascii_only: true,
+            ..Options::default()
+        };
+        let mut fm = FilterMachine::new(&opts);
+        let mut out = fm.push("a你b好".as_bytes()).unwrap().kept();
+        out.extend(fm.finish().unwrap().kept());
+        assert_eq!(out, b"ab");
+    }
+
+    #[test]
+    fn test_filter_machine_on_drop() {
+        let opts = Options {
+            ascii_only: true,
+            ..Options::default()
+        };
+        let dropped = Arc::new(Mutex::new(Vec::new()));
+        let dropped_clone = Arc::clone(&dropped);
+        let mut fm =
+            FilterMachine::new(&opts).with_on_drop(move |c, offset| {
+                dropped_clone.lock().unwrap().push((c, offset))
+            });
+        fm.push("a你b好".as_bytes()).unwrap();
+        fm.finish().unwrap();
+        assert_eq!(*dropped.lock().unwrap(), vec![('你', 1), ('好', 5)]);
+    }
+
+    #[test]
+    fn test_filter_machine_replacer() {
+        let opts = Options {
+            ascii_only: true,
+            ..Options::default()
+        };
+        let mut fm = FilterMachine::new(&opts).with_replacer(
+            |_: char, out: &mut dyn Write| -> io::Result<()> {
+                out.write_all(b"?")
+            },
+        );
+        let mut events = fm.push("a你".as_bytes()).unwrap();
+        events.events.extend(fm.finish().unwrap().events);
+        assert_eq!(
+            events.events[1],
+            Event::Replaced {
+                offset: 1,
+                c: '你',
+                bytes: b"?".to_vec()
+            }
+        );
+        assert_eq!(events.kept(), b"a?");
+    }
+
+    #[test]
+    fn test_filter_machine_replacer_error() {
+        let opts = Options {
+            ascii_only: true,
+            ..Options::default()
+        };
+        let mut fm = FilterMachine::new(&opts).with_replacer(
+            |_: char, _: &mut dyn Write| -> io::Result<()> {
+                Err(io::Error::other("no replacement"))
+            },
+        );
+        assert!(fm.push("a".as_bytes()).is_ok());
+        assert!(fm.push("你".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_filter_machine_collapse() {
+        let opts = Options {
+            ascii_only: true,
+            collapse: Some(" ".to_string()),
+            ..Options::default()
+        };
+        let mut fm = FilterMachine::new(&opts);
+        let mut events = fm.push("abc你".as_bytes()).unwrap();
+        events
+            .events
+            .extend(fm.push("好def".as_bytes()).unwrap().events);
+        events.events.extend(fm.finish().unwrap().events);
+        assert_eq!(
+            events.events[1..3],
+            [
+                Event::Replaced {
+                    offset: 3,
+                    c: '你',
+                    bytes: b" ".to_vec()
+                },
+                Event::Dropped {
+                    offset: 6, c: '好'
+                },
+            ]
+        );
+        assert_eq!(events.kept(), b"abc def");
+    }
+}
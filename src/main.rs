@@ -1,18 +1,2008 @@
-use clap::Parser;
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fs::File;
+use std::io::{self, IsTerminal};
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
-use ascii_filter::stdin_stdout_buffer_filter;
+use clap::parser::ValueSource;
+use clap::Subcommand;
+use clap::{Arg, ArgAction, CommandFactory, FromArgMatches, Parser};
+use regex::Regex;
 
-#[derive(Parser, Debug)]
+use ascii_filter::mapping::{
+    parse_rules, parse_tsv, ParseRulesError, UNITS_RULES,
+};
+#[cfg(feature = "unicode-category")]
+use ascii_filter::tables::GeneralCategory;
+#[cfg(feature = "unicode-script")]
+use ascii_filter::tables::Script;
+#[cfg(feature = "unicode-normalization")]
+use ascii_filter::Normalization;
+use ascii_filter::{
+    caught_signal, detect, end_input_on_signals, filter_files, filter_in_place,
+    filter_tree, filter_tree_in_place, open_output, restore, selftest, serve,
+    stdin_stdout_buffer_filter, watch_file, CharSet, Checkpoint, Compression,
+    ControlEscape, EmojiMode, Encoding, Endpoint, Error, Escape, Format,
+    HardLinks, Histogram, InvalidUtf8, LegacyCharset, Locale, Mapping,
+    Newlines, Options, Overlong, Profile, ReportFormat, Skipped, Stats,
+};
+
+mod bench;
+mod clipboard;
+mod completions;
+mod config;
+mod exit;
+mod git_filter;
+mod lint;
+#[cfg(feature = "tui")]
+mod tui;
+mod verbose;
+
+#[derive(Parser, Debug, Clone)]
 struct App {
-    /// Specify the buffer size, which default to 128.
-    #[clap(short = 'b', value_name = "BUFFER_SIZE", default_value_t = 128)]
+    /// Read the defaults of the options from FILE instead of
+    /// ~/.config/ascii-filter/config.toml.
+    #[clap(long = "config", value_name = "FILE")]
+    config: Option<PathBuf>,
+    /// Apply the named profile of the config file on top of its top-level
+    /// settings.
+    #[clap(long = "profile", value_name = "NAME")]
+    profile: Option<String>,
+    /// Specify the buffer size, which default to 128, in bytes or with a
+    /// binary unit, e.g. 64K, 1M or 4MiB, and at least 4.
+    #[clap(
+        short = 'b',
+        long = "buffer-size",
+        value_name = "BUFFER_SIZE",
+        default_value = "128",
+        value_parser = parse_size
+    )]
     buf_size: usize,
     /// To pass through a subset of ASCII characters only.
-    #[clap(short = 'a', default_value_t = false)]
+    #[clap(short = 'a', long = "ascii-only", default_value_t = false)]
     ascii_only: bool,
+    /// Keep the chars in SPEC instead of the ASCII subset, implying -a. SPEC
+    /// is a comma-separated list of chars and ranges, given literally, as
+    /// escapes (\t, \n, \r, \0, \\, \, and \-), or as hex codepoints, e.g.
+    /// `0x20-0x7E,\t,\n,\r` or `a-z,U+00E9`.
+    #[clap(long = "allow", value_name = "SPEC")]
+    allow: Option<CharSet>,
+    /// Keep the chars in STR too, implying -a, e.g. `абв`.
+    #[clap(
+        long = "allow-chars",
+        value_name = "STR",
+        allow_hyphen_values = true
+    )]
+    allow_chars: Option<String>,
+    /// Keep the printable chars of ISO-8859-1 and ISO-8859-15 too, implying
+    /// -a, i.e. the accented letters of Western European languages, symbols
+    /// like £, ½ and €, and the no-break space.
+    #[clap(long = "keep-latin1", default_value_t = false)]
+    keep_latin1: bool,
+    /// Keep the chars encodable in CHARSET too, implying -a, but its control
+    /// chars, without encoding the output, e.g. `iso-8859-15` or `cp437`
+    /// for a legacy printing system.
+    #[clap(long = "encodable-in", value_name = "CHARSET")]
+    encodable_in: Option<LegacyCharset>,
+    /// Keep the chars of the general categories in LIST too, implying -a,
+    /// e.g. `L,N` for the letters and digits of any script. A major class
+    /// like `L` stands for all of its categories.
+    #[cfg(feature = "unicode-category")]
+    #[clap(
+        long = "keep-categories",
+        value_name = "LIST",
+        value_parser = GeneralCategory::parse_list
+    )]
+    // spelled out so that clap takes the parsed list as one value
+    keep_categories: Option<::std::vec::Vec<GeneralCategory>>,
+    /// Drop the chars of the general categories in LIST, even if kept
+    /// otherwise, e.g. `Cf,Co` for format and private-use chars. Note that
+    /// tab and newline are of Cc.
+    #[cfg(feature = "unicode-category")]
+    #[clap(
+        long = "drop-categories",
+        value_name = "LIST",
+        value_parser = GeneralCategory::parse_list
+    )]
+    // spelled out so that clap takes the parsed list as one value
+    drop_categories: Option<::std::vec::Vec<GeneralCategory>>,
+    /// Keep the chars of the scripts in LIST too, implying -a, e.g.
+    /// `Latin,Greek,Common`. Digits and most punctuation are of Common.
+    #[cfg(feature = "unicode-script")]
+    #[clap(
+        long = "keep-script",
+        value_name = "LIST",
+        value_parser = Script::parse_list
+    )]
+    // spelled out so that clap takes the parsed list as one value
+    keep_scripts: Option<::std::vec::Vec<Script>>,
+    /// Drop invisible chars, e.g. zero-width spaces and joiners, BOMs, soft
+    /// hyphens and bidi controls, with or without -a.
+    #[clap(long = "strip-invisible", default_value_t = false)]
+    strip_invisible: bool,
+    /// Keep carriage returns too, with or without -a, e.g. those of CRLF
+    /// files.
+    #[clap(long = "keep-cr", default_value_t = false)]
+    keep_cr: bool,
+    /// Keep the control chars in STR too, with or without -a, given
+    /// literally or as escapes (\a, \b, \t, \n, \v, \f, \r, \e, \0 or
+    /// \xNN), e.g. `\r\x0c\x07` for CR, form feed and bell.
+    #[clap(
+        long = "keep-controls",
+        value_name = "STR",
+        value_parser = CharSet::parse_controls
+    )]
+    keep_controls: Option<CharSet>,
+    /// Convert the line endings of the output: "lf" converts CRLF to LF,
+    /// "crlf" converts LF to CRLF, and "keep" keeps both as they are, even
+    /// with -a. A CR which ends no line is filtered as usual.
+    #[clap(long = "newlines", value_name = "MODE")]
+    newlines: Option<Newlines>,
+    /// Decompress the input before filtering if it is gzip, e.g.
+    /// `ascii-filter --decompress app.log.gz`. Zstd and xz input is
+    /// recognized, but not supported.
+    #[clap(
+        long = "decompress",
+        default_value_t = false,
+        conflicts_with_all = [
+            "resume_state", "checkpoint_fd", "checkpoint_file"
+        ]
+    )]
+    decompress: bool,
+    /// Compress the output in FORMAT, by default that of the extension of
+    /// the -o FILE, e.g. `.gz`. Only gzip is supported; zstd and xz fail.
+    #[clap(
+        long = "compress",
+        value_name = "FORMAT",
+        conflicts_with_all = [
+            "resume_state", "checkpoint_fd", "checkpoint_file"
+        ]
+    )]
+    compress: Option<Compression>,
+    /// Decode the input from ENCODING before filtering: UTF-16LE, UTF-16BE,
+    /// UTF-32LE, UTF-32BE or LATIN1, which is decoded as Windows-1252. A BOM
+    /// at its start is dropped. UTF-8 and ASCII are filtered as they are.
+    /// Other encodings, e.g. Shift_JIS, are not supported; decode them with
+    /// `iconv -t UTF-8` first.
+    #[clap(
+        long = "from-encoding",
+        value_name = "ENCODING",
+        conflicts_with_all = [
+            "resume_state", "checkpoint_fd", "checkpoint_file"
+        ]
+    )]
+    from_encoding: Option<Encoding>,
+    /// Encode the output in ENCODING, one of those of --from-encoding, or
+    /// ASCII. The chars it has no code for are encoded as "?", so that with
+    /// -a none are. --write-bom writes the BOM of UTF-16 and UTF-32 only.
+    #[clap(
+        long = "to-encoding",
+        value_name = "ENCODING",
+        conflicts_with_all = [
+            "resume_state", "checkpoint_fd", "checkpoint_file"
+        ]
+    )]
+    to_encoding: Option<Encoding>,
+    /// Handle the invalid utf-8 sequences of the input: "drop" them, decode
+    /// each as U+FFFD with "replace" or its bytes as \xNN with "escape",
+    /// which is then filtered as usual, or fail at the first with "error".
+    #[clap(
+        long = "invalid-utf8",
+        value_name = "POLICY",
+        default_value = "drop"
+    )]
+    invalid_utf8: InvalidUtf8,
+    /// Expand tabs to spaces up to the next multiple of WIDTH columns, 8 by
+    /// default, as `expand` does, e.g. `--expand-tabs=4`.
+    #[clap(
+        long = "expand-tabs",
+        value_name = "WIDTH",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "8",
+        value_parser = parse_tab_width
+    )]
+    expand_tabs: Option<usize>,
+    /// Bound the output lines to N chars each, with their -n numbers and -H
+    /// paths, cutting a longer line by default, e.g. for a legacy consumer
+    /// of logs. Applies to text input only.
+    #[clap(
+        long = "max-line-length",
+        value_name = "N",
+        value_parser = parse_line_length,
+        conflicts_with_all = [
+            "logfmt", "syslog", "journal_export", "docker_json", "json",
+            "csv", "tsv", "markdown"
+        ]
+    )]
+    max_line_length: Option<usize>,
+    /// Cut each line longer than --max-line-length at it, the default.
+    #[clap(
+        long = "truncate",
+        default_value_t = false,
+        group = "overlong",
+        requires = "max_line_length"
+    )]
+    truncate: bool,
+    /// Break each line longer than --max-line-length into lines of it.
+    #[clap(
+        long = "wrap",
+        default_value_t = false,
+        group = "overlong",
+        requires = "max_line_length"
+    )]
+    wrap: bool,
+    /// Omit each line longer than --max-line-length.
+    #[clap(
+        long = "drop",
+        default_value_t = false,
+        group = "overlong",
+        requires = "max_line_length"
+    )]
+    drop: bool,
+    /// Output only the chars -a drops instead, unconverted, e.g. to audit
+    /// the foreign content of a corpus. Invalid bytes are still dropped.
+    #[clap(long = "invert", default_value_t = false)]
+    invert: bool,
+    /// Prepend a utf-8 byte order mark to the output.
+    #[clap(
+        long = "write-bom",
+        visible_alias = "add-bom",
+        default_value_t = false
+    )]
+    write_bom: bool,
+    /// Drop a byte order mark at the start of the input, which is otherwise
+    /// kept or dropped as the char U+FEFF, e.g. dropped with -a.
+    #[clap(long = "strip-bom", default_value_t = false)]
+    strip_bom: bool,
+    /// Terminate output records with NUL instead of newline.
+    #[clap(long = "out-null", default_value_t = false)]
+    out_null: bool,
+    /// Terminate output records with the given string instead of newline.
+    #[clap(
+        long = "out-record-sep",
+        value_name = "STR",
+        allow_hyphen_values = true,
+        conflicts_with = "out_null"
+    )]
+    out_record_sep: Option<String>,
+    /// Take NUL as the end of a line instead of newline, and keep it, e.g.
+    /// for `find -print0 | ascii-filter -a -0 | xargs -0`. Applies to text
+    /// input only.
+    #[clap(
+        short = '0',
+        long = "null",
+        default_value_t = false,
+        conflicts_with_all = [
+            "out_record_sep", "logfmt", "syslog", "journal_export",
+            "docker_json", "json", "csv", "tsv", "markdown"
+        ]
+    )]
+    null: bool,
+    /// Treat the input as logfmt, and filter only the values.
+    #[clap(long = "logfmt", default_value_t = false)]
+    logfmt: bool,
+    /// Treat the input as RFC 3164/5424 syslog messages, and filter only the
+    /// MSG part.
+    #[clap(
+        long = "syslog",
+        default_value_t = false,
+        conflicts_with = "logfmt"
+    )]
+    syslog: bool,
+    /// Treat the input as the systemd journal export format, and filter only
+    /// the textual field values.
+    #[clap(
+        long = "journal-export",
+        default_value_t = false,
+        conflicts_with_all = ["logfmt", "syslog"]
+    )]
+    journal_export: bool,
+    /// Treat the input as Docker/Kubernetes JSON log lines, and filter only
+    /// the `log` field.
+    #[clap(
+        long = "docker-json",
+        default_value_t = false,
+        conflicts_with_all = ["logfmt", "syslog", "journal_export"]
+    )]
+    docker_json: bool,
+    /// Treat the input as a stream of JSON values, e.g. a document or JSON
+    /// lines, and filter only the string values, re-escaping them, so that
+    /// the output stays valid JSON.
+    #[clap(
+        long = "json",
+        default_value_t = false,
+        conflicts_with_all = [
+            "logfmt", "syslog", "journal_export", "docker_json"
+        ]
+    )]
+    json: bool,
+    /// Filter the keys of objects too with --json.
+    #[clap(long = "json-keys", default_value_t = false, requires = "json")]
+    json_keys: bool,
+    /// Treat the input as CSV, and filter only the fields of --columns,
+    /// keeping the delimiters and the quoting, and quoting a field once
+    /// filtered if need be.
+    #[clap(
+        long = "csv",
+        default_value_t = false,
+        group = "table",
+        conflicts_with_all = [
+            "logfmt", "syslog", "journal_export", "docker_json", "json"
+        ]
+    )]
+    csv: bool,
+    /// Treat the input as TSV, as --csv does with tabs between the fields.
+    #[clap(
+        long = "tsv",
+        default_value_t = false,
+        group = "table",
+        conflicts_with_all = [
+            "logfmt", "syslog", "journal_export", "docker_json", "json", "csv"
+        ]
+    )]
+    tsv: bool,
+    /// Filter only the columns in LIST with --csv or --tsv, counted from 1,
+    /// e.g. `2,5` or `2-4,7`, instead of all of them.
+    #[clap(
+        long = "columns",
+        value_name = "LIST",
+        requires = "table",
+        value_parser = parse_columns
+    )]
+    // spelled out so that clap takes the parsed list as one value
+    columns: Option<::std::vec::Vec<usize>>,
+    /// Treat the input as Markdown, and filter only the prose, keeping the
+    /// fenced code blocks and the inline code spans byte for byte.
+    #[clap(
+        long = "markdown",
+        default_value_t = false,
+        conflicts_with_all = [
+            "logfmt", "syslog", "journal_export", "docker_json", "json", "csv",
+            "tsv"
+        ]
+    )]
+    markdown: bool,
+    /// Normalize the input to FORM (nfc, nfd, nfkc, nfkd) before filtering,
+    /// e.g. nfkc, which folds ligatures like ﬁ and full-width digits into
+    /// ASCII.
+    #[cfg(feature = "unicode-normalization")]
+    #[clap(long = "normalize", value_name = "FORM")]
+    normalize: Option<Normalization>,
+    /// Strip ANSI escape sequences, e.g. color codes, as whole units, instead
+    /// of leaving fragments like `[31m` behind.
+    #[clap(long = "strip-ansi", default_value_t = false)]
+    strip_ansi: bool,
+    /// Drop emoji, keep them even with -a, or replace them with shortcodes
+    /// like `:+1:`, as whole sequences, e.g. 👍🏽 or 👩‍💻.
+    #[clap(long = "emoji", value_name = "MODE")]
+    emoji: Option<EmojiMode>,
+    /// Filter by grapheme clusters: keep a cluster of several chars, e.g.
+    /// 👩‍💻 or e plus a combining accent, if all its chars are kept, or drop
+    /// and replace it as a whole, leaving no ZWJ or combining mark dangling.
+    #[cfg(feature = "unicode-segmentation")]
+    #[clap(long = "graphemes", default_value_t = false)]
+    graphemes: bool,
+    /// Drop what the regex RE matches in each line, after normalizing, e.g.
+    /// `[\x{1F300}-\x{1FAFF}]` for most emoji. A line is matched without its
+    /// newline.
+    #[clap(long = "drop-regex", value_name = "RE")]
+    drop_regex: Option<Regex>,
+    /// Keep what the regex RE matches in each line as is, unless matched by
+    /// --drop-regex, instead of filtering it, e.g. `\p{Greek}+`.
+    #[clap(long = "keep-regex", value_name = "RE")]
+    keep_regex: Option<Regex>,
+    /// Convert hostnames with non-ASCII labels in each line to punycode,
+    /// e.g. bücher.de to xn--bcher-kva.de, instead of filtering them.
+    #[clap(long = "punycode", default_value_t = false)]
+    punycode: bool,
+    /// Transliterate dropped chars per the conventions of the given language
+    /// (da, de, is, nb, sv, tr), e.g. German ö to oe.
+    #[clap(long = "translit-locale", value_name = "LOCALE")]
+    translit_locale: Option<Locale>,
+    /// Transliterate dropped chars per the rules in the given file, written in
+    /// a subset of the ICU transform syntax (e.g. `ü > ue;`). The rules take
+    /// precedence over the other transliterations.
+    #[clap(long = "translit-rules", value_name = "FILE")]
+    translit_rules: Vec<PathBuf>,
+    /// Convert dropped chars per the table in the given file, a TSV of a
+    /// char, literal or as a codepoint like U+00B5, and its replacement per
+    /// line (e.g. `°<TAB>deg`). The table takes precedence over the other
+    /// conversions.
+    #[clap(long = "map", value_name = "FILE")]
+    map: Vec<PathBuf>,
+    /// Spell out common symbols, e.g. © as (c) and ° as deg.
+    #[clap(long = "symbols", default_value_t = false)]
+    symbols: bool,
+    /// Convert arrows to ASCII, e.g. → to -> and ⇒ to =>.
+    #[clap(long = "arrows", default_value_t = false)]
+    arrows: bool,
+    /// Convert box-drawing chars to ASCII, i.e. `-`, `|` and `+`.
+    #[clap(long = "box-drawing", default_value_t = false)]
+    box_drawing: bool,
+    /// Convert bullets and middle dots to `*` or `-` list markers.
+    #[clap(long = "bullets", default_value_t = false)]
+    bullets: bool,
+    /// Convert Roman numerals like Ⅳ to ASCII letters like IV.
+    #[clap(long = "roman", default_value_t = false)]
+    roman: bool,
+    /// Convert circled, parenthesized and squared alphanumerics, e.g. ① to
+    /// (1).
+    #[clap(long = "enclosed", default_value_t = false)]
+    enclosed: bool,
+    /// Convert full-width letters, digits and punctuation, as typed with CJK
+    /// input methods, to ASCII, e.g. Ａ１， to A1,.
+    #[clap(long = "full-width", default_value_t = false)]
+    full_width: bool,
+    /// Spell out Greek letters, e.g. π to pi and Δ to Delta. Takes
+    /// precedence over --symbols, so that µ becomes mu.
+    #[clap(long = "greek", default_value_t = false)]
+    greek: bool,
+    /// Convert mathematical operators, e.g. × to x, ≤ to <= and ± to +/-.
+    #[clap(long = "math", default_value_t = false)]
+    math: bool,
+    /// Spell out unit symbols, e.g. ℃ to degC, Å to A and ‰ to 0/00. To extend
+    /// the table, pass rules with --translit-rules, which take precedence.
+    #[clap(long = "units", default_value_t = false)]
+    units: bool,
+    /// Convert typographic punctuation to ASCII, e.g. curly quotes to ' and ",
+    /// — to -- and … to ...
+    #[clap(long = "punctuation", default_value_t = false)]
+    punctuation: bool,
+    /// Convert Unicode spaces, e.g. no-break, thin and ideographic spaces, to
+    /// space, and line and paragraph separators to '\n', instead of dropping
+    /// them, which glues words together.
+    #[clap(long = "spaces", default_value_t = false)]
+    spaces: bool,
+    /// Convert the chars not converted by the options above to their closest
+    /// ASCII approximations, e.g. é to e, ß to ss and “ to ".
+    #[clap(long = "transliterate", default_value_t = false)]
+    transliterate: bool,
+    /// Fold the lookalikes of ASCII chars not converted by the options above
+    /// into them, e.g. Cyrillic а to a and Greek ο to o, to collapse
+    /// homoglyph spoofing in names and URLs.
+    #[cfg(feature = "unicode-confusables")]
+    #[clap(long = "confusables", default_value_t = false)]
+    confusables: bool,
+    /// Strip the diacritics of the chars not converted by the options above,
+    /// by canonical decomposition, e.g. café to cafe rather than caf.
+    #[cfg(feature = "unicode-normalization")]
+    #[clap(long = "strip-diacritics", default_value_t = false)]
+    strip_diacritics: bool,
+    /// Substitute each char dropped and not converted by the options above
+    /// with STR, e.g. `?`, instead of removing it, so that columns stay
+    /// aligned.
+    #[clap(long = "replace", value_name = "STR", allow_hyphen_values = true)]
+    replace: Option<String>,
+    /// Write the --replace STR at most N times, 1 if not given, for each run
+    /// of chars replaced with it, e.g. `?` rather than `????????` for a word
+    /// in another script.
+    #[clap(
+        long = "squeeze-replacements",
+        value_name = "N",
+        num_args = 0..=1,
+        default_missing_value = "1",
+        value_parser = parse_squeeze,
+        requires = "replace"
+    )]
+    squeeze_replacements: Option<usize>,
+    /// Substitute each char dropped and not converted by the options above
+    /// with its escape in STYLE instead, e.g. `\u{4F60}` for "unicode", the
+    /// default, `&#x4F60;` for "html", `\u4f60` for "json", with surrogate
+    /// pairs beyond the BMP, `%E4%BD%A0` for "url", or `=E4=BD=A0` for "qp",
+    /// which encodes the whole output as quoted-printable, with soft line
+    /// breaks, so that the output stays reversible and diff-able. The STYLE
+    /// is given as `--escape=html`.
+    #[clap(
+        long = "escape",
+        value_name = "STYLE",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "unicode",
+        conflicts_with = "replace"
+    )]
+    escape: Option<Escape>,
+    /// Substitute each run of chars dropped and not converted by the options
+    /// above with STR once, e.g. ' ', so that `abc你好def` becomes `abc def`
+    /// instead of `abcdef`.
+    #[clap(
+        long = "collapse",
+        value_name = "STR",
+        allow_hyphen_values = true,
+        conflicts_with_all = ["replace", "escape"]
+    )]
+    collapse: Option<String>,
+    /// Write SEP, e.g. ' ' or '-', where the chars dropped and not converted
+    /// by the options above sit between two alphanumeric chars, so that
+    /// `naïve` becomes `na ve` and `foo—bar` becomes `foo bar` instead of
+    /// `nave` and `foobar`.
+    #[clap(
+        long = "word-sep",
+        value_name = "SEP",
+        allow_hyphen_values = true,
+        conflicts_with_all = ["replace", "escape", "collapse"]
+    )]
+    word_sep: Option<String>,
+    /// Omit each line containing any char dropped and not converted by the
+    /// options above, instead of dropping the chars alone.
+    #[clap(
+        long = "drop-lines",
+        default_value_t = false,
+        conflicts_with_all = [
+            "replace", "escape", "collapse", "word_sep", "invert"
+        ]
+    )]
+    drop_lines: bool,
+    /// Prefix each output line with the number of its line of input, as
+    /// `cat -n` does, e.g. to find the lines in the source after filtering.
+    /// Applies to text input only.
+    #[clap(
+        short = 'n',
+        long = "line-numbers",
+        default_value_t = false,
+        conflicts_with_all = [
+            "logfmt", "syslog", "journal_export", "docker_json", "json",
+            "csv", "tsv", "markdown", "resume_state"
+        ]
+    )]
+    line_numbers: bool,
+    /// Prefix each output line with the path of its file and `:`, as `grep
+    /// -H` does, when filtering files. Applies to text input only.
+    #[clap(
+        short = 'H',
+        long = "with-filename",
+        default_value_t = false,
+        overrides_with = "no_filename"
+    )]
+    with_filename: bool,
+    /// Leave the paths out of the output lines, undoing -H, e.g. from the
+    /// config, and out of the lines of `check` and `report` too.
+    #[clap(
+        long = "no-filename",
+        default_value_t = false,
+        overrides_with = "with_filename"
+    )]
+    no_filename: bool,
+    /// Keep each char dropped otherwise, highlighted in reverse video, to
+    /// audit where the filter would drop chars before filtering. With WHEN
+    /// "auto", the default, stdout must be a terminal; "always" allows e.g.
+    /// `| less -R`.
+    #[clap(
+        long = "highlight",
+        value_name = "WHEN",
+        num_args = 0..=1,
+        default_missing_value = "auto",
+        value_parser = ["auto", "always"],
+        conflicts_with_all = [
+            "replace", "escape", "collapse", "word_sep", "drop_lines",
+            "invert"
+        ]
+    )]
+    highlight: Option<String>,
+    /// Substitute each control char dropped, e.g. ESC, with its escape in
+    /// STYLE, "caret", the default, for `^[` or "hex" for `\x1B`, before
+    /// the options above, so that diagnostic output stays inspectable.
+    #[clap(
+        long = "escape-controls",
+        value_name = "STYLE",
+        num_args = 0..=1,
+        default_missing_value = "caret"
+    )]
+    escape_controls: Option<ControlEscape>,
+    /// Print the built-in table of --units as --translit-rules rules, and
+    /// exit.
+    #[clap(long = "print-units-rules", default_value_t = false)]
+    print_units_rules: bool,
+    /// Compare the output with a reference decoding of the input by
+    /// `String::from_utf8_lossy` while filtering, and fail with exit code 1 on
+    /// any divergence. Applies to text input only.
+    #[clap(
+        long = "verify",
+        default_value_t = false,
+        conflicts_with_all = ["logfmt", "syslog", "journal_export", "docker_json"]
+    )]
+    verify: bool,
+    /// Filter the output a second time while filtering, and fail with exit
+    /// code 1 if that changes anything. Applies to text input only.
+    #[clap(
+        long = "check-idempotent",
+        default_value_t = false,
+        conflicts_with_all = ["logfmt", "syslog", "journal_export", "docker_json"]
+    )]
+    check_idempotent: bool,
+    /// Fail with exit code 1 and the byte offset if the filter changes
+    /// anything in the input, so that clean input is guaranteed to pass
+    /// through byte-exact. Applies to text input only.
+    #[clap(
+        long = "assert-clean",
+        default_value_t = false,
+        conflicts_with_all = ["logfmt", "syslog", "journal_export", "docker_json"]
+    )]
+    assert_clean: bool,
+    /// Fail with exit code 1 at the first char the filter drops or
+    /// replaces, or invalid UTF-8 sequence, naming it and its LINE:COLUMN,
+    /// e.g. to validate machine-generated files rather than repair them.
+    /// Applies to text input only.
+    #[clap(
+        long = "strict",
+        default_value_t = false,
+        conflicts_with_all = [
+            "invert", "logfmt", "syslog", "journal_export", "docker_json"
+        ]
+    )]
+    strict: bool,
+    /// Print the totals of bytes read, bytes written, chars removed, chars
+    /// replaced and invalid UTF-8 bytes to stderr at the end.
+    #[clap(long = "stats", default_value_t = false)]
+    stats: bool,
+    /// Print the N chars removed or replaced most, with their counts and
+    /// names, to stderr at the end.
+    #[clap(long = "stats-top", value_name = "N", value_parser = parse_stats_top)]
+    stats_top: Option<usize>,
+    /// Log to stderr the summary of each file and the throughput with -v,
+    /// the decisions, e.g. of the encodings, with -vv, and each char
+    /// dropped with -vvv, e.g. to find out why a char was removed.
+    #[clap(short = 'v', long = "verbose", action = ArgAction::Count)]
+    verbose: u8,
+    /// Append each char dropped to FILE, e.g. as an audit trail of what the
+    /// filter removed. Applies to text input only.
+    #[clap(
+        long = "rejects",
+        value_name = "FILE",
+        conflicts_with_all = [
+            "logfmt", "syslog", "journal_export", "docker_json",
+            "clean_output", "dirty_output"
+        ]
+    )]
+    rejects: Option<PathBuf>,
+    /// Write each char to --rejects on a line of its own with its position,
+    /// as `LINE:COLUMN: U+XXXX c`, instead of as is.
+    #[clap(
+        long = "reject-positions",
+        default_value_t = false,
+        requires = "rejects"
+    )]
+    reject_positions: bool,
+    /// Append a hex dump of each char dropped, and of each invalid UTF-8
+    /// sequence, to FILE, as `OFFSET: XX XX  U+XXXX c` or `OFFSET: XX XX
+    /// invalid`, e.g. to find where binary garbage enters a log. Applies to
+    /// text input only.
+    #[clap(
+        long = "rejects-hex",
+        value_name = "FILE",
+        conflicts_with_all = [
+            "logfmt", "syslog", "journal_export", "docker_json",
+            "clean_output", "dirty_output"
+        ]
+    )]
+    rejects_hex: Option<PathBuf>,
+    /// Write a diagnostic per char dropped to stderr, or to the file given
+    /// by --report-output, in FORMAT: jsonl writes a JSON object per line
+    /// with the file, line, column, byte offset, codepoint and action taken,
+    /// e.g. for dashboards and editor integrations. Applies to text input
+    /// only.
+    #[clap(
+        long = "report",
+        value_name = "FORMAT",
+        conflicts_with_all = [
+            "logfmt", "syslog", "journal_export", "docker_json",
+            "clean_output", "dirty_output"
+        ]
+    )]
+    report: Option<ReportFormat>,
+    /// Append the diagnostics of --report to FILE instead of stderr.
+    #[clap(long = "report-output", value_name = "FILE", requires = "report")]
+    report_output: Option<PathBuf>,
+    /// Write an undo log of the changes to FILE, replacing it, a line
+    /// `OFFSET LEN HEX` per run of chars removed or replaced, invalid UTF-8
+    /// bytes dropped or BOM stripped, from which `restore` restores the
+    /// original. Applies to a single text input, filtered by the char filter,
+    /// its substitutions and the regex rules only.
+    #[clap(
+        long = "undo-log",
+        value_name = "FILE",
+        conflicts_with_all = [
+            "logfmt", "syslog", "journal_export", "docker_json", "json",
+            "csv", "tsv", "markdown", "clean_output", "dirty_output",
+            "recursive", "listen"
+        ]
+    )]
+    undo_log: Option<PathBuf>,
+    /// Write a source map of the output to FILE, replacing it, a line per
+    /// segment of the output, `N` for N bytes output as they are input, and
+    /// `OUT IN` for OUT bytes, none if removed, standing for IN bytes of the
+    /// input, to translate the positions in the output back to the input.
+    /// Applies as --undo-log does.
+    #[clap(
+        long = "source-map",
+        value_name = "FILE",
+        conflicts_with_all = [
+            "logfmt", "syslog", "journal_export", "docker_json", "json",
+            "csv", "tsv", "markdown", "clean_output", "dirty_output",
+            "recursive", "listen"
+        ]
+    )]
+    source_map: Option<PathBuf>,
+    /// Record the progress in FILE every second, and resume from it
+    /// if it exists, e.g. after an interruption; FILE is removed once done.
+    /// The output is written exactly once if it is seekable, e.g. with
+    /// `>> out` or `1<> out`. Applies to text input only.
+    #[clap(
+        long = "resume-state",
+        value_name = "FILE",
+        conflicts_with_all = [
+            "logfmt", "syslog", "journal_export", "docker_json", "verify",
+            "check_idempotent", "assert_clean"
+        ]
+    )]
+    resume_state: Option<PathBuf>,
+    /// Write the input offset consumed so far as a line to file descriptor N
+    /// every second and at the end, once the output before it is flushed,
+    /// e.g. for log shippers to commit their read positions. Applies to text
+    /// input only.
+    #[clap(
+        long = "checkpoint-fd",
+        value_name = "N",
+        conflicts_with_all = [
+            "logfmt", "syslog", "journal_export", "docker_json", "verify",
+            "check_idempotent", "assert_clean"
+        ]
+    )]
+    checkpoint_fd: Option<i32>,
+    /// Like --checkpoint-fd, but replace FILE with the latest input offset.
+    #[clap(
+        long = "checkpoint-file",
+        value_name = "FILE",
+        conflicts_with_all = [
+            "checkpoint_fd", "logfmt", "syslog", "journal_export",
+            "docker_json", "verify", "check_idempotent", "assert_clean"
+        ]
+    )]
+    checkpoint_file: Option<PathBuf>,
+    /// Write the lines the filter leaves unchanged to FILE instead of stdout,
+    /// e.g. to pass clean traffic through while quarantining the rest with
+    /// --dirty-output. Applies to line-based input only.
+    #[clap(
+        long = "clean-output",
+        value_name = "FILE",
+        conflicts_with_all = [
+            "journal_export", "verify", "check_idempotent", "assert_clean",
+            "resume_state", "checkpoint_fd", "checkpoint_file"
+        ]
+    )]
+    clean_output: Option<PathBuf>,
+    /// Write the lines the filter changes, filtered, to FILE instead of
+    /// stdout.
+    #[clap(
+        long = "dirty-output",
+        value_name = "FILE",
+        conflicts_with_all = [
+            "journal_export", "verify", "check_idempotent", "assert_clean",
+            "resume_state", "checkpoint_fd", "checkpoint_file"
+        ]
+    )]
+    dirty_output: Option<PathBuf>,
+    /// Filter the files under the PATHs, recursively and in order, to stdout
+    /// instead of stdin, skipping binary files. Symbolic links are not
+    /// followed.
+    #[clap(
+        short = 'r',
+        long = "recursive",
+        default_value_t = false,
+        conflicts_with_all = [
+            "resume_state", "checkpoint_fd", "checkpoint_file",
+            "clean_output", "dirty_output"
+        ]
+    )]
+    recursive: bool,
+    /// Filter binary files too in --recursive runs, which are otherwise
+    /// sniffed by their magic numbers and NUL bytes, skipped and reported.
+    #[clap(long = "no-skip-binary", default_value_t = false)]
+    no_skip_binary: bool,
+    /// Filter the files ignored by .gitignore, .ignore and the like too in
+    /// --recursive runs, which are otherwise left out.
+    #[clap(long = "no-ignore", default_value_t = false)]
+    no_ignore: bool,
+    /// Filter hidden files and directories, e.g. .git, too in --recursive
+    /// runs, which are otherwise left out, even with --no-ignore.
+    #[clap(long = "hidden", default_value_t = false)]
+    hidden: bool,
+    /// Follow symbolic links in --recursive runs, within the PATHs only.
+    /// Links that loop, dangle or lead out of the PATHs are skipped and
+    /// reported.
+    #[clap(long = "follow-symlinks", default_value_t = false)]
+    follow_symlinks: bool,
+    /// How --recursive runs treat a file reachable by several paths, through
+    /// hard links or followed symbolic links: filter it once by the first
+    /// path ("preserve"), or by each path as a file of its own ("break").
+    /// With --in-place, "preserve" rewrites the file so that every link sees
+    /// the result, and "break" replaces each path with a file of its own.
+    #[clap(
+        long = "hard-links",
+        value_name = "POLICY",
+        value_parser = ["preserve", "break"],
+        default_value = "preserve"
+    )]
+    hard_links: String,
+    /// Write the output to the inherited file descriptor N instead of
+    /// stdout, e.g. `3>out.txt`, which leaves stdout free for reports.
+    #[clap(long = "output-fd", value_name = "N")]
+    output_fd: Option<i32>,
+    /// Write the output to FILE instead of stdout, through a temporary file
+    /// next to it renamed into place once done, so that a crash or a full
+    /// disk never leaves FILE truncated.
+    #[clap(
+        short = 'o',
+        long = "output",
+        value_name = "FILE",
+        conflicts_with_all = [
+            "output_fd", "resume_state", "checkpoint_fd", "checkpoint_file"
+        ]
+    )]
+    output: Option<PathBuf>,
+    /// Filter each PATH in place, as `sed -i` does, keeping a copy of it
+    /// with SUFFIX appended to its name if given, e.g. `-i.bak` or
+    /// `--in-place=.bak`; with --recursive, each file under the PATHs.
+    #[clap(
+        short = 'i',
+        long = "in-place",
+        value_name = "SUFFIX",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "",
+        requires = "paths",
+        conflicts_with_all = [
+            "output", "output_fd", "clean_output", "dirty_output"
+        ]
+    )]
+    in_place: Option<String>,
+    /// Filter the text in the system clipboard instead of stdin, e.g. text
+    /// copied out of a word processor, through pbpaste on macOS, PowerShell
+    /// on Windows, and wl-paste, xclip or xsel elsewhere.
+    #[clap(
+        long = "clipboard-in",
+        default_value_t = false,
+        conflicts_with_all = [
+            "paths", "follow", "watch", "listen", "timeout", "idle_timeout",
+            "resume_state", "checkpoint_fd", "checkpoint_file"
+        ]
+    )]
+    clipboard_in: bool,
+    /// Write the output to the system clipboard instead of stdout, e.g.
+    /// `--clipboard-in --clipboard-out` to sanitize the clipboard before
+    /// pasting it into a terminal.
+    #[clap(
+        long = "clipboard-out",
+        default_value_t = false,
+        conflicts_with_all = [
+            "paths", "follow", "watch", "listen", "output", "output_fd",
+            "clean_output", "dirty_output", "rate_limit", "lines_per_sec",
+            "resume_state", "checkpoint_fd", "checkpoint_file"
+        ]
+    )]
+    clipboard_out: bool,
+    /// Filter a large regular file on N threads, one per CPU by default, or
+    /// serially with 1, which also reads, filters and writes stdin from a
+    /// pipe on one thread rather than three.
+    #[clap(long = "threads", value_name = "N", value_parser = parse_threads)]
+    threads: Option<usize>,
+    /// Give up if no input arrives on stdin within SECS seconds, and exit
+    /// with 5 once the output so far is flushed.
+    #[clap(
+        long = "timeout",
+        value_name = "SECS",
+        value_parser = parse_secs,
+        conflicts_with_all = [
+            "resume_state", "checkpoint_fd", "checkpoint_file", "recursive"
+        ]
+    )]
+    timeout: Option<Duration>,
+    /// End the input if no input arrives on stdin within DURATION, also
+    /// with --follow, flushing the output so far and exiting as at the end
+    /// of input. DURATION is in seconds, or with a unit, e.g. 500ms, 30s,
+    /// 5m or 1h.
+    #[clap(
+        long = "idle-timeout",
+        value_name = "DURATION",
+        value_parser = parse_duration,
+        conflicts_with_all = [
+            "resume_state", "checkpoint_fd", "checkpoint_file", "recursive",
+            "timeout"
+        ]
+    )]
+    idle_timeout: Option<Duration>,
+    /// Flush the output after each line, e.g. to filter the output of a
+    /// REPL interactively, which is the default if stdin or stdout is a
+    /// terminal. Applies to text input only.
+    #[clap(long = "line-buffered", default_value_t = false)]
+    line_buffered: bool,
+    /// Filter each line typed at the terminal as it is entered, until
+    /// Ctrl-D, rather than exiting with a usage hint when stdin is a
+    /// terminal and no file is given.
+    #[clap(long = "interactive", default_value_t = false)]
+    interactive: bool,
+    /// Keep reading stdin at its end for more input, as `tail -f` does, e.g.
+    /// of a growing log file, flushing the output after each line. Applies
+    /// to text input only.
+    #[clap(
+        long = "follow",
+        default_value_t = false,
+        conflicts_with_all = [
+            "resume_state", "checkpoint_fd", "checkpoint_file", "recursive",
+            "clean_output", "dirty_output", "timeout"
+        ]
+    )]
+    follow: bool,
+    /// Show a progress bar of the files read, with the throughput and the
+    /// time left, on stderr if it is a terminal.
+    #[clap(long = "progress", default_value_t = false)]
+    progress: bool,
+    /// Write at most RATE bytes a second to stdout or --output-fd, with an
+    /// optional binary unit and `/s`, e.g. 9600, 64K or 1MB/s.
+    #[clap(
+        long = "rate-limit",
+        value_name = "RATE",
+        value_parser = parse_rate,
+        conflicts_with_all = [
+            "resume_state", "checkpoint_fd", "checkpoint_file", "output",
+            "clean_output", "dirty_output", "in_place", "watch", "listen"
+        ]
+    )]
+    rate_limit: Option<u64>,
+    /// Write at most N lines a second to stdout or --output-fd.
+    #[clap(
+        long = "lines-per-sec",
+        value_name = "N",
+        value_parser = parse_lines_per_sec,
+        conflicts_with_all = [
+            "resume_state", "checkpoint_fd", "checkpoint_file", "output",
+            "clean_output", "dirty_output", "in_place", "watch", "listen"
+        ]
+    )]
+    lines_per_sec: Option<u64>,
+    /// Filter FILE to the file given by --output, and again each time FILE
+    /// changes, e.g. to mirror a log into a clean copy for other tools.
+    #[clap(
+        long = "watch",
+        value_name = "FILE",
+        requires = "output",
+        conflicts_with_all = [
+            "resume_state", "checkpoint_fd", "checkpoint_file", "recursive",
+            "clean_output", "dirty_output", "timeout", "follow", "in_place"
+        ]
+    )]
+    watch: Option<PathBuf>,
+    /// Listen on ADDR, `tcp:HOST:PORT` or `unix:PATH`, and filter what each
+    /// connection sends back to it, or to --connect, flushing after each
+    /// line, e.g. as a sanitizing relay.
+    #[clap(
+        long = "listen",
+        value_name = "ADDR",
+        conflicts_with_all = [
+            "resume_state", "checkpoint_fd", "checkpoint_file", "recursive",
+            "clean_output", "dirty_output", "timeout", "follow", "watch",
+            "in_place", "output", "output_fd", "paths"
+        ]
+    )]
+    listen: Option<Endpoint>,
+    /// With --listen, filter each connection to a new connection to ADDR,
+    /// whose replies are relayed back unfiltered.
+    #[clap(long = "connect", value_name = "ADDR", requires = "listen")]
+    connect: Option<Endpoint>,
+    /// The files to filter in sequence to stdout, `-` for stdin, or stdin if
+    /// none; with --recursive, the files or directories to filter.
+    #[clap(
+        value_name = "PATH",
+        conflicts_with_all = [
+            "resume_state", "checkpoint_fd", "checkpoint_file",
+            "clean_output", "dirty_output", "timeout", "follow", "watch"
+        ]
+    )]
+    paths: Vec<PathBuf>,
+    #[clap(subcommand)]
+    command: Option<Command>,
+}
+
+// The subcommands take the options of the filter before or after their
+// names, see `args`.
+#[derive(Subcommand, Debug, Clone)]
+enum Command {
+    /// Filter the PATHs, as without a subcommand, which is the default.
+    Filter { paths: Vec<PathBuf> },
+    /// Filter each FILE in place, as with -i, keeping the original with
+    /// SUFFIX appended if given.
+    Fix {
+        /// Keep each original with SUFFIX appended, e.g. `.bak`.
+        #[clap(long = "backup", value_name = "SUFFIX")]
+        backup: Option<String>,
+        #[clap(required = true)]
+        files: Vec<PathBuf>,
+    },
+    /// Write nothing but the stats of filtering each FILE, or stdin if none,
+    /// summed, to stdout.
+    Stats { files: Vec<PathBuf> },
+    /// Run the filter configured by the options on generated adversarial
+    /// inputs, and check that it behaves.
+    Selftest,
+    /// Write nothing, and exit with 0 if the filter configured by the options
+    /// leaves each FILE, or stdin if none, unchanged, and with 1 otherwise,
+    /// reporting where it does not, e.g. as an ASCII linter.
+    Check { files: Vec<PathBuf> },
+    /// Print each char the filter configured by the options drops or
+    /// replaces in each FILE, or stdin if none, with its location, codepoint
+    /// and name, e.g. `foo.rs:12:40 U+00A0 NO-BREAK SPACE`, and exit with 1
+    /// if there is any. Applies the char filter and the mappings only.
+    Report { files: Vec<PathBuf> },
+    /// Print each char the filter configured by the options drops or
+    /// replaces in the files staged in git, as `report` does, and exit with
+    /// 1 if there is any, e.g. as a pre-commit hook. Binary files are left
+    /// out.
+    Hook,
+    /// Clean the files git stages with the filter configured by the
+    /// options, speaking the long-running filter process protocol of git on
+    /// stdin and stdout, e.g. with `git config filter.ascii.process
+    /// "ascii-filter -a git-filter"` and `*.txt filter=ascii` in
+    /// `.gitattributes`.
+    GitFilter,
+    /// Run the corpus in the FILEs through iconv, tr and the modes of this
+    /// tool, and report their throughput and how their outputs differ.
+    BenchCompare {
+        #[clap(required = true)]
+        files: Vec<PathBuf>,
+    },
+    /// Write the original of FILE, or stdin if none, filtered with
+    /// --undo-log LOG, to stdout.
+    Restore { log: PathBuf, file: Option<PathBuf> },
+    /// Report the likely encoding, the BOM and the classes of chars of each
+    /// FILE, and the options recommended to filter it, without filtering.
+    Detect {
+        #[clap(required = true)]
+        files: Vec<PathBuf>,
+    },
+    /// Preview the filtering of FILE side by side with the original, toggle
+    /// the options live, and write the result as plain text.
+    #[cfg(feature = "tui")]
+    Tui {
+        file: PathBuf,
+        /// Write the result to OUTPUT instead of stdout.
+        #[clap(short = 'o', value_name = "OUTPUT")]
+        output: Option<PathBuf>,
+    },
+    /// Write the completion script of the options and subcommands for SHELL
+    /// to stdout.
+    #[clap(hide = true)]
+    Completions { shell: completions::Shell },
+}
+
+/// The least buffer size, which holds any utf-8 char.
+const MIN_BUF_SIZE: usize = 4;
+
+/// Parse a number of bytes with an optional binary unit, e.g. `64K`, `1 m`
+/// or `4MiB`, or return `None` if it is not one, or overflows.
+fn parse_bytes(s: &str) -> Option<u64> {
+    let digits = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (n, unit) = s.split_at(digits);
+    let shift = match unit.trim_start().to_ascii_lowercase().as_str() {
+        "" | "b" => 0,
+        "k" | "kb" | "kib" => 10,
+        "m" | "mb" | "mib" => 20,
+        "g" | "gb" | "gib" => 30,
+        _ => return None,
+    };
+    n.parse::<u64>().ok()?.checked_mul(1 << shift)
+}
+
+/// Parse a buffer size of at least [`MIN_BUF_SIZE`] bytes, with an optional
+/// binary unit, e.g. `64K`, `1M` or `4MiB`.
+fn parse_size(s: &str) -> Result<usize, String> {
+    let err = || {
+        format!(
+            "{:?} is not a buffer size of at least {} bytes, e.g. 64K or 1M",
+            s, MIN_BUF_SIZE
+        )
+    };
+    parse_bytes(s)
+        .and_then(|n| usize::try_from(n).ok())
+        .filter(|&n| n >= MIN_BUF_SIZE)
+        .ok_or_else(err)
+}
+
+/// Parse a positive rate in bytes a second, with an optional binary unit
+/// and `/s`, e.g. `9600`, `64K` or `1MB/s`.
+fn parse_rate(s: &str) -> Result<u64, String> {
+    let err = || format!("{:?} is not a positive rate, e.g. 64K or 1MB/s", s);
+    parse_bytes(s.strip_suffix("/s").unwrap_or(s))
+        .filter(|&n| n > 0)
+        .ok_or_else(err)
+}
+
+fn parse_lines_per_sec(s: &str) -> Result<u64, String> {
+    s.parse::<u64>()
+        .ok()
+        .filter(|&n| n > 0)
+        .ok_or_else(|| format!("{:?} is not a positive number of lines", s))
+}
+
+/// Parse a positive number of seconds.
+fn parse_secs(s: &str) -> Result<Duration, String> {
+    s.parse::<f64>()
+        .ok()
+        .filter(|&secs| secs > 0.0)
+        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+        .ok_or_else(|| format!("{:?} is not a positive number of seconds", s))
+}
+
+/// Parse a positive duration in seconds, or with a unit, e.g. `500ms`,
+/// `30s`, `5m` or `1h`.
+fn parse_duration(s: &str) -> Result<Duration, String> {
+    let err = || format!("{:?} is not a positive duration, e.g. 30s or 5m", s);
+    let digits = s
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(s.len());
+    let (n, unit) = s.split_at(digits);
+    let scale = match unit {
+        "ms" => 0.001,
+        "" | "s" => 1.0,
+        "m" => 60.0,
+        "h" => 3600.0,
+        _ => return Err(err()),
+    };
+    n.parse::<f64>()
+        .ok()
+        .filter(|&n| n > 0.0)
+        .and_then(|n| Duration::try_from_secs_f64(n * scale).ok())
+        .ok_or_else(err)
+}
+
+/// Parse a comma-separated list of columns, counted from 1, and of ranges of
+/// them, e.g. `2,5` or `2-4,7`.
+fn parse_columns(s: &str) -> Result<Vec<usize>, String> {
+    let err = || format!("{:?} is not a list of columns, e.g. 2,5 or 2-4", s);
+    let column = |n: &str| n.parse::<usize>().ok().filter(|&n| n > 0);
+    let mut columns: Vec<usize> = Vec::new();
+    for item in s.split(',') {
+        let (first, last) = match item.split_once('-') {
+            Some((first, last)) => (column(first), column(last)),
+            None => (column(item), column(item)),
+        };
+        match (first, last) {
+            (Some(first), Some(last)) if first <= last => {
+                columns.extend(first..=last)
+            }
+            _ => return Err(err()),
+        }
+    }
+    Ok(columns)
+}
+
+/// Parse a positive tab width.
+fn parse_tab_width(s: &str) -> Result<usize, String> {
+    s.parse::<usize>()
+        .ok()
+        .filter(|&width| width > 0)
+        .ok_or_else(|| format!("{:?} is not a positive tab width", s))
+}
+
+fn parse_line_length(s: &str) -> Result<usize, String> {
+    s.parse::<usize>()
+        .ok()
+        .filter(|&n| n > 0)
+        .ok_or_else(|| format!("{:?} is not a positive line length", s))
+}
+
+fn parse_squeeze(s: &str) -> Result<usize, String> {
+    s.parse::<usize>()
+        .ok()
+        .filter(|&n| n > 0)
+        .ok_or_else(|| format!("{:?} is not a positive number of markers", s))
+}
+
+fn parse_stats_top(s: &str) -> Result<usize, String> {
+    s.parse::<usize>()
+        .ok()
+        .filter(|&n| n > 0)
+        .ok_or_else(|| format!("{:?} is not a positive number of chars", s))
+}
+
+fn parse_threads(s: &str) -> Result<usize, String> {
+    s.parse::<usize>()
+        .ok()
+        .filter(|&n| n > 0)
+        .ok_or_else(|| format!("{:?} is not a positive number of threads", s))
+}
+
+/// Load the mapping at `path` parsed by `parse`, e.g. transliteration rules,
+/// exiting on failure.
+fn load_mapping<P>(path: &Path, parse: P) -> Mapping
+where
+    P: FnOnce(&str) -> Result<HashMap<char, String>, ParseRulesError>,
+{
+    let text = std::fs::read_to_string(path).unwrap_or_else(|e| {
+        eprintln!("ascii-filter: {}: {}", path.display(), e);
+        process::exit(exit::IO);
+    });
+    let table = parse(&text).unwrap_or_else(|e| {
+        eprintln!("ascii-filter: {}: {}", path.display(), e);
+        process::exit(exit::USAGE);
+    });
+    Mapping::Table(Arc::new(table))
+}
+
+/// Run the self-test under `opts`, exiting with 1 if anything fails.
+fn run_selftest(opts: &Options) {
+    let report = selftest(opts);
+    for failure in &report.failures {
+        eprintln!("ascii-filter: selftest: {}", failure);
+    }
+    println!(
+        "selftest: {} cases, {} failures",
+        report.cases,
+        report.failures.len()
+    );
+    if !report.failures.is_empty() {
+        process::exit(exit::VIOLATIONS);
+    }
+}
+
+/// Return the option which enables `mapping`.
+fn mapping_flag(mapping: &Mapping) -> String {
+    match mapping {
+        Mapping::Translit(locale) => format!("--translit-locale {}", locale),
+        Mapping::Table(_) => "--translit-rules".to_string(),
+        Mapping::Symbols => "--symbols".to_string(),
+        Mapping::Arrows => "--arrows".to_string(),
+        Mapping::BoxDrawing => "--box-drawing".to_string(),
+        Mapping::Bullets => "--bullets".to_string(),
+        Mapping::Roman => "--roman".to_string(),
+        Mapping::Enclosed => "--enclosed".to_string(),
+        Mapping::FullWidth => "--full-width".to_string(),
+        Mapping::Greek => "--greek".to_string(),
+        Mapping::Math => "--math".to_string(),
+        Mapping::Units => "--units".to_string(),
+        Mapping::Punctuation => "--punctuation".to_string(),
+        Mapping::Spaces => "--spaces".to_string(),
+        Mapping::Unidecode => "--transliterate".to_string(),
+        #[cfg(feature = "unicode-confusables")]
+        Mapping::Confusables => "--confusables".to_string(),
+    }
+}
+
+/// Return the command recommended to filter the input of `profile` in
+/// `file`.
+fn recommend(profile: &Profile, file: &Path) -> String {
+    if profile.is_clean() {
+        return "none, the input is clean".to_string();
+    }
+    let mut command = vec!["ascii-filter".to_string()];
+    if !matches!(profile.encoding, Encoding::Ascii | Encoding::Utf8) {
+        command.push(format!("--from-encoding {}", profile.encoding));
+    }
+    let non_ascii = profile.control
+        + profile.c1
+        + profile.cjk
+        + profile.emoji
+        + profile.other;
+    if non_ascii > 0 {
+        command.push("-a".to_string());
+        // a single locale applies, the one mapping the most chars
+        let mut locale: Option<&(Mapping, usize)> = None;
+        for entry in &profile.mappable {
+            if matches!(entry.0, Mapping::Translit(_))
+                && locale.is_none_or(|&(_, n)| entry.1 > n)
+            {
+                locale = Some(entry);
+            }
+        }
+        command.extend(locale.map(|(mapping, _)| mapping_flag(mapping)));
+        command.extend(
+            profile
+                .mappable
+                .iter()
+                .filter(|(mapping, _)| !matches!(mapping, Mapping::Translit(_)))
+                .map(|(mapping, _)| mapping_flag(mapping)),
+        );
+    }
+    command.push(format!("< {}", file.display()));
+    command.join(" ")
+}
+
+/// Write the original of `file`, or stdin if `None` or `-`, to stdout per
+/// the undo `log`.
+fn run_restore(log: &Path, file: Option<&Path>) -> Result<(), Error> {
+    let log = io::BufReader::new(File::open(log)?);
+    let stdout = io::BufWriter::new(io::stdout().lock());
+    match file.filter(|file| *file != Path::new("-")) {
+        Some(file) => restore(File::open(file)?, log, stdout)?,
+        None => restore(io::stdin().lock(), log, stdout)?,
+    }
+    Ok(())
+}
+
+/// Report the profile of each of `files`, exiting with 3 if one cannot be
+/// read.
+fn run_detect(files: &[PathBuf]) {
+    for file in files {
+        let bytes = std::fs::read(file).unwrap_or_else(|e| {
+            eprintln!("ascii-filter: {}: {}", file.display(), e);
+            process::exit(exit::IO);
+        });
+        let profile = detect(&bytes);
+        println!(
+            "{}: {}, {}",
+            file.display(),
+            profile.encoding,
+            match profile.bom {
+                Some(_) => "with BOM",
+                None => "no BOM",
+            }
+        );
+        println!(
+            "  chars: {} ASCII, {} control, {} C1, {} CJK, {} emoji, {} other",
+            profile.ascii,
+            profile.control,
+            profile.c1,
+            profile.cjk,
+            profile.emoji,
+            profile.other
+        );
+        println!("  invalid bytes: {}", profile.invalid);
+        if !profile.mappable.is_empty() {
+            let mappable: Vec<String> = profile
+                .mappable
+                .iter()
+                .map(|(mapping, n)| {
+                    format!("{} by {}", n, mapping_flag(mapping))
+                })
+                .collect();
+            println!("  mappable: {}", mappable.join(", "));
+        }
+        println!("  recommendation: {}", recommend(&profile, file));
+    }
+}
+
+/// The hint printed when stdin is a terminal and no file is given, which
+/// would otherwise wait for input in silence.
+const TERMINAL_HINT: &str = "\
+ascii-filter: stdin is a terminal, with nothing to filter yet
+  ascii-filter [OPTIONS] FILE...     filter files
+  COMMAND | ascii-filter [OPTIONS]   filter the output of a command
+  ascii-filter --interactive         filter each line as typed, until Ctrl-D
+  ascii-filter --help                list the options
+";
+
+/// The time the run started, for the throughput.
+static START: OnceLock<Instant> = OnceLock::new();
+
+/// The chars removed or replaced so far, of which the number given by
+/// `--stats-top` is printed at the end.
+static HISTOGRAM: OnceLock<(usize, Arc<Mutex<Histogram>>)> = OnceLock::new();
+
+/// Print the chars removed or replaced most to stderr, per `--stats-top`.
+fn print_top() {
+    if let Some((n, histogram)) = HISTOGRAM.get() {
+        for (c, count) in histogram.lock().unwrap().top(*n) {
+            eprintln!("ascii-filter: {:>8} {}", count, lint::describe_char(c));
+        }
+    }
+}
+
+/// Print the stats of `result` to stderr if `show`, and the chars removed
+/// most per `--stats-top`, once filtered without an error, and log the
+/// throughput.
+fn print_stats(result: Result<Stats, Error>, show: bool) -> Result<(), Error> {
+    let stats = result?;
+    if let Some(start) = START.get() {
+        let secs = start.elapsed().as_secs_f64();
+        log::info!(
+            "{} bytes read in {:.3}s, {:.1} MiB/s",
+            stats.bytes_read,
+            secs,
+            stats.bytes_read as f64 / secs.max(f64::EPSILON) / (1 << 20) as f64
+        );
+    }
+    if show {
+        eprintln!("ascii-filter: {}", stats);
+    }
+    print_top();
+    Ok(())
+}
+
+/// Exit with the status of the error per [`exit::status`], but quietly with
+/// 0 once the reader of the output is gone, e.g. `head`, as the standard
+/// filters do.
+fn exit_on_error(result: Result<(), Error>) {
+    if let Err(e) = result {
+        if matches!(&e, Error::Io(e) if e.kind() == io::ErrorKind::BrokenPipe) {
+            process::exit(0);
+        }
+        eprintln!("ascii-filter: {}", e);
+        process::exit(exit::status(e.kind()));
+    }
+}
+
+/// Collect the filter options from the command line.
+fn options(app: App) -> Options {
+    let mut mappings: Vec<Mapping> = app
+        .map
+        .iter()
+        .map(|path| load_mapping(path, parse_tsv))
+        .collect();
+    mappings.extend(
+        app.translit_rules
+            .iter()
+            .map(|path| load_mapping(path, parse_rules)),
+    );
+    mappings.extend(app.translit_locale.map(Mapping::Translit));
+    if app.greek {
+        mappings.push(Mapping::Greek);
+    }
+    if app.units {
+        mappings.push(Mapping::Units);
+    }
+    if app.symbols {
+        mappings.push(Mapping::Symbols);
+    }
+    if app.arrows {
+        mappings.push(Mapping::Arrows);
+    }
+    if app.box_drawing {
+        mappings.push(Mapping::BoxDrawing);
+    }
+    if app.bullets {
+        mappings.push(Mapping::Bullets);
+    }
+    if app.roman {
+        mappings.push(Mapping::Roman);
+    }
+    if app.enclosed {
+        mappings.push(Mapping::Enclosed);
+    }
+    if app.full_width {
+        mappings.push(Mapping::FullWidth);
+    }
+    if app.math {
+        mappings.push(Mapping::Math);
+    }
+    if app.punctuation {
+        mappings.push(Mapping::Punctuation);
+    }
+    if app.spaces {
+        mappings.push(Mapping::Spaces);
+    }
+    if app.transliterate {
+        mappings.push(Mapping::Unidecode);
+    }
+    #[cfg(feature = "unicode-confusables")]
+    if app.confusables {
+        mappings.push(Mapping::Confusables);
+    }
+    Options {
+        buf_size: app.buf_size,
+        ascii_only: app.ascii_only,
+        allowed: match (app.allow, app.allow_chars) {
+            (None, None) => None,
+            (allow, chars) => {
+                let mut allowed = allow.unwrap_or_default();
+                allowed.extend(&chars.unwrap_or_default().chars().collect());
+                Some(allowed)
+            }
+        },
+        keep_latin1: app.keep_latin1,
+        encodable_in: app.encodable_in,
+        #[cfg(feature = "unicode-category")]
+        keep_categories: app.keep_categories.unwrap_or_default(),
+        #[cfg(feature = "unicode-category")]
+        drop_categories: app.drop_categories.unwrap_or_default(),
+        #[cfg(feature = "unicode-script")]
+        keep_scripts: app.keep_scripts.unwrap_or_default(),
+        strip_invisible: app.strip_invisible,
+        keep_cr: app.keep_cr,
+        keep_controls: app.keep_controls,
+        decompress: app.decompress,
+        compress: app.compress.or_else(|| {
+            app.output.as_deref().and_then(Compression::from_extension)
+        }),
+        from_encoding: app.from_encoding,
+        to_encoding: app.to_encoding,
+        invalid_utf8: app.invalid_utf8,
+        newlines: app.newlines,
+        expand_tabs: app.expand_tabs,
+        max_line_length: app.max_line_length,
+        overlong: if app.wrap {
+            Overlong::Wrap
+        } else if app.drop {
+            Overlong::Drop
+        } else {
+            Overlong::Truncate
+        },
+        invert: app.invert,
+        write_bom: app.write_bom,
+        strip_bom: app.strip_bom,
+        out_record_sep: if app.out_null {
+            Some("\0".to_string())
+        } else {
+            app.out_record_sep
+        },
+        null_data: app.null,
+        format: if app.logfmt {
+            Format::Logfmt
+        } else if app.syslog {
+            Format::Syslog
+        } else if app.journal_export {
+            Format::JournalExport
+        } else if app.docker_json {
+            Format::DockerJson
+        } else if app.json {
+            Format::Json {
+                keys: app.json_keys,
+            }
+        } else if app.csv {
+            Format::Csv { delimiter: b',' }
+        } else if app.tsv {
+            Format::Csv { delimiter: b'\t' }
+        } else if app.markdown {
+            Format::Markdown
+        } else {
+            Format::Text
+        },
+        columns: app.columns,
+        #[cfg(feature = "unicode-normalization")]
+        normalization: app.normalize,
+        strip_ansi: app.strip_ansi,
+        drop_regex: app.drop_regex,
+        emoji: app.emoji,
+        #[cfg(feature = "unicode-segmentation")]
+        graphemes: app.graphemes,
+        keep_regex: app.keep_regex,
+        punycode: app.punycode,
+        mappings,
+        #[cfg(feature = "unicode-normalization")]
+        strip_diacritics: app.strip_diacritics,
+        replacement: app.replace,
+        escape: app.escape,
+        control_escape: app.escape_controls,
+        collapse: app.collapse,
+        squeeze_replacements: app.squeeze_replacements,
+        word_sep: app.word_sep,
+        drop_lines: app.drop_lines,
+        line_numbers: app.line_numbers,
+        line_prefix: None,
+        filenames: match (app.with_filename, app.no_filename) {
+            (_, true) => Some(false),
+            (true, false) => Some(true),
+            (false, false) => None,
+        },
+        highlight: match app.highlight.as_deref() {
+            None => false,
+            Some("auto") if !io::stdout().is_terminal() => {
+                eprintln!(
+                    "ascii-filter: --highlight needs a terminal on stdout, \
+                     use --highlight=always to highlight anyway"
+                );
+                process::exit(exit::USAGE);
+            }
+            Some(_) => true,
+        },
+        verify: app.verify,
+        check_idempotent: app.check_idempotent,
+        assert_clean: app.assert_clean,
+        strict: app.strict,
+        rejects: app.rejects,
+        reject_positions: app.reject_positions,
+        rejects_hex: app.rejects_hex,
+        report: app.report,
+        report_output: app.report_output,
+        report_name: None,
+        undo_log: app.undo_log,
+        source_map: app.source_map,
+        histogram: app.stats_top.map(|n| {
+            Arc::clone(&HISTOGRAM.get_or_init(|| (n, Arc::default())).1)
+        }),
+        resume_state: app.resume_state,
+        clean_output: app.clean_output,
+        dirty_output: app.dirty_output,
+        skip_binary: !app.no_skip_binary,
+        ignore: !app.no_ignore,
+        hidden: app.hidden,
+        follow_symlinks: app.follow_symlinks,
+        output_fd: app.output_fd,
+        output: app.output,
+        threads: app.threads,
+        timeout: app.timeout,
+        idle_timeout: app.idle_timeout,
+        line_buffered: app.line_buffered
+            || io::stdin().is_terminal()
+            || io::stdout().is_terminal(),
+        follow: app.follow,
+        progress: app.progress && io::stderr().is_terminal(),
+        rate_limit: app.rate_limit,
+        lines_per_sec: app.lines_per_sec,
+        hard_links: match app.hard_links.as_str() {
+            "break" => HardLinks::Break,
+            _ => HardLinks::Preserve,
+        },
+        checkpoint: match (app.checkpoint_fd, app.checkpoint_file) {
+            (Some(fd), _) => Some(Checkpoint::Fd(fd)),
+            (None, path) => path.map(Checkpoint::File),
+        },
+    }
+}
+
+/// Return the command line of [`App`] for `args`, where each option may be
+/// given by the environment variable of its long name too, e.g.
+/// `ASCII_FILTER_BUFFER_SIZE` for `--buffer-size` or
+/// `ASCII_FILTER_ASCII_ONLY=true` for `-a`, unless it or an option it
+/// conflicts with is given in `args`, so that the command line overrides
+/// the environment.
+fn command(args: &[OsString]) -> clap::Command {
+    let mut built = App::command();
+    built.build();
+    let matches = built.clone().try_get_matches_from(args).ok();
+    let given = |arg: &Arg| {
+        matches.as_ref().is_some_and(|matches| {
+            matches.value_source(arg.get_id().as_str())
+                == Some(ValueSource::CommandLine)
+        })
+    };
+    // either of two options may declare their conflict
+    let conflict = |a: &Arg, b: &Arg| {
+        let declared = |a: &Arg, b: &Arg| {
+            built
+                .get_arg_conflicts_with(a)
+                .iter()
+                .any(|arg| arg.get_id() == b.get_id())
+        };
+        declared(a, b) || declared(b, a)
+    };
+    let overridden: Vec<clap::Id> = built
+        .get_arguments()
+        .filter(|arg| {
+            built.get_arguments().any(|other| {
+                given(other)
+                    && (other.get_id() == arg.get_id() || conflict(arg, other))
+            })
+        })
+        .map(|arg| arg.get_id().clone())
+        .collect();
+    App::command().mut_args(|arg| match arg.get_long() {
+        Some(long) if !overridden.contains(arg.get_id()) => {
+            let var = long.to_ascii_uppercase().replace('-', "_");
+            arg.env(format!("ASCII_FILTER_{}", var))
+        }
+        _ => arg,
+    })
+}
+
+/// Return the command line arguments, where `-iSUFFIX` is taken as
+/// `--in-place=SUFFIX`, as sed does, so that `-i FILE` is not. The value of
+/// an option, e.g. `--replace -ix`, is left as is. The options of the filter
+/// given after the name of a subcommand are moved before it, and the
+/// `filter` subcommand is taken out, so that e.g. `check -a FILE` is taken as
+/// `-a check FILE`, and `filter -a FILE` as `-a FILE`.
+fn args() -> Vec<OsString> {
+    let mut command = App::command();
+    command.build();
+    let takes_value =
+        |arg: &Arg| arg.get_num_args().is_some_and(|n| n.min_values() > 0);
+    // The subcommand given, if any, whose own arguments stay after it in
+    // `tail`.
+    let mut subcommand: Option<&clap::Command> = None;
+    let mut tail: Vec<OsString> = Vec::new();
+    let mut args: Vec<OsString> = Vec::new();
+    // If true, a positional argument may name a subcommand.
+    let mut first = true;
+    let mut options = true;
+    // If true, the next argument is the value of an option, of the
+    // subcommand if `own`.
+    let mut value = false;
+    let mut own = false;
+    for (i, arg) in std::env::args_os().enumerate() {
+        if i == 0 {
+            args.push(arg);
+            continue;
+        }
+        if std::mem::take(&mut value) {
+            match own {
+                true => tail.push(arg),
+                false => args.push(arg),
+            }
+            continue;
+        }
+        let s = arg.to_str().filter(|_| options);
+        // The arguments which an option may be, of the subcommand first.
+        let candidates = || {
+            let own_args = subcommand
+                .into_iter()
+                .flat_map(|sub| sub.get_arguments().map(|arg| (true, arg)));
+            own_args.chain(command.get_arguments().map(|arg| (false, arg)))
+        };
+        own = match s {
+            Some("--") => {
+                options = false;
+                first = false;
+                subcommand.is_some()
+            }
+            Some(s) if s.starts_with("--") => {
+                let long = &s[2..];
+                let name = long.split('=').next().unwrap();
+                let found =
+                    candidates().find(|(_, a)| a.get_long() == Some(name));
+                value = !long.contains('=')
+                    && found.is_some_and(|(_, arg)| takes_value(arg));
+                found.is_some_and(|(own, _)| own)
+            }
+            Some(s) if s.starts_with('-') && s.len() > 1 => {
+                let shorts = &s[1..];
+                if let Some(suffix) = shorts.strip_prefix('i') {
+                    if !suffix.is_empty() {
+                        let suffix = suffix.strip_prefix('=').unwrap_or(suffix);
+                        args.push(format!("--in-place={}", suffix).into());
+                        continue;
+                    }
+                }
+                // In a cluster, e.g. `-ab VALUE`, the first option taking a
+                // value takes the rest, or the next argument if there is
+                // none.
+                let mut own = false;
+                for (i, c) in shorts.char_indices() {
+                    let found =
+                        candidates().find(|(_, a)| a.get_short() == Some(c));
+                    if let Some((is_own, arg)) = found {
+                        own |= is_own;
+                        if takes_value(arg) {
+                            value = i + c.len_utf8() == shorts.len();
+                            break;
+                        }
+                    }
+                }
+                own
+            }
+            Some(s) if std::mem::take(&mut first) => {
+                if s == "filter" {
+                    continue;
+                }
+                subcommand = command.find_subcommand(s);
+                subcommand.is_some()
+            }
+            _ => {
+                first = false;
+                subcommand.is_some()
+            }
+        };
+        match own {
+            true => tail.push(arg),
+            false => args.push(arg),
+        }
+    }
+    args.extend(tail);
+    args
 }
 
 fn main() {
-    let app = App::parse();
-    stdin_stdout_buffer_filter(app.buf_size, app.ascii_only)
+    let args = args();
+    let matches = command(&args).get_matches_from(args);
+    let mut app = App::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    let types = config::apply(&mut app, &matches).unwrap_or_else(|e| {
+        eprintln!("ascii-filter: {}", e);
+        process::exit(exit::USAGE);
+    });
+    verbose::init(app.verbose);
+    START.get_or_init(Instant::now);
+    if app.print_units_rules {
+        print!("{}", UNITS_RULES);
+        return;
+    }
+    let log = match (&app.undo_log, &app.source_map) {
+        (Some(_), _) => Some("--undo-log"),
+        (None, Some(_)) => Some("--source-map"),
+        (None, None) => None,
+    };
+    if let Some(log) = log {
+        let inputs = match &app.command {
+            Some(Command::Fix { files, .. } | Command::Stats { files }) => {
+                files.len()
+            }
+            _ => app.paths.len(),
+        };
+        if inputs > 1 {
+            eprintln!("ascii-filter: {} applies to a single input", log);
+            process::exit(exit::USAGE);
+        }
+    }
+    match app.command.clone() {
+        None if app.recursive => {
+            let paths = if app.paths.is_empty() {
+                vec![PathBuf::from(".")]
+            } else {
+                app.paths.clone()
+            };
+            let types: HashMap<String, Options> = types
+                .into_iter()
+                .map(|(mime, app)| (mime, options(app)))
+                .collect();
+            let show_stats = app.stats;
+            let suffix = app.in_place.clone();
+            let opts = options(app);
+            let report_skip = |path: &Path, reason: &Skipped| {
+                eprintln!(
+                    "ascii-filter: skipped {}: {}",
+                    path.display(),
+                    reason
+                )
+            };
+            if let Some(suffix) = suffix {
+                let suffix = Some(suffix).filter(|s| !s.is_empty());
+                let result = filter_tree_in_place(
+                    &paths,
+                    suffix.as_deref(),
+                    &opts,
+                    &types,
+                    report_skip,
+                );
+                exit_on_error(print_stats(result, show_stats));
+                return;
+            }
+            let result = open_output(&opts).map_err(Error::from).and_then(
+                |mut output| {
+                    let stats = filter_tree(
+                        &paths,
+                        &mut output,
+                        &opts,
+                        &types,
+                        report_skip,
+                    )?;
+                    output.commit()?;
+                    Ok(stats)
+                },
+            );
+            exit_on_error(print_stats(result, show_stats));
+        }
+        None if app.listen.is_some() => {
+            let show_stats = app.stats;
+            let listen = app.listen.clone().unwrap();
+            let connect = app.connect.clone();
+            let opts = options(app);
+            exit_on_error(end_input_on_signals().map_err(Error::from));
+            exit_on_error(serve(
+                &listen,
+                connect.as_ref(),
+                &opts,
+                |peer, result| {
+                    if let Err(e) = print_stats(result, show_stats) {
+                        eprintln!("ascii-filter: {}: {}", peer, e);
+                    }
+                },
+            ));
+            if let Some(sig) = caught_signal() {
+                process::exit(128 + sig);
+            }
+        }
+        None if app.watch.is_some() => {
+            let show_stats = app.stats;
+            let path = app.watch.clone().unwrap();
+            let opts = options(app);
+            exit_on_error(watch_file(&path, &opts, |result| {
+                // a failed run is retried on the next change
+                if let Err(e) = print_stats(result, show_stats) {
+                    eprintln!("ascii-filter: {}: {}", path.display(), e);
+                }
+                ControlFlow::Continue(())
+            }));
+        }
+        None if app.in_place.is_some() => {
+            let show_stats = app.stats;
+            let paths = app.paths.clone();
+            let suffix = app.in_place.clone().filter(|s| !s.is_empty());
+            let opts = options(app);
+            let result =
+                paths.iter().try_fold(Stats::default(), |mut sum, path| {
+                    sum += filter_in_place(path, suffix.as_deref(), &opts)?;
+                    Ok(sum)
+                });
+            exit_on_error(print_stats(result, show_stats));
+        }
+        None if app.clipboard_in || app.clipboard_out => {
+            let show_stats = app.stats;
+            let (from, to) = (app.clipboard_in, app.clipboard_out);
+            let result = clipboard::filter_clipboard(from, to, &options(app));
+            exit_on_error(print_stats(result, show_stats));
+        }
+        None if !app.paths.is_empty() => {
+            let show_stats = app.stats;
+            let paths = app.paths.clone();
+            let opts = options(app);
+            let mut failed = false;
+            let result = open_output(&opts).map_err(Error::from).and_then(
+                |mut output| {
+                    let stats =
+                        filter_files(&paths, &mut output, &opts, |path, e| {
+                            eprintln!(
+                                "ascii-filter: {}: {}",
+                                path.display(),
+                                e
+                            );
+                            failed = true;
+                        })?;
+                    output.commit()?;
+                    Ok(stats)
+                },
+            );
+            exit_on_error(print_stats(result, show_stats));
+            // the rest of the files are filtered, as cat does
+            if failed {
+                process::exit(exit::IO);
+            }
+        }
+        None => {
+            if io::stdin().is_terminal() && !app.interactive {
+                eprint!("{}", TERMINAL_HINT);
+                process::exit(exit::USAGE);
+            }
+            let show_stats = app.stats;
+            // A file given by --output is replaced only once filtered whole.
+            if app.output.is_none() {
+                exit_on_error(end_input_on_signals().map_err(Error::from));
+            }
+            let result = stdin_stdout_buffer_filter(&options(app));
+            exit_on_error(print_stats(result, show_stats));
+            if let Some(sig) = caught_signal() {
+                process::exit(128 + sig);
+            }
+        }
+        Some(Command::Filter { .. }) => unreachable!("taken out by args()"),
+        Some(Command::Fix { backup, files }) => {
+            let show_stats = app.stats;
+            let opts = options(app);
+            let result =
+                files.iter().try_fold(Stats::default(), |mut sum, path| {
+                    sum += filter_in_place(path, backup.as_deref(), &opts)?;
+                    Ok(sum)
+                });
+            exit_on_error(print_stats(result, show_stats));
+        }
+        Some(Command::Stats { mut files }) => {
+            if files.is_empty() {
+                files.push(PathBuf::from("-"));
+            }
+            let mut failed = false;
+            let result =
+                filter_files(&files, io::sink(), &options(app), |path, e| {
+                    eprintln!("ascii-filter: {}: {}", path.display(), e);
+                    failed = true;
+                });
+            exit_on_error(result.map(|stats| println!("{}", stats)));
+            print_top();
+            if failed {
+                process::exit(exit::IO);
+            }
+        }
+        Some(Command::Selftest) => run_selftest(&options(app)),
+        Some(Command::Check { files }) => {
+            lint::run_check(&files, &options(app))
+        }
+        Some(Command::Report { files }) => {
+            lint::run_report(&files, &options(app))
+        }
+        Some(Command::Hook) => lint::run_hook(&options(app)),
+        Some(Command::GitFilter) => git_filter::run(&options(app)),
+        Some(Command::BenchCompare { files }) => bench::run(&files),
+        Some(Command::Detect { files }) => run_detect(&files),
+        Some(Command::Restore { log, file }) => {
+            exit_on_error(run_restore(&log, file.as_deref()))
+        }
+        #[cfg(feature = "tui")]
+        Some(Command::Tui { file, output }) => {
+            tui::run(&file, output.as_deref(), options(app))
+        }
+        Some(Command::Completions { shell }) => {
+            let mut stdout = io::stdout().lock();
+            let result =
+                completions::generate(shell, &mut App::command(), &mut stdout);
+            exit_on_error(result.map_err(Error::from));
+        }
+    }
 }
@@ -0,0 +1,58 @@
+//! ASCII renderings of arrows and box-drawing chars, so that CLI output and
+//! diagrams stay legible.
+
+pub(crate) fn arrow(c: char) -> Option<&'static str> {
+    Some(match c {
+        '→' | '➔' | '➜' | '➝' | '➞' | '➡' => "->",
+        '←' | '⬅' => "<-",
+        '↔' => "<->",
+        '↑' | '⬆' => "^",
+        '↓' | '⬇' => "v",
+        '⇒' => "=>",
+        '⇐' => "<=",
+        '⇔' => "<=>",
+        '⟶' => "-->",
+        '⟵' => "<--",
+        '⟷' => "<-->",
+        '⟹' => "==>",
+        '⟸' => "<==",
+        '⟺' => "<==>",
+        '↦' | '⟼' => "|->",
+        _ => return None,
+    })
+}
+
+pub(crate) fn box_drawing(c: char) -> Option<&'static str> {
+    Some(match c {
+        // light, heavy and dashed horizontals, and half lines
+        '\u{2500}' | '\u{2501}' | '\u{2504}' | '\u{2505}' | '\u{2508}'
+        | '\u{2509}' | '\u{254c}' | '\u{254d}' | '\u{2574}' | '\u{2576}'
+        | '\u{2578}' | '\u{257a}' | '\u{257c}' | '\u{257e}' => "-",
+        // light, heavy and dashed verticals, and half lines
+        '\u{2502}' | '\u{2503}' | '\u{2506}' | '\u{2507}' | '\u{250a}'
+        | '\u{250b}' | '\u{254e}' | '\u{254f}' | '\u{2575}' | '\u{2577}'
+        | '\u{2579}' | '\u{257b}' | '\u{257d}' | '\u{257f}' => "|",
+        '═' => "=",
+        '║' => "|",
+        '╱' => "/",
+        '╲' => "\\",
+        '╳' => "X",
+        // corners, junctions and arcs
+        '\u{2500}'..='\u{257f}' => "+",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::box_drawing;
+
+    #[test]
+    fn test_box_drawing() {
+        let drawn: String = "┌─┬─┐\n│a║b│\n└─┴─╯"
+            .chars()
+            .map(|c| box_drawing(c).map_or(c.to_string(), str::to_string))
+            .collect();
+        assert_eq!(drawn, "+-+-+\n|a|b|\n+-+-+");
+    }
+}
@@ -0,0 +1,33 @@
+//! ASCII list markers for bullets and the like, so that lists in exported
+//! rich text and PDFs keep their structure.
+
+pub(crate) fn get(c: char) -> Option<&'static str> {
+    Some(match c {
+        // round bullets
+        '•' | '◦' | '●' | '○' | '◉' | '⦿' | '⁌' | '⁍' | '∙' => {
+            "*"
+        }
+        // square and other geometric bullets
+        '▪' | '▫' | '■' | '□' | '◆' | '◇' | '♦' | '❖' => "*",
+        // triangular and arrow-like bullets
+        '‣' | '▸' | '▹' | '►' | '▻' | '➢' | '➤' | '⁃' => "-",
+        // middle dots
+        '·' | '⋅' | '・' | '･' => "-",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::get;
+
+    #[test]
+    fn test_get() {
+        let list: String = "• a\n◦ b\n▪ c\n‣ d\n· e"
+            .chars()
+            .map(|c| get(c).map_or(c.to_string(), str::to_string))
+            .collect();
+        assert_eq!(list, "* a\n* b\n* c\n- d\n- e");
+        assert_eq!(get('*'), None);
+    }
+}
@@ -0,0 +1,33 @@
+//! Stripping of diacritics by canonical decomposition, so that e.g. é, which
+//! decomposes to e and a combining acute accent, keeps its base letter.
+
+use unicode_normalization::char::{decompose_canonical, is_combining_mark};
+
+use crate::is_ascii_subset;
+
+/// Return `c` decomposed with the combining marks removed, if what remains
+/// is in the ASCII subset, e.g. e for é and nothing for a lone combining
+/// mark.
+pub(crate) fn strip(c: char) -> Option<String> {
+    let mut stripped = String::new();
+    decompose_canonical(c, |d| {
+        if !is_combining_mark(d) {
+            stripped.push(d);
+        }
+    });
+    stripped.chars().all(is_ascii_subset).then_some(stripped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::strip;
+
+    #[test]
+    fn test_strip() {
+        assert_eq!(strip('é').as_deref(), Some("e"));
+        assert_eq!(strip('Ǻ').as_deref(), Some("A"));
+        assert_eq!(strip('\u{301}').as_deref(), Some(""));
+        assert_eq!(strip('ß'), None);
+        assert_eq!(strip('你'), None);
+    }
+}
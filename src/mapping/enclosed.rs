@@ -0,0 +1,90 @@
+//! ASCII renderings of enclosed alphanumerics, as found in CJK documents and
+//! slide exports: circled and parenthesized forms to `(1)` and `(a)`, and
+//! squared forms to the bare letter.
+
+/// `(0)` to `(50)`.
+const PAREN_NUMBERS: [&str; 51] = [
+    "(0)", "(1)", "(2)", "(3)", "(4)", "(5)", "(6)", "(7)", "(8)", "(9)",
+    "(10)", "(11)", "(12)", "(13)", "(14)", "(15)", "(16)", "(17)", "(18)",
+    "(19)", "(20)", "(21)", "(22)", "(23)", "(24)", "(25)", "(26)", "(27)",
+    "(28)", "(29)", "(30)", "(31)", "(32)", "(33)", "(34)", "(35)", "(36)",
+    "(37)", "(38)", "(39)", "(40)", "(41)", "(42)", "(43)", "(44)", "(45)",
+    "(46)", "(47)", "(48)", "(49)", "(50)",
+];
+
+/// `(a)` to `(z)`.
+const PAREN_LOWER: [&str; 26] = [
+    "(a)", "(b)", "(c)", "(d)", "(e)", "(f)", "(g)", "(h)", "(i)", "(j)",
+    "(k)", "(l)", "(m)", "(n)", "(o)", "(p)", "(q)", "(r)", "(s)", "(t)",
+    "(u)", "(v)", "(w)", "(x)", "(y)", "(z)",
+];
+
+/// `(A)` to `(Z)`.
+const PAREN_UPPER: [&str; 26] = [
+    "(A)", "(B)", "(C)", "(D)", "(E)", "(F)", "(G)", "(H)", "(I)", "(J)",
+    "(K)", "(L)", "(M)", "(N)", "(O)", "(P)", "(Q)", "(R)", "(S)", "(T)",
+    "(U)", "(V)", "(W)", "(X)", "(Y)", "(Z)",
+];
+
+/// `0.` to `20.`.
+const FULL_STOP: [&str; 21] = [
+    "0.", "1.", "2.", "3.", "4.", "5.", "6.", "7.", "8.", "9.", "10.", "11.",
+    "12.", "13.", "14.", "15.", "16.", "17.", "18.", "19.", "20.",
+];
+
+/// `0,` to `9,`.
+const COMMA: [&str; 10] =
+    ["0,", "1,", "2,", "3,", "4,", "5,", "6,", "7,", "8,", "9,"];
+
+const UPPER: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+pub(crate) fn get(c: char) -> Option<&'static str> {
+    let n = c as usize;
+    Some(match c {
+        // circled and parenthesized digits
+        '\u{2460}'..='\u{2473}' => PAREN_NUMBERS[n - 0x2460 + 1],
+        '\u{2474}'..='\u{2487}' => PAREN_NUMBERS[n - 0x2474 + 1],
+        '\u{2488}'..='\u{249b}' => FULL_STOP[n - 0x2488 + 1],
+        '\u{24ea}' | '\u{24ff}' | '\u{1f10b}' | '\u{1f10c}' => PAREN_NUMBERS[0],
+        '\u{24eb}'..='\u{24f4}' => PAREN_NUMBERS[n - 0x24eb + 11],
+        '\u{24f5}'..='\u{24fe}' => PAREN_NUMBERS[n - 0x24f5 + 1],
+        '\u{2776}'..='\u{277f}' => PAREN_NUMBERS[n - 0x2776 + 1],
+        '\u{2780}'..='\u{2789}' => PAREN_NUMBERS[n - 0x2780 + 1],
+        '\u{278a}'..='\u{2793}' => PAREN_NUMBERS[n - 0x278a + 1],
+        '\u{3251}'..='\u{325f}' => PAREN_NUMBERS[n - 0x3251 + 21],
+        '\u{32b1}'..='\u{32bf}' => PAREN_NUMBERS[n - 0x32b1 + 36],
+        '\u{1f100}' => FULL_STOP[0],
+        '\u{1f101}'..='\u{1f10a}' => COMMA[n - 0x1f101],
+        // circled and parenthesized letters
+        '\u{249c}'..='\u{24b5}' => PAREN_LOWER[n - 0x249c],
+        '\u{24b6}'..='\u{24cf}' => PAREN_UPPER[n - 0x24b6],
+        '\u{24d0}'..='\u{24e9}' => PAREN_LOWER[n - 0x24d0],
+        '\u{1f110}'..='\u{1f129}' => PAREN_UPPER[n - 0x1f110],
+        '\u{1f150}'..='\u{1f169}' => PAREN_UPPER[n - 0x1f150],
+        // squared letters
+        '\u{1f130}'..='\u{1f149}' => letter(n - 0x1f130),
+        '\u{1f170}'..='\u{1f189}' => letter(n - 0x1f170),
+        _ => return None,
+    })
+}
+
+fn letter(i: usize) -> &'static str {
+    &UPPER[i..=i]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::get;
+
+    #[test]
+    fn test_get() {
+        assert_eq!(get('①'), Some("(1)"));
+        assert_eq!(get('⑳'), Some("(20)"));
+        assert_eq!(get('⒜'), Some("(a)"));
+        assert_eq!(get('⒛'), Some("20."));
+        assert_eq!(get('㊿'), Some("(50)"));
+        assert_eq!(get('🅰'), Some("A"));
+        assert_eq!(get('🅉'), Some("Z"));
+        assert_eq!(get('a'), None);
+    }
+}
@@ -0,0 +1,33 @@
+//! Full-width forms of ASCII, as typed with CJK input methods, e.g. Ａ１，
+//! to A1, and the ideographic space to a space.
+
+/// The printable ASCII chars, from the space to the tilde, which the
+/// full-width forms map to in order.
+const PRINTABLE: &str = " !\"#$%&'()*+,-./0123456789:;<=>?@\
+                         ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`\
+                         abcdefghijklmnopqrstuvwxyz{|}~";
+
+pub(crate) fn get(c: char) -> Option<&'static str> {
+    match c {
+        // the ideographic space, the full-width form of the space
+        '\u{3000}' => Some(" "),
+        '\u{ff01}'..='\u{ff5e}' => {
+            let i = (c as u32 - 0xff00) as usize;
+            Some(&PRINTABLE[i..i + 1])
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::get;
+
+    #[test]
+    fn test_fullwidth() {
+        let s: String =
+            "Ａ１，　ｚ～！".chars().map(|c| get(c).unwrap()).collect();
+        assert_eq!(s, "A1, z~!");
+        assert_eq!(get('\u{ff5f}'), None);
+    }
+}
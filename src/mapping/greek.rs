@@ -0,0 +1,58 @@
+//! Spelled-out names of Greek letters, so that formulas in scientific text
+//! stay interpretable, e.g. π to pi and Δ to Delta. Accented letters map to
+//! the names of their base letters.
+
+pub(crate) fn get(c: char) -> Option<&'static str> {
+    Some(match c {
+        'α' | 'ά' => "alpha",
+        'β' | 'ϐ' => "beta",
+        'γ' => "gamma",
+        'δ' => "delta",
+        'ε' | 'έ' | 'ϵ' => "epsilon",
+        'ζ' => "zeta",
+        'η' | 'ή' => "eta",
+        'θ' | 'ϑ' => "theta",
+        'ι' | 'ί' | 'ϊ' | 'ΐ' => "iota",
+        'κ' | 'ϰ' => "kappa",
+        'λ' => "lambda",
+        // including MICRO SIGN
+        'μ' | 'µ' => "mu",
+        'ν' => "nu",
+        'ξ' => "xi",
+        'ο' | 'ό' => "omicron",
+        'π' | 'ϖ' => "pi",
+        'ρ' | 'ϱ' => "rho",
+        'σ' | 'ς' => "sigma",
+        'τ' => "tau",
+        'υ' | 'ύ' | 'ϋ' | 'ΰ' => "upsilon",
+        'φ' | 'ϕ' => "phi",
+        'χ' => "chi",
+        'ψ' => "psi",
+        'ω' | 'ώ' => "omega",
+        'Α' | 'Ά' => "Alpha",
+        'Β' => "Beta",
+        'Γ' => "Gamma",
+        'Δ' => "Delta",
+        'Ε' | 'Έ' => "Epsilon",
+        'Ζ' => "Zeta",
+        'Η' | 'Ή' => "Eta",
+        'Θ' | 'ϴ' => "Theta",
+        'Ι' | 'Ί' | 'Ϊ' => "Iota",
+        'Κ' => "Kappa",
+        'Λ' => "Lambda",
+        'Μ' => "Mu",
+        'Ν' => "Nu",
+        'Ξ' => "Xi",
+        'Ο' | 'Ό' => "Omicron",
+        'Π' => "Pi",
+        'Ρ' => "Rho",
+        'Σ' => "Sigma",
+        'Τ' => "Tau",
+        'Υ' | 'Ύ' | 'Ϋ' | 'ϒ' => "Upsilon",
+        'Φ' => "Phi",
+        'Χ' => "Chi",
+        'Ψ' => "Psi",
+        'Ω' | 'Ώ' => "Omega",
+        _ => return None,
+    })
+}
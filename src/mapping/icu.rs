@@ -0,0 +1,202 @@
+//! Parsing of transliteration rules in a subset of the ICU transform syntax,
+//! so that existing rule files can be reused. Supported are conversion rules
+//! from a single char to a string, as in
+//!
+//! ```text
+//! # German
+//! ü > ue ;
+//! 'ß' → ss ;
+//! Ä <> AE ;
+//! ```
+//!
+//! with quoting, `\uXXXX`/`\x{...}` escapes and comments. Backward rules and
+//! `::` transform directives are ignored. Variables, contexts, sets and
+//! multi-char sources are not supported.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+/// An error in the rules, at 1-based `line`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseRulesError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseRulesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for ParseRulesError {}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Token {
+    Lit(char),
+    /// `>`, `<>` or `<`
+    Op(&'static str),
+}
+
+/// Parse `rules`, returning the forward mapping of the chars.
+pub fn parse_rules(
+    rules: &str,
+) -> Result<HashMap<char, String>, ParseRulesError> {
+    let mut table: HashMap<char, String> = HashMap::new();
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut chars = rules.chars().peekable();
+    let mut line: usize = 1;
+    let mut stmt_line: usize = 1;
+    let err = |line: usize, message: &str| ParseRulesError {
+        line,
+        message: message.to_string(),
+    };
+    while let Some(c) = chars.next() {
+        if tokens.is_empty() {
+            stmt_line = line;
+        }
+        match c {
+            '\n' => line += 1,
+            c if c.is_whitespace() => (),
+            '#' => while chars.next_if(|&c| c != '\n').is_some() {},
+            '\'' => loop {
+                match chars.next() {
+                    Some('\'') if chars.next_if_eq(&'\'').is_some() => {
+                        tokens.push(Token::Lit('\''))
+                    }
+                    Some('\'') => break,
+                    Some(c) => {
+                        if c == '\n' {
+                            line += 1;
+                        }
+                        tokens.push(Token::Lit(c));
+                    }
+                    None => return Err(err(line, "unterminated quote")),
+                }
+            },
+            '\\' => {
+                let c = parse_escape(&mut chars)
+                    .ok_or_else(|| err(line, "invalid escape"))?;
+                tokens.push(Token::Lit(c));
+            }
+            '>' | '→' => tokens.push(Token::Op(">")),
+            '↔' => tokens.push(Token::Op("<>")),
+            '<' | '←' => {
+                if c == '<' && chars.next_if_eq(&'>').is_some() {
+                    tokens.push(Token::Op("<>"));
+                } else {
+                    tokens.push(Token::Op("<"));
+                }
+            }
+            ':' if chars.next_if_eq(&':').is_some() => {
+                // a transform directive, as in `::NFD;`
+                while chars.next_if(|&c| c != ';').is_some() {}
+                chars.next();
+            }
+            ';' => {
+                add_rule(&mut table, &tokens)
+                    .map_err(|message| err(stmt_line, message))?;
+                tokens.clear();
+            }
+            '$' => return Err(err(line, "variables are not supported")),
+            '{' | '}' | '|' => {
+                return Err(err(line, "contexts are not supported"))
+            }
+            '[' | ']' => return Err(err(line, "sets are not supported")),
+            c if c.is_ascii_punctuation() => {
+                return Err(err(line, &format!("{:?} must be quoted", c)))
+            }
+            c => tokens.push(Token::Lit(c)),
+        }
+    }
+    if !tokens.is_empty() {
+        return Err(err(stmt_line, "missing ';'"));
+    }
+
+    Ok(table)
+}
+
+/// Parse the escape after a backslash.
+fn parse_escape<I: Iterator<Item = char>>(
+    chars: &mut std::iter::Peekable<I>,
+) -> Option<char> {
+    match chars.next()? {
+        'u' => {
+            let hex: String = (0..4).filter_map(|_| chars.next()).collect();
+            char::from_u32(u32::from_str_radix(&hex, 16).ok()?)
+        }
+        'x' if chars.next_if_eq(&'{').is_some() => {
+            let hex: String =
+                std::iter::from_fn(|| chars.next_if(|&c| c != '}')).collect();
+            chars.next_if_eq(&'}')?;
+            char::from_u32(u32::from_str_radix(&hex, 16).ok()?)
+        }
+        'n' => Some('\n'),
+        't' => Some('\t'),
+        c => Some(c),
+    }
+}
+
+fn add_rule(
+    table: &mut HashMap<char, String>,
+    tokens: &[Token],
+) -> Result<(), &'static str> {
+    if tokens.is_empty() {
+        return Ok(());
+    }
+    let Some(op) = tokens.iter().position(|t| matches!(t, Token::Op(_))) else {
+        return Err("missing '>'");
+    };
+    let (source, target) = (&tokens[..op], &tokens[op + 1..]);
+    if target.iter().any(|t| matches!(t, Token::Op(_))) {
+        return Err("more than one operator");
+    }
+    if tokens[op] == Token::Op("<") {
+        return Ok(());
+    }
+    let [Token::Lit(source)] = source else {
+        return Err("the source must be a single char");
+    };
+    let target: String = target
+        .iter()
+        .map(|t| match t {
+            Token::Lit(c) => *c,
+            Token::Op(_) => unreachable!(),
+        })
+        .collect();
+    // As in ICU, the first matching rule wins.
+    table.entry(*source).or_insert(target);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_rules;
+
+    #[test]
+    fn test_parse_rules() {
+        let table = parse_rules(
+            "# German\n\
+             ::NFC;\n\
+             ü > ue ; 'ß' → ss;\n\
+             \\u00C4 <> AE ; ö < x;\n\
+             ü > u;\n\
+             '''' > \\' ;",
+        )
+        .unwrap();
+        assert_eq!(table.len(), 4);
+        assert_eq!(table[&'ü'], "ue");
+        assert_eq!(table[&'ß'], "ss");
+        assert_eq!(table[&'Ä'], "AE");
+        assert_eq!(table[&'\''], "'");
+    }
+
+    #[test]
+    fn test_parse_rules_error() {
+        let e = parse_rules("a > b;\nab > c;").unwrap_err();
+        assert_eq!(e.line, 2);
+        assert!(parse_rules("$v = a;").is_err());
+        assert!(parse_rules("a > b").is_err());
+    }
+}
@@ -0,0 +1,196 @@
+//! Language-specific transliteration, since e.g. ö is spelled oe in German
+//! but o in Swedish.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// A language with its own transliteration conventions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    /// Danish
+    Da,
+    /// German
+    De,
+    /// Icelandic
+    Is,
+    /// Norwegian Bokmål
+    Nb,
+    /// Swedish
+    Sv,
+    /// Turkish
+    Tr,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 6] = [
+        Locale::Da,
+        Locale::De,
+        Locale::Is,
+        Locale::Nb,
+        Locale::Sv,
+        Locale::Tr,
+    ];
+
+    /// Return the BCP 47 language tag.
+    pub fn tag(&self) -> &'static str {
+        match self {
+            Locale::Da => "da",
+            Locale::De => "de",
+            Locale::Is => "is",
+            Locale::Nb => "nb",
+            Locale::Sv => "sv",
+            Locale::Tr => "tr",
+        }
+    }
+
+    /// Return what `c` is transliterated to in this language, if the
+    /// language has a convention for it.
+    pub fn get(&self, c: char) -> Option<&'static str> {
+        match self {
+            Locale::Da | Locale::Nb => da_nb(c),
+            Locale::De => de(c),
+            Locale::Is => is(c),
+            Locale::Sv => sv(c),
+            Locale::Tr => tr(c),
+        }
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.tag())
+    }
+}
+
+impl FromStr for Locale {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.to_ascii_lowercase();
+        // "no" is the macrolanguage of "nb"
+        let s = if s == "no" { "nb" } else { &s };
+        Locale::ALL
+            .into_iter()
+            .find(|l| l.tag() == s)
+            .ok_or_else(|| {
+                let tags: Vec<&str> =
+                    Locale::ALL.iter().map(Locale::tag).collect();
+                format!(
+                    "unknown locale {:?}, expected one of {}",
+                    s,
+                    tags.join(", ")
+                )
+            })
+    }
+}
+
+fn da_nb(c: char) -> Option<&'static str> {
+    Some(match c {
+        'æ' => "ae",
+        'Æ' => "AE",
+        'ø' => "oe",
+        'Ø' => "OE",
+        'å' => "aa",
+        'Å' => "AA",
+        _ => return None,
+    })
+}
+
+fn de(c: char) -> Option<&'static str> {
+    Some(match c {
+        'ä' => "ae",
+        'Ä' => "AE",
+        'ö' => "oe",
+        'Ö' => "OE",
+        'ü' => "ue",
+        'Ü' => "UE",
+        'ß' => "ss",
+        'ẞ' => "SS",
+        _ => return None,
+    })
+}
+
+fn is(c: char) -> Option<&'static str> {
+    Some(match c {
+        'þ' => "th",
+        'Þ' => "TH",
+        'ð' => "d",
+        'Ð' => "D",
+        'æ' => "ae",
+        'Æ' => "AE",
+        'ö' => "o",
+        'Ö' => "O",
+        'á' => "a",
+        'Á' => "A",
+        'é' => "e",
+        'É' => "E",
+        'í' => "i",
+        'Í' => "I",
+        'ó' => "o",
+        'Ó' => "O",
+        'ú' => "u",
+        'Ú' => "U",
+        'ý' => "y",
+        'Ý' => "Y",
+        _ => return None,
+    })
+}
+
+fn sv(c: char) -> Option<&'static str> {
+    Some(match c {
+        'å' => "a",
+        'Å' => "A",
+        'ä' => "a",
+        'Ä' => "A",
+        'ö' => "o",
+        'Ö' => "O",
+        'é' => "e",
+        'É' => "E",
+        _ => return None,
+    })
+}
+
+fn tr(c: char) -> Option<&'static str> {
+    Some(match c {
+        // dotless i and dotted capital I
+        'ı' => "i",
+        'İ' => "I",
+        'ç' => "c",
+        'Ç' => "C",
+        'ğ' => "g",
+        'Ğ' => "G",
+        'ö' => "o",
+        'Ö' => "O",
+        'ş' => "s",
+        'Ş' => "S",
+        'ü' => "u",
+        'Ü' => "U",
+        'â' => "a",
+        'Â' => "A",
+        'î' => "i",
+        'Î' => "I",
+        'û' => "u",
+        'Û' => "U",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Locale;
+
+    #[test]
+    fn test_locale_get() {
+        assert_eq!(Locale::De.get('ö'), Some("oe"));
+        assert_eq!(Locale::Sv.get('ö'), Some("o"));
+        assert_eq!(Locale::Tr.get('ı'), Some("i"));
+        assert_eq!(Locale::De.get('ı'), None);
+    }
+
+    #[test]
+    fn test_locale_from_str() {
+        assert_eq!("DE".parse(), Ok(Locale::De));
+        assert_eq!("no".parse(), Ok(Locale::Nb));
+        assert!("xx".parse::<Locale>().is_err());
+    }
+}
@@ -0,0 +1,54 @@
+//! ASCII equivalents of common mathematical operators, so that measurements
+//! and formulas keep their operators.
+
+pub(crate) fn get(c: char) -> Option<&'static str> {
+    Some(match c {
+        '×' | '⨯' => "x",
+        '÷' | '∕' => "/",
+        // MINUS SIGN
+        '−' => "-",
+        '∓' => "-/+",
+        '±' => "+/-",
+        '∗' => "*",
+        '≤' | '⩽' => "<=",
+        '≥' | '⩾' => ">=",
+        '≠' => "!=",
+        '≈' => "~=",
+        '≡' => "==",
+        '∼' => "~",
+        '≪' => "<<",
+        '≫' => ">>",
+        '∞' => "inf",
+        '√' => "sqrt",
+        '∑' => "sum",
+        '∏' => "prod",
+        '∫' => "int",
+        '∂' => "d",
+        '∆' => "Delta",
+        '∈' => "in",
+        '¬' => "!",
+        '∧' => "&",
+        '∨' => "|",
+        '¼' => "1/4",
+        '½' => "1/2",
+        '¾' => "3/4",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::get;
+
+    #[test]
+    fn test_get() {
+        assert_eq!(get('×'), Some("x"));
+        assert_eq!(get('÷'), Some("/"));
+        assert_eq!(get('−'), Some("-"));
+        assert_eq!(get('≤'), Some("<="));
+        assert_eq!(get('≥'), Some(">="));
+        assert_eq!(get('≠'), Some("!="));
+        assert_eq!(get('±'), Some("+/-"));
+        assert_eq!(get('-'), None);
+    }
+}
@@ -0,0 +1,241 @@
+//! Mappings of chars to ASCII strings, consulted for each char that would
+//! otherwise be dropped, so that text keeps its meaning instead of developing
+//! holes.
+
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::sync::Arc;
+
+use crate::{ControlEscape, Escape, Replacer};
+
+mod arrows;
+mod bullets;
+#[cfg(feature = "unicode-normalization")]
+mod diacritics;
+mod enclosed;
+mod fullwidth;
+mod greek;
+mod icu;
+mod locale;
+mod math;
+mod punctuation;
+mod roman;
+mod spaces;
+mod symbols;
+mod tsv;
+mod unidecode;
+mod units;
+
+pub use icu::{parse_rules, ParseRulesError};
+pub use locale::Locale;
+pub use tsv::parse_tsv;
+pub use units::RULES as UNITS_RULES;
+
+/// A table mapping chars to ASCII strings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mapping {
+    /// Language-specific transliteration, e.g. German ö to oe.
+    Translit(Locale),
+    /// A table loaded at runtime, e.g. by [`parse_rules`] or [`parse_tsv`].
+    Table(Arc<HashMap<char, String>>),
+    /// Spell-outs of symbols, e.g. © to (c).
+    Symbols,
+    /// Arrows, e.g. → to ->.
+    Arrows,
+    /// Box-drawing chars, e.g. ┌ to +.
+    BoxDrawing,
+    /// Bullets and list markers, e.g. • to *.
+    Bullets,
+    /// Roman numerals, e.g. Ⅳ to IV.
+    Roman,
+    /// Enclosed alphanumerics, e.g. ① to (1) and 🅰 to A.
+    Enclosed,
+    /// Full-width forms of ASCII, e.g. Ａ１， to A1,.
+    FullWidth,
+    /// Greek letters spelled out, e.g. π to pi.
+    Greek,
+    /// Mathematical operators, e.g. ≤ to <=.
+    Math,
+    /// Typographic punctuation, e.g. “ to " and — to --.
+    Punctuation,
+    /// Unicode spaces and line separators, e.g. NBSP and the ideographic
+    /// space to space, and U+2028 to '\n'.
+    Spaces,
+    /// Unit symbols, e.g. ℃ to degC, per the table [`UNITS_RULES`].
+    Units,
+    /// The closest ASCII approximations of Latin letters and typographic
+    /// punctuation, e.g. é to e, ß to ss and “ to ".
+    Unidecode,
+    /// Lookalikes of ASCII chars per the confusables of UTS #39, e.g.
+    /// Cyrillic а to a and Greek ο to o.
+    #[cfg(feature = "unicode-confusables")]
+    Confusables,
+}
+
+impl Mapping {
+    /// Return what `c` maps to, if at all.
+    pub fn get(&self, c: char) -> Option<&str> {
+        match self {
+            Mapping::Translit(locale) => locale.get(c),
+            Mapping::Table(table) => table.get(&c).map(String::as_str),
+            Mapping::Symbols => symbols::get(c),
+            Mapping::Arrows => arrows::arrow(c),
+            Mapping::BoxDrawing => arrows::box_drawing(c),
+            Mapping::Bullets => bullets::get(c),
+            Mapping::Roman => roman::get(c),
+            Mapping::Enclosed => enclosed::get(c),
+            Mapping::FullWidth => fullwidth::get(c),
+            Mapping::Greek => greek::get(c),
+            Mapping::Math => math::get(c),
+            Mapping::Punctuation => punctuation::get(c),
+            Mapping::Spaces => spaces::get(c),
+            Mapping::Units => units::get(c),
+            Mapping::Unidecode => unidecode::get(c),
+            #[cfg(feature = "unicode-confusables")]
+            Mapping::Confusables => crate::tables::confusable(c),
+        }
+    }
+}
+
+/// A [`Replacer`] which highlights dropped chars if enabled, escapes dropped
+/// control chars if enabled, looks up
+/// the other dropped chars in a list of mappings, the
+/// first one taking precedence, then strips the diacritics of the chars
+/// found in none if enabled, and substitutes the rest with their escapes or
+/// the replacement, or drops them if there is neither.
+#[derive(Debug, Clone)]
+pub(crate) struct MappingReplacer {
+    mappings: Vec<Mapping>,
+    #[cfg(feature = "unicode-normalization")]
+    strip_diacritics: bool,
+    highlight: bool,
+    control_escape: Option<ControlEscape>,
+    escape: Option<Escape>,
+    replacement: Option<String>,
+}
+
+impl MappingReplacer {
+    pub(crate) fn new(mappings: Vec<Mapping>) -> Self {
+        Self {
+            mappings,
+            #[cfg(feature = "unicode-normalization")]
+            strip_diacritics: false,
+            highlight: false,
+            control_escape: None,
+            escape: None,
+            replacement: None,
+        }
+    }
+
+    /// Substitute the chars found in no mapping with their decompositions
+    /// without the combining marks, if those are in the ASCII subset.
+    #[cfg(feature = "unicode-normalization")]
+    pub(crate) fn with_strip_diacritics(mut self) -> Self {
+        self.strip_diacritics = true;
+        self
+    }
+
+    /// Keep all chars, wrapping each in reverse video escapes instead, so
+    /// that they stand out on a terminal.
+    pub(crate) fn with_highlight(mut self) -> Self {
+        self.highlight = true;
+        self
+    }
+
+    /// Substitute control chars with their escapes in the style `escape`,
+    /// before looking them up in the mappings.
+    pub(crate) fn with_control_escape(mut self, escape: ControlEscape) -> Self {
+        self.control_escape = Some(escape);
+        self
+    }
+
+    /// Substitute the chars found in no mapping with their escapes in the
+    /// style `escape`, taking precedence over the replacement.
+    pub(crate) fn with_escape(mut self, escape: Escape) -> Self {
+        self.escape = Some(escape);
+        self
+    }
+
+    /// Substitute the chars found in no mapping with `replacement`.
+    pub(crate) fn with_replacement(mut self, replacement: String) -> Self {
+        self.replacement = Some(replacement);
+        self
+    }
+}
+
+impl Replacer for MappingReplacer {
+    fn replace(&mut self, c: char, out: &mut dyn Write) -> io::Result<()> {
+        if self.highlight {
+            return write!(out, "\x1b[7m{}\x1b[27m", c);
+        }
+        if let Some(escape) = self.control_escape {
+            if ControlEscape::applies_to(c) {
+                return escape.write(c, out);
+            }
+        }
+        if let Some(s) = self.mappings.iter().find_map(|m| m.get(c)) {
+            return out.write_all(s.as_bytes());
+        }
+        #[cfg(feature = "unicode-normalization")]
+        if self.strip_diacritics {
+            if let Some(s) = diacritics::strip(c) {
+                return out.write_all(s.as_bytes());
+            }
+        }
+        match (self.escape, &self.replacement) {
+            (Some(escape), _) => escape.write(c, out),
+            (None, Some(s)) => out.write_all(s.as_bytes()),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Locale, Mapping, MappingReplacer};
+    use crate::{Escape, Replacer};
+
+    #[test]
+    fn test_mapping_replacer_precedence() {
+        let mut r =
+            MappingReplacer::new(vec![Mapping::Greek, Mapping::Symbols]);
+        let mut out: Vec<u8> = Vec::new();
+        for c in "µ©".chars() {
+            r.replace(c, &mut out).unwrap();
+        }
+        assert_eq!(out, b"mu(c)");
+    }
+
+    #[test]
+    fn test_mapping_replacer() {
+        let mut r = MappingReplacer::new(vec![
+            Mapping::Translit(Locale::De),
+            Mapping::Symbols,
+        ]);
+        let mut out: Vec<u8> = Vec::new();
+        for c in "öé©".chars() {
+            r.replace(c, &mut out).unwrap();
+        }
+        assert_eq!(out, b"oe(c)");
+    }
+
+    #[test]
+    fn test_mapping_replacer_replacement() {
+        let mut r = MappingReplacer::new(vec![Mapping::Symbols])
+            .with_replacement("?".to_string());
+        let mut out: Vec<u8> = Vec::new();
+        for c in "©你".chars() {
+            r.replace(c, &mut out).unwrap();
+        }
+        assert_eq!(out, b"(c)?");
+
+        let mut r = MappingReplacer::new(vec![Mapping::Symbols])
+            .with_replacement("?".to_string())
+            .with_escape(Escape::Unicode);
+        let mut out: Vec<u8> = Vec::new();
+        for c in "©你".chars() {
+            r.replace(c, &mut out).unwrap();
+        }
+        assert_eq!(out, b"(c)\\u{4F60}");
+    }
+}
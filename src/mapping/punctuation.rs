@@ -0,0 +1,31 @@
+//! ASCII equivalents of typographic punctuation, so that prose copied from
+//! word processors keeps its quotes and dashes.
+
+pub(crate) fn get(c: char) -> Option<&'static str> {
+    Some(match c {
+        '‘' | '’' | '‚' | '‛' | '′' | '‹' | '›' => "'",
+        '“' | '”' | '„' | '‟' | '″' | '«' | '»' => "\"",
+        '‐' | '‑' | '‒' | '–' | '−' => "-",
+        '—' | '―' => "--",
+        '…' => "...",
+        '\u{a0}' | '\u{2002}'..='\u{200a}' | '\u{202f}' => " ",
+        '¡' => "!",
+        '¿' => "?",
+        '⁄' => "/",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::get;
+
+    #[test]
+    fn test_punctuation() {
+        let s: String = "«Oui» — “it’s…”"
+            .chars()
+            .map(|c| get(c).map_or(c.to_string(), str::to_string))
+            .collect();
+        assert_eq!(s, "\"Oui\" -- \"it's...\"");
+    }
+}
@@ -0,0 +1,39 @@
+//! ASCII letter sequences for the Roman numerals in the Number Forms block.
+
+const UPPER: [&str; 16] = [
+    "I", "II", "III", "IV", "V", "VI", "VII", "VIII", "IX", "X", "XI", "XII",
+    "L", "C", "D", "M",
+];
+
+const LOWER: [&str; 16] = [
+    "i", "ii", "iii", "iv", "v", "vi", "vii", "viii", "ix", "x", "xi", "xii",
+    "l", "c", "d", "m",
+];
+
+pub(crate) fn get(c: char) -> Option<&'static str> {
+    Some(match c {
+        '\u{2160}'..='\u{216f}' => UPPER[c as usize - 0x2160],
+        '\u{2170}'..='\u{217f}' => LOWER[c as usize - 0x2170],
+        // ROMAN NUMERAL ONE THOUSAND C D
+        '\u{2180}' => "M",
+        // ROMAN NUMERAL SIX LATE FORM
+        '\u{2185}' => "VI",
+        // ROMAN NUMERAL FIFTY EARLY FORM
+        '\u{2186}' => "L",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::get;
+
+    #[test]
+    fn test_get() {
+        assert_eq!(get('Ⅰ'), Some("I"));
+        assert_eq!(get('Ⅳ'), Some("IV"));
+        assert_eq!(get('Ⅿ'), Some("M"));
+        assert_eq!(get('ⅻ'), Some("xii"));
+        assert_eq!(get('X'), None);
+    }
+}
@@ -0,0 +1,33 @@
+//! ASCII equivalents of Unicode spaces and line separators, so that words
+//! separated by them are not glued together.
+
+pub(crate) fn get(c: char) -> Option<&'static str> {
+    Some(match c {
+        // no-break, fixed-width, thin, mathematical and ideographic spaces,
+        // and the Ogham space mark
+        '\u{a0}'
+        | '\u{1680}'
+        | '\u{2000}'..='\u{200a}'
+        | '\u{202f}'
+        | '\u{205f}'
+        | '\u{3000}' => " ",
+        // next line, line and paragraph separators
+        '\u{85}' | '\u{2028}' | '\u{2029}' => "\n",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::get;
+
+    #[test]
+    fn test_spaces() {
+        let s: String = "1\u{a0}000\u{2009}km\u{3000}ok\u{2028}next"
+            .chars()
+            .map(|c| get(c).map_or(c.to_string(), str::to_string))
+            .collect();
+        assert_eq!(s, "1 000 km ok\nnext");
+        assert_eq!(get('\u{200b}'), None);
+    }
+}
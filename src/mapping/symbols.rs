@@ -0,0 +1,41 @@
+//! Spell-outs of common symbols, so that legal and unit markers keep their
+//! meaning.
+
+pub(crate) fn get(c: char) -> Option<&'static str> {
+    Some(match c {
+        '©' => "(c)",
+        '®' => "(R)",
+        '™' => "(TM)",
+        '℠' => "(SM)",
+        '℗' => "(P)",
+        '°' => "deg",
+        'µ' => "u",
+        '§' => "S",
+        '¶' => "P",
+        '№' => "No.",
+        '℅' => "c/o",
+        '¢' => "c",
+        '£' => "GBP",
+        '¥' => "JPY",
+        '€' => "EUR",
+        '¦' => "|",
+        '†' => "+",
+        '‡' => "++",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::get;
+
+    #[test]
+    fn test_get() {
+        assert_eq!(get('©'), Some("(c)"));
+        assert_eq!(get('™'), Some("(TM)"));
+        assert_eq!(get('°'), Some("deg"));
+        assert_eq!(get('№'), Some("No."));
+        assert_eq!(get('€'), Some("EUR"));
+        assert_eq!(get('c'), None);
+    }
+}
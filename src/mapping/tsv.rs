@@ -0,0 +1,71 @@
+//! Parsing of mapping tables in TSV, a char, a tab and its replacement per
+//! line, e.g. `µ<TAB>u` or `U+00B0<TAB>deg`. The char is given literally or
+//! as a hex codepoint (`U+..` or `0x..`), and the replacement is the rest of
+//! the line, taken literally. Blank lines and lines starting with `#` are
+//! ignored.
+
+use std::collections::HashMap;
+
+use super::ParseRulesError;
+
+/// Parse the char of a line, given literally or as a hex codepoint.
+fn parse_char(s: &str) -> Option<char> {
+    let hex = s
+        .strip_prefix("U+")
+        .or_else(|| s.strip_prefix("u+"))
+        .or_else(|| s.strip_prefix("0x"))
+        .or_else(|| s.strip_prefix("0X"));
+    if let Some(hex) = hex {
+        return u32::from_str_radix(hex, 16).ok().and_then(char::from_u32);
+    }
+    let mut chars = s.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c),
+        _ => None,
+    }
+}
+
+/// Parse the TSV `table`, returning the mapping of the chars. The first line
+/// of a char takes precedence.
+pub fn parse_tsv(
+    table: &str,
+) -> Result<HashMap<char, String>, ParseRulesError> {
+    let mut mapping: HashMap<char, String> = HashMap::new();
+    for (i, line) in table.lines().enumerate() {
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let err = |message: String| ParseRulesError {
+            line: i + 1,
+            message,
+        };
+        let (c, replacement) = line
+            .split_once('\t')
+            .ok_or_else(|| err("expected a tab".to_string()))?;
+        let c = parse_char(c).ok_or_else(|| {
+            err(format!("expected a char or a codepoint, not {:?}", c))
+        })?;
+        mapping.entry(c).or_insert_with(|| replacement.to_string());
+    }
+    Ok(mapping)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_tsv;
+
+    #[test]
+    fn test_parse_tsv() {
+        let table =
+            parse_tsv("# units\nµ\tu\n\nU+00B0\tdeg\n0x2122\t(TM) \nµ\tmu\n")
+                .unwrap();
+        assert_eq!(table.len(), 3);
+        assert_eq!(table[&'µ'], "u");
+        assert_eq!(table[&'°'], "deg");
+        assert_eq!(table[&'™'], "(TM) ");
+
+        assert_eq!(parse_tsv("µ\tu\nµ u\n").unwrap_err().line, 2);
+        assert!(parse_tsv("ab\tc\n").is_err());
+        assert!(parse_tsv("U+110000\tc\n").is_err());
+    }
+}
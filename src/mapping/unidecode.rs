@@ -0,0 +1,96 @@
+//! The closest ASCII approximations of Latin letters and typographic
+//! punctuation, in the style of unidecode, e.g. é to e, ß to ss and “ to ",
+//! for when a lossy name beats a name with holes. Punctuation maps as by
+//! [`Mapping::Punctuation`](super::Mapping::Punctuation). Scripts with no ASCII
+//! approximation, e.g. CJK, are left to the other mappings.
+
+use super::punctuation;
+
+pub(crate) fn get(c: char) -> Option<&'static str> {
+    Some(match c {
+        'À' | 'Á' | 'Â' | 'Ã' | 'Ä' | 'Å' => "A",
+        'Ā' | 'Ă' | 'Ą' | 'Ǎ' | 'Ǻ' => "A",
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => "a",
+        'ā' | 'ă' | 'ą' | 'ǎ' | 'ǻ' => "a",
+        'Æ' | 'Ǽ' => "AE",
+        'æ' | 'ǽ' => "ae",
+        'Ç' | 'Ć' | 'Ĉ' | 'Ċ' | 'Č' => "C",
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+        'Ð' | 'Ď' | 'Đ' => "D",
+        'ð' | 'ď' | 'đ' => "d",
+        'È' | 'É' | 'Ê' | 'Ë' | 'Ē' | 'Ĕ' | 'Ė' | 'Ę' | 'Ě' => "E",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+        'Ĝ' | 'Ğ' | 'Ġ' | 'Ģ' => "G",
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+        'Ĥ' | 'Ħ' => "H",
+        'ĥ' | 'ħ' => "h",
+        'Ì' | 'Í' | 'Î' | 'Ï' | 'Ĩ' | 'Ī' => "I",
+        'Ĭ' | 'Į' | 'İ' | 'Ǐ' => "I",
+        'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' => "i",
+        'ĭ' | 'į' | 'ı' | 'ǐ' => "i",
+        'Ĳ' => "IJ",
+        'ĳ' => "ij",
+        'Ĵ' => "J",
+        'ĵ' => "j",
+        'Ķ' => "K",
+        'ķ' | 'ĸ' => "k",
+        'Ĺ' | 'Ļ' | 'Ľ' | 'Ŀ' | 'Ł' => "L",
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+        'Ñ' | 'Ń' | 'Ņ' | 'Ň' | 'Ŋ' => "N",
+        'ñ' | 'ń' | 'ņ' | 'ň' | 'ŉ' | 'ŋ' => "n",
+        'Ò' | 'Ó' | 'Ô' | 'Õ' | 'Ö' | 'Ø' => "O",
+        'Ō' | 'Ŏ' | 'Ő' | 'Ǒ' | 'Ǿ' => "O",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' => "o",
+        'ō' | 'ŏ' | 'ő' | 'ǒ' | 'ǿ' => "o",
+        'Œ' => "OE",
+        'œ' => "oe",
+        'Ŕ' | 'Ŗ' | 'Ř' => "R",
+        'ŕ' | 'ŗ' | 'ř' => "r",
+        'Ś' | 'Ŝ' | 'Ş' | 'Š' | 'Ș' => "S",
+        'ś' | 'ŝ' | 'ş' | 'š' | 'ș' | 'ſ' => "s",
+        'ß' => "ss",
+        'ẞ' => "SS",
+        'Ţ' | 'Ť' | 'Ŧ' | 'Ț' => "T",
+        'ţ' | 'ť' | 'ŧ' | 'ț' => "t",
+        'Þ' => "TH",
+        'þ' => "th",
+        'Ù' | 'Ú' | 'Û' | 'Ü' | 'Ũ' | 'Ū' => "U",
+        'Ŭ' | 'Ů' | 'Ű' | 'Ų' | 'Ǔ' => "U",
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' => "u",
+        'ŭ' | 'ů' | 'ű' | 'ų' | 'ǔ' => "u",
+        'Ŵ' => "W",
+        'ŵ' => "w",
+        'Ý' | 'Ÿ' | 'Ŷ' => "Y",
+        'ý' | 'ÿ' | 'ŷ' => "y",
+        'Ź' | 'Ż' | 'Ž' => "Z",
+        'ź' | 'ż' | 'ž' => "z",
+        'ﬀ' => "ff",
+        'ﬁ' => "fi",
+        'ﬂ' => "fl",
+        'ﬃ' => "ffi",
+        'ﬄ' => "ffl",
+        'ﬅ' | 'ﬆ' => "st",
+        '·' => ".",
+        '×' => "x",
+        '÷' => "/",
+        _ => return punctuation::get(c),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::get;
+
+    #[test]
+    fn test_unidecode() {
+        let s: String = "Crème “brûlée” – Straße…"
+            .chars()
+            .map(|c| match get(c) {
+                Some(s) => s.to_string(),
+                None => c.to_string(),
+            })
+            .collect();
+        assert_eq!(s, "Creme \"brulee\" - Strasse...");
+        assert_eq!(get('漢'), None);
+    }
+}
@@ -0,0 +1,34 @@
+//! ASCII spellings of unit symbols, e.g. ℃ to degC and ‰ to 0/00, for
+//! sensor and telemetry logs. The table is written in the rules syntax of
+//! [`parse_rules`](super::parse_rules), so that it can be copied and extended
+//! as a rule file.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use super::icu::parse_rules;
+
+/// The built-in table, as rules.
+pub const RULES: &str = include_str!("units.rules");
+
+pub(crate) fn get(c: char) -> Option<&'static str> {
+    static TABLE: OnceLock<HashMap<char, String>> = OnceLock::new();
+    TABLE
+        .get_or_init(|| parse_rules(RULES).unwrap())
+        .get(&c)
+        .map(String::as_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::get;
+
+    #[test]
+    fn test_get() {
+        assert_eq!(get('\u{2103}'), Some("degC"));
+        assert_eq!(get('\u{212b}'), Some("A"));
+        assert_eq!(get('‰'), Some("0/00"));
+        assert_eq!(get('㎧'), Some("m/s"));
+        assert_eq!(get('a'), None);
+    }
+}
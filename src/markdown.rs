@@ -0,0 +1,169 @@
+//! Filtering of Markdown, where fenced code blocks and inline code spans are
+//! kept byte for byte, and only the prose around them is filtered, so that
+//! e.g. the UTF-8 test data in a code sample survives.
+
+use std::io::{self, BufRead, Write};
+
+/// Return the fence of a code block which `line` opens, as its char and
+/// length, if any, per CommonMark: up to 3 spaces, then 3 or more backticks
+/// or tildes, and no backtick in the info string of a backtick fence.
+fn opening_fence(line: &[u8]) -> Option<(u8, usize)> {
+    let indent = line.iter().take_while(|&&b| b == b' ').count();
+    if indent > 3 {
+        return None;
+    }
+    let line = &line[indent..];
+    let c = *line.first().filter(|&&c| c == b'`' || c == b'~')?;
+    let len = line.iter().take_while(|&&b| b == c).count();
+    let info = &line[len..];
+    (len >= 3 && !(c == b'`' && info.contains(&b'`'))).then_some((c, len))
+}
+
+/// Return true if `line` closes a code block opened by `fence`: up to 3
+/// spaces, then at least as many of its char, then nothing but whitespace.
+fn closes(line: &[u8], (c, len): (u8, usize)) -> bool {
+    let indent = line.iter().take_while(|&&b| b == b' ').count();
+    let line = &line[indent..];
+    let n = line.iter().take_while(|&&b| b == c).count();
+    indent <= 3 && n >= len && line[n..].iter().all(u8::is_ascii_whitespace)
+}
+
+/// Return the length of the run of backticks at the start of `bytes`.
+fn backticks(bytes: &[u8]) -> usize {
+    bytes.iter().take_while(|&&b| b == b'`').count()
+}
+
+/// Write `line` to `out`, with the prose transformed by `filter`, and the
+/// inline code spans kept. A span is closed by a run of as many backticks
+/// as opened it, in the same line; a run of backticks which is not closed is
+/// prose.
+fn filter_line<F: FnMut(&[u8]) -> Vec<u8>>(
+    line: &[u8],
+    filter: &mut F,
+    out: &mut Vec<u8>,
+) {
+    let mut prose = 0;
+    let mut i = 0;
+    while i < line.len() {
+        if line[i] != b'`' {
+            i += 1;
+            continue;
+        }
+        let open = backticks(&line[i..]);
+        let mut j = i + open;
+        let close = loop {
+            match line[j..].iter().position(|&b| b == b'`') {
+                Some(k) => {
+                    let n = backticks(&line[j + k..]);
+                    if n == open {
+                        break Some(j + k + n);
+                    }
+                    j += k + n;
+                }
+                None => break None,
+            }
+        };
+        match close {
+            Some(end) => {
+                if prose < i {
+                    out.extend_from_slice(&filter(&line[prose..i]));
+                }
+                out.extend_from_slice(&line[i..end]);
+                prose = end;
+                i = end;
+            }
+            None => i += open,
+        }
+    }
+    if prose < line.len() {
+        out.extend_from_slice(&filter(&line[prose..]));
+    }
+}
+
+/// Read Markdown from `r`, and write it to `w` with the prose transformed by
+/// `filter`, line by line, and the fenced code blocks and the inline code
+/// spans kept as they are. A code block which is not closed runs to the end
+/// of the input, as in CommonMark.
+pub(crate) fn filter<R, W, F>(
+    r: &mut R,
+    w: &mut W,
+    filter: &mut F,
+) -> io::Result<()>
+where
+    R: BufRead,
+    W: Write,
+    F: FnMut(&[u8]) -> Vec<u8>,
+{
+    // the fence of the code block the line is in, if any
+    let mut fence: Option<(u8, usize)> = None;
+    let mut line: Vec<u8> = Vec::new();
+    let mut out: Vec<u8> = Vec::new();
+    while r.read_until(b'\n', &mut line)? > 0 {
+        let content = line.strip_suffix(b"\n").unwrap_or(&line);
+        let content = content.strip_suffix(b"\r").unwrap_or(content);
+        match fence {
+            Some(open) => {
+                if closes(content, open) {
+                    fence = None;
+                }
+                w.write_all(&line)?;
+            }
+            None => {
+                fence = opening_fence(content);
+                if fence.is_some() {
+                    w.write_all(&line)?;
+                } else {
+                    filter_line(content, filter, &mut out);
+                    out.extend_from_slice(&line[content.len()..]);
+                    w.write_all(&out)?;
+                    out.clear();
+                }
+            }
+        }
+        line.clear();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::filter;
+
+    fn drop_non_ascii(bytes: &[u8]) -> Vec<u8> {
+        bytes.iter().copied().filter(u8::is_ascii).collect()
+    }
+
+    fn filter_str(input: &str) -> String {
+        let mut w: Vec<u8> = Vec::new();
+        filter(&mut input.as_bytes(), &mut w, &mut drop_non_ascii).unwrap();
+        String::from_utf8(w).unwrap()
+    }
+
+    #[test]
+    fn test_filter() {
+        let input = "# Café\r\n\
+                     Use `naïve` or ``a ` é`` but not ` é\n\
+                     ```rust\n\
+                     let s = \"é\";\n\
+                     ```\n\
+                     ~~~~\n\
+                     ~~~\n\
+                     ü\n\
+                     ~~~~\n\
+                     → done\n";
+        let expected = "# Caf\r\n\
+                        Use `naïve` or ``a ` é`` but not ` \n\
+                        ```rust\n\
+                        let s = \"é\";\n\
+                        ```\n\
+                        ~~~~\n\
+                        ~~~\n\
+                        ü\n\
+                        ~~~~\n \
+                        done\n";
+        assert_eq!(filter_str(input), expected);
+        // a fence which is not closed, and one indented too much
+        assert_eq!(filter_str("```\né"), "```\né");
+        assert_eq!(filter_str("    ```\né\n"), "    ```\n\n");
+    }
+}
@@ -0,0 +1,106 @@
+//! Memory mapping of regular files, so that large inputs are filtered
+//! directly from the page cache rather than copied through a small buffer.
+//! The file must not be truncated while mapped, or reading the mapping
+//! faults, as with any mapping.
+
+use std::fs::File;
+use std::io;
+use std::ops::Deref;
+
+/// A read-only mapping of a whole file.
+#[cfg(unix)]
+pub(crate) struct Mmap {
+    ptr: *mut libc::c_void,
+    len: usize,
+}
+
+#[cfg(unix)]
+impl Mmap {
+    /// Map `file`, returning `None` if it is empty, whose mapping fails.
+    pub(crate) fn map(file: &File) -> io::Result<Option<Self>> {
+        use std::os::fd::AsRawFd;
+        let len = usize::try_from(file.metadata()?.len()).map_err(|_| {
+            io::Error::new(io::ErrorKind::Unsupported, "file too large to map")
+        })?;
+        if len == 0 {
+            return Ok(None);
+        }
+        // SAFETY: a fresh shared read-only mapping of a valid fd, which
+        // aliases no Rust memory.
+        let ptr = unsafe {
+            libc::mmap(
+                std::ptr::null_mut(),
+                len,
+                libc::PROT_READ,
+                libc::MAP_SHARED,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+        // The mapping is read in order, so ask for read-ahead; failing to is
+        // harmless.
+        // SAFETY: `ptr..ptr + len` is the mapping just made.
+        unsafe { libc::madvise(ptr, len, libc::MADV_SEQUENTIAL) };
+        Ok(Some(Self { ptr, len }))
+    }
+}
+
+#[cfg(unix)]
+impl Deref for Mmap {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        // SAFETY: the mapping is readable and lives as long as `self`.
+        unsafe { std::slice::from_raw_parts(self.ptr as *const u8, self.len) }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for Mmap {
+    fn drop(&mut self) {
+        // SAFETY: the mapping is no longer borrowed.
+        unsafe { libc::munmap(self.ptr, self.len) };
+    }
+}
+
+/// A mapping, which is never made where unsupported.
+#[cfg(not(unix))]
+pub(crate) struct Mmap(Vec<u8>);
+
+#[cfg(not(unix))]
+impl Mmap {
+    pub(crate) fn map(_file: &File) -> io::Result<Option<Self>> {
+        Ok(None)
+    }
+}
+
+#[cfg(not(unix))]
+impl Deref for Mmap {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::Mmap;
+    use std::fs::{self, File};
+
+    #[test]
+    fn test_mmap() {
+        let path = std::env::temp_dir()
+            .join(format!("ascii-filter-mmap-{}", std::process::id()));
+        fs::write(&path, "héllo").unwrap();
+        let map = Mmap::map(&File::open(&path).unwrap()).unwrap().unwrap();
+        assert_eq!(&map[..], "héllo".as_bytes());
+        drop(map);
+        fs::write(&path, "").unwrap();
+        assert!(Mmap::map(&File::open(&path).unwrap()).unwrap().is_none());
+        fs::remove_file(&path).unwrap();
+    }
+}
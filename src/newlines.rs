@@ -0,0 +1,65 @@
+//! Conversion of the line endings of the output.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// How the line endings of the input end up in the output. A CR which does
+/// not end a line is subject to the char filter either way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Newlines {
+    /// End every line with LF, converting CRLF.
+    Lf,
+    /// End every line with CRLF, converting LF.
+    Crlf,
+    /// Keep CRLF and LF as they are, even if CR would be dropped otherwise.
+    Keep,
+}
+
+impl Newlines {
+    pub const ALL: [Newlines; 3] =
+        [Newlines::Lf, Newlines::Crlf, Newlines::Keep];
+
+    /// Return the name of the line ending policy, e.g. "crlf".
+    pub fn name(&self) -> &'static str {
+        match self {
+            Newlines::Lf => "lf",
+            Newlines::Crlf => "crlf",
+            Newlines::Keep => "keep",
+        }
+    }
+}
+
+impl fmt::Display for Newlines {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Newlines {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Newlines::ALL
+            .into_iter()
+            .find(|newlines| newlines.name() == s)
+            .ok_or_else(|| {
+                format!(
+                    "unknown line endings {:?}, expected one of lf, crlf, keep",
+                    s
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Newlines;
+
+    #[test]
+    fn test_newlines_from_str() {
+        for newlines in Newlines::ALL {
+            assert_eq!(newlines.to_string().parse(), Ok(newlines));
+        }
+        assert!("CRLF".parse::<Newlines>().is_err());
+    }
+}
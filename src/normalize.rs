@@ -0,0 +1,150 @@
+//! Unicode normalization of the stream before filtering, e.g. NFKC, which
+//! folds compatibility chars like ﬁ and full-width digits into ASCII.
+
+use std::fmt;
+use std::str::FromStr;
+
+use unicode_normalization::char::{
+    canonical_combining_class, is_combining_mark,
+};
+use unicode_normalization::UnicodeNormalization;
+
+/// A Unicode normalization form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Normalization {
+    /// Canonical decomposition followed by canonical composition.
+    Nfc,
+    /// Canonical decomposition.
+    Nfd,
+    /// Compatibility decomposition followed by canonical composition.
+    Nfkc,
+    /// Compatibility decomposition.
+    Nfkd,
+}
+
+impl Normalization {
+    pub const ALL: [Normalization; 4] = [
+        Normalization::Nfc,
+        Normalization::Nfd,
+        Normalization::Nfkc,
+        Normalization::Nfkd,
+    ];
+
+    /// Return the name of the form in lowercase, e.g. "nfkc".
+    pub fn name(&self) -> &'static str {
+        match self {
+            Normalization::Nfc => "nfc",
+            Normalization::Nfd => "nfd",
+            Normalization::Nfkc => "nfkc",
+            Normalization::Nfkd => "nfkd",
+        }
+    }
+
+    /// Return `s` normalized to this form.
+    pub fn normalize(&self, s: &str) -> String {
+        match self {
+            Normalization::Nfc => s.nfc().collect(),
+            Normalization::Nfd => s.nfd().collect(),
+            Normalization::Nfkc => s.nfkc().collect(),
+            Normalization::Nfkd => s.nfkd().collect(),
+        }
+    }
+}
+
+impl fmt::Display for Normalization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Normalization {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.to_ascii_lowercase();
+        Normalization::ALL
+            .into_iter()
+            .find(|n| n.name() == s)
+            .ok_or_else(|| {
+                format!(
+                    "unknown normalization form {:?}, expected one of nfc, \
+                     nfd, nfkc, nfkd",
+                    s
+                )
+            })
+    }
+}
+
+/// Return true if nothing before `c` interacts with `c` and what follows
+/// under normalization, so that the text may be normalized in pieces split
+/// before `c`. Hangul vowels and trailing consonants compose with the
+/// syllable before them despite being starters.
+fn is_boundary(c: char) -> bool {
+    canonical_combining_class(c) == 0
+        && !is_combining_mark(c)
+        && !matches!(c, '\u{1161}'..='\u{1175}' | '\u{11a8}'..='\u{11c2}')
+}
+
+/// Normalizes text pushed in pieces, holding back the text after the last
+/// boundary, which may yet combine with what is pushed next.
+#[derive(Debug, Clone)]
+pub(crate) struct Normalizer {
+    form: Normalization,
+    /// The text not yet normalized, which starts at a boundary.
+    held: String,
+}
+
+impl Normalizer {
+    pub(crate) fn new(form: Normalization) -> Self {
+        Self {
+            form,
+            held: String::new(),
+        }
+    }
+
+    /// Normalize `s` after the text held back, returning what is decided.
+    pub(crate) fn push(&mut self, s: &str) -> String {
+        self.held.push_str(s);
+        let split = self
+            .held
+            .char_indices()
+            .rev()
+            .find(|&(_, c)| is_boundary(c))
+            .map_or(0, |(i, _)| i);
+        let rest = self.held.split_off(split);
+        let decided = std::mem::replace(&mut self.held, rest);
+        self.form.normalize(&decided)
+    }
+
+    /// Normalize and return the text held back, e.g. at the end of input.
+    pub(crate) fn finish(&mut self) -> String {
+        let held = std::mem::take(&mut self.held);
+        self.form.normalize(&held)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Normalization, Normalizer};
+
+    #[test]
+    fn test_normalizer() {
+        let mut n = Normalizer::new(Normalization::Nfc);
+        let mut out = n.push("cafe");
+        // the combining acute accent composes across pushes
+        out += &n.push("\u{301} \u{1100}");
+        out += &n.push("\u{1161}");
+        out += &n.finish();
+        assert_eq!(out, "café \u{ac00}");
+
+        let mut n = Normalizer::new(Normalization::Nfkc);
+        let out = n.push("ﬁ１²") + &n.finish();
+        assert_eq!(out, "fi12");
+    }
+
+    #[test]
+    fn test_normalization_from_str() {
+        assert_eq!("NFKC".parse(), Ok(Normalization::Nfkc));
+        assert!("nfx".parse::<Normalization>().is_err());
+    }
+}
@@ -0,0 +1,178 @@
+//! The output of a run, which replaces an output file atomically, so that a
+//! crash or a full disk never leaves it truncated.
+
+use std::ffi::OsString;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// A file written next to the output file in its place, and renamed over it
+/// once complete.
+struct Replacement {
+    file: BufWriter<File>,
+    temp: PathBuf,
+    path: PathBuf,
+}
+
+enum Sink {
+    Stream(Box<dyn Write + Send>),
+    Replacement(Replacement),
+}
+
+/// Where a run writes its output, a stream written in place, or a file
+/// replaced by [`Destination::commit`] once the run succeeds. Dropping it
+/// uncommitted leaves the file as it was.
+pub struct Destination {
+    sink: Sink,
+    /// If true, the file is replaced.
+    committed: bool,
+}
+
+impl Destination {
+    /// Write to `stream` in place.
+    pub(crate) fn stream(stream: Box<dyn Write + Send>) -> Self {
+        Self {
+            sink: Sink::Stream(stream),
+            committed: false,
+        }
+    }
+
+    /// Write to a temporary file in the directory of `path`, to be renamed
+    /// to `path` once committed, with the permissions and, on Unix, the
+    /// owner of `path` if it exists.
+    pub(crate) fn replace(path: &Path) -> io::Result<Self> {
+        let name = path.file_name().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{}: not a file name", path.display()),
+            )
+        })?;
+        let mut temp_name = OsString::from(".");
+        temp_name.push(name);
+        temp_name.push(format!(".{}.tmp", std::process::id()));
+        let temp = path.with_file_name(temp_name);
+        let file = File::create(&temp).map_err(|e| {
+            io::Error::new(e.kind(), format!("{}: {}", temp.display(), e))
+        })?;
+        if let Ok(metadata) = fs::metadata(path) {
+            // Changing the owner needs privileges, so it is best effort.
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::MetadataExt;
+                let _ = std::os::unix::fs::fchown(
+                    &file,
+                    Some(metadata.uid()),
+                    Some(metadata.gid()),
+                );
+            }
+            file.set_permissions(metadata.permissions())?;
+        }
+        Ok(Self {
+            sink: Sink::Replacement(Replacement {
+                file: BufWriter::new(file),
+                temp,
+                path: path.to_path_buf(),
+            }),
+            committed: false,
+        })
+    }
+
+    /// Flush the output, and replace the output file with it, if any.
+    pub fn commit(mut self) -> io::Result<()> {
+        match &mut self.sink {
+            Sink::Stream(stream) => stream.flush(),
+            Sink::Replacement(replacement) => {
+                replacement.file.flush()?;
+                replacement.file.get_ref().sync_all()?;
+                fs::rename(&replacement.temp, &replacement.path)?;
+                self.committed = true;
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Destination {
+    /// Flush the output, and copy it over the output file, if any, instead
+    /// of replacing it, so that the other hard links to the file see it too.
+    /// Unlike [`Destination::commit`], a crash midway leaves the file partly
+    /// written.
+    pub(crate) fn commit_in_place(mut self) -> io::Result<()> {
+        match &mut self.sink {
+            Sink::Stream(stream) => stream.flush(),
+            Sink::Replacement(replacement) => {
+                replacement.file.flush()?;
+                let mut temp = File::open(&replacement.temp)?;
+                let mut file = OpenOptions::new()
+                    .write(true)
+                    .truncate(true)
+                    .open(&replacement.path)?;
+                io::copy(&mut temp, &mut file)?;
+                file.sync_all()
+            }
+        }
+    }
+}
+
+impl Write for Destination {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match &mut self.sink {
+            Sink::Stream(stream) => stream.write(buf),
+            Sink::Replacement(replacement) => replacement.file.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match &mut self.sink {
+            Sink::Stream(stream) => stream.flush(),
+            Sink::Replacement(replacement) => replacement.file.flush(),
+        }
+    }
+}
+
+impl Drop for Destination {
+    fn drop(&mut self) {
+        if let (Sink::Replacement(replacement), false) =
+            (&self.sink, self.committed)
+        {
+            let _ = fs::remove_file(&replacement.temp);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Destination;
+    use std::fs;
+    use std::io::Write;
+
+    #[test]
+    fn test_destination_replace() {
+        let dir = std::env::temp_dir()
+            .join(format!("ascii-filter-output-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("out");
+        fs::write(&path, "old").unwrap();
+
+        // left as it was unless committed
+        let mut output = Destination::replace(&path).unwrap();
+        output.write_all(b"partial").unwrap();
+        drop(output);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "old");
+
+        let mut output = Destination::replace(&path).unwrap();
+        output.write_all(b"new").unwrap();
+        output.commit().unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+        // the other links see the file rewritten in place
+        fs::hard_link(&path, dir.join("link")).unwrap();
+        let mut output = Destination::replace(&path).unwrap();
+        output.write_all(b"newer").unwrap();
+        output.commit_in_place().unwrap();
+        assert_eq!(fs::read_to_string(dir.join("link")).unwrap(), "newer");
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 2);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
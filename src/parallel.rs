@@ -0,0 +1,183 @@
+//! Filtering of a large text in memory, e.g. a mapped file, on several
+//! threads. The text is split into chunks after '\n', which the filter
+//! decides apart when it keeps '\n', since no state of it, e.g. the text
+//! held back for normalization, a regex rule or a dropped line, carries past
+//! a kept '\n'. The chunks are filtered in batches of one per thread, and
+//! written in order.
+
+use std::io::{self, Write};
+use std::num::NonZeroUsize;
+use std::thread;
+
+use crate::charset::CharFilter;
+use crate::{write_through, FilterWriter, InvalidUtf8, Options, Stats};
+
+/// The size of the chunks filtered by a thread at a time.
+pub(crate) const CHUNK_LEN: usize = 8 << 20;
+
+/// Return the number of threads to filter `input` on per `opts`, where 1
+/// means filtering it serially, e.g. since it is small or the chunks are not
+/// independent.
+pub(crate) fn threads(input: &[u8], opts: &Options) -> usize {
+    // The offsets of the invalid bytes in a chunk, and its line numbers, are
+    // not those of the input, and a chunk may split a NUL-delimited line.
+    let independent = !opts.invert
+        && opts.rejects.is_none()
+        && opts.rejects_hex.is_none()
+        && opts.report.is_none()
+        && !opts.strict
+        && opts.undo_log.is_none()
+        && opts.source_map.is_none()
+        && !opts.line_numbers
+        && !opts.null_data
+        && opts.invalid_utf8 != InvalidUtf8::Error
+        && CharFilter::new(opts).keeps('\n');
+    if input.len() <= CHUNK_LEN || !independent {
+        return 1;
+    }
+    opts.threads.unwrap_or_else(|| {
+        thread::available_parallelism().map_or(1, NonZeroUsize::get)
+    })
+}
+
+/// Split `input` into chunks of at least `len` bytes, each but the last
+/// ending with '\n'.
+fn split_lines(input: &[u8], len: usize) -> Vec<&[u8]> {
+    let mut chunks: Vec<&[u8]> = Vec::new();
+    let mut rest = input;
+    while rest.len() > len {
+        let end = match rest[len..].iter().position(|&b| b == b'\n') {
+            Some(i) => len + i + 1,
+            None => rest.len(),
+        };
+        let (chunk, tail) = rest.split_at(end);
+        chunks.push(chunk);
+        rest = tail;
+    }
+    if !rest.is_empty() {
+        chunks.push(rest);
+    }
+    chunks
+}
+
+/// Filter `chunk` per `opts` in memory, where `first` is true if it starts
+/// the input.
+fn filter_chunk(
+    chunk: &[u8],
+    opts: &Options,
+    first: bool,
+) -> io::Result<(Vec<u8>, Stats)> {
+    let mut out: Vec<u8> = Vec::with_capacity(chunk.len());
+    let fw = FilterWriter::from_options(&mut out, opts)
+        .with_strip_bom(opts.strip_bom && first);
+    let stats = write_through(fw, chunk)?;
+    Ok((out, stats))
+}
+
+/// Filter `input` to `w` per `opts` on `threads` threads in chunks of about
+/// `chunk_len` bytes, returning the stats of the filtering.
+pub(crate) fn filter<W: Write>(
+    input: &[u8],
+    w: &mut W,
+    opts: &Options,
+    threads: usize,
+    chunk_len: usize,
+) -> io::Result<Stats> {
+    let mut stats = Stats::default();
+    let mut first = true;
+    for batch in split_lines(input, chunk_len).chunks(threads) {
+        let outputs: Vec<io::Result<(Vec<u8>, Stats)>> =
+            thread::scope(|scope| {
+                let handles: Vec<_> = batch
+                    .iter()
+                    .map(|&chunk| {
+                        let first = std::mem::take(&mut first);
+                        scope.spawn(move || filter_chunk(chunk, opts, first))
+                    })
+                    .collect();
+                handles
+                    .into_iter()
+                    .map(|handle| handle.join().unwrap())
+                    .collect()
+            });
+        for output in outputs {
+            let (bytes, chunk_stats) = output?;
+            w.write_all(&bytes)?;
+            stats += chunk_stats;
+        }
+    }
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{filter, split_lines, threads, CHUNK_LEN};
+    use crate::{filter_bytes, CharSet, Options};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_split_lines() {
+        assert_eq!(
+            split_lines(b"ab\ncd\nef", 1),
+            [&b"ab\n"[..], &b"cd\n"[..], &b"ef"[..]]
+        );
+        assert_eq!(split_lines(b"abc\nd", 2), [&b"abc\n"[..], &b"d"[..]]);
+        assert_eq!(split_lines(b"abc", 1), [&b"abc"[..]]);
+        assert!(split_lines(b"", 1).is_empty());
+    }
+
+    #[test]
+    fn test_filter_parallel() {
+        let opts = Options {
+            ascii_only: true,
+            drop_lines: true,
+            ..Options::default()
+        };
+        let input = "ok\nnot ök\n".repeat(10);
+        let mut out: Vec<u8> = Vec::new();
+        let stats = filter(input.as_bytes(), &mut out, &opts, 3, 4).unwrap();
+        assert_eq!(out, filter_bytes(input.as_bytes(), &opts));
+        assert_eq!(out, "ok\n".repeat(10).as_bytes());
+        assert_eq!(stats.bytes_read, input.len() as u64);
+        assert_eq!(stats.chars_removed, 10);
+    }
+
+    #[test]
+    fn test_filter_parallel_strip_bom() {
+        let opts = Options {
+            strip_bom: true,
+            ..Options::default()
+        };
+        let input = "\u{feff}ok\n".repeat(10);
+        let mut out: Vec<u8> = Vec::new();
+        filter(input.as_bytes(), &mut out, &opts, 3, 4).unwrap();
+        assert_eq!(out, &input.as_bytes()["\u{feff}".len()..]);
+    }
+
+    #[test]
+    fn test_threads() {
+        let large = vec![b'a'; CHUNK_LEN + 1];
+        let opts = Options {
+            ascii_only: true,
+            threads: Some(4),
+            ..Options::default()
+        };
+        assert_eq!(threads(&large, &opts), 4);
+        assert_eq!(threads(&large[..CHUNK_LEN], &opts), 1);
+        let invert = Options {
+            invert: true,
+            ..opts.clone()
+        };
+        assert_eq!(threads(&large, &invert), 1);
+        let rejects = Options {
+            rejects: Some(PathBuf::from("rejects")),
+            ..opts.clone()
+        };
+        assert_eq!(threads(&large, &rejects), 1);
+        let drops_newline = Options {
+            allowed: Some(CharSet::from_iter(['a'])),
+            ..opts
+        };
+        assert_eq!(threads(&large, &drops_newline), 1);
+    }
+}
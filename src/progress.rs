@@ -0,0 +1,148 @@
+//! A progress bar of the input read, with the throughput and the time left,
+//! e.g. for the files of a run over a multi-gigabyte dump.
+
+use std::io::{self, BufRead, Read, Write};
+use std::time::{Duration, Instant};
+
+/// The time between redraws.
+const INTERVAL: Duration = Duration::from_millis(200);
+
+/// The width of the bar, in chars.
+const BAR_WIDTH: usize = 30;
+
+/// Return `bytes` with a binary unit, e.g. "1.5 MiB".
+fn human(bytes: f64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    match unit {
+        0 => format!("{} B", value as u64),
+        unit => format!("{:.1} {}", value, UNITS[unit]),
+    }
+}
+
+/// Return `secs` as e.g. "1:02:03" or "2:03".
+fn clock(secs: u64) -> String {
+    match (secs / 3600, secs / 60 % 60, secs % 60) {
+        (0, m, s) => format!("{}:{:02}", m, s),
+        (h, m, s) => format!("{}:{:02}:{:02}", h, m, s),
+    }
+}
+
+/// Return the progress line of `done` bytes of `total`, if known, read in
+/// `elapsed`.
+fn line(done: u64, total: Option<u64>, elapsed: Duration) -> String {
+    let secs = elapsed.as_secs_f64();
+    let rate = if secs > 0.0 { done as f64 / secs } else { 0.0 };
+    let Some(total) = total.filter(|&total| total > 0) else {
+        return format!("{} {}/s", human(done as f64), human(rate));
+    };
+    let done = done.min(total);
+    let filled = (done as u128 * BAR_WIDTH as u128 / total as u128) as usize;
+    let eta = match rate > 0.0 {
+        true => clock(((total - done) as f64 / rate).ceil() as u64),
+        false => "-:--".to_string(),
+    };
+    format!(
+        "[{}{}] {:>3}% {} / {} {}/s ETA {}",
+        "#".repeat(filled),
+        "-".repeat(BAR_WIDTH - filled),
+        done as u128 * 100 / total as u128,
+        human(done as f64),
+        human(total as f64),
+        human(rate),
+        eta
+    )
+}
+
+/// Draws the progress of the input read on stderr, which is a terminal, in
+/// place, at most every [`INTERVAL`].
+pub(crate) struct Progress {
+    /// The bytes of input in all, if known.
+    total: Option<u64>,
+    done: u64,
+    start: Instant,
+    drawn: Option<Instant>,
+}
+
+impl Progress {
+    pub(crate) fn new(total: Option<u64>) -> Self {
+        Self {
+            total,
+            done: 0,
+            start: Instant::now(),
+            drawn: None,
+        }
+    }
+
+    /// Count `n` more bytes read, and redraw if due.
+    fn advance(&mut self, n: usize) {
+        self.done += n as u64;
+        if self.drawn.is_some_and(|drawn| drawn.elapsed() < INTERVAL) {
+            return;
+        }
+        self.drawn = Some(Instant::now());
+        let line = line(self.done, self.total, self.start.elapsed());
+        // the progress is best effort
+        let _ = write!(io::stderr().lock(), "\r{}\x1b[K", line);
+    }
+}
+
+impl Drop for Progress {
+    /// Erase the bar, e.g. for the stats or the error printed after it.
+    fn drop(&mut self) {
+        if self.drawn.is_some() {
+            let _ = write!(io::stderr().lock(), "\r\x1b[K");
+        }
+    }
+}
+
+/// Counts the bytes consumed through it in a [`Progress`].
+pub(crate) struct ProgressReader<'a, R> {
+    pub(crate) inner: R,
+    pub(crate) progress: &'a mut Progress,
+}
+
+impl<R: Read> Read for ProgressReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.progress.advance(n);
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for ProgressReader<'_, R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt);
+        self.progress.advance(amt);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{clock, human, line};
+    use std::time::Duration;
+
+    #[test]
+    fn test_progress_line() {
+        assert_eq!(human(512.0), "512 B");
+        assert_eq!(human(1536.0 * 1024.0), "1.5 MiB");
+        assert_eq!(clock(123), "2:03");
+        assert_eq!(clock(3723), "1:02:03");
+        let secs = Duration::from_secs(2);
+        assert_eq!(
+            line(1 << 20, Some(4 << 20), secs),
+            "[#######-----------------------]  25% 1.0 MiB / 4.0 MiB \
+             512.0 KiB/s ETA 0:06"
+        );
+        assert_eq!(line(1 << 20, None, secs), "1.0 MiB 512.0 KiB/s");
+    }
+}
@@ -0,0 +1,122 @@
+//! The Punycode of RFC 3492, and the ASCII form of hostnames with it, e.g.
+//! `xn--bcher-kva.de` for `bücher.de`.
+
+const BASE: u32 = 36;
+const T_MIN: u32 = 1;
+const T_MAX: u32 = 26;
+const SKEW: u32 = 38;
+const DAMP: u32 = 700;
+const INITIAL_BIAS: u32 = 72;
+const INITIAL_N: u32 = 0x80;
+
+/// Return the bias after a delta, per section 6.1 of RFC 3492.
+fn adapt(delta: u32, points: u32, first: bool) -> u32 {
+    let mut delta = if first { delta / DAMP } else { delta / 2 };
+    delta += delta / points;
+    let mut k = 0;
+    while delta > ((BASE - T_MIN) * T_MAX) / 2 {
+        delta /= BASE - T_MIN;
+        k += BASE;
+    }
+    k + (BASE - T_MIN + 1) * delta / (delta + SKEW)
+}
+
+fn digit(d: u32) -> char {
+    match d {
+        0..=25 => (b'a' + d as u8) as char,
+        _ => (b'0' + (d - 26) as u8) as char,
+    }
+}
+
+/// Return the Punycode of `input`, or `None` if it overflows, which takes
+/// an input far longer than any label.
+pub(crate) fn encode(input: &str) -> Option<String> {
+    let code_points: Vec<u32> = input.chars().map(u32::from).collect();
+    let mut out: String = input.chars().filter(char::is_ascii).collect();
+    let basic = out.len() as u32;
+    if basic > 0 {
+        out.push('-');
+    }
+    let mut n = INITIAL_N;
+    let mut delta: u32 = 0;
+    let mut bias = INITIAL_BIAS;
+    // the code points handled so far
+    let mut h = basic;
+    while (h as usize) < code_points.len() {
+        let m = code_points.iter().copied().filter(|&c| c >= n).min()?;
+        delta = delta.checked_add((m - n).checked_mul(h + 1)?)?;
+        n = m;
+        for &c in &code_points {
+            if c < n {
+                delta = delta.checked_add(1)?;
+            }
+            if c != n {
+                continue;
+            }
+            let mut q = delta;
+            let mut k = BASE;
+            loop {
+                let t = k.saturating_sub(bias).clamp(T_MIN, T_MAX);
+                if q < t {
+                    break;
+                }
+                out.push(digit(t + (q - t) % (BASE - t)));
+                q = (q - t) / (BASE - t);
+                k += BASE;
+            }
+            out.push(digit(q));
+            bias = adapt(delta, h + 1, h == basic);
+            delta = 0;
+            h += 1;
+        }
+        delta += 1;
+        n += 1;
+    }
+    Some(out)
+}
+
+/// Return the ASCII form of `host`, with each label which is not ASCII
+/// lowercased and written as `xn--` and its Punycode, or `None` if a label
+/// overflows. The labels are not normalized or validated further, as IDNA
+/// does.
+pub(crate) fn host_to_ascii(host: &str) -> Option<String> {
+    let labels = host.split('.').map(|label| match label.is_ascii() {
+        true => Some(label.to_string()),
+        false => Some(format!("xn--{}", encode(&label.to_lowercase())?)),
+    });
+    Some(labels.collect::<Option<Vec<String>>>()?.join("."))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{encode, host_to_ascii};
+
+    #[test]
+    fn test_encode() {
+        // the samples of RFC 3492, and of common names
+        let samples = [
+            ("bücher", "bcher-kva"),
+            ("münchen", "mnchen-3ya"),
+            ("ü", "tda"),
+            ("例子", "fsqu00a"),
+            ("他们为什么不说中文", "ihqwcrb4cv8a8dqg056pqjye"),
+            ("Pročprostěnemluvíčesky", "Proprostnemluvesky-uyb24dma41a"),
+            ("abc", "abc-"),
+        ];
+        for (input, expected) in samples {
+            assert_eq!(encode(input).as_deref(), Some(expected), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_host_to_ascii() {
+        assert_eq!(
+            host_to_ascii("www.Bücher.de").as_deref(),
+            Some("www.xn--bcher-kva.de")
+        );
+        assert_eq!(
+            host_to_ascii("例子.测试").as_deref(),
+            Some("xn--fsqu00a.xn--0zwm56d")
+        );
+    }
+}
@@ -0,0 +1,155 @@
+//! The quoted-printable encoding of RFC 2045, for the output of
+//! [`Escape::Qp`](crate::Escape::Qp).
+
+use std::io::{self, Write};
+
+/// The most chars of a line, but for the `=` of a soft line break.
+const MAX_LINE: usize = 75;
+
+/// Encodes the bytes written through it as quoted-printable: the bytes but
+/// printable ASCII, and `=`, as `=XX`, and the whitespace ending a line too,
+/// with soft line breaks keeping each line at 76 chars at most. A '\n', or a
+/// "\r\n", is a line break, which is kept.
+pub(crate) struct QpWriter<W> {
+    inner: W,
+    /// The chars of the current line written.
+    column: usize,
+    /// A space or tab written last, which is encoded if it ends a line.
+    space: Option<u8>,
+    /// If true, a '\r' is written last, which is a line break if followed by
+    /// '\n'.
+    cr: bool,
+    /// If true, the soft line breaks are "\r\n", as the last line break.
+    crlf: bool,
+    /// The encoded bytes of a write.
+    encoded: Vec<u8>,
+}
+
+impl<W: Write> QpWriter<W> {
+    pub(crate) fn new(inner: W) -> Self {
+        Self {
+            inner,
+            column: 0,
+            space: None,
+            cr: false,
+            crlf: false,
+            encoded: Vec::new(),
+        }
+    }
+
+    /// Append `b` as is, or encoded if `encode`, breaking the line first if
+    /// it does not fit.
+    fn push(&mut self, b: u8, encode: bool) {
+        let len = if encode { 3 } else { 1 };
+        if self.column + len > MAX_LINE {
+            let soft_break: &[u8] = if self.crlf { b"=\r\n" } else { b"=\n" };
+            self.encoded.extend_from_slice(soft_break);
+            self.column = 0;
+        }
+        match encode {
+            true => self.encoded.extend(format!("={:02X}", b).bytes()),
+            false => self.encoded.push(b),
+        }
+        self.column += len;
+    }
+
+    /// Append the space or tab written last, if any, encoded if `line_end`.
+    fn push_space(&mut self, line_end: bool) {
+        if let Some(space) = self.space.take() {
+            self.push(space, line_end);
+        }
+    }
+
+    fn push_byte(&mut self, b: u8) {
+        if std::mem::take(&mut self.cr) {
+            if b == b'\n' {
+                self.push_space(true);
+                self.encoded.extend_from_slice(b"\r\n");
+                self.column = 0;
+                self.crlf = true;
+                return;
+            }
+            self.push_space(false);
+            self.push(b'\r', true);
+        }
+        match b {
+            b'\r' => self.cr = true,
+            b'\n' => {
+                self.push_space(true);
+                self.encoded.push(b'\n');
+                self.column = 0;
+                self.crlf = false;
+            }
+            b' ' | b'\t' => {
+                self.push_space(false);
+                self.space = Some(b);
+            }
+            b => {
+                self.push_space(false);
+                self.push(b, !matches!(b, b'!'..=b'~') || b == b'=');
+            }
+        }
+    }
+
+    /// Encode what is held back at the end of the output, and flush.
+    pub(crate) fn finish(&mut self) -> io::Result<()> {
+        self.encoded.clear();
+        self.push_space(true);
+        if std::mem::take(&mut self.cr) {
+            self.push(b'\r', true);
+        }
+        self.inner.write_all(&self.encoded)?;
+        self.inner.flush()
+    }
+}
+
+impl<W: Write> Write for QpWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.encoded.clear();
+        for &b in buf {
+            self.push_byte(b);
+        }
+        self.inner.write_all(&self.encoded)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QpWriter;
+    use std::io::Write;
+
+    /// Encode `input` written in chunks of `len` bytes.
+    fn encode(input: &[u8], len: usize) -> String {
+        let mut w = QpWriter::new(Vec::new());
+        for chunk in input.chunks(len) {
+            w.write_all(chunk).unwrap();
+        }
+        w.finish().unwrap();
+        String::from_utf8(w.inner).unwrap()
+    }
+
+    #[test]
+    fn test_qp_writer() {
+        let input = "a=b café \r\nx\t\ny\rz \n".as_bytes();
+        for len in 1..=input.len() {
+            assert_eq!(
+                encode(input, len),
+                "a=3Db caf=C3=A9=20\r\nx=09\ny=0Dz=20\n"
+            );
+        }
+        assert_eq!(encode(b"end ", 1), "end=20");
+        // soft line breaks, which keep =XX whole
+        let line = format!("{}é{}", "x".repeat(73), "y".repeat(80));
+        let encoded = encode(line.as_bytes(), 7);
+        let lines: Vec<&str> = encoded.split('\n').collect();
+        assert_eq!(lines[0], format!("{}=", "x".repeat(73)));
+        assert_eq!(lines[1], format!("=C3=A9{}=", "y".repeat(69)));
+        assert_eq!(lines[2], "y".repeat(11));
+        assert!(lines.iter().all(|line| line.len() <= 76));
+    }
+}
@@ -0,0 +1,144 @@
+//! The dual of [`FilterWriter`], which filters what is read through it, so
+//! that a cleaned stream can be fed to anything taking a `Read`.
+
+use std::io::{self, Read, Write};
+
+use crate::{FilterWriter, Options};
+
+/// A `Read` adapter which yields the bytes read from `inner` filtered per
+/// [`Options`], making the same decisions as [`filter`](crate::filter) with
+/// [`Format::Text`](crate::Format::Text).
+pub struct FilterReader<R: Read> {
+    inner: R,
+    /// The chunk of input read last.
+    buf: Vec<u8>,
+    /// True once `inner` is exhausted.
+    eof: bool,
+    /// The filtered output, of which `out[pos..]` is not yet read.
+    out: FilterWriter<Vec<u8>>,
+    pos: usize,
+}
+
+impl<R: Read> FilterReader<R> {
+    pub fn new(inner: R, opts: &Options) -> Self {
+        Self {
+            inner,
+            buf: vec![0u8; opts.buf_size.max(1)],
+            eof: false,
+            out: FilterWriter::from_options(Vec::new(), opts),
+            pos: 0,
+        }
+    }
+
+    /// Call `on_drop(c, offset)` for each char `c` dropped or replaced at
+    /// input `offset`, see [`FilterWriter::with_on_drop`].
+    pub fn with_on_drop<F: FnMut(char, usize) + Send + 'static>(
+        mut self,
+        on_drop: F,
+    ) -> Self {
+        self.out = self.out.with_on_drop(on_drop);
+        self
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Return `inner`, where what is read but not yet filtered or read out
+    /// is lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Filter the next chunk of input, returning false at the end of input.
+    fn decide(&mut self) -> io::Result<bool> {
+        if self.eof {
+            return Ok(false);
+        }
+        let n = loop {
+            match self.inner.read(&mut self.buf) {
+                Ok(n) => break n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        };
+        if n == 0 {
+            // the text held back for normalization, if any
+            self.eof = true;
+            self.out.end_input()?;
+            self.out.flush()?;
+        } else {
+            self.out.write_all(&self.buf[..n])?;
+        }
+        Ok(true)
+    }
+}
+
+impl<R: Read> Read for FilterReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.pos == self.out.get_ref().len() {
+            self.out.get_mut().clear();
+            self.pos = 0;
+            if !self.decide()? {
+                return Ok(0);
+            }
+        }
+        let out = &self.out.get_ref()[self.pos..];
+        let n = out.len().min(buf.len());
+        buf[..n].copy_from_slice(&out[..n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FilterReader;
+    use crate::{filter_bytes, Options};
+    use std::io::Read;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_filter_reader() {
+        let input = include_bytes!("../corrupted_lipsum.txt");
+        for ascii_only in [false, true] {
+            let opts = Options {
+                buf_size: 16,
+                ascii_only,
+                ..Options::default()
+            };
+            let mut r = FilterReader::new(&input[..], &opts);
+            let mut out: Vec<u8> = Vec::new();
+            // in small reads
+            let mut buf = [0u8; 7];
+            loop {
+                let n = r.read(&mut buf).unwrap();
+                if n == 0 {
+                    break;
+                }
+                out.extend_from_slice(&buf[..n]);
+            }
+            assert_eq!(out, filter_bytes(input, &opts));
+        }
+    }
+
+    #[test]
+    fn test_filter_reader_on_drop() {
+        let opts = Options {
+            buf_size: 16,
+            ascii_only: true,
+            ..Options::default()
+        };
+        let dropped = Arc::new(Mutex::new(Vec::new()));
+        let dropped_clone = Arc::clone(&dropped);
+        let mut r =
+            FilterReader::new(&b"a\xe4\xbd\xa0\xffb\xe5\xa5\xbd"[..], &opts)
+                .with_on_drop(move |c, offset| {
+                    dropped_clone.lock().unwrap().push((c, offset))
+                });
+        let mut out = String::new();
+        r.read_to_string(&mut out).unwrap();
+        assert_eq!(out, "ab");
+        assert_eq!(*dropped.lock().unwrap(), vec![('你', 1), ('好', 6)]);
+    }
+}
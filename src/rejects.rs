@@ -0,0 +1,295 @@
+//! Recording of the chars the filter drops to a side output, e.g. as an audit
+//! trail of exactly what it removed.
+
+use std::fmt;
+use std::io::{self, Write};
+use std::str::FromStr;
+
+use crate::json;
+
+/// The format of the per-occurrence diagnostics of the chars dropped, e.g.
+/// for dashboards and editor integrations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    /// A JSON object per line, e.g. `{"file":"a.txt","line":2,"column":3,
+    /// "offset":7,"codepoint":"U+00E9","char":"é","action":"replaced",
+    /// "replacement":"e"}`, where `file` is `null` for stdin.
+    Jsonl,
+}
+
+impl ReportFormat {
+    pub const ALL: [ReportFormat; 1] = [ReportFormat::Jsonl];
+
+    /// Return the name of the format, e.g. "jsonl".
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReportFormat::Jsonl => "jsonl",
+        }
+    }
+}
+
+impl fmt::Display for ReportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ReportFormat::ALL
+            .into_iter()
+            .find(|format| format.name() == s)
+            .ok_or_else(|| {
+                format!("unknown report format {:?}, expected jsonl", s)
+            })
+    }
+}
+
+/// How [`Rejects`] writes each char dropped.
+pub(crate) enum Record {
+    /// The char alone.
+    Chars,
+    /// A line `LINE:COLUMN: U+XXXX c`.
+    Positions,
+    /// A line per [`ReportFormat::Jsonl`], naming the file given.
+    Jsonl(Option<String>),
+}
+
+/// Writes each char dropped, optionally annotated with its position, keeping
+/// track of the position of the text filtered across pieces.
+pub(crate) struct Rejects {
+    out: Box<dyn Write + Send>,
+    record: Record,
+    /// The line of the next char, from 1.
+    line: usize,
+    /// The column of the next char in chars, from 1.
+    column: usize,
+    /// The byte offset of the next char in the input.
+    offset: u64,
+}
+
+impl Rejects {
+    pub(crate) fn new(out: Box<dyn Write + Send>, record: Record) -> Self {
+        Self {
+            out,
+            record,
+            line: 1,
+            column: 1,
+            offset: 0,
+        }
+    }
+
+    /// Advance the position over `s`, which is not dropped.
+    pub(crate) fn advance(&mut self, s: &str) {
+        if let Record::Chars = self.record {
+            return;
+        }
+        for c in s.chars() {
+            self.step(c);
+        }
+    }
+
+    /// Return the line and the column of the next char, from 1.
+    pub(crate) fn position(&self) -> (usize, usize) {
+        (self.line, self.column)
+    }
+
+    /// Advance the offset over `len` bytes of input which are no text, e.g.
+    /// invalid utf-8 dropped.
+    pub(crate) fn skip(&mut self, len: usize) {
+        self.offset += len as u64;
+    }
+
+    fn step(&mut self, c: char) {
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        self.offset += c.len_utf8() as u64;
+    }
+
+    /// Write the dropped `c`, substituted by `replacement` if not `None`, and
+    /// advance the position over it.
+    pub(crate) fn reject(
+        &mut self,
+        c: char,
+        replacement: Option<&[u8]>,
+    ) -> io::Result<()> {
+        match &self.record {
+            Record::Chars => return write!(self.out, "{}", c),
+            Record::Positions => {
+                write!(
+                    self.out,
+                    "{}:{}: U+{:04X}",
+                    self.line, self.column, c as u32
+                )?;
+                if !c.is_control() {
+                    write!(self.out, " {}", c)?;
+                }
+                writeln!(self.out)?;
+            }
+            Record::Jsonl(file) => {
+                let mut line: Vec<u8> = Vec::new();
+                line.extend_from_slice(b"{\"file\":");
+                match file {
+                    Some(file) => json_string(file, &mut line),
+                    None => line.extend_from_slice(b"null"),
+                }
+                write!(
+                    line,
+                    ",\"line\":{},\"column\":{},\"offset\":{},\
+                     \"codepoint\":\"U+{:04X}\",\"char\":",
+                    self.line, self.column, self.offset, c as u32
+                )?;
+                json_string(c.encode_utf8(&mut [0; 4]), &mut line);
+                match replacement {
+                    Some(replacement) => {
+                        line.extend_from_slice(
+                            b",\"action\":\"replaced\",\"replacement\":",
+                        );
+                        let replacement = String::from_utf8_lossy(replacement);
+                        json_string(&replacement, &mut line);
+                    }
+                    None => line.extend_from_slice(b",\"action\":\"removed\""),
+                }
+                line.extend_from_slice(b"}\n");
+                // a line at once, as the report may be stderr, unbuffered
+                self.out.write_all(&line)?;
+            }
+        }
+        self.step(c);
+        Ok(())
+    }
+
+    pub(crate) fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+}
+
+/// Append `s` to `out` as a JSON string, quotes and all.
+fn json_string(s: &str, out: &mut Vec<u8>) {
+    out.push(b'"');
+    json::encode_string(s, out);
+    out.push(b'"');
+}
+
+/// Writes a hex dump line `OFFSET: XX XX  DECODED` per byte sequence
+/// dropped, where the offset is hexadecimal, and the decoded text is
+/// `U+XXXX c` for a char, or `invalid` for invalid utf-8.
+pub(crate) struct HexRejects {
+    out: Box<dyn Write + Send>,
+}
+
+impl HexRejects {
+    pub(crate) fn new(out: Box<dyn Write + Send>) -> Self {
+        Self { out }
+    }
+
+    fn dump(&mut self, offset: u64, bytes: &[u8]) -> io::Result<()> {
+        write!(self.out, "{:08x}:", offset)?;
+        for b in bytes {
+            write!(self.out, " {:02x}", b)?;
+        }
+        write!(self.out, " ")
+    }
+
+    /// Write the dropped `c` at input `offset`.
+    pub(crate) fn reject(&mut self, c: char, offset: u64) -> io::Result<()> {
+        self.dump(offset, c.encode_utf8(&mut [0; 4]).as_bytes())?;
+        write!(self.out, " U+{:04X}", c as u32)?;
+        if !c.is_control() {
+            write!(self.out, " {}", c)?;
+        }
+        writeln!(self.out)
+    }
+
+    /// Write the invalid `bytes` at input `offset`.
+    pub(crate) fn reject_invalid(
+        &mut self,
+        bytes: &[u8],
+        offset: u64,
+    ) -> io::Result<()> {
+        self.dump(offset, bytes)?;
+        writeln!(self.out, " invalid")
+    }
+
+    pub(crate) fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HexRejects, Record, Rejects};
+    use std::io::{self, Write};
+    use std::sync::{Arc, Mutex};
+
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_rejects_positions() {
+        let out = Arc::new(Mutex::new(Vec::new()));
+        let mut rejects =
+            Rejects::new(Box::new(Shared(out.clone())), Record::Positions);
+        rejects.advance("ab\nc");
+        rejects.reject('é', None).unwrap();
+        rejects.advance("d");
+        rejects.reject('\u{7}', Some(b"")).unwrap();
+        assert_eq!(
+            String::from_utf8(std::mem::take(&mut *out.lock().unwrap()))
+                .unwrap(),
+            "2:2: U+00E9 é\n2:4: U+0007\n"
+        );
+    }
+
+    #[test]
+    fn test_rejects_jsonl() {
+        let out = Arc::new(Mutex::new(Vec::new()));
+        let record = Record::Jsonl(Some("dir/\"a\".txt".to_string()));
+        let mut rejects = Rejects::new(Box::new(Shared(out.clone())), record);
+        rejects.advance("ab\nc");
+        rejects.reject('é', Some(b"e")).unwrap();
+        rejects.skip(1);
+        rejects.reject('\u{7}', None).unwrap();
+        assert_eq!(
+            String::from_utf8(std::mem::take(&mut *out.lock().unwrap()))
+                .unwrap(),
+            "{\"file\":\"dir/\\\"a\\\".txt\",\"line\":2,\"column\":2,\
+             \"offset\":4,\"codepoint\":\"U+00E9\",\"char\":\"é\",\
+             \"action\":\"replaced\",\"replacement\":\"e\"}\n\
+             {\"file\":\"dir/\\\"a\\\".txt\",\"line\":2,\"column\":3,\
+             \"offset\":7,\"codepoint\":\"U+0007\",\"char\":\"\\u0007\",\
+             \"action\":\"removed\"}\n"
+        );
+    }
+
+    #[test]
+    fn test_hex_rejects() {
+        let out = Arc::new(Mutex::new(Vec::new()));
+        let mut rejects = HexRejects::new(Box::new(Shared(out.clone())));
+        rejects.reject('你', 3).unwrap();
+        rejects.reject('\u{7}', 6).unwrap();
+        rejects.reject_invalid(b"\xff\xfe", 0x1a).unwrap();
+        assert_eq!(
+            String::from_utf8(std::mem::take(&mut *out.lock().unwrap()))
+                .unwrap(),
+            "00000003: e4 bd a0  U+4F60 你\n\
+             00000006: 07  U+0007\n\
+             0000001a: ff fe  invalid\n"
+        );
+    }
+}
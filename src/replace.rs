@@ -0,0 +1,192 @@
+//! Pluggable substitution of the chars the filter would otherwise drop.
+
+use std::io::{self, Write};
+use std::str::FromStr;
+
+/// A strategy to substitute dropped chars. `replace` is called with each char
+/// `c` that the filter removes, and writes whatever should stand in its place
+/// (possibly nothing) to `out`.
+///
+/// Closures of the same signature implement this trait.
+pub trait Replacer {
+    fn replace(&mut self, c: char, out: &mut dyn Write) -> io::Result<()>;
+}
+
+impl<F> Replacer for F
+where
+    F: FnMut(char, &mut dyn Write) -> io::Result<()>,
+{
+    fn replace(&mut self, c: char, out: &mut dyn Write) -> io::Result<()> {
+        self(c, out)
+    }
+}
+
+/// A style of escapes written in place of dropped chars, which keeps the
+/// output reversible unless the input itself contains such escapes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Escape {
+    /// Rust-style `\u{XXXX}`, e.g. `\u{4F60}` for 你.
+    Unicode,
+    /// HTML numeric character references, e.g. `&#x4F60;` for 你, which a
+    /// browser renders as the char.
+    Html,
+    /// JSON-style `\uXXXX`, with a surrogate pair for a char beyond the
+    /// BMP, e.g. `\u4f60` for 你 and `\ud83d\ude00` for 😀, so that the
+    /// output can be embedded in a JSON string.
+    Json,
+    /// Percent-encoding of the UTF-8 bytes, e.g. `%E4%BD%A0` for 你, for
+    /// URLs and HTTP headers.
+    Url,
+    /// Quoted-printable, per RFC 2045, e.g. `=E4=BD=A0` for 你, for email
+    /// bodies. The chars are written as they are, since the whole output is
+    /// encoded, with soft line breaks, by the functions filtering streams,
+    /// e.g. [`filter`](crate::filter), and not by the others, e.g.
+    /// [`filter_str`](crate::filter_str).
+    Qp,
+}
+
+impl Escape {
+    /// Write the escape of `c` to `out`.
+    pub fn write(self, c: char, out: &mut dyn Write) -> io::Result<()> {
+        match self {
+            Escape::Unicode => write!(out, "\\u{{{:04X}}}", c as u32),
+            Escape::Html => write!(out, "&#x{:X};", c as u32),
+            Escape::Json => {
+                let mut units = [0u16; 2];
+                for unit in c.encode_utf16(&mut units) {
+                    write!(out, "\\u{:04x}", unit)?;
+                }
+                Ok(())
+            }
+            Escape::Qp => {
+                let mut buf = [0u8; 4];
+                out.write_all(c.encode_utf8(&mut buf).as_bytes())
+            }
+            Escape::Url => {
+                let mut buf = [0u8; 4];
+                for b in c.encode_utf8(&mut buf).bytes() {
+                    write!(out, "%{:02X}", b)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl FromStr for Escape {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "unicode" => Ok(Escape::Unicode),
+            "html" => Ok(Escape::Html),
+            "json" => Ok(Escape::Json),
+            "url" => Ok(Escape::Url),
+            "qp" => Ok(Escape::Qp),
+            _ => Err(format!("unknown escape style {:?}", s)),
+        }
+    }
+}
+
+/// A style of escapes written in place of dropped control chars, i.e. C0
+/// controls and DEL, so that diagnostic output stays inspectable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControlEscape {
+    /// Caret notation, e.g. `^A` for U+0001, `^[` for ESC and `^?` for DEL.
+    Caret,
+    /// Hex escapes, e.g. `\x01` for U+0001 and `\x1B` for ESC.
+    Hex,
+}
+
+impl ControlEscape {
+    /// Return true if `c` is a control char this escapes.
+    pub fn applies_to(c: char) -> bool {
+        c.is_ascii_control()
+    }
+
+    /// Write the escape of the control char `c` to `out`.
+    pub fn write(self, c: char, out: &mut dyn Write) -> io::Result<()> {
+        match self {
+            ControlEscape::Caret => {
+                write!(out, "^{}", char::from(c as u8 ^ 0x40))
+            }
+            ControlEscape::Hex => write!(out, "\\x{:02X}", c as u32),
+        }
+    }
+}
+
+impl FromStr for ControlEscape {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "caret" => Ok(ControlEscape::Caret),
+            "hex" => Ok(ControlEscape::Hex),
+            _ => Err(format!(
+                "unknown control escape style {:?}, expected caret or hex",
+                s
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ControlEscape, Escape};
+
+    #[test]
+    fn test_escape_unicode() {
+        let mut out: Vec<u8> = Vec::new();
+        for c in "你é😀".chars() {
+            Escape::Unicode.write(c, &mut out).unwrap();
+        }
+        assert_eq!(out, b"\\u{4F60}\\u{00E9}\\u{1F600}");
+    }
+
+    #[test]
+    fn test_escape_html() {
+        let mut out: Vec<u8> = Vec::new();
+        for c in "你é😀".chars() {
+            Escape::Html.write(c, &mut out).unwrap();
+        }
+        assert_eq!(out, b"&#x4F60;&#xE9;&#x1F600;");
+        assert_eq!("html".parse(), Ok(Escape::Html));
+    }
+
+    #[test]
+    fn test_escape_json() {
+        let mut out: Vec<u8> = Vec::new();
+        for c in "你é😀".chars() {
+            Escape::Json.write(c, &mut out).unwrap();
+        }
+        assert_eq!(out, b"\\u4f60\\u00e9\\ud83d\\ude00");
+        assert_eq!("json".parse(), Ok(Escape::Json));
+    }
+
+    #[test]
+    fn test_escape_url() {
+        let mut out: Vec<u8> = Vec::new();
+        for c in "你é".chars() {
+            Escape::Url.write(c, &mut out).unwrap();
+        }
+        assert_eq!(out, b"%E4%BD%A0%C3%A9");
+        assert_eq!("url".parse(), Ok(Escape::Url));
+    }
+
+    #[test]
+    fn test_control_escape() {
+        let mut out: Vec<u8> = Vec::new();
+        for c in "\0\x01\x1b\x7f".chars() {
+            ControlEscape::Caret.write(c, &mut out).unwrap();
+        }
+        assert_eq!(out, b"^@^A^[^?");
+
+        let mut out: Vec<u8> = Vec::new();
+        for c in "\x01\x1b".chars() {
+            ControlEscape::Hex.write(c, &mut out).unwrap();
+        }
+        assert_eq!(out, b"\\x01\\x1B");
+        assert!(ControlEscape::applies_to('\x7f'));
+        assert!(!ControlEscape::applies_to('\u{85}'));
+    }
+}
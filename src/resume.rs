@@ -0,0 +1,248 @@
+//! Resumable filtering, which records the input and output offsets in a state
+//! file every so often, so that an interrupted run can continue where it left
+//! off. Checkpoints of the input offset can be emitted alongside, once the
+//! output before them is flushed. A checkpoint is taken only at a kept '\n',
+//! where the filter holds nothing back, so its decisions after a checkpoint
+//! depend only on the input after it, and the resumed output is the same as
+//! that of an uninterrupted run. If the output is seekable, it is truncated
+//! to the recorded offset, so that every output byte is written exactly once.
+//! Only the positions in `--reject-positions` restart from the checkpoint.
+
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::stats::CountingWriter;
+use crate::{
+    buffer_filter_checkpointed, caught_signal, fd, with_rejects, Checkpoint,
+    FilterWriter, Options, Stats, UTF8_BOM,
+};
+
+/// The first line of a state file.
+const MAGIC: &str = "ascii-filter resume state v1";
+
+/// The time between checkpoints.
+const INTERVAL: Duration = Duration::from_secs(1);
+
+/// The offsets of a checkpoint, from the start of the input and the output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct State {
+    input: u64,
+    output: u64,
+    /// The output offset at which the run started.
+    origin: u64,
+    buf_size: usize,
+}
+
+impl State {
+    fn parse(s: &str) -> Option<Self> {
+        let mut lines = s.lines();
+        if lines.next()? != MAGIC {
+            return None;
+        }
+        let mut field = |key: &str| {
+            lines
+                .next()?
+                .strip_prefix(key)?
+                .strip_prefix('=')?
+                .parse()
+                .ok()
+        };
+        Some(Self {
+            input: field("input")?,
+            output: field("output")?,
+            origin: field("origin")?,
+            buf_size: field("buf_size")? as usize,
+        })
+    }
+
+    fn format(&self) -> String {
+        format!(
+            "{}\ninput={}\noutput={}\norigin={}\nbuf_size={}\n",
+            MAGIC, self.input, self.output, self.origin, self.buf_size
+        )
+    }
+}
+
+/// Load the state at `path`, or `None` if there is none.
+fn load(path: &Path) -> io::Result<Option<State>> {
+    match fs::read_to_string(path) {
+        Ok(s) => State::parse(&s).map(Some).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: invalid resume state", path.display()),
+            )
+        }),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Replace the file at `path` with `contents` atomically, so that an
+/// interruption never leaves a partial file behind.
+fn replace(path: &Path, contents: &str) -> io::Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let mut file = File::create(&tmp)?;
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()?;
+    fs::rename(&tmp, path)
+}
+
+/// Where the checkpoints are emitted.
+enum Sink {
+    /// A line per checkpoint.
+    Stream(File),
+    /// Replaced with the latest checkpoint.
+    File(PathBuf),
+}
+
+impl Sink {
+    fn open(checkpoint: &Checkpoint) -> io::Result<Self> {
+        match checkpoint {
+            Checkpoint::Fd(fd) => Ok(Sink::Stream(fd::inherited(*fd)?)),
+            Checkpoint::File(path) => Ok(Sink::File(path.clone())),
+        }
+    }
+
+    fn emit(&mut self, input_offset: u64) -> io::Result<()> {
+        match self {
+            Sink::Stream(file) => writeln!(file, "{}", input_offset),
+            Sink::File(path) => replace(path, &format!("{}\n", input_offset)),
+        }
+    }
+}
+
+/// Return stdin and the output per `opts.output_fd`, as files.
+fn files(opts: &Options) -> io::Result<(File, File)> {
+    let input = fd::stdin_file()?;
+    match opts.output_fd {
+        Some(n) => Ok((input, fd::inherited(n)?)),
+        None => Ok((input, fd::stdout_file()?)),
+    }
+}
+
+/// Filter stdin to the output per `opts` as a text, resuming from the state in
+/// `opts.resume_state` if there is one, and emitting checkpoints to
+/// `opts.checkpoint`. The state is removed once the input is done. Return the
+/// stats of this run, from where it resumed.
+pub(crate) fn filter(opts: &Options) -> io::Result<Stats> {
+    let (mut input, mut output) = files(opts)?;
+    let seekable = output.stream_position().is_ok();
+    let loaded = match &opts.resume_state {
+        Some(path) => load(path)?.map(|state| (path, state)),
+        None => None,
+    };
+    let mut state = match loaded {
+        Some((path, state)) => {
+            if state.buf_size != opts.buf_size {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "{}: recorded with buffer size {}",
+                        path.display(),
+                        state.buf_size
+                    ),
+                ));
+            }
+            if input.seek(SeekFrom::Start(state.input)).is_err() {
+                // a stream replayed from the start
+                io::copy(&mut (&mut input).take(state.input), &mut io::sink())?;
+            }
+            if seekable {
+                output.set_len(state.output)?;
+                output.seek(SeekFrom::Start(state.output))?;
+            }
+            state
+        }
+        None => {
+            let origin = if seekable {
+                output.seek(SeekFrom::End(0))?
+            } else {
+                0
+            };
+            let state = State {
+                input: input.stream_position().unwrap_or(0),
+                output: origin,
+                origin,
+                buf_size: opts.buf_size,
+            };
+            if let Some(path) = &opts.resume_state {
+                // record the origin before writing anything
+                replace(path, &state.format())?;
+            }
+            state
+        }
+    };
+    let mut stats = Stats::default();
+    if opts.write_bom && state.output == state.origin {
+        output.write_all(UTF8_BOM)?;
+        state.output += UTF8_BOM.len() as u64;
+        stats.bytes_written += UTF8_BOM.len() as u64;
+    }
+    let mut sink = opts.checkpoint.as_ref().map(Sink::open).transpose()?;
+
+    let mut w = CountingWriter {
+        inner: BufWriter::new(output),
+        count: state.output,
+    };
+    let fw = FilterWriter::from_options(&mut w, opts)
+        // a BOM only starts the input of the first run
+        .with_strip_bom(opts.strip_bom && state.output == state.origin);
+    let mut fw = with_rejects(fw, opts)?;
+    let mut consumed = state.input;
+    let mut saved_at = Instant::now();
+    let mut input = BufReader::with_capacity(opts.buf_size.max(1), input);
+    buffer_filter_checkpointed(opts.buf_size, &mut input, &mut fw, |n, fw| {
+        consumed += n as u64;
+        // Flushing elsewhere would end the text held back early.
+        if saved_at.elapsed() >= INTERVAL && fw.at_line_end() {
+            fw.flush()?;
+            if let Some(path) = &opts.resume_state {
+                let state = State {
+                    input: consumed,
+                    output: fw.backend.count,
+                    ..state
+                };
+                replace(path, &state.format())?;
+            }
+            if let Some(sink) = &mut sink {
+                sink.emit(consumed)?;
+            }
+            saved_at = Instant::now();
+        }
+        Ok(())
+    })?;
+    fw.flush()?;
+    stats += fw.stats();
+    if let Some(sink) = &mut sink {
+        sink.emit(consumed)?;
+    }
+    match &opts.resume_state {
+        // An interrupted run is resumed from the state saved last.
+        Some(_) if caught_signal().is_some() => Ok(stats),
+        Some(path) => match fs::remove_file(path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(stats),
+        },
+        None => Ok(stats),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::State;
+
+    #[test]
+    fn test_state() {
+        let state = State {
+            input: 1 << 40,
+            output: 12,
+            origin: 3,
+            buf_size: 128,
+        };
+        assert_eq!(State::parse(&state.format()), Some(state));
+        assert_eq!(State::parse("input=1\n"), None);
+    }
+}
@@ -0,0 +1,176 @@
+//! Regex rules applied to each line of the decoded text, which drop what
+//! they match, or keep it past the char filter, and the hostnames converted
+//! to punycode.
+
+use regex::Regex;
+
+/// How a span of a line is treated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Rule {
+    /// Subject to the char filter.
+    Filter,
+    /// Kept as is.
+    Keep,
+    /// Dropped without substitution.
+    Drop,
+    /// A hostname with labels which are not ASCII, converted to punycode.
+    Punycode,
+}
+
+/// Matches what looks like a hostname: labels of letters, marks, digits and
+/// inner hyphens, separated by dots, the last of letters only.
+const HOSTNAME: &str = concat!(
+    r"(?:[\p{L}\p{M}\p{N}](?:[\p{L}\p{M}\p{N}-]*[\p{L}\p{M}\p{N}])?\.)+",
+    r"\p{L}[\p{L}\p{M}]+",
+);
+
+/// Holds text back until the end of its line, so that the rules match
+/// against whole lines however the text is written.
+#[derive(Debug, Clone)]
+pub(crate) struct LineRules {
+    /// Drop what this matches, before anything else.
+    drop: Option<Regex>,
+    /// Keep what this matches, unless dropped.
+    keep: Option<Regex>,
+    /// If not `None`, convert the hostnames this matches, unless kept or
+    /// dropped, if not ASCII.
+    hostnames: Option<Regex>,
+    /// The text of the line not yet ended.
+    line: String,
+}
+
+impl LineRules {
+    pub(crate) fn new(drop: Option<Regex>, keep: Option<Regex>) -> Self {
+        Self {
+            drop,
+            keep,
+            hostnames: None,
+            line: String::new(),
+        }
+    }
+
+    /// Convert the hostnames with labels which are not ASCII too.
+    pub(crate) fn with_hostnames(mut self) -> Self {
+        self.hostnames = Some(Regex::new(HOSTNAME).unwrap());
+        self
+    }
+
+    /// Append `s` to the text held back, returning the lines ended, if any,
+    /// with their `end`, e.g. '\n'.
+    pub(crate) fn push(&mut self, s: &str, end: char) -> Option<String> {
+        self.line.push_str(s);
+        let end = self.line.rfind(end)? + 1;
+        let rest = self.line.split_off(end);
+        Some(std::mem::replace(&mut self.line, rest))
+    }
+
+    /// Return the text held back, e.g. at the end of input, which then
+    /// counts as a line.
+    pub(crate) fn finish(&mut self) -> String {
+        std::mem::take(&mut self.line)
+    }
+
+    /// Split `lines` into the spans each rule applies to, in order, where
+    /// the rules match against each line without its `end`, e.g. '\n'.
+    pub(crate) fn split<'a>(
+        &self,
+        lines: &'a str,
+        end: char,
+    ) -> Vec<(Rule, &'a str)> {
+        let mut spans: Vec<(Rule, &'a str)> = Vec::new();
+        for line in lines.split_inclusive(end) {
+            let text = line.strip_suffix(end).unwrap_or(line);
+            let mut rules = vec![Rule::Filter; text.len()];
+            if let Some(re) = &self.hostnames {
+                for m in re.find_iter(text).filter(|m| !m.as_str().is_ascii()) {
+                    rules[m.range()].fill(Rule::Punycode);
+                }
+            }
+            // dropping wins over keeping where both match
+            for (re, rule) in
+                [(&self.keep, Rule::Keep), (&self.drop, Rule::Drop)]
+            {
+                let Some(re) = re else {
+                    continue;
+                };
+                for m in re.find_iter(text) {
+                    rules[m.range()].fill(rule);
+                }
+            }
+            let mut start: usize = 0;
+            for i in 1..=text.len() {
+                if i == text.len() || rules[i] != rules[start] {
+                    spans.push((rules[start], &text[start..i]));
+                    start = i;
+                }
+            }
+            if text.len() < line.len() {
+                spans.push((Rule::Filter, &line[text.len()..]));
+            }
+        }
+        spans
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LineRules, Rule};
+    use regex::Regex;
+
+    #[test]
+    fn test_line_rules() {
+        let mut rules = LineRules::new(
+            Some(Regex::new("[0-9]+$").unwrap()),
+            Some(Regex::new("é[a-z]*").unwrap()),
+        );
+        assert_eq!(rules.push("café 12", '\n'), None);
+        let lines = rules.push("3\nété 4", '\n').unwrap();
+        assert_eq!(lines, "café 123\n");
+        assert_eq!(
+            rules.split(&lines, '\n'),
+            [
+                (Rule::Filter, "caf"),
+                (Rule::Keep, "é"),
+                (Rule::Filter, " "),
+                (Rule::Drop, "123"),
+                (Rule::Filter, "\n"),
+            ]
+        );
+        let rest = rules.finish();
+        assert_eq!(
+            rules.split(&rest, '\n'),
+            [(Rule::Keep, "été"), (Rule::Filter, " "), (Rule::Drop, "4")]
+        );
+        // NUL-delimited records
+        let lines = rules.push("12\n3\0é", '\0').unwrap();
+        assert_eq!(
+            rules.split(&lines, '\0'),
+            [
+                (Rule::Filter, "12\n"),
+                (Rule::Drop, "3"),
+                (Rule::Filter, "\0"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_line_rules_hostnames() {
+        let rules = LineRules::new(Some(Regex::new("ß").unwrap()), None)
+            .with_hostnames();
+        assert_eq!(
+            rules
+                .split("é mail@bücher.de, café.fr. example.com süß.de\n", '\n'),
+            [
+                (Rule::Filter, "é mail@"),
+                (Rule::Punycode, "bücher.de"),
+                (Rule::Filter, ", "),
+                (Rule::Punycode, "café.fr"),
+                (Rule::Filter, ". example.com "),
+                (Rule::Punycode, "sü"),
+                (Rule::Drop, "ß"),
+                (Rule::Punycode, ".de"),
+                (Rule::Filter, "\n"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,249 @@
+//! A self-test of the filter under given [`Options`], on generated
+//! adversarial inputs, so that a build and configuration can be validated on
+//! the platform at hand.
+
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::charset::CharFilter;
+use crate::{filter_bytes, Event, FilterMachine, InvalidUtf8, Options};
+
+/// An input on which an invariant does not hold.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelftestFailure {
+    /// The name of the input.
+    pub case: String,
+    pub message: String,
+}
+
+impl fmt::Display for SelftestFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.case, self.message)
+    }
+}
+
+/// The outcome of [`selftest`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SelftestReport {
+    /// The number of inputs tested.
+    pub cases: usize,
+    pub failures: Vec<SelftestFailure>,
+}
+
+/// The multibyte chars split across chunk boundaries, of each length.
+const MULTIBYTE: [&str; 3] = ["é", "€", "😀"];
+
+/// Invalid sequences: truncated, lone continuation, overlong, surrogate,
+/// beyond U+10FFFF, and never valid bytes.
+const INVALID: [&[u8]; 9] = [
+    b"\xc3",
+    b"\xe2\x82",
+    b"\xf0\x9f\x98",
+    b"\x80",
+    b"\xc0\x80",
+    b"\xe0\x80\x80",
+    b"\xed\xa0\x80",
+    b"\xf4\x90\x80\x80",
+    b"\xff\xfe",
+];
+
+/// The length of the huge runs.
+const RUN_LEN: usize = 1 << 16;
+
+/// Generate the inputs, as pairs of name and bytes.
+fn cases(buf_size: usize) -> Vec<(String, Vec<u8>)> {
+    let mut cases: Vec<(String, Vec<u8>)> = Vec::new();
+    // every offset of a multibyte char or an invalid sequence relative to
+    // the first two chunks
+    for pad in 0..=2 * buf_size {
+        for s in MULTIBYTE {
+            let mut bytes = vec![b'a'; pad];
+            bytes.extend_from_slice(s.as_bytes());
+            bytes.extend_from_slice(b"z\n");
+            cases.push((format!("{:?} after {} bytes", s, pad), bytes));
+        }
+        for seq in INVALID {
+            let mut bytes = vec![b'a'; pad];
+            bytes.extend_from_slice(seq);
+            bytes.extend_from_slice("é\n".as_bytes());
+            cases.push((format!("{:x?} after {} bytes", seq, pad), bytes));
+        }
+    }
+    // huge runs
+    cases.push(("run of ASCII".to_string(), vec![b'a'; RUN_LEN]));
+    cases.push((
+        "run of multibyte chars".to_string(),
+        "€".repeat(RUN_LEN / 3).into_bytes(),
+    ));
+    cases.push(("run of continuation bytes".to_string(), vec![0x80; RUN_LEN]));
+    cases.push(("run of leading bytes".to_string(), vec![0xf0; RUN_LEN]));
+    // random bytes, biased towards the interesting ones
+    let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+    for i in 0..64 {
+        let bytes = (0..rng.next() % 4096)
+            .map(|_| match rng.next() % 4 {
+                0 => b'a',
+                1 => 0x80 | (rng.next() as u8 & 0x3f),
+                2 => 0xc0 | (rng.next() as u8 & 0x3f),
+                _ => rng.next() as u8,
+            })
+            .collect();
+        cases.push((format!("random #{}", i), bytes));
+    }
+    cases
+}
+
+/// A deterministic pseudo-random generator, so that failures reproduce.
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+/// Check the invariants on `input`, returning the first one that does not
+/// hold.
+fn check(input: &[u8], opts: &Options) -> Result<(), String> {
+    let output =
+        panic::catch_unwind(AssertUnwindSafe(|| filter_bytes(input, opts)))
+            .map_err(|_| "the filter panics".to_string())?;
+    if std::str::from_utf8(&output).is_err() {
+        return Err("the output is not valid utf-8".to_string());
+    }
+    // the regex rules, emoji handling, line ending conversion and tab
+    // expansion decide spans of the output beyond the char filter, and the
+    // events do not reflect them
+    if opts.has_regex_rules()
+        || opts.emoji.is_some()
+        || opts.newlines.is_some()
+        || opts.expand_tabs.is_some()
+    {
+        return Ok(());
+    }
+    let chars = CharFilter::new(opts);
+    if !chars.keeps_all() {
+        if let Some(c) = std::str::from_utf8(&output)
+            .unwrap()
+            .chars()
+            // highlighting keeps the chars dropped otherwise
+            .find(|&c| chars.keeps(c) == opts.invert && !opts.highlight)
+        {
+            return Err(format!("the output contains {:?}", c));
+        }
+    } else if opts.invert {
+        if !output.is_empty() {
+            return Err("the output is not empty".to_string());
+        }
+    } else if !opts.normalizes()
+        && !opts.strip_ansi
+        && std::str::from_utf8(input).is_ok()
+        && output != input
+    {
+        return Err("valid utf-8 input is not passed through".to_string());
+    }
+
+    // the events account for every input byte, in order, and agree with the
+    // output, however the input is chunked, unless normalized, stripped of
+    // escapes or of whole lines, or with the invalid sequences decoded, which
+    // the events do not reflect
+    if opts.normalizes()
+        || opts.strip_ansi
+        || opts.drop_lines
+        || opts.invalid_utf8 != InvalidUtf8::Drop
+    {
+        return Ok(());
+    }
+    for chunk_size in [input.len().max(1), 1, 7] {
+        let mut machine = FilterMachine::new(opts);
+        let mut events: Vec<Event> = Vec::new();
+        for chunk in input.chunks(chunk_size) {
+            events
+                .extend(machine.push(chunk).map_err(|e| e.to_string())?.events);
+        }
+        events.extend(machine.finish().map_err(|e| e.to_string())?.events);
+        let mut expected_offset: usize = 0;
+        let mut kept: Vec<u8> = Vec::new();
+        for event in &events {
+            let (offset, len) = match event {
+                Event::Kept { offset, bytes } => {
+                    kept.extend_from_slice(bytes);
+                    (*offset, bytes.len())
+                }
+                Event::Replaced { offset, c, bytes } => {
+                    kept.extend_from_slice(bytes);
+                    (*offset, c.len_utf8())
+                }
+                Event::Dropped { offset, c } => (*offset, c.len_utf8()),
+                Event::Invalid { offset, bytes } => (*offset, bytes.len()),
+            };
+            if offset != expected_offset {
+                return Err(format!(
+                    "in chunks of {}, an event at offset {} where {} is \
+                     expected",
+                    chunk_size, offset, expected_offset
+                ));
+            }
+            expected_offset += len;
+        }
+        if expected_offset != input.len() {
+            return Err(format!(
+                "in chunks of {}, the events cover {} of {} bytes",
+                chunk_size,
+                expected_offset,
+                input.len()
+            ));
+        }
+        if kept != output {
+            return Err(format!(
+                "in chunks of {}, the events disagree with the output",
+                chunk_size
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Run the filter configured by `opts` on generated adversarial inputs, and
+/// check that it never panics, always outputs valid utf-8, outputs only the
+/// kept chars if filtering them and passes valid utf-8 through otherwise,
+/// and reports events consistent with the output.
+pub fn selftest(opts: &Options) -> SelftestReport {
+    let mut report = SelftestReport::default();
+    // keep the panic messages of failing cases out of the report
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| ()));
+    for (case, input) in cases(opts.buf_size) {
+        report.cases += 1;
+        if let Err(message) = check(&input, opts) {
+            report.failures.push(SelftestFailure { case, message });
+        }
+    }
+    panic::set_hook(hook);
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::selftest;
+    use crate::{Locale, Mapping, Options};
+
+    #[test]
+    fn test_selftest() {
+        for ascii_only in [false, true] {
+            let opts = Options {
+                buf_size: 16,
+                ascii_only,
+                mappings: vec![Mapping::Translit(Locale::De), Mapping::Symbols],
+                ..Options::default()
+            };
+            let report = selftest(&opts);
+            assert!(report.cases > 0);
+            assert_eq!(report.failures, vec![]);
+        }
+    }
+}
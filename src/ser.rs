@@ -0,0 +1,494 @@
+//! A `serde::Serializer` adapter that filters every string passing through
+//! it, so that anything serialized with it is clean by construction,
+//! whatever the data format.
+
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::sync::{Mutex, OnceLock};
+
+use serde::ser::{
+    Serialize, SerializeMap, SerializeSeq, SerializeStruct,
+    SerializeStructVariant, SerializeTuple, SerializeTupleStruct,
+    SerializeTupleVariant, Serializer,
+};
+
+use crate::{filter_str, filter_string, Options};
+
+/// Wrap `inner`, filtering every `str` and `char` per `opts` before
+/// delegating. Map keys, and the names of types, fields and variants, are
+/// filtered too.
+pub struct SanitizingSerializer<'a, S> {
+    inner: S,
+    opts: &'a Options,
+}
+
+impl<'a, S: Serializer> SanitizingSerializer<'a, S> {
+    pub fn new(inner: S, opts: &'a Options) -> Self {
+        Self { inner, opts }
+    }
+}
+
+/// A `Serialize` view of `T` that serializes through
+/// [`SanitizingSerializer`].
+pub struct Sanitized<'a, T: ?Sized> {
+    value: &'a T,
+    opts: &'a Options,
+}
+
+impl<'a, T: ?Sized> Sanitized<'a, T> {
+    pub fn new(value: &'a T, opts: &'a Options) -> Self {
+        Self { value, opts }
+    }
+}
+
+impl<'a, T: ?Sized + Serialize> Serialize for Sanitized<'a, T> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        self.value
+            .serialize(SanitizingSerializer::new(s, self.opts))
+    }
+}
+
+/// Wrapper of the compound serializers of the inner serializer, which
+/// sanitizes the elements.
+pub struct Compound<'a, C> {
+    inner: C,
+    opts: &'a Options,
+}
+
+impl<'a, C> Compound<'a, C> {
+    fn new(inner: C, opts: &'a Options) -> Self {
+        Self { inner, opts }
+    }
+}
+
+/// Filter the name `name` of a type, field or variant per `opts`. Since the
+/// serializers take the names as `&'static str`, each distinct name changed
+/// by the filter is leaked once, which is bounded as the names come from the
+/// types serialized.
+fn filter_name(name: &'static str, opts: &Options) -> &'static str {
+    static NAMES: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+    let filtered = match filter_str(name, opts) {
+        Cow::Borrowed(_) => return name,
+        Cow::Owned(filtered) => filtered,
+    };
+    let mut names = NAMES.get_or_init(Default::default).lock().unwrap();
+    match names.get(filtered.as_str()) {
+        Some(&interned) => interned,
+        None => {
+            let interned: &'static str = filtered.leak();
+            names.insert(interned);
+            interned
+        }
+    }
+}
+
+impl<'a, S: Serializer> Serializer for SanitizingSerializer<'a, S> {
+    type Ok = S::Ok;
+    type Error = S::Error;
+    type SerializeSeq = Compound<'a, S::SerializeSeq>;
+    type SerializeTuple = Compound<'a, S::SerializeTuple>;
+    type SerializeTupleStruct = Compound<'a, S::SerializeTupleStruct>;
+    type SerializeTupleVariant = Compound<'a, S::SerializeTupleVariant>;
+    type SerializeMap = Compound<'a, S::SerializeMap>;
+    type SerializeStruct = Compound<'a, S::SerializeStruct>;
+    type SerializeStructVariant = Compound<'a, S::SerializeStructVariant>;
+
+    fn serialize_bool(self, v: bool) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_bool(v)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_i8(v)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_i16(v)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_i32(v)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_i64(v)
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_i128(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_u8(v)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_u16(v)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_u32(v)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_u64(v)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_u128(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_f32(v)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_f64(v)
+    }
+
+    fn serialize_char(self, v: char) -> Result<S::Ok, S::Error> {
+        let mut buf = [0u8; 4];
+        let filtered = filter_string(v.encode_utf8(&mut buf), self.opts);
+        let mut chars = filtered.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => self.inner.serialize_char(c),
+            // `v` has been dropped, which no `char` represents
+            _ => self.inner.serialize_str(&filtered),
+        }
+    }
+
+    fn serialize_str(self, v: &str) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_str(&filter_string(v, self.opts))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_bytes(v)
+    }
+
+    fn serialize_none(self) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_none()
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(
+        self,
+        value: &T,
+    ) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_some(&Sanitized::new(value, self.opts))
+    }
+
+    fn serialize_unit(self) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_unit()
+    }
+
+    fn serialize_unit_struct(
+        self,
+        name: &'static str,
+    ) -> Result<S::Ok, S::Error> {
+        self.inner
+            .serialize_unit_struct(filter_name(name, self.opts))
+    }
+
+    fn serialize_unit_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+    ) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_unit_variant(
+            filter_name(name, self.opts),
+            variant_index,
+            filter_name(variant, self.opts),
+        )
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_newtype_struct(
+            filter_name(name, self.opts),
+            &Sanitized::new(value, self.opts),
+        )
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_newtype_variant(
+            filter_name(name, self.opts),
+            variant_index,
+            filter_name(variant, self.opts),
+            &Sanitized::new(value, self.opts),
+        )
+    }
+
+    fn serialize_seq(
+        self,
+        len: Option<usize>,
+    ) -> Result<Self::SerializeSeq, S::Error> {
+        Ok(Compound::new(self.inner.serialize_seq(len)?, self.opts))
+    }
+
+    fn serialize_tuple(
+        self,
+        len: usize,
+    ) -> Result<Self::SerializeTuple, S::Error> {
+        Ok(Compound::new(self.inner.serialize_tuple(len)?, self.opts))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, S::Error> {
+        Ok(Compound::new(
+            self.inner
+                .serialize_tuple_struct(filter_name(name, self.opts), len)?,
+            self.opts,
+        ))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, S::Error> {
+        Ok(Compound::new(
+            self.inner.serialize_tuple_variant(
+                filter_name(name, self.opts),
+                variant_index,
+                filter_name(variant, self.opts),
+                len,
+            )?,
+            self.opts,
+        ))
+    }
+
+    fn serialize_map(
+        self,
+        len: Option<usize>,
+    ) -> Result<Self::SerializeMap, S::Error> {
+        Ok(Compound::new(self.inner.serialize_map(len)?, self.opts))
+    }
+
+    fn serialize_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, S::Error> {
+        Ok(Compound::new(
+            self.inner
+                .serialize_struct(filter_name(name, self.opts), len)?,
+            self.opts,
+        ))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant, S::Error> {
+        Ok(Compound::new(
+            self.inner.serialize_struct_variant(
+                filter_name(name, self.opts),
+                variant_index,
+                filter_name(variant, self.opts),
+                len,
+            )?,
+            self.opts,
+        ))
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+}
+
+impl<'a, C: SerializeSeq> SerializeSeq for Compound<'a, C> {
+    type Ok = C::Ok;
+    type Error = C::Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), C::Error> {
+        self.inner
+            .serialize_element(&Sanitized::new(value, self.opts))
+    }
+
+    fn end(self) -> Result<C::Ok, C::Error> {
+        self.inner.end()
+    }
+}
+
+impl<'a, C: SerializeTuple> SerializeTuple for Compound<'a, C> {
+    type Ok = C::Ok;
+    type Error = C::Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), C::Error> {
+        self.inner
+            .serialize_element(&Sanitized::new(value, self.opts))
+    }
+
+    fn end(self) -> Result<C::Ok, C::Error> {
+        self.inner.end()
+    }
+}
+
+impl<'a, C: SerializeTupleStruct> SerializeTupleStruct for Compound<'a, C> {
+    type Ok = C::Ok;
+    type Error = C::Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), C::Error> {
+        self.inner
+            .serialize_field(&Sanitized::new(value, self.opts))
+    }
+
+    fn end(self) -> Result<C::Ok, C::Error> {
+        self.inner.end()
+    }
+}
+
+impl<'a, C: SerializeTupleVariant> SerializeTupleVariant for Compound<'a, C> {
+    type Ok = C::Ok;
+    type Error = C::Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), C::Error> {
+        self.inner
+            .serialize_field(&Sanitized::new(value, self.opts))
+    }
+
+    fn end(self) -> Result<C::Ok, C::Error> {
+        self.inner.end()
+    }
+}
+
+impl<'a, C: SerializeMap> SerializeMap for Compound<'a, C> {
+    type Ok = C::Ok;
+    type Error = C::Error;
+
+    fn serialize_key<T: ?Sized + Serialize>(
+        &mut self,
+        key: &T,
+    ) -> Result<(), C::Error> {
+        self.inner.serialize_key(&Sanitized::new(key, self.opts))
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), C::Error> {
+        self.inner
+            .serialize_value(&Sanitized::new(value, self.opts))
+    }
+
+    fn end(self) -> Result<C::Ok, C::Error> {
+        self.inner.end()
+    }
+}
+
+impl<'a, C: SerializeStruct> SerializeStruct for Compound<'a, C> {
+    type Ok = C::Ok;
+    type Error = C::Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), C::Error> {
+        self.inner.serialize_field(
+            filter_name(key, self.opts),
+            &Sanitized::new(value, self.opts),
+        )
+    }
+
+    fn skip_field(&mut self, key: &'static str) -> Result<(), C::Error> {
+        self.inner.skip_field(filter_name(key, self.opts))
+    }
+
+    fn end(self) -> Result<C::Ok, C::Error> {
+        self.inner.end()
+    }
+}
+
+impl<'a, C: SerializeStructVariant> SerializeStructVariant for Compound<'a, C> {
+    type Ok = C::Ok;
+    type Error = C::Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), C::Error> {
+        self.inner.serialize_field(
+            filter_name(key, self.opts),
+            &Sanitized::new(value, self.opts),
+        )
+    }
+
+    fn skip_field(&mut self, key: &'static str) -> Result<(), C::Error> {
+        self.inner.skip_field(filter_name(key, self.opts))
+    }
+
+    fn end(self) -> Result<C::Ok, C::Error> {
+        self.inner.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Sanitized;
+    use crate::Options;
+    use serde::Serialize;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_sanitized() {
+        let opts = Options {
+            ascii_only: true,
+            ..Options::default()
+        };
+        let mut map: BTreeMap<&str, (Vec<&str>, Option<char>, char)> =
+            BTreeMap::new();
+        map.insert("kéy", (vec!["vä", "ok"], Some('a'), 'ü'));
+        let json = serde_json::to_string(&Sanitized::new(&map, &opts)).unwrap();
+        assert_eq!(json, r#"{"ky":[["v","ok"],"a",""]}"#);
+    }
+
+    #[derive(Serialize)]
+    enum Änum {
+        Ünit,
+        Strüct { fïeld: &'static str },
+    }
+
+    #[test]
+    fn test_sanitized_names() {
+        let opts = Options {
+            ascii_only: true,
+            ..Options::default()
+        };
+        let json = serde_json::to_string(&Sanitized::new(
+            &[Änum::Ünit, Änum::Strüct { fïeld: "vä" }],
+            &opts,
+        ))
+        .unwrap();
+        assert_eq!(json, r#"["nit",{"Strct":{"feld":"v"}}]"#);
+    }
+}
@@ -0,0 +1,333 @@
+//! Serving the filter on a socket, where each connection accepted is
+//! filtered and echoed back, or relayed to another socket.
+
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::thread;
+
+use crate::{caught_signal, filter, follow, Error, Options, Stats};
+
+/// The address of a socket, e.g. `tcp:127.0.0.1:9000` or
+/// `unix:/run/filter.sock`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Endpoint {
+    /// A TCP socket, by host and port.
+    Tcp(String),
+    /// A Unix domain socket, by path.
+    Unix(PathBuf),
+}
+
+impl fmt::Display for Endpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Endpoint::Tcp(addr) => write!(f, "tcp:{}", addr),
+            Endpoint::Unix(path) => write!(f, "unix:{}", path.display()),
+        }
+    }
+}
+
+impl FromStr for Endpoint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some(("tcp", addr)) if !addr.is_empty() => {
+                Ok(Endpoint::Tcp(addr.to_string()))
+            }
+            Some(("unix", path)) if !path.is_empty() => {
+                Ok(Endpoint::Unix(PathBuf::from(path)))
+            }
+            _ => Err(format!(
+                "invalid socket address {:?}, expected tcp:HOST:PORT or \
+                 unix:PATH",
+                s
+            )),
+        }
+    }
+}
+
+/// A connected socket of either kind.
+enum Stream {
+    Tcp(TcpStream),
+    #[cfg(unix)]
+    Unix(UnixStream),
+}
+
+impl Stream {
+    fn connect(endpoint: &Endpoint) -> io::Result<Self> {
+        match endpoint {
+            Endpoint::Tcp(addr) => TcpStream::connect(addr).map(Stream::Tcp),
+            #[cfg(unix)]
+            Endpoint::Unix(path) => UnixStream::connect(path).map(Stream::Unix),
+            #[cfg(not(unix))]
+            Endpoint::Unix(_) => Err(unix_unsupported()),
+        }
+    }
+
+    /// Signal the end of what is written to the peer.
+    fn shutdown_write(&self) -> io::Result<()> {
+        let result = match self {
+            Stream::Tcp(s) => s.shutdown(Shutdown::Write),
+            #[cfg(unix)]
+            Stream::Unix(s) => s.shutdown(Shutdown::Write),
+        };
+        match result {
+            // the peer is gone already
+            Err(e) if e.kind() == io::ErrorKind::NotConnected => Ok(()),
+            result => result,
+        }
+    }
+}
+
+impl Read for &Stream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Stream::Tcp(s) => (&*s).read(buf),
+            #[cfg(unix)]
+            Stream::Unix(s) => (&*s).read(buf),
+        }
+    }
+}
+
+impl Write for &Stream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Stream::Tcp(s) => (&*s).write(buf),
+            #[cfg(unix)]
+            Stream::Unix(s) => (&*s).write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Stream::Tcp(s) => (&*s).flush(),
+            #[cfg(unix)]
+            Stream::Unix(s) => (&*s).flush(),
+        }
+    }
+}
+
+/// A listening socket of either kind.
+enum Listener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix(UnixListener),
+}
+
+impl Listener {
+    fn bind(endpoint: &Endpoint) -> io::Result<Self> {
+        match endpoint {
+            Endpoint::Tcp(addr) => TcpListener::bind(addr).map(Listener::Tcp),
+            #[cfg(unix)]
+            Endpoint::Unix(path) => {
+                UnixListener::bind(path).map(Listener::Unix)
+            }
+            #[cfg(not(unix))]
+            Endpoint::Unix(_) => Err(unix_unsupported()),
+        }
+    }
+
+    /// Wait for a connection to accept, and fail with
+    /// [`io::ErrorKind::Interrupted`] on a signal, which the accept itself
+    /// retries on.
+    #[cfg(unix)]
+    fn wait(&self) -> io::Result<()> {
+        use std::os::fd::AsRawFd;
+        let fd = match self {
+            Listener::Tcp(l) => l.as_raw_fd(),
+            Listener::Unix(l) => l.as_raw_fd(),
+        };
+        let mut pollfd = libc::pollfd {
+            fd,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        // SAFETY: `pollfd` is valid for the call.
+        match unsafe { libc::poll(&mut pollfd, 1, -1) } {
+            -1 => Err(io::Error::last_os_error()),
+            _ => Ok(()),
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn wait(&self) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Accept a connection, returning it with a description of the peer.
+    fn accept(&self) -> io::Result<(Stream, String)> {
+        match self {
+            Listener::Tcp(l) => {
+                let (s, addr) = l.accept()?;
+                Ok((Stream::Tcp(s), addr.to_string()))
+            }
+            #[cfg(unix)]
+            Listener::Unix(l) => {
+                let (s, addr) = l.accept()?;
+                let peer = match addr.as_pathname() {
+                    Some(path) => path.display().to_string(),
+                    None => "unix socket peer".to_string(),
+                };
+                Ok((Stream::Unix(s), peer))
+            }
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn unix_unsupported() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        "unix sockets are only supported on unix",
+    )
+}
+
+/// Filter what the peer of `client` sends, back to it, or to a new
+/// connection to `upstream` if any, whose replies are relayed to the peer
+/// unfiltered.
+fn handle(
+    client: Stream,
+    upstream: Option<&Endpoint>,
+    opts: &Options,
+) -> Result<Stats, Error> {
+    let Some(upstream) = upstream else {
+        let stats = filter(&client, &client, opts)?;
+        client.shutdown_write()?;
+        return Ok(stats);
+    };
+    let server = Stream::connect(upstream)?;
+    thread::scope(|s| {
+        let relay = s.spawn(|| -> io::Result<()> {
+            io::copy(&mut &server, &mut &client)?;
+            client.shutdown_write()
+        });
+        let result = filter(&client, &server, opts);
+        // the replies end once the server sees the end of the requests
+        server.shutdown_write()?;
+        relay.join().unwrap()?;
+        result
+    })
+}
+
+/// Listen on `listen`, and filter each connection accepted per `opts`, in a
+/// thread of its own, back to its peer, or to a new connection to `connect`
+/// if any, whose replies are relayed unfiltered, e.g. as a sanitizing relay
+/// in front of a service which chokes on non-ASCII input. The output is
+/// flushed after each line. `on_connection` is called with the peer and the
+/// result of each connection once it is closed. Serves until the accept is
+/// interrupted by a signal handled by
+/// [`end_input_on_signals`](crate::end_input_on_signals), after the
+/// connections open are closed, and the Unix socket, if any, is removed.
+/// The options specific to the standard streams do not apply, as with
+/// [`filter`].
+pub fn serve<F>(
+    listen: &Endpoint,
+    connect: Option<&Endpoint>,
+    opts: &Options,
+    on_connection: F,
+) -> Result<(), Error>
+where
+    F: Fn(&str, Result<Stats, Error>) + Sync,
+{
+    let listener = Listener::bind(listen)?;
+    let opts = Options {
+        line_buffered: true,
+        ..opts.clone()
+    };
+    let (opts, on_connection) = (&opts, &on_connection);
+    let result = thread::scope(|s| loop {
+        match listener.wait().and_then(|()| listener.accept()) {
+            Ok((client, peer)) => {
+                s.spawn(move || {
+                    on_connection(&peer, handle(client, connect, opts))
+                });
+            }
+            Err(_) if caught_signal().is_some() => return Ok(()),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            // e.g. too many open files, until a connection is closed
+            Err(e) => {
+                on_connection(&listen.to_string(), Err(e.into()));
+                thread::sleep(follow::INTERVAL);
+            }
+        }
+    });
+    if let Endpoint::Unix(path) = listen {
+        let _ = std::fs::remove_file(path);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{handle, Endpoint, Stream};
+    use crate::Options;
+    use std::io::{Read, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::thread;
+
+    #[test]
+    fn test_endpoint_from_str() {
+        for s in ["tcp:127.0.0.1:9000", "unix:/run/filter.sock"] {
+            assert_eq!(s.parse::<Endpoint>().unwrap().to_string(), s);
+        }
+        assert!("127.0.0.1:9000".parse::<Endpoint>().is_err());
+        assert!("tcp:".parse::<Endpoint>().is_err());
+    }
+
+    /// Return a connected pair of TCP streams, client side first.
+    fn pair(listener: &TcpListener) -> (TcpStream, TcpStream) {
+        let client = TcpStream::connect(listener.local_addr().unwrap());
+        let (server, _) = listener.accept().unwrap();
+        (client.unwrap(), server)
+    }
+
+    #[test]
+    fn test_handle() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let opts = Options {
+            ascii_only: true,
+            ..Options::default()
+        };
+
+        // echo
+        let (mut client, server) = pair(&listener);
+        let handler = thread::spawn({
+            let opts = opts.clone();
+            move || handle(Stream::Tcp(server), None, &opts).unwrap()
+        });
+        client.write_all("a你b\n".as_bytes()).unwrap();
+        client.shutdown(std::net::Shutdown::Write).unwrap();
+        let mut echoed = String::new();
+        client.read_to_string(&mut echoed).unwrap();
+        assert_eq!(echoed, "ab\n");
+        assert_eq!(handler.join().unwrap().chars_removed, 1);
+
+        // relay
+        let upstream = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint =
+            Endpoint::Tcp(upstream.local_addr().unwrap().to_string());
+        let service = thread::spawn(move || {
+            let (mut s, _) = upstream.accept().unwrap();
+            let mut got = String::new();
+            s.read_to_string(&mut got).unwrap();
+            s.write_all(format!("got {}é", got.trim()).as_bytes())
+                .unwrap();
+        });
+        let (mut client, server) = pair(&listener);
+        let handler = thread::spawn(move || {
+            handle(Stream::Tcp(server), Some(&endpoint), &opts).unwrap()
+        });
+        client.write_all("x→y\n".as_bytes()).unwrap();
+        client.shutdown(std::net::Shutdown::Write).unwrap();
+        let mut reply = String::new();
+        client.read_to_string(&mut reply).unwrap();
+        assert_eq!(reply, "got xyé");
+        handler.join().unwrap();
+        service.join().unwrap();
+    }
+}
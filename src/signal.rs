@@ -0,0 +1,77 @@
+//! Ending the input on SIGINT or SIGTERM, so that an interrupted run still
+//! flushes the output so far and finishes its reports.
+
+use std::io;
+use std::sync::atomic::{AtomicI32, Ordering};
+
+/// The signal caught first, or 0 if none.
+static CAUGHT: AtomicI32 = AtomicI32::new(0);
+
+/// Return the signal which ended the input, if any.
+pub fn caught_signal() -> Option<i32> {
+    match CAUGHT.load(Ordering::SeqCst) {
+        0 => None,
+        sig => Some(sig),
+    }
+}
+
+#[cfg(unix)]
+extern "C" fn handle(sig: libc::c_int) {
+    if CAUGHT.swap(sig, Ordering::SeqCst) != 0 {
+        // A second signal gives up on finishing.
+        // SAFETY: `_exit` is async-signal-safe.
+        unsafe { libc::_exit(128 + sig) };
+    }
+}
+
+/// Handle SIGINT and SIGTERM by ending the input, so that the filtering in
+/// progress, e.g. of [`stdin_stdout_buffer_filter`], flushes the output so
+/// far and returns, after which [`caught_signal`] returns the signal. A
+/// blocking read of the input is interrupted, but the input of the
+/// line-based formats is read to the end of a line first. A second signal
+/// exits at once with 128 plus its number. Does nothing but on Unix.
+///
+/// [`stdin_stdout_buffer_filter`]: crate::stdin_stdout_buffer_filter
+#[cfg(unix)]
+pub fn end_input_on_signals() -> io::Result<()> {
+    for sig in [libc::SIGINT, libc::SIGTERM] {
+        // SAFETY: a zeroed `sigaction` is valid, and its fields are set
+        // before use.
+        let mut action: libc::sigaction = unsafe { std::mem::zeroed() };
+        action.sa_sigaction = handle as extern "C" fn(libc::c_int) as usize;
+        // Without SA_RESTART, so that a blocking read fails with EINTR.
+        action.sa_flags = 0;
+        // SAFETY: `action` and its mask are valid for the calls.
+        let ok = unsafe {
+            libc::sigemptyset(&mut action.sa_mask);
+            libc::sigaction(sig, &action, std::ptr::null_mut())
+        };
+        if ok != 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn end_input_on_signals() -> io::Result<()> {
+    Ok(())
+}
+
+/// Leave SIGINT and SIGTERM to the other threads, so that they interrupt the
+/// blocking read of the input on the thread reading it rather than land on
+/// this one. Does nothing but on Unix.
+#[cfg(unix)]
+pub(crate) fn block_on_this_thread() {
+    // SAFETY: a zeroed `sigset_t` is valid, and initialized before use.
+    unsafe {
+        let mut set: libc::sigset_t = std::mem::zeroed();
+        libc::sigemptyset(&mut set);
+        libc::sigaddset(&mut set, libc::SIGINT);
+        libc::sigaddset(&mut set, libc::SIGTERM);
+        libc::pthread_sigmask(libc::SIG_BLOCK, &set, std::ptr::null_mut());
+    }
+}
+
+#[cfg(not(unix))]
+pub(crate) fn block_on_this_thread() {}
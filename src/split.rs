@@ -0,0 +1,92 @@
+//! Routing of clean and dirty lines to separate sinks, so that lines changed
+//! by the filter can be quarantined while clean lines pass straight through.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufWriter, Write};
+use std::path::Path;
+
+use crate::{open_output, Destination, FilterWriter, Options, UTF8_BOM};
+
+fn open(path: Option<&Path>, opts: &Options) -> io::Result<Destination> {
+    match path {
+        Some(path) => Ok(Destination::stream(Box::new(BufWriter::new(
+            File::create(path)?,
+        )))),
+        None => open_output(opts),
+    }
+}
+
+/// Read lines from `r`, and write the lines unchanged by `filter_line` to
+/// `opts.clean_output`, and the others filtered to `opts.dirty_output`, where
+/// a missing sink is the output, stdout or `opts.output_fd`. Return the
+/// numbers of bytes read and written.
+pub(crate) fn filter<R, F>(
+    r: &mut R,
+    opts: &Options,
+    mut filter_line: F,
+) -> io::Result<(u64, u64)>
+where
+    R: BufRead,
+    F: FnMut(&[u8]) -> Vec<u8>,
+{
+    let mut clean = open(opts.clean_output.as_deref(), opts)?;
+    let mut dirty = open(opts.dirty_output.as_deref(), opts)?;
+    let mut read: u64 = 0;
+    let mut written: u64 = 0;
+    if opts.write_bom {
+        clean.write_all(UTF8_BOM)?;
+        dirty.write_all(UTF8_BOM)?;
+        written += 2 * UTF8_BOM.len() as u64;
+    }
+    let record_sep = opts.out_record_sep.clone().map(String::into_bytes);
+    let mut clean_fw = FilterWriter::new(&mut clean, false)
+        .with_record_sep(record_sep.clone());
+    let mut dirty_fw =
+        FilterWriter::new(&mut dirty, false).with_record_sep(record_sep);
+    let mut line: Vec<u8> = Vec::new();
+    while r.read_until(b'\n', &mut line)? > 0 {
+        read += line.len() as u64;
+        let filtered = filter_line(&line);
+        if filtered == line {
+            clean_fw.write_all(&line)?;
+        } else {
+            dirty_fw.write_all(&filtered)?;
+        }
+        line.clear();
+    }
+    clean_fw.flush()?;
+    dirty_fw.flush()?;
+    written += clean_fw.stats().bytes_written + dirty_fw.stats().bytes_written;
+    clean.commit()?;
+    dirty.commit()?;
+    Ok((read, written))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::filter;
+    use crate::Options;
+    use std::fs;
+
+    #[test]
+    fn test_filter() {
+        let dir = std::env::temp_dir()
+            .join(format!("ascii-filter-split-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let opts = Options {
+            clean_output: Some(dir.join("clean")),
+            dirty_output: Some(dir.join("dirty")),
+            ..Options::default()
+        };
+        let drop_non_ascii =
+            |line: &[u8]| line.iter().copied().filter(u8::is_ascii).collect();
+        let input = "ok\nnot ök\nok too".as_bytes();
+        assert_eq!(
+            filter(&mut &input[..], &opts, drop_non_ascii).unwrap(),
+            (17, 15)
+        );
+        assert_eq!(fs::read(dir.join("clean")).unwrap(), b"ok\nok too");
+        assert_eq!(fs::read(dir.join("dirty")).unwrap(), b"not k\n");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,219 @@
+//! Filtering of stdin in three stages which overlap, the reads on this
+//! thread, and the filtering and the writes on their own threads, handing
+//! the chunks over through bounded channels, so that a fast input and output
+//! keep the filtering busy rather than waiting on each other in turn.
+
+use std::io::{self, BufRead, Read, Write};
+use std::panic;
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::thread;
+
+use crate::signal::block_on_this_thread;
+use crate::{caught_signal, filter_stream, open_output, Error, Options, Stats};
+
+/// The size of the chunks handed over.
+const CHUNK_LEN: usize = 64 << 10;
+
+/// The chunks each channel holds, so that one is filled while the other is
+/// taken.
+const DEPTH: usize = 2;
+
+/// Return true if stdin is filtered in stages per `opts`, unless serially
+/// per `opts.threads`, or flushing each line, which wants the latency of a
+/// single thread, or on a single CPU.
+pub(crate) fn enabled(opts: &Options) -> bool {
+    opts.threads != Some(1)
+        && !opts.flushes_lines()
+        && opts.clean_output.is_none()
+        && opts.dirty_output.is_none()
+        && thread::available_parallelism().is_ok_and(|n| n.get() > 1)
+}
+
+/// Filter `r` to the output per `opts` in stages, returning the stats of the
+/// filtering. The reads stay on this thread, so that a signal ending the
+/// input interrupts them.
+pub(crate) fn filter<R: Read>(
+    r: &mut R,
+    opts: &Options,
+) -> Result<Stats, Error> {
+    let mut output = open_output(opts)?;
+    let (input_tx, input_rx) = mpsc::sync_channel(DEPTH);
+    let (output_tx, output_rx) = mpsc::sync_channel::<Vec<u8>>(DEPTH);
+    let (filtered, written) = thread::scope(|scope| {
+        let destination = &mut output;
+        let writer = scope.spawn(move || {
+            block_on_this_thread();
+            output_rx
+                .into_iter()
+                .try_for_each(|chunk| destination.write_all(&chunk))
+        });
+        let filterer = scope.spawn(move || {
+            block_on_this_thread();
+            let mut input = ChunkReader {
+                chunks: input_rx,
+                chunk: Vec::new(),
+                pos: 0,
+            };
+            let mut output = ChunkWriter {
+                chunks: output_tx,
+                chunk: Vec::with_capacity(CHUNK_LEN),
+            };
+            let stats = filter_stream(&mut input, &mut output, opts)?;
+            output.flush()?;
+            Ok::<Stats, Error>(stats)
+        });
+        read_chunks(r, &input_tx);
+        drop(input_tx);
+        let filtered = filterer.join();
+        (filtered, writer.join())
+    });
+    let filtered = filtered.unwrap_or_else(|e| panic::resume_unwind(e));
+    // The filtering fails too once the writes do, for want of a writer.
+    written.unwrap_or_else(|e| panic::resume_unwind(e))?;
+    let stats = filtered?;
+    output.commit()?;
+    Ok(stats)
+}
+
+/// Send the chunks read from `r` to `chunks`, until the end of input, a
+/// failed read, a signal ending the input, or the filtering taking no more.
+fn read_chunks<R: Read>(r: &mut R, chunks: &SyncSender<io::Result<Vec<u8>>>) {
+    loop {
+        let mut chunk = vec![0; CHUNK_LEN];
+        let n = match r.read(&mut chunk) {
+            Ok(0) => return,
+            Ok(n) => n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {
+                if caught_signal().is_some() {
+                    return;
+                }
+                continue;
+            }
+            Err(e) => {
+                let _ = chunks.send(Err(e));
+                return;
+            }
+        };
+        chunk.truncate(n);
+        if chunks.send(Ok(chunk)).is_err() || caught_signal().is_some() {
+            return;
+        }
+    }
+}
+
+/// Reads the chunks received as the input of the filtering, ending once the
+/// reads end.
+struct ChunkReader {
+    chunks: Receiver<io::Result<Vec<u8>>>,
+    chunk: Vec<u8>,
+    /// The offset of the rest of `chunk`.
+    pos: usize,
+}
+
+impl Read for ChunkReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let rest = self.fill_buf()?;
+        let n = rest.len().min(buf.len());
+        buf[..n].copy_from_slice(&rest[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl BufRead for ChunkReader {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        while self.pos == self.chunk.len() {
+            let Ok(chunk) = self.chunks.recv() else {
+                break;
+            };
+            self.chunk = chunk?;
+            self.pos = 0;
+        }
+        Ok(&self.chunk[self.pos..])
+    }
+
+    fn consume(&mut self, n: usize) {
+        self.pos += n;
+    }
+}
+
+/// Sends the output of the filtering to the writes in chunks, the last one
+/// once flushed.
+struct ChunkWriter {
+    chunks: SyncSender<Vec<u8>>,
+    chunk: Vec<u8>,
+}
+
+impl ChunkWriter {
+    fn send(&mut self) -> io::Result<()> {
+        let chunk =
+            std::mem::replace(&mut self.chunk, Vec::with_capacity(CHUNK_LEN));
+        self.chunks.send(chunk).map_err(|_| {
+            io::Error::new(io::ErrorKind::BrokenPipe, "the output stopped")
+        })
+    }
+}
+
+impl Write for ChunkWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.chunk.extend_from_slice(buf);
+        if self.chunk.len() >= CHUNK_LEN {
+            self.send()?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self.chunk.is_empty() {
+            true => Ok(()),
+            false => self.send(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_chunks, ChunkReader, ChunkWriter, CHUNK_LEN};
+    use std::io::{self, BufRead, Read, Write};
+    use std::sync::mpsc;
+    use std::thread;
+
+    #[test]
+    fn test_chunks() {
+        let input: Vec<u8> = (0..3 * CHUNK_LEN).map(|i| i as u8).collect();
+        let (tx, rx) = mpsc::sync_channel(2);
+        let reads = thread::spawn({
+            let input = input.clone();
+            move || read_chunks(&mut input.as_slice(), &tx)
+        });
+        let mut r = ChunkReader {
+            chunks: rx,
+            chunk: Vec::new(),
+            pos: 0,
+        };
+        let (tx, rx) = mpsc::sync_channel(2);
+        let writes = thread::spawn(move || rx.into_iter().collect::<Vec<_>>());
+        let mut w = ChunkWriter {
+            chunks: tx,
+            chunk: Vec::new(),
+        };
+        assert_eq!(r.fill_buf().unwrap().len(), CHUNK_LEN);
+        io::copy(&mut r, &mut w).unwrap();
+        w.write_all(b"end").unwrap();
+        w.flush().unwrap();
+        drop(w);
+        reads.join().unwrap();
+        let chunks: Vec<Vec<u8>> = writes.join().unwrap();
+        assert_eq!(chunks.concat(), [input.as_slice(), b"end"].concat());
+        // the end of input, and a failed read
+        assert_eq!(r.read(&mut [0; 4]).unwrap(), 0);
+        let (tx, rx) = mpsc::sync_channel(2);
+        tx.send(Err(io::ErrorKind::Other.into())).unwrap();
+        let mut r = ChunkReader {
+            chunks: rx,
+            chunk: Vec::new(),
+            pos: 0,
+        };
+        assert!(r.fill_buf().is_err());
+    }
+}
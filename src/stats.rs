@@ -0,0 +1,185 @@
+//! Counting of what the filter reads, writes, removes and replaces, e.g. for
+//! a summary at the end of a run.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{self, BufRead, Read, Write};
+use std::ops::AddAssign;
+
+/// The totals of a filter so far.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// The bytes of input read, invalid ones included.
+    pub bytes_read: u64,
+    /// The bytes of output written.
+    pub bytes_written: u64,
+    /// The chars dropped by the char filter without substitution.
+    pub chars_removed: u64,
+    /// The chars dropped by the char filter and substituted, e.g. by a
+    /// mapping.
+    pub chars_replaced: u64,
+    /// The bytes of input dropped as invalid utf-8.
+    pub invalid_bytes: u64,
+}
+
+impl AddAssign for Stats {
+    fn add_assign(&mut self, other: Self) {
+        self.bytes_read += other.bytes_read;
+        self.bytes_written += other.bytes_written;
+        self.chars_removed += other.chars_removed;
+        self.chars_replaced += other.chars_replaced;
+        self.invalid_bytes += other.invalid_bytes;
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} bytes read, {} bytes written, {} chars removed, {} chars \
+             replaced, {} invalid bytes",
+            self.bytes_read,
+            self.bytes_written,
+            self.chars_removed,
+            self.chars_replaced,
+            self.invalid_bytes
+        )
+    }
+}
+
+/// The number of times each char was dropped or replaced so far, e.g. to see
+/// what a corpus loses most, which the totals of [`Stats`] do not tell.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Histogram {
+    counts: HashMap<char, u64>,
+}
+
+impl Histogram {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count `c` once more.
+    pub fn record(&mut self, c: char) {
+        *self.counts.entry(c).or_insert(0) += 1;
+    }
+
+    /// Return the number of times `c` was counted.
+    pub fn count(&self, c: char) -> u64 {
+        self.counts.get(&c).copied().unwrap_or(0)
+    }
+
+    /// Return the `n` chars counted most with their counts, the most first,
+    /// and in codepoint order among the same count.
+    pub fn top(&self, n: usize) -> Vec<(char, u64)> {
+        let mut top: Vec<(char, u64)> =
+            self.counts.iter().map(|(&c, &count)| (c, count)).collect();
+        top.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        top.truncate(n);
+        top
+    }
+}
+
+impl AddAssign<&Histogram> for Histogram {
+    fn add_assign(&mut self, other: &Histogram) {
+        for (&c, &count) in &other.counts {
+            *self.counts.entry(c).or_insert(0) += count;
+        }
+    }
+}
+
+/// Counts the bytes consumed through it.
+pub(crate) struct CountingReader<R> {
+    pub(crate) inner: R,
+    pub(crate) count: u64,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.count += n as u64;
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for CountingReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt);
+        self.count += amt as u64;
+    }
+}
+
+/// Counts the bytes written through it.
+pub(crate) struct CountingWriter<W> {
+    pub(crate) inner: W,
+    pub(crate) count: u64,
+}
+
+impl<W: Write> Write for CountingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.count += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CountingReader, Histogram, Stats};
+    use std::io::{BufRead, Read};
+
+    #[test]
+    fn test_counting_reader() {
+        let mut r = CountingReader {
+            inner: &b"ab\ncd"[..],
+            count: 0,
+        };
+        let mut line: Vec<u8> = Vec::new();
+        r.read_until(b'\n', &mut line).unwrap();
+        assert_eq!(r.count, 3);
+        r.read_to_end(&mut line).unwrap();
+        assert_eq!(r.count, 5);
+    }
+
+    #[test]
+    fn test_stats_display() {
+        let mut stats = Stats {
+            bytes_read: 4,
+            chars_removed: 1,
+            ..Stats::default()
+        };
+        stats += Stats {
+            bytes_written: 2,
+            invalid_bytes: 1,
+            ..Stats::default()
+        };
+        assert_eq!(
+            stats.to_string(),
+            "4 bytes read, 2 bytes written, 1 chars removed, 0 chars \
+             replaced, 1 invalid bytes"
+        );
+    }
+
+    #[test]
+    fn test_histogram() {
+        let mut histogram = Histogram::new();
+        for c in "“a”\u{a0}b\u{a0}".chars() {
+            histogram.record(c);
+        }
+        let mut other = Histogram::new();
+        other.record('”');
+        histogram += &other;
+        assert_eq!(histogram.count('”'), 2);
+        assert_eq!(histogram.count('c'), 0);
+        assert_eq!(histogram.top(2), [('\u{a0}', 2), ('”', 2)]);
+        assert_eq!(histogram.top(9).len(), 5);
+    }
+}
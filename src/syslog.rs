@@ -0,0 +1,160 @@
+//! Syslog-aware filtering, where only the MSG part of RFC 3164/5424 messages
+//! is filtered, so that the priority, timestamp and the rest of the header
+//! reach syslog receivers intact.
+
+/// Filter the MSG part of one syslog `line` with `filter`, which maps raw
+/// bytes to filtered bytes. A line without syslog framing is filtered as a
+/// whole.
+pub(crate) fn filter_line<F: FnMut(&[u8]) -> Vec<u8>>(
+    line: &[u8],
+    filter: &mut F,
+) -> Vec<u8> {
+    let offset = msg_offset(line);
+    let mut out = line[..offset].to_vec();
+    out.extend(filter(&line[offset..]));
+    out
+}
+
+/// Return the offset at which the MSG part of `line` begins, or 0 if `line`
+/// is not framed.
+fn msg_offset(line: &[u8]) -> usize {
+    let Some(i) = pri_end(line) else {
+        return 0;
+    };
+    let version_end = skip_digits(line, i);
+    if version_end > i && line.get(version_end) == Some(&b' ') {
+        rfc5424_msg_offset(line, version_end + 1).unwrap_or(0)
+    } else {
+        rfc3164_msg_offset(line, i).unwrap_or(0)
+    }
+}
+
+/// Return the offset just past `<PRI>`.
+fn pri_end(line: &[u8]) -> Option<usize> {
+    if line.first() != Some(&b'<') {
+        return None;
+    }
+    let i = skip_digits(line, 1);
+    if (2..=4).contains(&i) && line.get(i) == Some(&b'>') {
+        Some(i + 1)
+    } else {
+        None
+    }
+}
+
+/// `i` is the offset just past `VERSION SP`.
+fn rfc5424_msg_offset(line: &[u8], mut i: usize) -> Option<usize> {
+    // TIMESTAMP HOSTNAME APP-NAME PROCID MSGID
+    for _ in 0..5 {
+        i = skip_field(line, i)?;
+    }
+    // STRUCTURED-DATA
+    match line.get(i) {
+        Some(b'-') => i += 1,
+        Some(b'[') => {
+            while line.get(i) == Some(&b'[') {
+                i = skip_sd_element(line, i)?;
+            }
+        }
+        _ => return None,
+    }
+    match line.get(i) {
+        Some(b' ') => Some(i + 1),
+        // no MSG
+        Some(b'\r') | Some(b'\n') | None => Some(i),
+        _ => None,
+    }
+}
+
+/// `i` is the offset just past `<PRI>`.
+fn rfc3164_msg_offset(line: &[u8], i: usize) -> Option<usize> {
+    // TIMESTAMP, as in "Oct 11 22:14:15 "
+    let ts = line.get(i..i + 16)?;
+    let is_ts = ts[..3].iter().all(u8::is_ascii_alphabetic)
+        && ts[3] == b' '
+        && (ts[4] == b' ' || ts[4].is_ascii_digit())
+        && ts[5].is_ascii_digit()
+        && ts[6] == b' '
+        && ts[9] == b':'
+        && ts[12] == b':'
+        && ts[15] == b' ';
+    if !is_ts {
+        return None;
+    }
+    // HOSTNAME
+    skip_field(line, i + 16)
+}
+
+fn skip_digits(line: &[u8], mut i: usize) -> usize {
+    while i < line.len() && line[i].is_ascii_digit() {
+        i += 1;
+    }
+    i
+}
+
+/// Skip a nonempty field of printable ASCII and the space after it.
+fn skip_field(line: &[u8], start: usize) -> Option<usize> {
+    let mut i = start;
+    while i < line.len() && line[i].is_ascii_graphic() {
+        i += 1;
+    }
+    if i > start && line.get(i) == Some(&b' ') {
+        Some(i + 1)
+    } else {
+        None
+    }
+}
+
+/// Skip an SD-ELEMENT `[id param="value" ...]` starting at `i`, where values
+/// may contain `\"`, `\\` and `\]` escapes.
+fn skip_sd_element(line: &[u8], mut i: usize) -> Option<usize> {
+    i += 1;
+    let mut quoted = false;
+    while i < line.len() {
+        match line[i] {
+            b'\\' if quoted => i += 1,
+            b'"' => quoted = !quoted,
+            b']' if !quoted => return Some(i + 1),
+            _ => (),
+        }
+        i += 1;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::filter_line;
+
+    fn drop_non_ascii(bytes: &[u8]) -> Vec<u8> {
+        bytes.iter().copied().filter(u8::is_ascii).collect()
+    }
+
+    #[test]
+    fn test_filter_line_rfc5424() {
+        let line = "<165>1 2003-10-11T22:14:15.003Z mymachine.example.com \
+                    evntslog - ID47 [exampleSDID@32473 iut=\"3\" \
+                    eventSource=\"Appé\\]\"] An application event lög\n";
+        assert_eq!(
+            filter_line(line.as_bytes(), &mut drop_non_ascii),
+            "<165>1 2003-10-11T22:14:15.003Z mymachine.example.com \
+             evntslog - ID47 [exampleSDID@32473 iut=\"3\" \
+             eventSource=\"Appé\\]\"] An application event lg\n"
+                .as_bytes()
+        );
+    }
+
+    #[test]
+    fn test_filter_line_rfc3164() {
+        let line = "<34>Oct 11 22:14:15 mymachine su: 'su root' fäiled\n";
+        assert_eq!(
+            filter_line(line.as_bytes(), &mut drop_non_ascii),
+            "<34>Oct 11 22:14:15 mymachine su: 'su root' filed\n".as_bytes()
+        );
+    }
+
+    #[test]
+    fn test_filter_line_unframed() {
+        assert_eq!(filter_line("<1>ü".as_bytes(), &mut drop_non_ascii), b"<1>");
+    }
+}
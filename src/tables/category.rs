@@ -0,0 +1,129 @@
+//! The Unicode general category.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+/// The general category of a char, as in the `gc` property of the UCD.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GeneralCategory {
+    Lu,
+    Ll,
+    Lt,
+    Lm,
+    Lo,
+    Mn,
+    Mc,
+    Me,
+    Nd,
+    Nl,
+    No,
+    Pc,
+    Pd,
+    Ps,
+    Pe,
+    Pi,
+    Pf,
+    Po,
+    Sm,
+    Sc,
+    Sk,
+    So,
+    Zs,
+    Zl,
+    Zp,
+    Cc,
+    Cf,
+    Cs,
+    Co,
+    Cn,
+}
+
+use GeneralCategory::*;
+
+impl GeneralCategory {
+    pub const ALL: [GeneralCategory; 30] = [
+        Lu, Ll, Lt, Lm, Lo, Mn, Mc, Me, Nd, Nl, No, Pc, Pd, Ps, Pe, Pi, Pf, Po,
+        Sm, Sc, Sk, So, Zs, Zl, Zp, Cc, Cf, Cs, Co, Cn,
+    ];
+
+    /// Parse a comma-separated list of categories, e.g. `Cf,Co`, where a
+    /// major class like `L` stands for all of its categories.
+    pub fn parse_list(s: &str) -> Result<Vec<GeneralCategory>, String> {
+        let mut categories: Vec<GeneralCategory> = Vec::new();
+        for name in s.split(',').map(str::trim) {
+            let matching: Vec<GeneralCategory> = match name.len() {
+                1 => GeneralCategory::ALL
+                    .into_iter()
+                    .filter(|gc| gc.to_string().starts_with(name))
+                    .collect(),
+                _ => name.parse().into_iter().collect(),
+            };
+            if matching.is_empty() {
+                return Err(format!("unknown general category {:?}", name));
+            }
+            categories.extend(matching);
+        }
+        Ok(categories)
+    }
+}
+
+impl fmt::Display for GeneralCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+impl FromStr for GeneralCategory {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        GeneralCategory::ALL
+            .into_iter()
+            .find(|gc| gc.to_string() == s)
+            .ok_or_else(|| format!("unknown general category {:?}", s))
+    }
+}
+
+include_table!("general_category.rs");
+
+/// Return the general category of `c`.
+pub fn general_category(c: char) -> GeneralCategory {
+    let c = c as u32;
+    GENERAL_CATEGORY
+        .binary_search_by(|&(first, last, _)| {
+            if last < c {
+                Ordering::Less
+            } else if first > c {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            }
+        })
+        .map_or(Cn, |i| GENERAL_CATEGORY[i].2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{general_category, GeneralCategory};
+
+    #[test]
+    fn test_general_category() {
+        assert_eq!(general_category('a'), GeneralCategory::Ll);
+        assert_eq!(general_category('\u{7}'), GeneralCategory::Cc);
+        assert_eq!(general_category('\u{a0}'), GeneralCategory::Zs);
+        assert_eq!(general_category('你'), GeneralCategory::Lo);
+        assert_eq!(general_category('\u{200b}'), GeneralCategory::Cf);
+        assert_eq!(general_category('\u{e000}'), GeneralCategory::Co);
+        assert_eq!(general_category('\u{378}'), GeneralCategory::Cn);
+    }
+
+    #[test]
+    fn test_parse_list() {
+        use GeneralCategory::*;
+        assert_eq!(GeneralCategory::parse_list("Cf,Co"), Ok(vec![Cf, Co]));
+        assert_eq!(GeneralCategory::parse_list("Z"), Ok(vec![Zs, Zl, Zp]));
+        assert!(GeneralCategory::parse_list("Xx").is_err());
+        assert!(GeneralCategory::parse_list("X").is_err());
+    }
+}
@@ -0,0 +1,30 @@
+//! The confusables of UTS #39 which look like printable ASCII, e.g. Cyrillic
+//! а like a.
+
+include_table!("confusables.rs");
+
+/// Return the printable ASCII string `c` is confusable with, if `c` is not
+/// ASCII itself, e.g. "a" for Cyrillic а and "O" for Greek Ο.
+pub fn confusable(c: char) -> Option<&'static str> {
+    let c = c as u32;
+    CONFUSABLES
+        .binary_search_by_key(&c, |&(source, _)| source)
+        .ok()
+        .map(|i| CONFUSABLES[i].1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::confusable;
+
+    #[test]
+    fn test_confusable() {
+        assert_eq!(confusable('а'), Some("a"));
+        assert_eq!(confusable('ο'), Some("o"));
+        assert_eq!(confusable('Ａ'), Some("A"));
+        // the skeleton of m is rn
+        assert_eq!(confusable('\u{217f}'), Some("m"));
+        assert_eq!(confusable('a'), None);
+        assert_eq!(confusable('你'), None);
+    }
+}
@@ -0,0 +1,38 @@
+//! Unicode data tables, each behind its own cargo feature so that downstream
+//! users can control the binary size.
+//!
+//! The tables are generated by `build.rs` from the Unicode Character Database
+//! of version [`UNICODE_VERSION`], and can be regenerated from other UCD files
+//! as documented there.
+
+/// Include the generated table `$file`, either the one regenerated into
+/// `OUT_DIR` or the checked-in one.
+macro_rules! include_table {
+    ($file:literal) => {
+        #[cfg(ascii_filter_ucd)]
+        include!(concat!(env!("OUT_DIR"), "/", $file));
+        #[cfg(not(ascii_filter_ucd))]
+        include!($file);
+    };
+}
+
+include_table!("unicode_version.rs");
+
+#[cfg(feature = "unicode-category")]
+mod category;
+#[cfg(feature = "unicode-confusables")]
+mod confusable;
+// the names are too many to check in, and come from `unicode_names2` instead
+#[cfg(feature = "unicode-names")]
+mod name;
+#[cfg(feature = "unicode-script")]
+mod script;
+
+#[cfg(feature = "unicode-category")]
+pub use category::{general_category, GeneralCategory};
+#[cfg(feature = "unicode-confusables")]
+pub use confusable::confusable;
+#[cfg(feature = "unicode-names")]
+pub use name::name;
+#[cfg(feature = "unicode-script")]
+pub use script::{script, Script};
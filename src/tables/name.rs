@@ -0,0 +1,19 @@
+//! Lookup of the names of chars, e.g. for diagnostics.
+
+/// Return the name of `c`, e.g. "NO-BREAK SPACE" for U+00A0, or `None` if it
+/// has none, e.g. a control or an unassigned codepoint.
+pub fn name(c: char) -> Option<String> {
+    unicode_names2::name(c).map(|name| name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::name;
+
+    #[test]
+    fn test_name() {
+        assert_eq!(name('\u{a0}').as_deref(), Some("NO-BREAK SPACE"));
+        assert_eq!(name('你').as_deref(), Some("CJK UNIFIED IDEOGRAPH-4F60"));
+        assert_eq!(name('\x1b'), None);
+    }
+}
@@ -0,0 +1,100 @@
+//! The Unicode script.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+/// The script of a char, as in the `sc` property of the UCD, e.g. `Latin`,
+/// or `Common` for chars used by several scripts like digits and most
+/// punctuation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Script(u8);
+
+impl Script {
+    /// The script of unassigned codepoints.
+    pub const UNKNOWN: Script = Script(0);
+
+    /// Return all the scripts, starting with [`Script::UNKNOWN`].
+    pub fn all() -> impl Iterator<Item = Script> {
+        (0..SCRIPT_NAMES.len() as u8).map(Script)
+    }
+
+    /// Return the name of the script as in the UCD, e.g. "Old_Italic".
+    pub fn name(&self) -> &'static str {
+        SCRIPT_NAMES[self.0 as usize]
+    }
+
+    /// Parse a comma-separated list of scripts, e.g. `Latin,Greek,Common`.
+    pub fn parse_list(s: &str) -> Result<Vec<Script>, String> {
+        s.split(',').map(|name| name.trim().parse()).collect()
+    }
+}
+
+impl fmt::Display for Script {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Return `name` lowercased and without the separators '_', '-' and ' ',
+/// so that e.g. "old italic" matches "Old_Italic".
+fn loose(name: &str) -> String {
+    name.chars()
+        .filter(|c| !matches!(c, '_' | '-' | ' '))
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+impl FromStr for Script {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = loose(s);
+        Script::all()
+            .find(|sc| loose(sc.name()) == name)
+            .ok_or_else(|| format!("unknown script {:?}", s))
+    }
+}
+
+include_table!("scripts.rs");
+
+/// Return the script of `c`.
+pub fn script(c: char) -> Script {
+    let c = c as u32;
+    SCRIPT
+        .binary_search_by(|&(first, last, _)| {
+            if last < c {
+                Ordering::Less
+            } else if first > c {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            }
+        })
+        .map_or(Script::UNKNOWN, |i| SCRIPT[i].2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{script, Script};
+
+    #[test]
+    fn test_script() {
+        let name = |c| script(c).name();
+        assert_eq!(name('a'), "Latin");
+        assert_eq!(name('é'), "Latin");
+        assert_eq!(name('1'), "Common");
+        assert_eq!(name('α'), "Greek");
+        assert_eq!(name('你'), "Han");
+        assert_eq!(name('\u{301}'), "Inherited");
+        assert_eq!(script('\u{378}'), Script::UNKNOWN);
+    }
+
+    #[test]
+    fn test_parse_list() {
+        let scripts = Script::parse_list("Latin, greek,old italic").unwrap();
+        let names: Vec<&str> = scripts.iter().map(Script::name).collect();
+        assert_eq!(names, ["Latin", "Greek", "Old_Italic"]);
+        assert!(Script::parse_list("Klingon").is_err());
+    }
+}
@@ -0,0 +1,1113 @@
+// Generated by build.rs from the UCD. Do not edit.
+
+#[rustfmt::skip]
+const SCRIPT_NAMES: &[&str] = &[
+    "Unknown",
+    "Adlam",
+    "Ahom",
+    "Anatolian_Hieroglyphs",
+    "Arabic",
+    "Armenian",
+    "Avestan",
+    "Balinese",
+    "Bamum",
+    "Bassa_Vah",
+    "Batak",
+    "Bengali",
+    "Bhaiksuki",
+    "Bopomofo",
+    "Brahmi",
+    "Braille",
+    "Buginese",
+    "Buhid",
+    "Canadian_Aboriginal",
+    "Carian",
+    "Caucasian_Albanian",
+    "Chakma",
+    "Cham",
+    "Cherokee",
+    "Chorasmian",
+    "Common",
+    "Coptic",
+    "Cuneiform",
+    "Cypriot",
+    "Cypro_Minoan",
+    "Cyrillic",
+    "Deseret",
+    "Devanagari",
+    "Dives_Akuru",
+    "Dogra",
+    "Duployan",
+    "Egyptian_Hieroglyphs",
+    "Elbasan",
+    "Elymaic",
+    "Ethiopic",
+    "Georgian",
+    "Glagolitic",
+    "Gothic",
+    "Grantha",
+    "Greek",
+    "Gujarati",
+    "Gunjala_Gondi",
+    "Gurmukhi",
+    "Han",
+    "Hangul",
+    "Hanifi_Rohingya",
+    "Hanunoo",
+    "Hatran",
+    "Hebrew",
+    "Hiragana",
+    "Imperial_Aramaic",
+    "Inherited",
+    "Inscriptional_Pahlavi",
+    "Inscriptional_Parthian",
+    "Javanese",
+    "Kaithi",
+    "Kannada",
+    "Katakana",
+    "Kayah_Li",
+    "Kharoshthi",
+    "Khitan_Small_Script",
+    "Khmer",
+    "Khojki",
+    "Khudawadi",
+    "Lao",
+    "Latin",
+    "Lepcha",
+    "Limbu",
+    "Linear_A",
+    "Linear_B",
+    "Lisu",
+    "Lycian",
+    "Lydian",
+    "Mahajani",
+    "Makasar",
+    "Malayalam",
+    "Mandaic",
+    "Manichaean",
+    "Marchen",
+    "Masaram_Gondi",
+    "Medefaidrin",
+    "Meetei_Mayek",
+    "Mende_Kikakui",
+    "Meroitic_Cursive",
+    "Meroitic_Hieroglyphs",
+    "Miao",
+    "Modi",
+    "Mongolian",
+    "Mro",
+    "Multani",
+    "Myanmar",
+    "Nabataean",
+    "Nandinagari",
+    "New_Tai_Lue",
+    "Newa",
+    "Nko",
+    "Nushu",
+    "Nyiakeng_Puachue_Hmong",
+    "Ogham",
+    "Ol_Chiki",
+    "Old_Hungarian",
+    "Old_Italic",
+    "Old_North_Arabian",
+    "Old_Permic",
+    "Old_Persian",
+    "Old_Sogdian",
+    "Old_South_Arabian",
+    "Old_Turkic",
+    "Old_Uyghur",
+    "Oriya",
+    "Osage",
+    "Osmanya",
+    "Pahawh_Hmong",
+    "Palmyrene",
+    "Pau_Cin_Hau",
+    "Phags_Pa",
+    "Phoenician",
+    "Psalter_Pahlavi",
+    "Rejang",
+    "Runic",
+    "Samaritan",
+    "Saurashtra",
+    "Sharada",
+    "Shavian",
+    "Siddham",
+    "SignWriting",
+    "Sinhala",
+    "Sogdian",
+    "Sora_Sompeng",
+    "Soyombo",
+    "Sundanese",
+    "Syloti_Nagri",
+    "Syriac",
+    "Tagalog",
+    "Tagbanwa",
+    "Tai_Le",
+    "Tai_Tham",
+    "Tai_Viet",
+    "Takri",
+    "Tamil",
+    "Tangsa",
+    "Tangut",
+    "Telugu",
+    "Thaana",
+    "Thai",
+    "Tibetan",
+    "Tifinagh",
+    "Tirhuta",
+    "Toto",
+    "Ugaritic",
+    "Vai",
+    "Vithkuqi",
+    "Wancho",
+    "Warang_Citi",
+    "Yezidi",
+    "Yi",
+    "Zanabazar_Square",
+];
+
+#[rustfmt::skip]
+const SCRIPT: &[(u32, u32, Script)] = &[
+    (0x0, 0x40, Script(25)),
+    (0x41, 0x5a, Script(70)),
+    (0x5b, 0x60, Script(25)),
+    (0x61, 0x7a, Script(70)),
+    (0x7b, 0xa9, Script(25)),
+    (0xaa, 0xaa, Script(70)),
+    (0xab, 0xb9, Script(25)),
+    (0xba, 0xba, Script(70)),
+    (0xbb, 0xbf, Script(25)),
+    (0xc0, 0xd6, Script(70)),
+    (0xd7, 0xd7, Script(25)),
+    (0xd8, 0xf6, Script(70)),
+    (0xf7, 0xf7, Script(25)),
+    (0xf8, 0x2b8, Script(70)),
+    (0x2b9, 0x2df, Script(25)),
+    (0x2e0, 0x2e4, Script(70)),
+    (0x2e5, 0x2e9, Script(25)),
+    (0x2ea, 0x2eb, Script(13)),
+    (0x2ec, 0x2ff, Script(25)),
+    (0x300, 0x36f, Script(56)),
+    (0x370, 0x373, Script(44)),
+    (0x374, 0x374, Script(25)),
+    (0x375, 0x377, Script(44)),
+    (0x37a, 0x37d, Script(44)),
+    (0x37e, 0x37e, Script(25)),
+    (0x37f, 0x37f, Script(44)),
+    (0x384, 0x384, Script(44)),
+    (0x385, 0x385, Script(25)),
+    (0x386, 0x386, Script(44)),
+    (0x387, 0x387, Script(25)),
+    (0x388, 0x38a, Script(44)),
+    (0x38c, 0x38c, Script(44)),
+    (0x38e, 0x3a1, Script(44)),
+    (0x3a3, 0x3e1, Script(44)),
+    (0x3e2, 0x3ef, Script(26)),
+    (0x3f0, 0x3ff, Script(44)),
+    (0x400, 0x484, Script(30)),
+    (0x485, 0x486, Script(56)),
+    (0x487, 0x52f, Script(30)),
+    (0x531, 0x556, Script(5)),
+    (0x559, 0x58a, Script(5)),
+    (0x58d, 0x58f, Script(5)),
+    (0x591, 0x5c7, Script(53)),
+    (0x5d0, 0x5ea, Script(53)),
+    (0x5ef, 0x5f4, Script(53)),
+    (0x600, 0x604, Script(4)),
+    (0x605, 0x605, Script(25)),
+    (0x606, 0x60b, Script(4)),
+    (0x60c, 0x60c, Script(25)),
+    (0x60d, 0x61a, Script(4)),
+    (0x61b, 0x61b, Script(25)),
+    (0x61c, 0x61e, Script(4)),
+    (0x61f, 0x61f, Script(25)),
+    (0x620, 0x63f, Script(4)),
+    (0x640, 0x640, Script(25)),
+    (0x641, 0x64a, Script(4)),
+    (0x64b, 0x655, Script(56)),
+    (0x656, 0x66f, Script(4)),
+    (0x670, 0x670, Script(56)),
+    (0x671, 0x6dc, Script(4)),
+    (0x6dd, 0x6dd, Script(25)),
+    (0x6de, 0x6ff, Script(4)),
+    (0x700, 0x70d, Script(137)),
+    (0x70f, 0x74a, Script(137)),
+    (0x74d, 0x74f, Script(137)),
+    (0x750, 0x77f, Script(4)),
+    (0x780, 0x7b1, Script(148)),
+    (0x7c0, 0x7fa, Script(100)),
+    (0x7fd, 0x7ff, Script(100)),
+    (0x800, 0x82d, Script(125)),
+    (0x830, 0x83e, Script(125)),
+    (0x840, 0x85b, Script(81)),
+    (0x85e, 0x85e, Script(81)),
+    (0x860, 0x86a, Script(137)),
+    (0x870, 0x88e, Script(4)),
+    (0x890, 0x891, Script(4)),
+    (0x898, 0x8e1, Script(4)),
+    (0x8e2, 0x8e2, Script(25)),
+    (0x8e3, 0x8ff, Script(4)),
+    (0x900, 0x950, Script(32)),
+    (0x951, 0x954, Script(56)),
+    (0x955, 0x963, Script(32)),
+    (0x964, 0x965, Script(25)),
+    (0x966, 0x97f, Script(32)),
+    (0x980, 0x983, Script(11)),
+    (0x985, 0x98c, Script(11)),
+    (0x98f, 0x990, Script(11)),
+    (0x993, 0x9a8, Script(11)),
+    (0x9aa, 0x9b0, Script(11)),
+    (0x9b2, 0x9b2, Script(11)),
+    (0x9b6, 0x9b9, Script(11)),
+    (0x9bc, 0x9c4, Script(11)),
+    (0x9c7, 0x9c8, Script(11)),
+    (0x9cb, 0x9ce, Script(11)),
+    (0x9d7, 0x9d7, Script(11)),
+    (0x9dc, 0x9dd, Script(11)),
+    (0x9df, 0x9e3, Script(11)),
+    (0x9e6, 0x9fe, Script(11)),
+    (0xa01, 0xa03, Script(47)),
+    (0xa05, 0xa0a, Script(47)),
+    (0xa0f, 0xa10, Script(47)),
+    (0xa13, 0xa28, Script(47)),
+    (0xa2a, 0xa30, Script(47)),
+    (0xa32, 0xa33, Script(47)),
+    (0xa35, 0xa36, Script(47)),
+    (0xa38, 0xa39, Script(47)),
+    (0xa3c, 0xa3c, Script(47)),
+    (0xa3e, 0xa42, Script(47)),
+    (0xa47, 0xa48, Script(47)),
+    (0xa4b, 0xa4d, Script(47)),
+    (0xa51, 0xa51, Script(47)),
+    (0xa59, 0xa5c, Script(47)),
+    (0xa5e, 0xa5e, Script(47)),
+    (0xa66, 0xa76, Script(47)),
+    (0xa81, 0xa83, Script(45)),
+    (0xa85, 0xa8d, Script(45)),
+    (0xa8f, 0xa91, Script(45)),
+    (0xa93, 0xaa8, Script(45)),
+    (0xaaa, 0xab0, Script(45)),
+    (0xab2, 0xab3, Script(45)),
+    (0xab5, 0xab9, Script(45)),
+    (0xabc, 0xac5, Script(45)),
+    (0xac7, 0xac9, Script(45)),
+    (0xacb, 0xacd, Script(45)),
+    (0xad0, 0xad0, Script(45)),
+    (0xae0, 0xae3, Script(45)),
+    (0xae6, 0xaf1, Script(45)),
+    (0xaf9, 0xaff, Script(45)),
+    (0xb01, 0xb03, Script(114)),
+    (0xb05, 0xb0c, Script(114)),
+    (0xb0f, 0xb10, Script(114)),
+    (0xb13, 0xb28, Script(114)),
+    (0xb2a, 0xb30, Script(114)),
+    (0xb32, 0xb33, Script(114)),
+    (0xb35, 0xb39, Script(114)),
+    (0xb3c, 0xb44, Script(114)),
+    (0xb47, 0xb48, Script(114)),
+    (0xb4b, 0xb4d, Script(114)),
+    (0xb55, 0xb57, Script(114)),
+    (0xb5c, 0xb5d, Script(114)),
+    (0xb5f, 0xb63, Script(114)),
+    (0xb66, 0xb77, Script(114)),
+    (0xb82, 0xb83, Script(144)),
+    (0xb85, 0xb8a, Script(144)),
+    (0xb8e, 0xb90, Script(144)),
+    (0xb92, 0xb95, Script(144)),
+    (0xb99, 0xb9a, Script(144)),
+    (0xb9c, 0xb9c, Script(144)),
+    (0xb9e, 0xb9f, Script(144)),
+    (0xba3, 0xba4, Script(144)),
+    (0xba8, 0xbaa, Script(144)),
+    (0xbae, 0xbb9, Script(144)),
+    (0xbbe, 0xbc2, Script(144)),
+    (0xbc6, 0xbc8, Script(144)),
+    (0xbca, 0xbcd, Script(144)),
+    (0xbd0, 0xbd0, Script(144)),
+    (0xbd7, 0xbd7, Script(144)),
+    (0xbe6, 0xbfa, Script(144)),
+    (0xc00, 0xc0c, Script(147)),
+    (0xc0e, 0xc10, Script(147)),
+    (0xc12, 0xc28, Script(147)),
+    (0xc2a, 0xc39, Script(147)),
+    (0xc3c, 0xc44, Script(147)),
+    (0xc46, 0xc48, Script(147)),
+    (0xc4a, 0xc4d, Script(147)),
+    (0xc55, 0xc56, Script(147)),
+    (0xc58, 0xc5a, Script(147)),
+    (0xc5d, 0xc5d, Script(147)),
+    (0xc60, 0xc63, Script(147)),
+    (0xc66, 0xc6f, Script(147)),
+    (0xc77, 0xc7f, Script(147)),
+    (0xc80, 0xc8c, Script(61)),
+    (0xc8e, 0xc90, Script(61)),
+    (0xc92, 0xca8, Script(61)),
+    (0xcaa, 0xcb3, Script(61)),
+    (0xcb5, 0xcb9, Script(61)),
+    (0xcbc, 0xcc4, Script(61)),
+    (0xcc6, 0xcc8, Script(61)),
+    (0xcca, 0xccd, Script(61)),
+    (0xcd5, 0xcd6, Script(61)),
+    (0xcdd, 0xcde, Script(61)),
+    (0xce0, 0xce3, Script(61)),
+    (0xce6, 0xcef, Script(61)),
+    (0xcf1, 0xcf2, Script(61)),
+    (0xd00, 0xd0c, Script(80)),
+    (0xd0e, 0xd10, Script(80)),
+    (0xd12, 0xd44, Script(80)),
+    (0xd46, 0xd48, Script(80)),
+    (0xd4a, 0xd4f, Script(80)),
+    (0xd54, 0xd63, Script(80)),
+    (0xd66, 0xd7f, Script(80)),
+    (0xd81, 0xd83, Script(131)),
+    (0xd85, 0xd96, Script(131)),
+    (0xd9a, 0xdb1, Script(131)),
+    (0xdb3, 0xdbb, Script(131)),
+    (0xdbd, 0xdbd, Script(131)),
+    (0xdc0, 0xdc6, Script(131)),
+    (0xdca, 0xdca, Script(131)),
+    (0xdcf, 0xdd4, Script(131)),
+    (0xdd6, 0xdd6, Script(131)),
+    (0xdd8, 0xddf, Script(131)),
+    (0xde6, 0xdef, Script(131)),
+    (0xdf2, 0xdf4, Script(131)),
+    (0xe01, 0xe3a, Script(149)),
+    (0xe3f, 0xe3f, Script(25)),
+    (0xe40, 0xe5b, Script(149)),
+    (0xe81, 0xe82, Script(69)),
+    (0xe84, 0xe84, Script(69)),
+    (0xe86, 0xe8a, Script(69)),
+    (0xe8c, 0xea3, Script(69)),
+    (0xea5, 0xea5, Script(69)),
+    (0xea7, 0xebd, Script(69)),
+    (0xec0, 0xec4, Script(69)),
+    (0xec6, 0xec6, Script(69)),
+    (0xec8, 0xecd, Script(69)),
+    (0xed0, 0xed9, Script(69)),
+    (0xedc, 0xedf, Script(69)),
+    (0xf00, 0xf47, Script(150)),
+    (0xf49, 0xf6c, Script(150)),
+    (0xf71, 0xf97, Script(150)),
+    (0xf99, 0xfbc, Script(150)),
+    (0xfbe, 0xfcc, Script(150)),
+    (0xfce, 0xfd4, Script(150)),
+    (0xfd5, 0xfd8, Script(25)),
+    (0xfd9, 0xfda, Script(150)),
+    (0x1000, 0x109f, Script(95)),
+    (0x10a0, 0x10c5, Script(40)),
+    (0x10c7, 0x10c7, Script(40)),
+    (0x10cd, 0x10cd, Script(40)),
+    (0x10d0, 0x10fa, Script(40)),
+    (0x10fb, 0x10fb, Script(25)),
+    (0x10fc, 0x10ff, Script(40)),
+    (0x1100, 0x11ff, Script(49)),
+    (0x1200, 0x1248, Script(39)),
+    (0x124a, 0x124d, Script(39)),
+    (0x1250, 0x1256, Script(39)),
+    (0x1258, 0x1258, Script(39)),
+    (0x125a, 0x125d, Script(39)),
+    (0x1260, 0x1288, Script(39)),
+    (0x128a, 0x128d, Script(39)),
+    (0x1290, 0x12b0, Script(39)),
+    (0x12b2, 0x12b5, Script(39)),
+    (0x12b8, 0x12be, Script(39)),
+    (0x12c0, 0x12c0, Script(39)),
+    (0x12c2, 0x12c5, Script(39)),
+    (0x12c8, 0x12d6, Script(39)),
+    (0x12d8, 0x1310, Script(39)),
+    (0x1312, 0x1315, Script(39)),
+    (0x1318, 0x135a, Script(39)),
+    (0x135d, 0x137c, Script(39)),
+    (0x1380, 0x1399, Script(39)),
+    (0x13a0, 0x13f5, Script(23)),
+    (0x13f8, 0x13fd, Script(23)),
+    (0x1400, 0x167f, Script(18)),
+    (0x1680, 0x169c, Script(103)),
+    (0x16a0, 0x16ea, Script(124)),
+    (0x16eb, 0x16ed, Script(25)),
+    (0x16ee, 0x16f8, Script(124)),
+    (0x1700, 0x1715, Script(138)),
+    (0x171f, 0x171f, Script(138)),
+    (0x1720, 0x1734, Script(51)),
+    (0x1735, 0x1736, Script(25)),
+    (0x1740, 0x1753, Script(17)),
+    (0x1760, 0x176c, Script(139)),
+    (0x176e, 0x1770, Script(139)),
+    (0x1772, 0x1773, Script(139)),
+    (0x1780, 0x17dd, Script(66)),
+    (0x17e0, 0x17e9, Script(66)),
+    (0x17f0, 0x17f9, Script(66)),
+    (0x1800, 0x1801, Script(92)),
+    (0x1802, 0x1803, Script(25)),
+    (0x1804, 0x1804, Script(92)),
+    (0x1805, 0x1805, Script(25)),
+    (0x1806, 0x1819, Script(92)),
+    (0x1820, 0x1878, Script(92)),
+    (0x1880, 0x18aa, Script(92)),
+    (0x18b0, 0x18f5, Script(18)),
+    (0x1900, 0x191e, Script(72)),
+    (0x1920, 0x192b, Script(72)),
+    (0x1930, 0x193b, Script(72)),
+    (0x1940, 0x1940, Script(72)),
+    (0x1944, 0x194f, Script(72)),
+    (0x1950, 0x196d, Script(140)),
+    (0x1970, 0x1974, Script(140)),
+    (0x1980, 0x19ab, Script(98)),
+    (0x19b0, 0x19c9, Script(98)),
+    (0x19d0, 0x19da, Script(98)),
+    (0x19de, 0x19df, Script(98)),
+    (0x19e0, 0x19ff, Script(66)),
+    (0x1a00, 0x1a1b, Script(16)),
+    (0x1a1e, 0x1a1f, Script(16)),
+    (0x1a20, 0x1a5e, Script(141)),
+    (0x1a60, 0x1a7c, Script(141)),
+    (0x1a7f, 0x1a89, Script(141)),
+    (0x1a90, 0x1a99, Script(141)),
+    (0x1aa0, 0x1aad, Script(141)),
+    (0x1ab0, 0x1ace, Script(56)),
+    (0x1b00, 0x1b4c, Script(7)),
+    (0x1b50, 0x1b7e, Script(7)),
+    (0x1b80, 0x1bbf, Script(135)),
+    (0x1bc0, 0x1bf3, Script(10)),
+    (0x1bfc, 0x1bff, Script(10)),
+    (0x1c00, 0x1c37, Script(71)),
+    (0x1c3b, 0x1c49, Script(71)),
+    (0x1c4d, 0x1c4f, Script(71)),
+    (0x1c50, 0x1c7f, Script(104)),
+    (0x1c80, 0x1c88, Script(30)),
+    (0x1c90, 0x1cba, Script(40)),
+    (0x1cbd, 0x1cbf, Script(40)),
+    (0x1cc0, 0x1cc7, Script(135)),
+    (0x1cd0, 0x1cd2, Script(56)),
+    (0x1cd3, 0x1cd3, Script(25)),
+    (0x1cd4, 0x1ce0, Script(56)),
+    (0x1ce1, 0x1ce1, Script(25)),
+    (0x1ce2, 0x1ce8, Script(56)),
+    (0x1ce9, 0x1cec, Script(25)),
+    (0x1ced, 0x1ced, Script(56)),
+    (0x1cee, 0x1cf3, Script(25)),
+    (0x1cf4, 0x1cf4, Script(56)),
+    (0x1cf5, 0x1cf7, Script(25)),
+    (0x1cf8, 0x1cf9, Script(56)),
+    (0x1cfa, 0x1cfa, Script(25)),
+    (0x1d00, 0x1d25, Script(70)),
+    (0x1d26, 0x1d2a, Script(44)),
+    (0x1d2b, 0x1d2b, Script(30)),
+    (0x1d2c, 0x1d5c, Script(70)),
+    (0x1d5d, 0x1d61, Script(44)),
+    (0x1d62, 0x1d65, Script(70)),
+    (0x1d66, 0x1d6a, Script(44)),
+    (0x1d6b, 0x1d77, Script(70)),
+    (0x1d78, 0x1d78, Script(30)),
+    (0x1d79, 0x1dbe, Script(70)),
+    (0x1dbf, 0x1dbf, Script(44)),
+    (0x1dc0, 0x1dff, Script(56)),
+    (0x1e00, 0x1eff, Script(70)),
+    (0x1f00, 0x1f15, Script(44)),
+    (0x1f18, 0x1f1d, Script(44)),
+    (0x1f20, 0x1f45, Script(44)),
+    (0x1f48, 0x1f4d, Script(44)),
+    (0x1f50, 0x1f57, Script(44)),
+    (0x1f59, 0x1f59, Script(44)),
+    (0x1f5b, 0x1f5b, Script(44)),
+    (0x1f5d, 0x1f5d, Script(44)),
+    (0x1f5f, 0x1f7d, Script(44)),
+    (0x1f80, 0x1fb4, Script(44)),
+    (0x1fb6, 0x1fc4, Script(44)),
+    (0x1fc6, 0x1fd3, Script(44)),
+    (0x1fd6, 0x1fdb, Script(44)),
+    (0x1fdd, 0x1fef, Script(44)),
+    (0x1ff2, 0x1ff4, Script(44)),
+    (0x1ff6, 0x1ffe, Script(44)),
+    (0x2000, 0x200b, Script(25)),
+    (0x200c, 0x200d, Script(56)),
+    (0x200e, 0x2064, Script(25)),
+    (0x2066, 0x2070, Script(25)),
+    (0x2071, 0x2071, Script(70)),
+    (0x2074, 0x207e, Script(25)),
+    (0x207f, 0x207f, Script(70)),
+    (0x2080, 0x208e, Script(25)),
+    (0x2090, 0x209c, Script(70)),
+    (0x20a0, 0x20c0, Script(25)),
+    (0x20d0, 0x20f0, Script(56)),
+    (0x2100, 0x2125, Script(25)),
+    (0x2126, 0x2126, Script(44)),
+    (0x2127, 0x2129, Script(25)),
+    (0x212a, 0x212b, Script(70)),
+    (0x212c, 0x2131, Script(25)),
+    (0x2132, 0x2132, Script(70)),
+    (0x2133, 0x214d, Script(25)),
+    (0x214e, 0x214e, Script(70)),
+    (0x214f, 0x215f, Script(25)),
+    (0x2160, 0x2188, Script(70)),
+    (0x2189, 0x218b, Script(25)),
+    (0x2190, 0x2426, Script(25)),
+    (0x2440, 0x244a, Script(25)),
+    (0x2460, 0x27ff, Script(25)),
+    (0x2800, 0x28ff, Script(15)),
+    (0x2900, 0x2b73, Script(25)),
+    (0x2b76, 0x2b95, Script(25)),
+    (0x2b97, 0x2bff, Script(25)),
+    (0x2c00, 0x2c5f, Script(41)),
+    (0x2c60, 0x2c7f, Script(70)),
+    (0x2c80, 0x2cf3, Script(26)),
+    (0x2cf9, 0x2cff, Script(26)),
+    (0x2d00, 0x2d25, Script(40)),
+    (0x2d27, 0x2d27, Script(40)),
+    (0x2d2d, 0x2d2d, Script(40)),
+    (0x2d30, 0x2d67, Script(151)),
+    (0x2d6f, 0x2d70, Script(151)),
+    (0x2d7f, 0x2d7f, Script(151)),
+    (0x2d80, 0x2d96, Script(39)),
+    (0x2da0, 0x2da6, Script(39)),
+    (0x2da8, 0x2dae, Script(39)),
+    (0x2db0, 0x2db6, Script(39)),
+    (0x2db8, 0x2dbe, Script(39)),
+    (0x2dc0, 0x2dc6, Script(39)),
+    (0x2dc8, 0x2dce, Script(39)),
+    (0x2dd0, 0x2dd6, Script(39)),
+    (0x2dd8, 0x2dde, Script(39)),
+    (0x2de0, 0x2dff, Script(30)),
+    (0x2e00, 0x2e5d, Script(25)),
+    (0x2e80, 0x2e99, Script(48)),
+    (0x2e9b, 0x2ef3, Script(48)),
+    (0x2f00, 0x2fd5, Script(48)),
+    (0x2ff0, 0x2ffb, Script(25)),
+    (0x3000, 0x3004, Script(25)),
+    (0x3005, 0x3005, Script(48)),
+    (0x3006, 0x3006, Script(25)),
+    (0x3007, 0x3007, Script(48)),
+    (0x3008, 0x3020, Script(25)),
+    (0x3021, 0x3029, Script(48)),
+    (0x302a, 0x302d, Script(56)),
+    (0x302e, 0x302f, Script(49)),
+    (0x3030, 0x3037, Script(25)),
+    (0x3038, 0x303b, Script(48)),
+    (0x303c, 0x303f, Script(25)),
+    (0x3041, 0x3096, Script(54)),
+    (0x3099, 0x309a, Script(56)),
+    (0x309b, 0x309c, Script(25)),
+    (0x309d, 0x309f, Script(54)),
+    (0x30a0, 0x30a0, Script(25)),
+    (0x30a1, 0x30fa, Script(62)),
+    (0x30fb, 0x30fc, Script(25)),
+    (0x30fd, 0x30ff, Script(62)),
+    (0x3105, 0x312f, Script(13)),
+    (0x3131, 0x318e, Script(49)),
+    (0x3190, 0x319f, Script(25)),
+    (0x31a0, 0x31bf, Script(13)),
+    (0x31c0, 0x31e3, Script(25)),
+    (0x31f0, 0x31ff, Script(62)),
+    (0x3200, 0x321e, Script(49)),
+    (0x3220, 0x325f, Script(25)),
+    (0x3260, 0x327e, Script(49)),
+    (0x327f, 0x32cf, Script(25)),
+    (0x32d0, 0x32fe, Script(62)),
+    (0x32ff, 0x32ff, Script(25)),
+    (0x3300, 0x3357, Script(62)),
+    (0x3358, 0x33ff, Script(25)),
+    (0x3400, 0x4dbf, Script(48)),
+    (0x4dc0, 0x4dff, Script(25)),
+    (0x4e00, 0x9fff, Script(48)),
+    (0xa000, 0xa48c, Script(160)),
+    (0xa490, 0xa4c6, Script(160)),
+    (0xa4d0, 0xa4ff, Script(75)),
+    (0xa500, 0xa62b, Script(155)),
+    (0xa640, 0xa69f, Script(30)),
+    (0xa6a0, 0xa6f7, Script(8)),
+    (0xa700, 0xa721, Script(25)),
+    (0xa722, 0xa787, Script(70)),
+    (0xa788, 0xa78a, Script(25)),
+    (0xa78b, 0xa7ca, Script(70)),
+    (0xa7d0, 0xa7d1, Script(70)),
+    (0xa7d3, 0xa7d3, Script(70)),
+    (0xa7d5, 0xa7d9, Script(70)),
+    (0xa7f2, 0xa7ff, Script(70)),
+    (0xa800, 0xa82c, Script(136)),
+    (0xa830, 0xa839, Script(25)),
+    (0xa840, 0xa877, Script(120)),
+    (0xa880, 0xa8c5, Script(126)),
+    (0xa8ce, 0xa8d9, Script(126)),
+    (0xa8e0, 0xa8ff, Script(32)),
+    (0xa900, 0xa92d, Script(63)),
+    (0xa92e, 0xa92e, Script(25)),
+    (0xa92f, 0xa92f, Script(63)),
+    (0xa930, 0xa953, Script(123)),
+    (0xa95f, 0xa95f, Script(123)),
+    (0xa960, 0xa97c, Script(49)),
+    (0xa980, 0xa9cd, Script(59)),
+    (0xa9cf, 0xa9cf, Script(25)),
+    (0xa9d0, 0xa9d9, Script(59)),
+    (0xa9de, 0xa9df, Script(59)),
+    (0xa9e0, 0xa9fe, Script(95)),
+    (0xaa00, 0xaa36, Script(22)),
+    (0xaa40, 0xaa4d, Script(22)),
+    (0xaa50, 0xaa59, Script(22)),
+    (0xaa5c, 0xaa5f, Script(22)),
+    (0xaa60, 0xaa7f, Script(95)),
+    (0xaa80, 0xaac2, Script(142)),
+    (0xaadb, 0xaadf, Script(142)),
+    (0xaae0, 0xaaf6, Script(86)),
+    (0xab01, 0xab06, Script(39)),
+    (0xab09, 0xab0e, Script(39)),
+    (0xab11, 0xab16, Script(39)),
+    (0xab20, 0xab26, Script(39)),
+    (0xab28, 0xab2e, Script(39)),
+    (0xab30, 0xab5a, Script(70)),
+    (0xab5b, 0xab5b, Script(25)),
+    (0xab5c, 0xab64, Script(70)),
+    (0xab65, 0xab65, Script(44)),
+    (0xab66, 0xab69, Script(70)),
+    (0xab6a, 0xab6b, Script(25)),
+    (0xab70, 0xabbf, Script(23)),
+    (0xabc0, 0xabed, Script(86)),
+    (0xabf0, 0xabf9, Script(86)),
+    (0xac00, 0xd7a3, Script(49)),
+    (0xd7b0, 0xd7c6, Script(49)),
+    (0xd7cb, 0xd7fb, Script(49)),
+    (0xf900, 0xfa6d, Script(48)),
+    (0xfa70, 0xfad9, Script(48)),
+    (0xfb00, 0xfb06, Script(70)),
+    (0xfb13, 0xfb17, Script(5)),
+    (0xfb1d, 0xfb36, Script(53)),
+    (0xfb38, 0xfb3c, Script(53)),
+    (0xfb3e, 0xfb3e, Script(53)),
+    (0xfb40, 0xfb41, Script(53)),
+    (0xfb43, 0xfb44, Script(53)),
+    (0xfb46, 0xfb4f, Script(53)),
+    (0xfb50, 0xfbc2, Script(4)),
+    (0xfbd3, 0xfd3d, Script(4)),
+    (0xfd3e, 0xfd3f, Script(25)),
+    (0xfd40, 0xfd8f, Script(4)),
+    (0xfd92, 0xfdc7, Script(4)),
+    (0xfdcf, 0xfdcf, Script(4)),
+    (0xfdf0, 0xfdff, Script(4)),
+    (0xfe00, 0xfe0f, Script(56)),
+    (0xfe10, 0xfe19, Script(25)),
+    (0xfe20, 0xfe2d, Script(56)),
+    (0xfe2e, 0xfe2f, Script(30)),
+    (0xfe30, 0xfe52, Script(25)),
+    (0xfe54, 0xfe66, Script(25)),
+    (0xfe68, 0xfe6b, Script(25)),
+    (0xfe70, 0xfe74, Script(4)),
+    (0xfe76, 0xfefc, Script(4)),
+    (0xfeff, 0xfeff, Script(25)),
+    (0xff01, 0xff20, Script(25)),
+    (0xff21, 0xff3a, Script(70)),
+    (0xff3b, 0xff40, Script(25)),
+    (0xff41, 0xff5a, Script(70)),
+    (0xff5b, 0xff65, Script(25)),
+    (0xff66, 0xff6f, Script(62)),
+    (0xff70, 0xff70, Script(25)),
+    (0xff71, 0xff9d, Script(62)),
+    (0xff9e, 0xff9f, Script(25)),
+    (0xffa0, 0xffbe, Script(49)),
+    (0xffc2, 0xffc7, Script(49)),
+    (0xffca, 0xffcf, Script(49)),
+    (0xffd2, 0xffd7, Script(49)),
+    (0xffda, 0xffdc, Script(49)),
+    (0xffe0, 0xffe6, Script(25)),
+    (0xffe8, 0xffee, Script(25)),
+    (0xfff9, 0xfffd, Script(25)),
+    (0x10000, 0x1000b, Script(74)),
+    (0x1000d, 0x10026, Script(74)),
+    (0x10028, 0x1003a, Script(74)),
+    (0x1003c, 0x1003d, Script(74)),
+    (0x1003f, 0x1004d, Script(74)),
+    (0x10050, 0x1005d, Script(74)),
+    (0x10080, 0x100fa, Script(74)),
+    (0x10100, 0x10102, Script(25)),
+    (0x10107, 0x10133, Script(25)),
+    (0x10137, 0x1013f, Script(25)),
+    (0x10140, 0x1018e, Script(44)),
+    (0x10190, 0x1019c, Script(25)),
+    (0x101a0, 0x101a0, Script(44)),
+    (0x101d0, 0x101fc, Script(25)),
+    (0x101fd, 0x101fd, Script(56)),
+    (0x10280, 0x1029c, Script(76)),
+    (0x102a0, 0x102d0, Script(19)),
+    (0x102e0, 0x102e0, Script(56)),
+    (0x102e1, 0x102fb, Script(25)),
+    (0x10300, 0x10323, Script(106)),
+    (0x1032d, 0x1032f, Script(106)),
+    (0x10330, 0x1034a, Script(42)),
+    (0x10350, 0x1037a, Script(108)),
+    (0x10380, 0x1039d, Script(154)),
+    (0x1039f, 0x1039f, Script(154)),
+    (0x103a0, 0x103c3, Script(109)),
+    (0x103c8, 0x103d5, Script(109)),
+    (0x10400, 0x1044f, Script(31)),
+    (0x10450, 0x1047f, Script(128)),
+    (0x10480, 0x1049d, Script(116)),
+    (0x104a0, 0x104a9, Script(116)),
+    (0x104b0, 0x104d3, Script(115)),
+    (0x104d8, 0x104fb, Script(115)),
+    (0x10500, 0x10527, Script(37)),
+    (0x10530, 0x10563, Script(20)),
+    (0x1056f, 0x1056f, Script(20)),
+    (0x10570, 0x1057a, Script(156)),
+    (0x1057c, 0x1058a, Script(156)),
+    (0x1058c, 0x10592, Script(156)),
+    (0x10594, 0x10595, Script(156)),
+    (0x10597, 0x105a1, Script(156)),
+    (0x105a3, 0x105b1, Script(156)),
+    (0x105b3, 0x105b9, Script(156)),
+    (0x105bb, 0x105bc, Script(156)),
+    (0x10600, 0x10736, Script(73)),
+    (0x10740, 0x10755, Script(73)),
+    (0x10760, 0x10767, Script(73)),
+    (0x10780, 0x10785, Script(70)),
+    (0x10787, 0x107b0, Script(70)),
+    (0x107b2, 0x107ba, Script(70)),
+    (0x10800, 0x10805, Script(28)),
+    (0x10808, 0x10808, Script(28)),
+    (0x1080a, 0x10835, Script(28)),
+    (0x10837, 0x10838, Script(28)),
+    (0x1083c, 0x1083c, Script(28)),
+    (0x1083f, 0x1083f, Script(28)),
+    (0x10840, 0x10855, Script(55)),
+    (0x10857, 0x1085f, Script(55)),
+    (0x10860, 0x1087f, Script(118)),
+    (0x10880, 0x1089e, Script(96)),
+    (0x108a7, 0x108af, Script(96)),
+    (0x108e0, 0x108f2, Script(52)),
+    (0x108f4, 0x108f5, Script(52)),
+    (0x108fb, 0x108ff, Script(52)),
+    (0x10900, 0x1091b, Script(121)),
+    (0x1091f, 0x1091f, Script(121)),
+    (0x10920, 0x10939, Script(77)),
+    (0x1093f, 0x1093f, Script(77)),
+    (0x10980, 0x1099f, Script(89)),
+    (0x109a0, 0x109b7, Script(88)),
+    (0x109bc, 0x109cf, Script(88)),
+    (0x109d2, 0x109ff, Script(88)),
+    (0x10a00, 0x10a03, Script(64)),
+    (0x10a05, 0x10a06, Script(64)),
+    (0x10a0c, 0x10a13, Script(64)),
+    (0x10a15, 0x10a17, Script(64)),
+    (0x10a19, 0x10a35, Script(64)),
+    (0x10a38, 0x10a3a, Script(64)),
+    (0x10a3f, 0x10a48, Script(64)),
+    (0x10a50, 0x10a58, Script(64)),
+    (0x10a60, 0x10a7f, Script(111)),
+    (0x10a80, 0x10a9f, Script(107)),
+    (0x10ac0, 0x10ae6, Script(82)),
+    (0x10aeb, 0x10af6, Script(82)),
+    (0x10b00, 0x10b35, Script(6)),
+    (0x10b39, 0x10b3f, Script(6)),
+    (0x10b40, 0x10b55, Script(58)),
+    (0x10b58, 0x10b5f, Script(58)),
+    (0x10b60, 0x10b72, Script(57)),
+    (0x10b78, 0x10b7f, Script(57)),
+    (0x10b80, 0x10b91, Script(122)),
+    (0x10b99, 0x10b9c, Script(122)),
+    (0x10ba9, 0x10baf, Script(122)),
+    (0x10c00, 0x10c48, Script(112)),
+    (0x10c80, 0x10cb2, Script(105)),
+    (0x10cc0, 0x10cf2, Script(105)),
+    (0x10cfa, 0x10cff, Script(105)),
+    (0x10d00, 0x10d27, Script(50)),
+    (0x10d30, 0x10d39, Script(50)),
+    (0x10e60, 0x10e7e, Script(4)),
+    (0x10e80, 0x10ea9, Script(159)),
+    (0x10eab, 0x10ead, Script(159)),
+    (0x10eb0, 0x10eb1, Script(159)),
+    (0x10f00, 0x10f27, Script(110)),
+    (0x10f30, 0x10f59, Script(132)),
+    (0x10f70, 0x10f89, Script(113)),
+    (0x10fb0, 0x10fcb, Script(24)),
+    (0x10fe0, 0x10ff6, Script(38)),
+    (0x11000, 0x1104d, Script(14)),
+    (0x11052, 0x11075, Script(14)),
+    (0x1107f, 0x1107f, Script(14)),
+    (0x11080, 0x110c2, Script(60)),
+    (0x110cd, 0x110cd, Script(60)),
+    (0x110d0, 0x110e8, Script(133)),
+    (0x110f0, 0x110f9, Script(133)),
+    (0x11100, 0x11134, Script(21)),
+    (0x11136, 0x11147, Script(21)),
+    (0x11150, 0x11176, Script(78)),
+    (0x11180, 0x111df, Script(127)),
+    (0x111e1, 0x111f4, Script(131)),
+    (0x11200, 0x11211, Script(67)),
+    (0x11213, 0x1123e, Script(67)),
+    (0x11280, 0x11286, Script(94)),
+    (0x11288, 0x11288, Script(94)),
+    (0x1128a, 0x1128d, Script(94)),
+    (0x1128f, 0x1129d, Script(94)),
+    (0x1129f, 0x112a9, Script(94)),
+    (0x112b0, 0x112ea, Script(68)),
+    (0x112f0, 0x112f9, Script(68)),
+    (0x11300, 0x11303, Script(43)),
+    (0x11305, 0x1130c, Script(43)),
+    (0x1130f, 0x11310, Script(43)),
+    (0x11313, 0x11328, Script(43)),
+    (0x1132a, 0x11330, Script(43)),
+    (0x11332, 0x11333, Script(43)),
+    (0x11335, 0x11339, Script(43)),
+    (0x1133b, 0x1133b, Script(56)),
+    (0x1133c, 0x11344, Script(43)),
+    (0x11347, 0x11348, Script(43)),
+    (0x1134b, 0x1134d, Script(43)),
+    (0x11350, 0x11350, Script(43)),
+    (0x11357, 0x11357, Script(43)),
+    (0x1135d, 0x11363, Script(43)),
+    (0x11366, 0x1136c, Script(43)),
+    (0x11370, 0x11374, Script(43)),
+    (0x11400, 0x1145b, Script(99)),
+    (0x1145d, 0x11461, Script(99)),
+    (0x11480, 0x114c7, Script(152)),
+    (0x114d0, 0x114d9, Script(152)),
+    (0x11580, 0x115b5, Script(129)),
+    (0x115b8, 0x115dd, Script(129)),
+    (0x11600, 0x11644, Script(91)),
+    (0x11650, 0x11659, Script(91)),
+    (0x11660, 0x1166c, Script(92)),
+    (0x11680, 0x116b9, Script(143)),
+    (0x116c0, 0x116c9, Script(143)),
+    (0x11700, 0x1171a, Script(2)),
+    (0x1171d, 0x1172b, Script(2)),
+    (0x11730, 0x11746, Script(2)),
+    (0x11800, 0x1183b, Script(34)),
+    (0x118a0, 0x118f2, Script(158)),
+    (0x118ff, 0x118ff, Script(158)),
+    (0x11900, 0x11906, Script(33)),
+    (0x11909, 0x11909, Script(33)),
+    (0x1190c, 0x11913, Script(33)),
+    (0x11915, 0x11916, Script(33)),
+    (0x11918, 0x11935, Script(33)),
+    (0x11937, 0x11938, Script(33)),
+    (0x1193b, 0x11946, Script(33)),
+    (0x11950, 0x11959, Script(33)),
+    (0x119a0, 0x119a7, Script(97)),
+    (0x119aa, 0x119d7, Script(97)),
+    (0x119da, 0x119e4, Script(97)),
+    (0x11a00, 0x11a47, Script(161)),
+    (0x11a50, 0x11aa2, Script(134)),
+    (0x11ab0, 0x11abf, Script(18)),
+    (0x11ac0, 0x11af8, Script(119)),
+    (0x11c00, 0x11c08, Script(12)),
+    (0x11c0a, 0x11c36, Script(12)),
+    (0x11c38, 0x11c45, Script(12)),
+    (0x11c50, 0x11c6c, Script(12)),
+    (0x11c70, 0x11c8f, Script(83)),
+    (0x11c92, 0x11ca7, Script(83)),
+    (0x11ca9, 0x11cb6, Script(83)),
+    (0x11d00, 0x11d06, Script(84)),
+    (0x11d08, 0x11d09, Script(84)),
+    (0x11d0b, 0x11d36, Script(84)),
+    (0x11d3a, 0x11d3a, Script(84)),
+    (0x11d3c, 0x11d3d, Script(84)),
+    (0x11d3f, 0x11d47, Script(84)),
+    (0x11d50, 0x11d59, Script(84)),
+    (0x11d60, 0x11d65, Script(46)),
+    (0x11d67, 0x11d68, Script(46)),
+    (0x11d6a, 0x11d8e, Script(46)),
+    (0x11d90, 0x11d91, Script(46)),
+    (0x11d93, 0x11d98, Script(46)),
+    (0x11da0, 0x11da9, Script(46)),
+    (0x11ee0, 0x11ef8, Script(79)),
+    (0x11fb0, 0x11fb0, Script(75)),
+    (0x11fc0, 0x11ff1, Script(144)),
+    (0x11fff, 0x11fff, Script(144)),
+    (0x12000, 0x12399, Script(27)),
+    (0x12400, 0x1246e, Script(27)),
+    (0x12470, 0x12474, Script(27)),
+    (0x12480, 0x12543, Script(27)),
+    (0x12f90, 0x12ff2, Script(29)),
+    (0x13000, 0x1342e, Script(36)),
+    (0x13430, 0x13438, Script(36)),
+    (0x14400, 0x14646, Script(3)),
+    (0x16800, 0x16a38, Script(8)),
+    (0x16a40, 0x16a5e, Script(93)),
+    (0x16a60, 0x16a69, Script(93)),
+    (0x16a6e, 0x16a6f, Script(93)),
+    (0x16a70, 0x16abe, Script(145)),
+    (0x16ac0, 0x16ac9, Script(145)),
+    (0x16ad0, 0x16aed, Script(9)),
+    (0x16af0, 0x16af5, Script(9)),
+    (0x16b00, 0x16b45, Script(117)),
+    (0x16b50, 0x16b59, Script(117)),
+    (0x16b5b, 0x16b61, Script(117)),
+    (0x16b63, 0x16b77, Script(117)),
+    (0x16b7d, 0x16b8f, Script(117)),
+    (0x16e40, 0x16e9a, Script(85)),
+    (0x16f00, 0x16f4a, Script(90)),
+    (0x16f4f, 0x16f87, Script(90)),
+    (0x16f8f, 0x16f9f, Script(90)),
+    (0x16fe0, 0x16fe0, Script(146)),
+    (0x16fe1, 0x16fe1, Script(101)),
+    (0x16fe2, 0x16fe3, Script(48)),
+    (0x16fe4, 0x16fe4, Script(65)),
+    (0x16ff0, 0x16ff1, Script(48)),
+    (0x17000, 0x187f7, Script(146)),
+    (0x18800, 0x18aff, Script(146)),
+    (0x18b00, 0x18cd5, Script(65)),
+    (0x18d00, 0x18d08, Script(146)),
+    (0x1aff0, 0x1aff3, Script(62)),
+    (0x1aff5, 0x1affb, Script(62)),
+    (0x1affd, 0x1affe, Script(62)),
+    (0x1b000, 0x1b000, Script(62)),
+    (0x1b001, 0x1b11f, Script(54)),
+    (0x1b120, 0x1b122, Script(62)),
+    (0x1b150, 0x1b152, Script(54)),
+    (0x1b164, 0x1b167, Script(62)),
+    (0x1b170, 0x1b2fb, Script(101)),
+    (0x1bc00, 0x1bc6a, Script(35)),
+    (0x1bc70, 0x1bc7c, Script(35)),
+    (0x1bc80, 0x1bc88, Script(35)),
+    (0x1bc90, 0x1bc99, Script(35)),
+    (0x1bc9c, 0x1bc9f, Script(35)),
+    (0x1bca0, 0x1bca3, Script(25)),
+    (0x1cf00, 0x1cf2d, Script(56)),
+    (0x1cf30, 0x1cf46, Script(56)),
+    (0x1cf50, 0x1cfc3, Script(25)),
+    (0x1d000, 0x1d0f5, Script(25)),
+    (0x1d100, 0x1d126, Script(25)),
+    (0x1d129, 0x1d166, Script(25)),
+    (0x1d167, 0x1d169, Script(56)),
+    (0x1d16a, 0x1d17a, Script(25)),
+    (0x1d17b, 0x1d182, Script(56)),
+    (0x1d183, 0x1d184, Script(25)),
+    (0x1d185, 0x1d18b, Script(56)),
+    (0x1d18c, 0x1d1a9, Script(25)),
+    (0x1d1aa, 0x1d1ad, Script(56)),
+    (0x1d1ae, 0x1d1ea, Script(25)),
+    (0x1d200, 0x1d245, Script(44)),
+    (0x1d2e0, 0x1d2f3, Script(25)),
+    (0x1d300, 0x1d356, Script(25)),
+    (0x1d360, 0x1d378, Script(25)),
+    (0x1d400, 0x1d454, Script(25)),
+    (0x1d456, 0x1d49c, Script(25)),
+    (0x1d49e, 0x1d49f, Script(25)),
+    (0x1d4a2, 0x1d4a2, Script(25)),
+    (0x1d4a5, 0x1d4a6, Script(25)),
+    (0x1d4a9, 0x1d4ac, Script(25)),
+    (0x1d4ae, 0x1d4b9, Script(25)),
+    (0x1d4bb, 0x1d4bb, Script(25)),
+    (0x1d4bd, 0x1d4c3, Script(25)),
+    (0x1d4c5, 0x1d505, Script(25)),
+    (0x1d507, 0x1d50a, Script(25)),
+    (0x1d50d, 0x1d514, Script(25)),
+    (0x1d516, 0x1d51c, Script(25)),
+    (0x1d51e, 0x1d539, Script(25)),
+    (0x1d53b, 0x1d53e, Script(25)),
+    (0x1d540, 0x1d544, Script(25)),
+    (0x1d546, 0x1d546, Script(25)),
+    (0x1d54a, 0x1d550, Script(25)),
+    (0x1d552, 0x1d6a5, Script(25)),
+    (0x1d6a8, 0x1d7cb, Script(25)),
+    (0x1d7ce, 0x1d7ff, Script(25)),
+    (0x1d800, 0x1da8b, Script(130)),
+    (0x1da9b, 0x1da9f, Script(130)),
+    (0x1daa1, 0x1daaf, Script(130)),
+    (0x1df00, 0x1df1e, Script(70)),
+    (0x1e000, 0x1e006, Script(41)),
+    (0x1e008, 0x1e018, Script(41)),
+    (0x1e01b, 0x1e021, Script(41)),
+    (0x1e023, 0x1e024, Script(41)),
+    (0x1e026, 0x1e02a, Script(41)),
+    (0x1e100, 0x1e12c, Script(102)),
+    (0x1e130, 0x1e13d, Script(102)),
+    (0x1e140, 0x1e149, Script(102)),
+    (0x1e14e, 0x1e14f, Script(102)),
+    (0x1e290, 0x1e2ae, Script(153)),
+    (0x1e2c0, 0x1e2f9, Script(157)),
+    (0x1e2ff, 0x1e2ff, Script(157)),
+    (0x1e7e0, 0x1e7e6, Script(39)),
+    (0x1e7e8, 0x1e7eb, Script(39)),
+    (0x1e7ed, 0x1e7ee, Script(39)),
+    (0x1e7f0, 0x1e7fe, Script(39)),
+    (0x1e800, 0x1e8c4, Script(87)),
+    (0x1e8c7, 0x1e8d6, Script(87)),
+    (0x1e900, 0x1e94b, Script(1)),
+    (0x1e950, 0x1e959, Script(1)),
+    (0x1e95e, 0x1e95f, Script(1)),
+    (0x1ec71, 0x1ecb4, Script(25)),
+    (0x1ed01, 0x1ed3d, Script(25)),
+    (0x1ee00, 0x1ee03, Script(4)),
+    (0x1ee05, 0x1ee1f, Script(4)),
+    (0x1ee21, 0x1ee22, Script(4)),
+    (0x1ee24, 0x1ee24, Script(4)),
+    (0x1ee27, 0x1ee27, Script(4)),
+    (0x1ee29, 0x1ee32, Script(4)),
+    (0x1ee34, 0x1ee37, Script(4)),
+    (0x1ee39, 0x1ee39, Script(4)),
+    (0x1ee3b, 0x1ee3b, Script(4)),
+    (0x1ee42, 0x1ee42, Script(4)),
+    (0x1ee47, 0x1ee47, Script(4)),
+    (0x1ee49, 0x1ee49, Script(4)),
+    (0x1ee4b, 0x1ee4b, Script(4)),
+    (0x1ee4d, 0x1ee4f, Script(4)),
+    (0x1ee51, 0x1ee52, Script(4)),
+    (0x1ee54, 0x1ee54, Script(4)),
+    (0x1ee57, 0x1ee57, Script(4)),
+    (0x1ee59, 0x1ee59, Script(4)),
+    (0x1ee5b, 0x1ee5b, Script(4)),
+    (0x1ee5d, 0x1ee5d, Script(4)),
+    (0x1ee5f, 0x1ee5f, Script(4)),
+    (0x1ee61, 0x1ee62, Script(4)),
+    (0x1ee64, 0x1ee64, Script(4)),
+    (0x1ee67, 0x1ee6a, Script(4)),
+    (0x1ee6c, 0x1ee72, Script(4)),
+    (0x1ee74, 0x1ee77, Script(4)),
+    (0x1ee79, 0x1ee7c, Script(4)),
+    (0x1ee7e, 0x1ee7e, Script(4)),
+    (0x1ee80, 0x1ee89, Script(4)),
+    (0x1ee8b, 0x1ee9b, Script(4)),
+    (0x1eea1, 0x1eea3, Script(4)),
+    (0x1eea5, 0x1eea9, Script(4)),
+    (0x1eeab, 0x1eebb, Script(4)),
+    (0x1eef0, 0x1eef1, Script(4)),
+    (0x1f000, 0x1f02b, Script(25)),
+    (0x1f030, 0x1f093, Script(25)),
+    (0x1f0a0, 0x1f0ae, Script(25)),
+    (0x1f0b1, 0x1f0bf, Script(25)),
+    (0x1f0c1, 0x1f0cf, Script(25)),
+    (0x1f0d1, 0x1f0f5, Script(25)),
+    (0x1f100, 0x1f1ad, Script(25)),
+    (0x1f1e6, 0x1f1ff, Script(25)),
+    (0x1f200, 0x1f200, Script(54)),
+    (0x1f201, 0x1f202, Script(25)),
+    (0x1f210, 0x1f23b, Script(25)),
+    (0x1f240, 0x1f248, Script(25)),
+    (0x1f250, 0x1f251, Script(25)),
+    (0x1f260, 0x1f265, Script(25)),
+    (0x1f300, 0x1f6d7, Script(25)),
+    (0x1f6dd, 0x1f6ec, Script(25)),
+    (0x1f6f0, 0x1f6fc, Script(25)),
+    (0x1f700, 0x1f773, Script(25)),
+    (0x1f780, 0x1f7d8, Script(25)),
+    (0x1f7e0, 0x1f7eb, Script(25)),
+    (0x1f7f0, 0x1f7f0, Script(25)),
+    (0x1f800, 0x1f80b, Script(25)),
+    (0x1f810, 0x1f847, Script(25)),
+    (0x1f850, 0x1f859, Script(25)),
+    (0x1f860, 0x1f887, Script(25)),
+    (0x1f890, 0x1f8ad, Script(25)),
+    (0x1f8b0, 0x1f8b1, Script(25)),
+    (0x1f900, 0x1fa53, Script(25)),
+    (0x1fa60, 0x1fa6d, Script(25)),
+    (0x1fa70, 0x1fa74, Script(25)),
+    (0x1fa78, 0x1fa7c, Script(25)),
+    (0x1fa80, 0x1fa86, Script(25)),
+    (0x1fa90, 0x1faac, Script(25)),
+    (0x1fab0, 0x1faba, Script(25)),
+    (0x1fac0, 0x1fac5, Script(25)),
+    (0x1fad0, 0x1fad9, Script(25)),
+    (0x1fae0, 0x1fae7, Script(25)),
+    (0x1faf0, 0x1faf6, Script(25)),
+    (0x1fb00, 0x1fb92, Script(25)),
+    (0x1fb94, 0x1fbca, Script(25)),
+    (0x1fbf0, 0x1fbf9, Script(25)),
+    (0x20000, 0x2a6df, Script(48)),
+    (0x2a700, 0x2b738, Script(48)),
+    (0x2b740, 0x2b81d, Script(48)),
+    (0x2b820, 0x2cea1, Script(48)),
+    (0x2ceb0, 0x2ebe0, Script(48)),
+    (0x2f800, 0x2fa1d, Script(48)),
+    (0x30000, 0x3134a, Script(48)),
+    (0xe0001, 0xe0001, Script(25)),
+    (0xe0020, 0xe007f, Script(25)),
+    (0xe0100, 0xe01ef, Script(56)),
+];
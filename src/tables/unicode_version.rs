@@ -0,0 +1,3 @@
+// Generated by build.rs from the UCD. Do not edit.
+
+pub const UNICODE_VERSION: &str = "14.0.0";
@@ -0,0 +1,64 @@
+//! Expansion of tabs to spaces up to the next tab stop, as `expand` does.
+
+use std::borrow::Cow;
+
+/// Expands the tabs of the output written in pieces, keeping track of the
+/// column across them. Each char takes one column.
+#[derive(Debug, Clone)]
+pub(crate) struct TabExpander {
+    /// The distance between tab stops.
+    width: usize,
+    /// The column of the next char, from 0.
+    column: usize,
+}
+
+impl TabExpander {
+    pub(crate) fn new(width: usize) -> Self {
+        Self {
+            width: width.max(1),
+            column: 0,
+        }
+    }
+
+    /// Return the utf-8 `bytes` with their tabs expanded.
+    pub(crate) fn expand<'a>(&mut self, bytes: &'a [u8]) -> Cow<'a, [u8]> {
+        let mut out: Option<Vec<u8>> = None;
+        for (i, &b) in bytes.iter().enumerate() {
+            match b {
+                b'\t' => {
+                    let n = self.width - self.column % self.width;
+                    let out = out.get_or_insert_with(|| bytes[..i].to_vec());
+                    out.resize(out.len() + n, b' ');
+                    self.column += n;
+                    continue;
+                }
+                b'\n' => self.column = 0,
+                // the continuation bytes of a multibyte char
+                0x80..=0xbf => (),
+                _ => self.column += 1,
+            }
+            if let Some(out) = &mut out {
+                out.push(b);
+            }
+        }
+        match out {
+            Some(out) => Cow::Owned(out),
+            None => Cow::Borrowed(bytes),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TabExpander;
+
+    #[test]
+    fn test_tab_expander() {
+        let mut tabs = TabExpander::new(4);
+        let mut out: Vec<u8> = Vec::new();
+        for piece in ["a\tbc", "\tdé", "f\t\n\tg"] {
+            out.extend_from_slice(&tabs.expand(piece.as_bytes()));
+        }
+        assert_eq!(out, "a   bc  déf \n    g".as_bytes());
+    }
+}
@@ -0,0 +1,107 @@
+//! Throttling of the output, e.g. to relay it to a slow serial console,
+//! which would otherwise drop what it cannot take.
+
+use std::io::{self, Write};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// The number of pieces a second of output at the byte rate is written in,
+/// so that it flows evenly rather than in bursts of a second each.
+const PIECES_PER_SEC: u64 = 10;
+
+/// Writes to the inner writer at most a number of bytes, and of lines, a
+/// second on average since the first write, flushing it before each wait.
+pub(crate) struct Throttle<W> {
+    inner: W,
+    bytes_per_sec: Option<u64>,
+    lines_per_sec: Option<u64>,
+    /// The byte ending a line.
+    end: u8,
+    /// The time of the first write.
+    start: Option<Instant>,
+    /// The bytes, and the complete lines, written so far.
+    bytes: u64,
+    lines: u64,
+}
+
+impl<W: Write> Throttle<W> {
+    pub(crate) fn new(
+        inner: W,
+        bytes_per_sec: Option<u64>,
+        lines_per_sec: Option<u64>,
+        end: u8,
+    ) -> Self {
+        Self {
+            inner,
+            bytes_per_sec: bytes_per_sec.map(|rate| rate.max(1)),
+            lines_per_sec: lines_per_sec.map(|rate| rate.max(1)),
+            end,
+            start: None,
+            bytes: 0,
+            lines: 0,
+        }
+    }
+
+    /// Wait until `sent` units at `rate` a second are due.
+    fn pace(&mut self, sent: u64, rate: u64) -> io::Result<()> {
+        let start = *self.start.get_or_insert_with(Instant::now);
+        let due = Duration::from_secs_f64(sent as f64 / rate as f64);
+        if let Some(wait) = due.checked_sub(start.elapsed()) {
+            self.inner.flush()?;
+            thread::sleep(wait);
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for Throttle<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // the part of `buf` which is due once the output so far is
+        let mut len = buf.len();
+        if let Some(rate) = self.bytes_per_sec {
+            self.pace(self.bytes, rate)?;
+            len = len.min((rate / PIECES_PER_SEC).max(1) as usize);
+        }
+        if let Some(rate) = self.lines_per_sec {
+            self.pace(self.lines, rate)?;
+            if let Some(i) = buf[..len].iter().position(|&b| b == self.end) {
+                len = i + 1;
+            }
+        }
+        let n = self.inner.write(&buf[..len])?;
+        self.bytes += n as u64;
+        self.lines +=
+            buf[..n].iter().filter(|&&b| b == self.end).count() as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Throttle;
+    use std::io::Write;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_throttle() {
+        // 10 bytes a second, written a byte at a time
+        let start = Instant::now();
+        let mut out: Vec<u8> = Vec::new();
+        let mut w = Throttle::new(&mut out, Some(10), None, b'\n');
+        w.write_all(b"abc").unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        assert_eq!(out, b"abc");
+
+        // 20 lines a second
+        let start = Instant::now();
+        let mut out: Vec<u8> = Vec::new();
+        let mut w = Throttle::new(&mut out, None, Some(20), b'\n');
+        w.write_all(b"a\nb\nc\nd").unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(150));
+        assert_eq!(out, b"a\nb\nc\nd");
+    }
+}
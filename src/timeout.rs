@@ -0,0 +1,115 @@
+//! Reading with a timeout, so that a stalled upstream ends the input instead
+//! of blocking forever.
+
+use std::io::{self, Read};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+/// The size of the chunks read in the background.
+const CHUNK_LEN: usize = 8192;
+
+/// Reads from a reader moved to a background thread, and ends the input if
+/// nothing arrives within the timeout. The thread is left blocked in `read`
+/// then, until the process exits.
+pub(crate) struct TimeoutReader {
+    chunks: Receiver<io::Result<Vec<u8>>>,
+    /// The chunk being read, and the offset into it.
+    chunk: Vec<u8>,
+    pos: usize,
+    timeout: Duration,
+    /// True once the timeout has passed.
+    pub(crate) timed_out: bool,
+}
+
+impl TimeoutReader {
+    pub(crate) fn new<R: Read + Send + 'static>(
+        mut r: R,
+        timeout: Duration,
+    ) -> Self {
+        let (tx, rx) = mpsc::sync_channel(1);
+        thread::spawn(move || loop {
+            let mut chunk = vec![0u8; CHUNK_LEN];
+            let result = r.read(&mut chunk).map(|n| {
+                chunk.truncate(n);
+                chunk
+            });
+            let end = matches!(&result, Ok(chunk) if chunk.is_empty())
+                || result.is_err();
+            if tx.send(result).is_err() || end {
+                return;
+            }
+        });
+        Self {
+            chunks: rx,
+            chunk: Vec::new(),
+            pos: 0,
+            timeout,
+            timed_out: false,
+        }
+    }
+}
+
+impl Read for TimeoutReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.pos == self.chunk.len() {
+            if self.timed_out {
+                return Ok(0);
+            }
+            self.chunk = match self.chunks.recv_timeout(self.timeout) {
+                Ok(chunk) => chunk?,
+                Err(RecvTimeoutError::Timeout) => {
+                    self.timed_out = true;
+                    Vec::new()
+                }
+                // the end of input was received already
+                Err(RecvTimeoutError::Disconnected) => Vec::new(),
+            };
+            self.pos = 0;
+        }
+        let n = buf.len().min(self.chunk.len() - self.pos);
+        buf[..n].copy_from_slice(&self.chunk[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TimeoutReader;
+    use std::io::{self, Read};
+    use std::thread;
+    use std::time::Duration;
+
+    /// Yields its chunks, then stalls.
+    struct Stalling(Vec<&'static [u8]>);
+
+    impl Read for Stalling {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.0.is_empty() {
+                thread::sleep(Duration::from_secs(3600));
+            }
+            let chunk = self.0.remove(0);
+            buf[..chunk.len()].copy_from_slice(chunk);
+            Ok(chunk.len())
+        }
+    }
+
+    #[test]
+    fn test_timeout_reader() {
+        let mut r = TimeoutReader::new(
+            Stalling(vec![b"ab", b"c"]),
+            Duration::from_millis(100),
+        );
+        let mut out: Vec<u8> = Vec::new();
+        r.read_to_end(&mut out).unwrap();
+        assert_eq!(out, b"abc");
+        assert!(r.timed_out);
+
+        let mut r = TimeoutReader::new(&b"abc"[..], Duration::from_secs(60));
+        out.clear();
+        r.read_to_end(&mut out).unwrap();
+        assert_eq!(out, b"abc");
+        assert!(!r.timed_out);
+    }
+}
@@ -0,0 +1,382 @@
+//! Decoding of an input in another encoding than utf-8, as a stage ahead of
+//! the filter, so that its chars are filtered rather than dropped as invalid
+//! utf-8, and encoding of the output in another encoding, as a stage after
+//! it.
+
+use std::io::{self, BufRead, Read, Write};
+
+use ascii_filter_core::utf8::{Utf8Chunk, Utf8Decoder};
+
+use crate::Encoding;
+
+/// The chars of Windows-1252 at 0x80 to 0x9F, where ISO 8859-1 has C1
+/// controls, which the bytes undefined in Windows-1252 decode as.
+const WINDOWS_1252: [char; 32] = [
+    '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ',
+    '\u{8d}', 'Ž', '\u{8f}', '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜',
+    '™', 'š', '›', 'œ', '\u{9d}', 'ž', 'Ÿ',
+];
+
+/// Return true if input in `encoding` is decoded before filtering, i.e. it
+/// is not utf-8 or ASCII.
+pub(crate) fn transcodes(encoding: Encoding) -> bool {
+    !matches!(encoding, Encoding::Utf8 | Encoding::Ascii)
+}
+
+/// Return true if output in `encoding` is encoded after filtering, i.e. it
+/// is not utf-8.
+pub(crate) fn encodes(encoding: Encoding) -> bool {
+    encoding != Encoding::Utf8
+}
+
+/// Return true if `encoding` has a BOM, i.e. it is a utf-16 or utf-32 one.
+pub(crate) fn has_bom(encoding: Encoding) -> bool {
+    matches!(
+        encoding,
+        Encoding::Utf16Le
+            | Encoding::Utf16Be
+            | Encoding::Utf32Le
+            | Encoding::Utf32Be
+    )
+}
+
+/// Decodes input in an encoding to utf-8 incrementally, carrying an
+/// incomplete code unit or surrogate pair over to the next chunk.
+pub(crate) struct Decoder {
+    encoding: Encoding,
+    /// The bytes of the incomplete code unit or surrogate pair.
+    pending: Vec<u8>,
+    /// If true, nothing was decoded yet, so that a BOM is dropped.
+    at_start: bool,
+}
+
+impl Decoder {
+    pub(crate) fn new(encoding: Encoding) -> Self {
+        Self {
+            encoding,
+            pending: Vec::new(),
+            at_start: true,
+        }
+    }
+
+    /// Decode `input` after the bytes carried over, appending the text to
+    /// `out`. Units which decode to no char are decoded as U+FFFD.
+    pub(crate) fn decode(&mut self, input: &[u8], out: &mut String) {
+        let start = out.len();
+        match self.encoding {
+            Encoding::Latin1 => out.extend(input.iter().map(|&b| match b {
+                0x80..=0x9f => WINDOWS_1252[b as usize - 0x80],
+                b => b as char,
+            })),
+            Encoding::Utf16Le | Encoding::Utf16Be => {
+                self.pending.extend_from_slice(input);
+                let be = self.encoding == Encoding::Utf16Be;
+                let mut units: Vec<u16> = self
+                    .pending
+                    .chunks_exact(2)
+                    .map(|unit| match be {
+                        true => u16::from_be_bytes([unit[0], unit[1]]),
+                        false => u16::from_le_bytes([unit[0], unit[1]]),
+                    })
+                    .collect();
+                let mut used = units.len() * 2;
+                // wait for the low surrogate after a high one
+                if units.last().is_some_and(|u| (0xd800..0xdc00).contains(u)) {
+                    units.pop();
+                    used -= 2;
+                }
+                out.extend(char::decode_utf16(units).map(|c| {
+                    c.unwrap_or_else(|e| {
+                        log::debug!(
+                            "unpaired surrogate {:04x} in {}, decoded as \
+                             U+FFFD",
+                            e.unpaired_surrogate(),
+                            self.encoding
+                        );
+                        char::REPLACEMENT_CHARACTER
+                    })
+                }));
+                self.pending.drain(..used);
+            }
+            Encoding::Utf32Le | Encoding::Utf32Be => {
+                self.pending.extend_from_slice(input);
+                let be = self.encoding == Encoding::Utf32Be;
+                let units = self.pending.chunks_exact(4);
+                let used = units.len() * 4;
+                out.extend(units.map(|unit| {
+                    let unit = [unit[0], unit[1], unit[2], unit[3]];
+                    let n = match be {
+                        true => u32::from_be_bytes(unit),
+                        false => u32::from_le_bytes(unit),
+                    };
+                    char::from_u32(n).unwrap_or_else(|| {
+                        log::debug!(
+                            "no char {:08x} in {}, decoded as U+FFFD",
+                            n,
+                            self.encoding
+                        );
+                        char::REPLACEMENT_CHARACTER
+                    })
+                }));
+                self.pending.drain(..used);
+            }
+            Encoding::Utf8 | Encoding::Ascii => {
+                unreachable!("utf-8 is decoded by the filter itself")
+            }
+        }
+        if self.at_start && out.len() > start {
+            self.at_start = false;
+            if out[start..].starts_with('\u{feff}') {
+                out.replace_range(start..start + '\u{feff}'.len_utf8(), "");
+            }
+        }
+    }
+
+    /// Decode the bytes carried over at the end of input, which are an
+    /// incomplete unit, as U+FFFD, appending it to `out`.
+    pub(crate) fn finish(&mut self, out: &mut String) {
+        if !std::mem::take(&mut self.pending).is_empty() {
+            log::debug!(
+                "incomplete unit at the end of {}, decoded as U+FFFD",
+                self.encoding
+            );
+            out.push(char::REPLACEMENT_CHARACTER);
+        }
+    }
+}
+
+/// Decodes what is read from `inner` to utf-8.
+pub(crate) struct DecodingReader<R> {
+    inner: R,
+    decoder: Decoder,
+    /// The text decoded but not read yet, from `pos`.
+    decoded: String,
+    pos: usize,
+    eof: bool,
+}
+
+impl<R: BufRead> DecodingReader<R> {
+    pub(crate) fn new(inner: R, encoding: Encoding) -> Self {
+        Self {
+            inner,
+            decoder: Decoder::new(encoding),
+            decoded: String::new(),
+            pos: 0,
+            eof: false,
+        }
+    }
+}
+
+impl<R: BufRead> Read for DecodingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let decoded = self.fill_buf()?;
+        let n = decoded.len().min(buf.len());
+        buf[..n].copy_from_slice(&decoded[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for DecodingReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        while self.pos == self.decoded.len() && !self.eof {
+            self.decoded.clear();
+            self.pos = 0;
+            let input = self.inner.fill_buf()?;
+            if input.is_empty() {
+                self.eof = true;
+                self.decoder.finish(&mut self.decoded);
+                break;
+            }
+            let n = input.len();
+            self.decoder.decode(input, &mut self.decoded);
+            self.inner.consume(n);
+        }
+        Ok(&self.decoded.as_bytes()[self.pos..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos += amt;
+    }
+}
+
+/// Return `?`, the code of `c`, which `encoding` has no code for.
+fn no_code(encoding: Encoding, c: char) -> u8 {
+    log::debug!("no code for {:?} in {}, encoded as ?", c, encoding);
+    b'?'
+}
+
+/// Encode `c` in `encoding`, appending it to `out`, as `?` if the encoding
+/// has no code for it.
+fn encode(encoding: Encoding, c: char, out: &mut Vec<u8>) {
+    match encoding {
+        Encoding::Ascii => out.push(match c.is_ascii() {
+            true => c as u8,
+            false => no_code(encoding, c),
+        }),
+        Encoding::Latin1 => out.push(match c as u32 {
+            n @ (0..=0x7f | 0xa0..=0xff) => n as u8,
+            _ => WINDOWS_1252
+                .iter()
+                .position(|&d| d == c)
+                .map_or_else(|| no_code(encoding, c), |i| 0x80 + i as u8),
+        }),
+        Encoding::Utf16Le | Encoding::Utf16Be => {
+            for unit in c.encode_utf16(&mut [0; 2]) {
+                out.extend(match encoding {
+                    Encoding::Utf16Be => unit.to_be_bytes(),
+                    _ => unit.to_le_bytes(),
+                });
+            }
+        }
+        Encoding::Utf32Le => out.extend((c as u32).to_le_bytes()),
+        Encoding::Utf32Be => out.extend((c as u32).to_be_bytes()),
+        Encoding::Utf8 => unreachable!("utf-8 is written by the filter itself"),
+    }
+}
+
+/// Encodes the utf-8 written through it in an encoding, carrying an
+/// incomplete char over to the next write. The chars which the encoding has
+/// no code for, and the invalid utf-8 sequences, are encoded as `?`.
+pub(crate) struct EncodingWriter<W> {
+    inner: W,
+    encoding: Encoding,
+    utf8: Utf8Decoder,
+    /// The encoded bytes of a write.
+    encoded: Vec<u8>,
+}
+
+impl<W: Write> EncodingWriter<W> {
+    pub(crate) fn new(inner: W, encoding: Encoding) -> Self {
+        Self {
+            inner,
+            encoding,
+            utf8: Utf8Decoder::default(),
+            encoded: Vec::new(),
+        }
+    }
+
+    /// Encode the incomplete char carried over at the end of output as `?`,
+    /// and flush.
+    pub(crate) fn finish(&mut self) -> io::Result<()> {
+        if !self.utf8.finish().is_empty() {
+            self.encoded.clear();
+            encode(self.encoding, '?', &mut self.encoded);
+            self.inner.write_all(&self.encoded)?;
+        }
+        self.inner.flush()
+    }
+}
+
+impl<W: Write> Write for EncodingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let (encoding, encoded) = (self.encoding, &mut self.encoded);
+        encoded.clear();
+        self.utf8.decode(buf, |chunk| {
+            match chunk {
+                Utf8Chunk::Valid(text) => {
+                    text.chars().for_each(|c| encode(encoding, c, encoded))
+                }
+                Utf8Chunk::Invalid(_) => encode(encoding, '?', encoded),
+            }
+            io::Result::Ok(())
+        })?;
+        self.inner.write_all(&self.encoded)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Decoder, DecodingReader, EncodingWriter};
+    use crate::Encoding;
+    use std::io::{BufReader, Read, Write};
+
+    /// Decode `input` in `encoding` in chunks of `len` bytes.
+    fn decode(encoding: Encoding, input: &[u8], len: usize) -> String {
+        let mut decoder = Decoder::new(encoding);
+        let mut out = String::new();
+        for chunk in input.chunks(len) {
+            decoder.decode(chunk, &mut out);
+        }
+        decoder.finish(&mut out);
+        out
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(
+            decode(Encoding::Latin1, b"caf\xe9 \x80\x81", 1),
+            "café €\u{81}"
+        );
+        let utf16le: Vec<u8> = "\u{feff}a😀é"
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect();
+        let utf16be: Vec<u8> =
+            "a😀é".encode_utf16().flat_map(u16::to_be_bytes).collect();
+        for len in 1..=4 {
+            assert_eq!(decode(Encoding::Utf16Le, &utf16le, len), "a😀é");
+            assert_eq!(decode(Encoding::Utf16Be, &utf16be, len), "a😀é");
+        }
+        // a lone surrogate, and an incomplete unit at the end
+        assert_eq!(
+            decode(Encoding::Utf16Le, b"\x00\xd8a\x00b", 1),
+            "\u{fffd}a\u{fffd}"
+        );
+        let utf32be: Vec<u8> = "a😀"
+            .chars()
+            .flat_map(|c| (c as u32).to_be_bytes())
+            .collect();
+        for len in 1..=5 {
+            assert_eq!(decode(Encoding::Utf32Be, &utf32be, len), "a😀");
+        }
+        assert_eq!(
+            decode(Encoding::Utf32Le, b"\x00\xd8\x00\x00", 3),
+            "\u{fffd}"
+        );
+    }
+
+    #[test]
+    fn test_decoding_reader() {
+        let input: Vec<u8> =
+            "ab\nçd".encode_utf16().flat_map(u16::to_le_bytes).collect();
+        let mut r = DecodingReader::new(
+            BufReader::with_capacity(3, &input[..]),
+            Encoding::Utf16Le,
+        );
+        let mut out = String::new();
+        r.read_to_string(&mut out).unwrap();
+        assert_eq!(out, "ab\nçd");
+    }
+
+    #[test]
+    fn test_encoding_writer() {
+        let encoded = |encoding, writes: &[&[u8]]| {
+            let mut w = EncodingWriter::new(Vec::new(), encoding);
+            for bytes in writes {
+                w.write_all(bytes).unwrap();
+            }
+            w.finish().unwrap();
+            w.inner
+        };
+        // a char split across writes
+        let text = "café €—ж😀";
+        let (head, tail) = text.as_bytes().split_at(4);
+        assert_eq!(
+            encoded(Encoding::Latin1, &[head, tail]),
+            b"caf\xe9 \x80\x97??"
+        );
+        assert_eq!(encoded(Encoding::Ascii, &[head, tail]), b"caf? ????");
+        let utf16be: Vec<u8> =
+            text.encode_utf16().flat_map(u16::to_be_bytes).collect();
+        assert_eq!(encoded(Encoding::Utf16Be, &[head, tail]), utf16be);
+        assert_eq!(encoded(Encoding::Utf32Le, &[b"a\xff"]), b"a\0\0\0?\0\0\0");
+        // an incomplete char at the end
+        assert_eq!(encoded(Encoding::Latin1, &[b"a\xe4\xbd"]), b"a?");
+    }
+}
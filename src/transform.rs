@@ -0,0 +1,411 @@
+//! Composable stages transforming a stream, e.g. decoding, normalizing,
+//! transliterating and filtering it, chained in a [`Pipeline`], to which
+//! library users may add stages of their own.
+
+use std::io::{self, Write};
+
+use ascii_filter_core::utf8::{Utf8Chunk, Utf8Decoder};
+
+use crate::ansi::AnsiStripper;
+use crate::mapping::Mapping;
+#[cfg(feature = "unicode-normalization")]
+use crate::normalize::{Normalization, Normalizer};
+use crate::transcode::{self, Decoder};
+use crate::{Encoding, FilterWriter, Options, Stats};
+
+/// A stage of a stream transformation. `feed` is called with the next bytes
+/// of the stream in turn, which may split a char anywhere, and writes the
+/// output decided so far to `out`; `finish` is called at the end of the
+/// stream, and writes the output held back, if any.
+///
+/// Closures of the same signature as `feed` implement this trait, e.g. for
+/// a stage which holds nothing back.
+pub trait Transform {
+    fn feed(&mut self, input: &[u8], out: &mut dyn Write) -> io::Result<()>;
+
+    fn finish(&mut self, out: &mut dyn Write) -> io::Result<()> {
+        let _ = out;
+        Ok(())
+    }
+}
+
+impl<F> Transform for F
+where
+    F: FnMut(&[u8], &mut dyn Write) -> io::Result<()>,
+{
+    fn feed(&mut self, input: &[u8], out: &mut dyn Write) -> io::Result<()> {
+        self(input, out)
+    }
+}
+
+/// Feed `input` through `stages` in order, writing the output of the last
+/// to `out`.
+fn feed_all(
+    stages: &mut [Box<dyn Transform>],
+    input: &[u8],
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    match stages.split_first_mut() {
+        None => out.write_all(input),
+        Some((stage, [])) => stage.feed(input, out),
+        Some((stage, rest)) => {
+            let mut next = Vec::new();
+            stage.feed(input, &mut next)?;
+            match next.is_empty() {
+                true => Ok(()),
+                false => feed_all(rest, &next, out),
+            }
+        }
+    }
+}
+
+/// Stages run in order, each fed the output of the one before, which is a
+/// stage itself.
+#[derive(Default)]
+pub struct Pipeline {
+    stages: Vec<Box<dyn Transform>>,
+}
+
+impl Pipeline {
+    /// Create the pipeline of no stages, which passes the input as is.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create the pipeline filtering input per `opts`: decoding it from
+    /// `opts.from_encoding`, if any, and filtering it as
+    /// [`FilterWriter::from_options`] does.
+    pub fn from_options(opts: &Options) -> Self {
+        let mut pipeline = Self::new();
+        if let Some(encoding) = opts.from_encoding {
+            pipeline = pipeline.then(Decode::new(encoding));
+        }
+        pipeline.then(Filter::new(opts))
+    }
+
+    /// Add `stage` after the stages so far.
+    pub fn then<T: Transform + 'static>(mut self, stage: T) -> Self {
+        self.stages.push(Box::new(stage));
+        self
+    }
+
+    /// Return the writer transforming what is written to it through the
+    /// pipeline into `inner`.
+    pub fn writer<W: Write>(self, inner: W) -> TransformWriter<Self, W> {
+        TransformWriter::new(self, inner)
+    }
+}
+
+impl Transform for Pipeline {
+    fn feed(&mut self, input: &[u8], out: &mut dyn Write) -> io::Result<()> {
+        feed_all(&mut self.stages, input, out)
+    }
+
+    /// Finish the stages in order, each after feeding the output held back
+    /// by those before it.
+    fn finish(&mut self, out: &mut dyn Write) -> io::Result<()> {
+        for i in 0..self.stages.len() {
+            let (stage, rest) = self.stages[i..].split_first_mut().unwrap();
+            if rest.is_empty() {
+                return stage.finish(out);
+            }
+            let mut held = Vec::new();
+            stage.finish(&mut held)?;
+            if !held.is_empty() {
+                feed_all(rest, &held, out)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes what is written to it through a [`Transform`] into the inner
+/// writer.
+pub struct TransformWriter<T: Transform, W: Write> {
+    transform: T,
+    inner: W,
+}
+
+impl<T: Transform, W: Write> TransformWriter<T, W> {
+    pub fn new(transform: T, inner: W) -> Self {
+        Self { transform, inner }
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// End the stream: write the output held back by the transform, flush
+    /// the inner writer and return it.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.transform.finish(&mut self.inner)?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+impl<T: Transform, W: Write> Write for TransformWriter<T, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.transform.feed(buf, &mut self.inner)?;
+        Ok(buf.len())
+    }
+
+    /// Flush the inner writer, but not the output held back by the
+    /// transform, which [`finish`](Self::finish) writes.
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Decodes the input in an encoding to UTF-8, dropping a BOM at the start,
+/// and decoding the units which are no char as U+FFFD. Input in UTF-8 or
+/// ASCII passes as is.
+pub struct Decode {
+    decoder: Option<Decoder>,
+}
+
+impl Decode {
+    pub fn new(encoding: Encoding) -> Self {
+        let decoder = transcode::transcodes(encoding);
+        Self {
+            decoder: decoder.then(|| Decoder::new(encoding)),
+        }
+    }
+}
+
+impl Transform for Decode {
+    fn feed(&mut self, input: &[u8], out: &mut dyn Write) -> io::Result<()> {
+        let Some(decoder) = &mut self.decoder else {
+            return out.write_all(input);
+        };
+        let mut text = String::new();
+        decoder.decode(input, &mut text);
+        out.write_all(text.as_bytes())
+    }
+
+    fn finish(&mut self, out: &mut dyn Write) -> io::Result<()> {
+        let mut text = String::new();
+        if let Some(decoder) = &mut self.decoder {
+            decoder.finish(&mut text);
+        }
+        out.write_all(text.as_bytes())
+    }
+}
+
+/// Strips the ANSI escape sequences, as [`FilterWriter::with_strip_ansi`]
+/// does. Invalid UTF-8 passes as is, for a filter after to decide on.
+#[derive(Default)]
+pub struct StripAnsi {
+    utf8: Utf8Decoder,
+    stripper: AnsiStripper,
+}
+
+impl StripAnsi {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Transform for StripAnsi {
+    fn feed(&mut self, input: &[u8], out: &mut dyn Write) -> io::Result<()> {
+        self.utf8.decode(input, |chunk| match chunk {
+            Utf8Chunk::Valid(s) => {
+                out.write_all(self.stripper.strip(s).as_bytes())
+            }
+            Utf8Chunk::Invalid(bytes) => out.write_all(bytes),
+        })
+    }
+
+    fn finish(&mut self, out: &mut dyn Write) -> io::Result<()> {
+        out.write_all(self.utf8.finish())
+    }
+}
+
+/// Normalizes the text to a form, holding back the text which may yet
+/// combine with what is fed next. Invalid UTF-8 passes as is, for a filter
+/// after to decide on.
+#[cfg(feature = "unicode-normalization")]
+pub struct Normalize {
+    utf8: Utf8Decoder,
+    normalizer: Normalizer,
+}
+
+#[cfg(feature = "unicode-normalization")]
+impl Normalize {
+    pub fn new(form: Normalization) -> Self {
+        Self {
+            utf8: Utf8Decoder::default(),
+            normalizer: Normalizer::new(form),
+        }
+    }
+}
+
+#[cfg(feature = "unicode-normalization")]
+impl Transform for Normalize {
+    fn feed(&mut self, input: &[u8], out: &mut dyn Write) -> io::Result<()> {
+        self.utf8.decode(input, |chunk| match chunk {
+            Utf8Chunk::Valid(s) => {
+                out.write_all(self.normalizer.push(s).as_bytes())
+            }
+            Utf8Chunk::Invalid(bytes) => {
+                // nothing combines across the invalid bytes
+                out.write_all(self.normalizer.finish().as_bytes())?;
+                out.write_all(bytes)
+            }
+        })
+    }
+
+    fn finish(&mut self, out: &mut dyn Write) -> io::Result<()> {
+        out.write_all(self.normalizer.finish().as_bytes())?;
+        out.write_all(self.utf8.finish())
+    }
+}
+
+/// Converts each char outside ASCII found in the mappings, consulted in
+/// order, e.g. ö to oe with [`Mapping::Translit`], and passes the others as
+/// they are, for a filter after to decide on, as does invalid UTF-8.
+pub struct Transliterate {
+    utf8: Utf8Decoder,
+    mappings: Vec<Mapping>,
+}
+
+impl Transliterate {
+    pub fn new(mappings: Vec<Mapping>) -> Self {
+        Self {
+            utf8: Utf8Decoder::default(),
+            mappings,
+        }
+    }
+}
+
+impl Transform for Transliterate {
+    fn feed(&mut self, input: &[u8], out: &mut dyn Write) -> io::Result<()> {
+        let mappings = &self.mappings;
+        self.utf8.decode(input, |chunk| {
+            let s = match chunk {
+                Utf8Chunk::Valid(s) => s,
+                Utf8Chunk::Invalid(bytes) => return out.write_all(bytes),
+            };
+            let mut start = 0;
+            for (i, c) in s.char_indices().filter(|(_, c)| !c.is_ascii()) {
+                let Some(ascii) = mappings.iter().find_map(|m| m.get(c)) else {
+                    continue;
+                };
+                out.write_all(&s.as_bytes()[start..i])?;
+                out.write_all(ascii.as_bytes())?;
+                start = i + c.len_utf8();
+            }
+            out.write_all(&s.as_bytes()[start..])
+        })
+    }
+
+    fn finish(&mut self, out: &mut dyn Write) -> io::Result<()> {
+        out.write_all(self.utf8.finish())
+    }
+}
+
+/// Filters the input through a [`FilterWriter`], e.g. as the last stage of a
+/// pipeline.
+pub struct Filter {
+    fw: FilterWriter<Vec<u8>>,
+}
+
+impl Filter {
+    /// Create the filter of [`FilterWriter::from_options`].
+    pub fn new(opts: &Options) -> Self {
+        Self::with_writer(FilterWriter::from_options(Vec::new(), opts))
+    }
+
+    /// Create the filter of `fw`, e.g. one with a replacer of its own.
+    pub fn with_writer(fw: FilterWriter<Vec<u8>>) -> Self {
+        Self { fw }
+    }
+
+    /// Return the totals so far, see [`FilterWriter::stats`].
+    pub fn stats(&self) -> Stats {
+        self.fw.stats()
+    }
+}
+
+impl Transform for Filter {
+    fn feed(&mut self, input: &[u8], out: &mut dyn Write) -> io::Result<()> {
+        self.fw.write_all(input)?;
+        out.write_all(&std::mem::take(self.fw.get_mut()))
+    }
+
+    fn finish(&mut self, out: &mut dyn Write) -> io::Result<()> {
+        self.fw.finish()?;
+        out.write_all(&std::mem::take(self.fw.get_mut()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Decode, Filter, Pipeline, StripAnsi, Transliterate};
+    use crate::{Encoding, Locale, Mapping, Options};
+    use std::io::{self, Write};
+
+    #[test]
+    fn test_pipeline() {
+        let opts = Options {
+            ascii_only: true,
+            replacement: Some("?".to_string()),
+            ..Options::default()
+        };
+        let input: Vec<u8> = "\u{feff}\x1b[1mGrüße\x1b[0m, 你!\n"
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect();
+        let shout = |input: &[u8], out: &mut dyn Write| -> io::Result<()> {
+            out.write_all(&input.to_ascii_uppercase())
+        };
+        let mut w = Pipeline::new()
+            .then(Decode::new(Encoding::Utf16Le))
+            .then(StripAnsi::new())
+            .then(Transliterate::new(vec![Mapping::Translit(Locale::De)]))
+            .then(shout)
+            .then(Filter::new(&opts))
+            .writer(Vec::new());
+        // a byte at a time, splitting the units and the chars
+        for b in &input {
+            w.write_all(&[*b]).unwrap();
+        }
+        assert_eq!(w.finish().unwrap(), b"GRUESSE, ?!\n");
+
+        // a pipeline of no stages passes the input as is
+        let mut w = Pipeline::new().writer(Vec::new());
+        w.write_all("你".as_bytes()).unwrap();
+        assert_eq!(w.finish().unwrap(), "你".as_bytes());
+    }
+
+    #[cfg(feature = "unicode-normalization")]
+    #[test]
+    fn test_pipeline_finish() {
+        use super::Normalize;
+        use crate::Normalization;
+
+        // the e held back by the normalizer is filtered at the end
+        let opts = Options {
+            ascii_only: true,
+            ..Options::default()
+        };
+        let mut w = Pipeline::new()
+            .then(Normalize::new(Normalization::Nfd))
+            .then(Filter::new(&opts))
+            .writer(Vec::new());
+        w.write_all("caf\u{e9}".as_bytes()).unwrap();
+        assert_eq!(w.finish().unwrap(), b"cafe");
+
+        let mut w = Pipeline::from_options(&Options {
+            from_encoding: Some(Encoding::Latin1),
+            ..opts
+        })
+        .writer(Vec::new());
+        w.write_all(b"caf\xe9 \x80").unwrap();
+        assert_eq!(w.finish().unwrap(), b"caf ");
+    }
+}
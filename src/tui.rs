@@ -0,0 +1,299 @@
+//! An interactive preview, showing the original and the filtered text side
+//! by side with the removed and replaced chars highlighted. The options can
+//! be toggled live before the result is written.
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process;
+
+use ratatui::backend::CrosstermBackend;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::crossterm::execute;
+use ratatui::crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen,
+    LeaveAlternateScreen,
+};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph};
+use ratatui::{Frame, Terminal};
+
+use ascii_filter::{Event as FilterEvent, FilterMachine, Locale, Mapping};
+use ascii_filter::{Options, Output};
+
+use crate::exit;
+
+/// The mappings that can be toggled, in the order they apply, as on the
+/// command line.
+const TOGGLES: [(Mapping, &str); 9] = [
+    (Mapping::Greek, "greek"),
+    (Mapping::Units, "units"),
+    (Mapping::Symbols, "symbols"),
+    (Mapping::Arrows, "arrows"),
+    (Mapping::BoxDrawing, "box-drawing"),
+    (Mapping::Bullets, "bullets"),
+    (Mapping::Roman, "roman"),
+    (Mapping::Enclosed, "enclosed"),
+    (Mapping::Math, "math"),
+];
+
+const DROPPED: Style = Style::new().fg(Color::White).bg(Color::Red);
+const REPLACED: Style = Style::new().fg(Color::Black).bg(Color::Yellow);
+
+struct State {
+    opts: Options,
+    /// The rule tables, which always apply first.
+    tables: Vec<Mapping>,
+    locale: Option<Locale>,
+    enabled: [bool; TOGGLES.len()],
+    input: Vec<u8>,
+    output: Output,
+    scroll: (u16, u16),
+}
+
+impl State {
+    fn new(input: Vec<u8>, opts: Options) -> Self {
+        let tables = opts
+            .mappings
+            .iter()
+            .filter(|m| matches!(m, Mapping::Table(_)))
+            .cloned()
+            .collect();
+        let locale = opts.mappings.iter().find_map(|m| match m {
+            Mapping::Translit(locale) => Some(*locale),
+            _ => None,
+        });
+        let enabled = TOGGLES.map(|(m, _)| opts.mappings.contains(&m));
+        let mut state = Self {
+            opts,
+            tables,
+            locale,
+            enabled,
+            input,
+            output: Output::default(),
+            scroll: (0, 0),
+        };
+        state.refilter();
+        state
+    }
+
+    /// Rebuild the mappings from the toggles, and filter the input again.
+    fn refilter(&mut self) {
+        let mut mappings = self.tables.clone();
+        mappings.extend(self.locale.map(Mapping::Translit));
+        for (i, (m, _)) in TOGGLES.iter().enumerate() {
+            if self.enabled[i] {
+                mappings.push(m.clone());
+            }
+        }
+        self.opts.mappings = mappings;
+        let mut machine = FilterMachine::new(&self.opts);
+        // The replacer per the options writes to a `Vec`, which never fails.
+        self.output = machine.push(&self.input).unwrap();
+        self.output.events.extend(machine.finish().unwrap().events);
+    }
+
+    fn cycle_locale(&mut self) {
+        let i = match self.locale {
+            None => 0,
+            Some(locale) => {
+                Locale::ALL.iter().position(|&l| l == locale).unwrap() + 1
+            }
+        };
+        self.locale = Locale::ALL.get(i).copied();
+    }
+
+    /// Handle `key`, returning whether to write the result, or exit without
+    /// writing.
+    fn handle(&mut self, key: KeyCode) -> Option<bool> {
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => return Some(false),
+            KeyCode::Char('w') => return Some(true),
+            KeyCode::Char('a') => {
+                self.opts.ascii_only = !self.opts.ascii_only;
+                self.refilter();
+            }
+            KeyCode::Char('l') => {
+                self.cycle_locale();
+                self.refilter();
+            }
+            KeyCode::Char(c @ '1'..='9') => {
+                let i = c as usize - '1' as usize;
+                self.enabled[i] = !self.enabled[i];
+                self.refilter();
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.scroll.0 = self.scroll.0.saturating_add(1)
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.scroll.0 = self.scroll.0.saturating_sub(1)
+            }
+            KeyCode::PageDown => {
+                self.scroll.0 = self.scroll.0.saturating_add(20)
+            }
+            KeyCode::PageUp => self.scroll.0 = self.scroll.0.saturating_sub(20),
+            KeyCode::Right => self.scroll.1 = self.scroll.1.saturating_add(4),
+            KeyCode::Left => self.scroll.1 = self.scroll.1.saturating_sub(4),
+            KeyCode::Home => self.scroll = (0, 0),
+            _ => (),
+        }
+        None
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let [main, footer] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(2)])
+                .areas(frame.area());
+        let [left, right] = Layout::horizontal([
+            Constraint::Percentage(50),
+            Constraint::Percentage(50),
+        ])
+        .areas(main);
+
+        let mut original: Vec<Line> = vec![Line::default()];
+        let mut filtered: Vec<Line> = vec![Line::default()];
+        let (mut dropped, mut replaced) = (0usize, 0usize);
+        for event in &self.output.events {
+            match event {
+                FilterEvent::Kept { bytes, .. } => {
+                    let text = String::from_utf8_lossy(bytes);
+                    push_text(&mut original, &text, Style::new());
+                    push_text(&mut filtered, &text, Style::new());
+                }
+                FilterEvent::Dropped { c, .. } => {
+                    dropped += 1;
+                    push_text(&mut original, &c.to_string(), DROPPED);
+                }
+                FilterEvent::Replaced { c, bytes, .. } => {
+                    replaced += 1;
+                    push_text(&mut original, &c.to_string(), REPLACED);
+                    let text = String::from_utf8_lossy(bytes);
+                    push_text(&mut filtered, &text, REPLACED);
+                }
+                FilterEvent::Invalid { bytes, .. } => {
+                    dropped += 1;
+                    let text: String =
+                        bytes.iter().map(|b| format!("\\x{:02x}", b)).collect();
+                    push_text(&mut original, &text, DROPPED);
+                }
+            }
+        }
+        frame.render_widget(
+            Paragraph::new(original)
+                .block(Block::bordered().title(" original "))
+                .scroll(self.scroll),
+            left,
+        );
+        frame.render_widget(
+            Paragraph::new(filtered)
+                .block(Block::bordered().title(format!(
+                    " filtered: {} dropped, {} replaced ",
+                    dropped, replaced
+                )))
+                .scroll(self.scroll),
+            right,
+        );
+
+        let on = Style::new().add_modifier(Modifier::REVERSED);
+        let toggle = |key: &str, label: String, enabled: bool| {
+            let style = if enabled { on } else { Style::new() };
+            [Span::raw(format!(" {} ", key)), Span::styled(label, style)]
+        };
+        let mut spans: Vec<Span> = Vec::new();
+        spans.extend(toggle(
+            "a",
+            "ascii-only".to_string(),
+            self.opts.ascii_only,
+        ));
+        let locale = self.locale.map_or("none".to_string(), |l| l.to_string());
+        spans.extend(toggle(
+            "l",
+            format!("locale={}", locale),
+            self.locale.is_some(),
+        ));
+        for (i, (_, label)) in TOGGLES.iter().enumerate() {
+            spans.extend(toggle(
+                &(i + 1).to_string(),
+                label.to_string(),
+                self.enabled[i],
+            ));
+        }
+        frame.render_widget(
+            Paragraph::new(vec![
+                Line::from(spans),
+                Line::raw(" w write and quit  q quit  arrows/j/k scroll"),
+            ]),
+            footer,
+        );
+    }
+}
+
+/// Append `text` in `style` to `lines`, starting a new line at each '\n'.
+/// Tabs and other control chars are rendered as spaces, so that they cannot
+/// confuse the terminal.
+fn push_text(lines: &mut Vec<Line<'static>>, text: &str, style: Style) {
+    for (i, part) in text.split('\n').enumerate() {
+        if i > 0 {
+            lines.push(Line::default());
+        }
+        if !part.is_empty() {
+            let part: String = part
+                .chars()
+                .map(|c| if c.is_control() { ' ' } else { c })
+                .collect();
+            lines
+                .last_mut()
+                .unwrap()
+                .push_span(Span::styled(part, style));
+        }
+    }
+}
+
+/// Run the preview of `file` starting with `opts`, and write the result to
+/// `output` or stdout if asked to. Record separators and input formats are
+/// not applied to the result.
+pub fn run(file: &Path, output: Option<&Path>, opts: Options) {
+    let input = fs::read(file).unwrap_or_else(|e| {
+        eprintln!("ascii-filter: {}: {}", file.display(), e);
+        process::exit(exit::IO);
+    });
+    let mut state = State::new(input, opts);
+
+    // draw on stderr, so that stdout can receive the result
+    enable_raw_mode().unwrap();
+    execute!(io::stderr(), EnterAlternateScreen).unwrap();
+    let mut terminal =
+        Terminal::new(CrosstermBackend::new(io::stderr())).unwrap();
+    let write = loop {
+        terminal.draw(|frame| state.draw(frame)).unwrap();
+        if let Event::Key(key) = event::read().unwrap() {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            if let Some(write) = state.handle(key.code) {
+                break write;
+            }
+        }
+    };
+    disable_raw_mode().unwrap();
+    execute!(io::stderr(), LeaveAlternateScreen).unwrap();
+
+    if !write {
+        return;
+    }
+    let mut result: Vec<u8> = Vec::new();
+    if state.opts.write_bom {
+        result.extend_from_slice(b"\xef\xbb\xbf");
+    }
+    result.extend(state.output.kept());
+    let written = match output {
+        Some(path) => fs::write(path, &result),
+        None => io::stdout().write_all(&result),
+    };
+    if let Err(e) = written {
+        eprintln!("ascii-filter: {}", e);
+        process::exit(exit::IO);
+    }
+}
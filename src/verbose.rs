@@ -0,0 +1,63 @@
+//! The logger of `-v`, which prints the records of the filter, e.g. the
+//! per-file summaries, the encoding fallbacks or each char dropped, to
+//! stderr.
+
+use std::io::Write;
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+/// Prints the records up to its level to stderr.
+struct StderrLogger;
+
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            let level = record.level().as_str().to_ascii_lowercase();
+            // the log is best effort
+            let _ = writeln!(
+                std::io::stderr().lock(),
+                "ascii-filter: {}: {}",
+                level,
+                record.args()
+            );
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Return the level of `verbose` repetitions of `-v`: the summaries with
+/// one, the decisions with two, and each char dropped with three.
+fn level(verbose: u8) -> LevelFilter {
+    match verbose {
+        0 => LevelFilter::Off,
+        1 => Level::Info.to_level_filter(),
+        2 => Level::Debug.to_level_filter(),
+        _ => Level::Trace.to_level_filter(),
+    }
+}
+
+/// Log to stderr at the level of `verbose` repetitions of `-v`, if any.
+pub(crate) fn init(verbose: u8) {
+    if verbose > 0 && log::set_logger(&StderrLogger).is_ok() {
+        log::set_max_level(level(verbose));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::level;
+    use log::LevelFilter;
+
+    #[test]
+    fn test_level() {
+        assert_eq!(level(0), LevelFilter::Off);
+        assert_eq!(level(1), LevelFilter::Info);
+        assert_eq!(level(2), LevelFilter::Debug);
+        assert_eq!(level(5), LevelFilter::Trace);
+    }
+}
@@ -0,0 +1,571 @@
+//! Recursive filtering of the files under given paths, which skips binary
+//! and ignored files by default, so that a blanket run over a repository
+//! leaves images, executables and build artifacts out of the output.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use ignore::WalkBuilder;
+
+use crate::{
+    file_line_prefix, filter_stream, report_name, rewrite, Error, Options,
+    Stats,
+};
+
+/// The number of bytes sniffed at the start of a file.
+const SNIFF_LEN: usize = 8192;
+
+/// Magic numbers of common binary formats, with the offset at which they
+/// appear, and their MIME types.
+const MAGIC: [(usize, &[u8], &str); 24] = [
+    (0, b"\x7fELF", "application/x-executable"),
+    (0, b"MZ", "application/vnd.microsoft.portable-executable"),
+    (0, b"\xfe\xed\xfa\xce", "application/x-mach-binary"),
+    (0, b"\xfe\xed\xfa\xcf", "application/x-mach-binary"),
+    (0, b"\xce\xfa\xed\xfe", "application/x-mach-binary"),
+    (0, b"\xcf\xfa\xed\xfe", "application/x-mach-binary"),
+    (0, b"\xca\xfe\xba\xbe", "application/java-vm"),
+    (0, b"\0asm", "application/wasm"),
+    (0, b"\x89PNG\r\n\x1a\n", "image/png"),
+    (0, b"\xff\xd8\xff", "image/jpeg"),
+    (0, b"GIF8", "image/gif"),
+    (0, b"%PDF-", "application/pdf"),
+    (0, b"PK\x03\x04", "application/zip"),
+    (0, b"\x1f\x8b", "application/gzip"),
+    (0, b"BZh", "application/x-bzip2"),
+    (0, b"\xfd7zXZ\0", "application/x-xz"),
+    (0, b"(\xb5/\xfd", "application/zstd"),
+    (0, b"7z\xbc\xaf\x27\x1c", "application/x-7z-compressed"),
+    (257, b"ustar", "application/x-tar"),
+    (0, b"SQLite format 3\0", "application/vnd.sqlite3"),
+    (0, b"OggS", "audio/ogg"),
+    (0, b"fLaC", "audio/flac"),
+    (0, b"ID3", "audio/mpeg"),
+    (4, b"ftyp", "video/mp4"),
+];
+
+/// The MIME types of text files by their extensions.
+const EXTENSIONS: [(&str, &str); 28] = [
+    ("c", "text/x-c"),
+    ("cc", "text/x-c++"),
+    ("cpp", "text/x-c++"),
+    ("css", "text/css"),
+    ("csv", "text/csv"),
+    ("go", "text/x-go"),
+    ("h", "text/x-c"),
+    ("hpp", "text/x-c++"),
+    ("htm", "text/html"),
+    ("html", "text/html"),
+    ("java", "text/x-java"),
+    ("js", "text/javascript"),
+    ("json", "application/json"),
+    ("log", "text/plain"),
+    ("md", "text/markdown"),
+    ("py", "text/x-python"),
+    ("rb", "text/x-ruby"),
+    ("rs", "text/x-rust"),
+    ("sh", "text/x-shellscript"),
+    ("svg", "image/svg+xml"),
+    ("tex", "text/x-tex"),
+    ("toml", "application/toml"),
+    ("ts", "text/x-typescript"),
+    ("tsv", "text/tab-separated-values"),
+    ("txt", "text/plain"),
+    ("xml", "application/xml"),
+    ("yaml", "application/yaml"),
+    ("yml", "application/yaml"),
+];
+
+/// The MIME types of scripts by the interpreters in their shebang lines.
+const INTERPRETERS: [(&str, &str); 6] = [
+    ("bash", "text/x-shellscript"),
+    ("node", "text/javascript"),
+    ("perl", "text/x-perl"),
+    ("python", "text/x-python"),
+    ("ruby", "text/x-ruby"),
+    ("sh", "text/x-shellscript"),
+];
+
+/// Return the MIME type of binary `head`, the start of a file, if it looks
+/// binary. Besides the magic numbers, a file is binary if NUL bytes make up
+/// more than 1/32 of `head`. Corrupted text files, which may contain a few
+/// NUL bytes, are not binary.
+pub(crate) fn sniff_binary(head: &[u8]) -> Option<&'static str> {
+    for (offset, magic, mime) in MAGIC {
+        if head.get(offset..offset + magic.len()) == Some(magic) {
+            return Some(mime);
+        }
+    }
+    let nul = head.iter().filter(|&&b| b == 0).count();
+    if nul * 32 > head.len() {
+        return Some("application/octet-stream");
+    }
+    None
+}
+
+/// Return the MIME type of the file at `path` starting with `head`, by its
+/// content, and by its extension for text files, e.g. `"text/x-python"`.
+/// Text files of no known type are `"text/plain"`.
+pub fn sniff_type(path: &Path, head: &[u8]) -> &'static str {
+    if let Some(mime) = sniff_binary(head) {
+        return mime;
+    }
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase());
+    if let Some(ext) = extension {
+        if let Some(&(_, mime)) = EXTENSIONS.iter().find(|(e, _)| *e == ext) {
+            return mime;
+        }
+    }
+    if let Some(shebang) = head.strip_prefix(b"#!") {
+        let line = shebang.split(|&b| b == b'\n').next().unwrap_or_default();
+        let line = String::from_utf8_lossy(line);
+        // `#!/usr/bin/env python3` or `#!/bin/sh -e`
+        let mut words = line.split_whitespace();
+        let mut program = words.next().unwrap_or_default();
+        if program.ends_with("/env") {
+            program = words.next().unwrap_or_default();
+        }
+        let name = program.rsplit('/').next().unwrap_or_default();
+        let name =
+            name.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+        if let Some(&(_, mime)) = INTERPRETERS.iter().find(|(n, _)| *n == name)
+        {
+            return mime;
+        }
+    }
+    let start = head.trim_ascii_start();
+    if start.starts_with(b"<?xml") {
+        return "application/xml";
+    }
+    let lower = start[..start.len().min(14)].to_ascii_lowercase();
+    if lower.starts_with(b"<!doctype html") || lower.starts_with(b"<html") {
+        return "text/html";
+    }
+    "text/plain"
+}
+
+/// Why [`filter_tree`] skips a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Skipped {
+    /// The file looks binary, of this MIME type.
+    Binary(&'static str),
+    /// The symbolic link leads back to an ancestor directory.
+    Loop,
+    /// The symbolic link leads nowhere.
+    Dangling,
+    /// The symbolic link leads out of the paths given.
+    OutsideTree,
+    /// The file is the same as this one filtered before, by a hard link or a
+    /// symbolic link.
+    Linked(PathBuf),
+}
+
+impl fmt::Display for Skipped {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Skipped::Binary(mime) => write!(f, "binary ({})", mime),
+            Skipped::Loop => write!(f, "symbolic link loop"),
+            Skipped::Dangling => write!(f, "dangling symbolic link"),
+            Skipped::OutsideTree => write!(f, "outside the tree"),
+            Skipped::Linked(path) => {
+                write!(f, "same file as {}", path.display())
+            }
+        }
+    }
+}
+
+/// How [`filter_tree`] and [`filter_tree_in_place`] treat a file reachable
+/// by several paths, through hard links or followed symbolic links.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HardLinks {
+    /// Filter the file once, as one file, by the first path in order. In
+    /// place, the file is rewritten, so that every link sees the result.
+    #[default]
+    Preserve,
+    /// Filter the file by each path, as a file of its own. In place, each
+    /// path is replaced with a file of its own, leaving the other links to
+    /// the original.
+    Break,
+}
+
+/// Return the path and the skip reason of walk error `e`, of the path
+/// `path` if any, or `None` if it is a real error.
+fn skipped(
+    e: &ignore::Error,
+    path: Option<&Path>,
+) -> Option<(PathBuf, Skipped)> {
+    match e {
+        ignore::Error::WithPath { path, err } => skipped(err, Some(path)),
+        ignore::Error::WithDepth { err, .. }
+        | ignore::Error::WithLineNumber { err, .. } => skipped(err, path),
+        ignore::Error::Loop { child, .. } => {
+            Some((child.clone(), Skipped::Loop))
+        }
+        ignore::Error::Io(io) if io.kind() == io::ErrorKind::NotFound => {
+            let path = path.filter(|path| path.is_symlink())?;
+            Some((path.to_path_buf(), Skipped::Dangling))
+        }
+        _ => None,
+    }
+}
+
+/// Return the identity of the file at `path`, the same for all its links.
+#[cfg(unix)]
+fn file_id(path: &Path) -> io::Result<Option<(u64, u64)>> {
+    use std::os::unix::fs::MetadataExt;
+    let metadata = std::fs::metadata(path)?;
+    Ok(Some((metadata.dev(), metadata.ino())))
+}
+
+#[cfg(not(unix))]
+fn file_id(_path: &Path) -> io::Result<Option<(u64, u64)>> {
+    Ok(None)
+}
+
+/// Append the files under `path` to `files`, in the order of their names. If
+/// `opts.ignore`, the files ignored by `.gitignore`, `.ignore` and the like
+/// are left out, and unless `opts.hidden`, hidden files. Symbolic links are followed if
+/// `opts.follow_symlinks`, within `path` only. Files skipped otherwise are
+/// reported with `on_skip`.
+fn collect<F>(
+    path: &Path,
+    opts: &Options,
+    files: &mut Vec<PathBuf>,
+    on_skip: &mut F,
+) -> io::Result<()>
+where
+    F: FnMut(&Path, &Skipped),
+{
+    let root = path.canonicalize()?;
+    let walk = WalkBuilder::new(path)
+        .standard_filters(opts.ignore)
+        .hidden(!opts.hidden)
+        .follow_links(opts.follow_symlinks)
+        .sort_by_file_name(|a, b| a.cmp(b))
+        .build();
+    for entry in walk {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => match skipped(&e, None) {
+                Some((path, reason)) => {
+                    on_skip(&path, &reason);
+                    continue;
+                }
+                None => return Err(io::Error::other(e)),
+            },
+        };
+        if !entry.file_type().is_some_and(|t| t.is_file()) {
+            continue;
+        }
+        if opts.follow_symlinks
+            && !entry.path().canonicalize()?.starts_with(&root)
+        {
+            on_skip(entry.path(), &Skipped::OutsideTree);
+            continue;
+        }
+        files.push(entry.into_path());
+    }
+    Ok(())
+}
+
+/// Filter the files under `paths` in sequence to `w`, each per the options
+/// in `types` for its MIME type as returned by [`sniff_type`], or per `opts`.
+/// Ignored files are left out per `opts.ignore`, hidden files per
+/// `opts.hidden`, binary files per
+/// `opts.skip_binary`, and files reachable by several paths per
+/// `opts.hard_links`. Skipped files are reported with `on_skip`. Each line
+/// is prefixed with the path of its file per `opts.filenames`. Return the
+/// stats of the files filtered, summed.
+pub fn filter_tree<W, F>(
+    paths: &[PathBuf],
+    w: &mut W,
+    opts: &Options,
+    types: &HashMap<String, Options>,
+    mut on_skip: F,
+) -> Result<Stats, Error>
+where
+    W: Write,
+    F: FnMut(&Path, &Skipped),
+{
+    let mut files: Vec<PathBuf> = Vec::new();
+    for path in paths {
+        collect(path, opts, &mut files, &mut on_skip)?;
+    }
+    // the BOM starts the output, not each file
+    let mut write_bom = opts.write_bom;
+    let mut stats = Stats::default();
+    each_sniffed(
+        files,
+        opts,
+        types,
+        &mut on_skip,
+        |file, mut r, file_opts| {
+            let mut file_opts = file_opts.clone();
+            file_opts.write_bom = write_bom;
+            file_opts.line_prefix = file_line_prefix(file, opts);
+            file_opts.report_name = report_name(file);
+            let file_stats = filter_stream(&mut r, w, &file_opts)?;
+            log::info!("{}: {}", file.display(), file_stats);
+            stats += file_stats;
+            write_bom = false;
+            Ok(())
+        },
+    )?;
+    Ok(stats)
+}
+
+/// Filter the files under `paths` in place, as
+/// [`filter_in_place`](crate::filter_in_place) does, each per the options in
+/// `types` for its MIME type, or per `opts`, leaving files out as
+/// [`filter_tree`] does. A file reachable by several paths is rewritten once
+/// or replaced by each path per `opts.hard_links`. Skipped files are
+/// reported with `on_skip`. Return the stats of the files filtered, summed.
+pub fn filter_tree_in_place<F>(
+    paths: &[PathBuf],
+    backup_suffix: Option<&str>,
+    opts: &Options,
+    types: &HashMap<String, Options>,
+    mut on_skip: F,
+) -> Result<Stats, Error>
+where
+    F: FnMut(&Path, &Skipped),
+{
+    let mut files: Vec<PathBuf> = Vec::new();
+    for path in paths {
+        collect(path, opts, &mut files, &mut on_skip)?;
+    }
+    let mut stats = Stats::default();
+    each_sniffed(files, opts, types, &mut on_skip, |file, r, file_opts| {
+        // the file is read anew to be replaced
+        drop(r);
+        let file_stats =
+            rewrite(file, backup_suffix, file_opts, opts.hard_links)?;
+        log::info!("{}: {}", file.display(), file_stats);
+        stats += file_stats;
+        Ok(())
+    })?;
+    Ok(stats)
+}
+
+/// Call `f(file, r, file_opts)` for each file of `files` to filter in order,
+/// with its reader `r`, which has sniffed its start, and its options per
+/// `types`, or `opts`. Binary files and the repeated paths of a file are left
+/// out and reported with `on_skip` per `opts`.
+fn each_sniffed<F, G>(
+    files: Vec<PathBuf>,
+    opts: &Options,
+    types: &HashMap<String, Options>,
+    on_skip: &mut F,
+    mut f: G,
+) -> Result<(), Error>
+where
+    F: FnMut(&Path, &Skipped),
+    G: FnMut(&Path, BufReader<File>, &Options) -> Result<(), Error>,
+{
+    let mut seen: HashMap<(u64, u64), PathBuf> = HashMap::new();
+    for file in files {
+        if opts.hard_links == HardLinks::Preserve {
+            if let Some(id) = file_id(&file)? {
+                if let Some(first) = seen.get(&id) {
+                    on_skip(&file, &Skipped::Linked(first.clone()));
+                    continue;
+                }
+                seen.insert(id, file.clone());
+            }
+        }
+        let mut r = BufReader::with_capacity(SNIFF_LEN, File::open(&file)?);
+        let head = r.fill_buf()?;
+        if opts.skip_binary {
+            if let Some(mime) = sniff_binary(head) {
+                on_skip(&file, &Skipped::Binary(mime));
+                continue;
+            }
+        }
+        let mime = sniff_type(&file, head);
+        log::debug!("{}: sniffed as {}", file.display(), mime);
+        let file_opts = types.get(mime).unwrap_or(opts);
+        f(&file, r, file_opts)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{sniff_binary, sniff_type};
+    use std::path::Path;
+
+    #[test]
+    fn test_sniff_binary() {
+        assert_eq!(
+            sniff_binary(b"\x7fELF\x02\x01"),
+            Some("application/x-executable")
+        );
+        assert_eq!(sniff_binary(b"\x89PNG\r\n\x1a\n\0\0"), Some("image/png"));
+        assert_eq!(
+            sniff_binary(b"a\0b\0c\0"),
+            Some("application/octet-stream")
+        );
+        let corrupted = include_bytes!("../corrupted_lipsum.txt");
+        assert_eq!(sniff_binary(&corrupted[..8192]), None);
+        assert_eq!(sniff_binary(b""), None);
+    }
+
+    #[test]
+    fn test_sniff_type() {
+        let sniff = |path: &str, head: &[u8]| sniff_type(Path::new(path), head);
+        assert_eq!(sniff("a/b.PY", b"import os\n"), "text/x-python");
+        assert_eq!(sniff("data.csv", b"a,b\n1,2\n"), "text/csv");
+        assert_eq!(sniff("run", b"#!/usr/bin/env python3\n"), "text/x-python");
+        assert_eq!(sniff("run", b"#!/bin/sh -e\n"), "text/x-shellscript");
+        assert_eq!(sniff("page", b"\n<!DOCTYPE html>"), "text/html");
+        assert_eq!(sniff("logo.csv", b"\x89PNG\r\n\x1a\n"), "image/png");
+        assert_eq!(sniff("README", b"hello\n"), "text/plain");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_filter_tree_links() {
+        use super::{filter_tree, HardLinks, Skipped};
+        use crate::Options;
+        use std::collections::HashMap;
+        use std::fs;
+        use std::os::unix::fs::symlink;
+        use std::path::PathBuf;
+
+        let dir = std::env::temp_dir()
+            .join(format!("ascii-filter-walk-{}", std::process::id()));
+        let tree = dir.join("tree");
+        fs::create_dir_all(&tree).unwrap();
+        fs::write(dir.join("outside"), "outside\n").unwrap();
+        fs::write(tree.join("a"), "a\n").unwrap();
+        fs::hard_link(tree.join("a"), tree.join("b")).unwrap();
+        symlink(tree.join("a"), tree.join("c")).unwrap();
+        symlink(&tree, tree.join("d")).unwrap();
+        symlink(dir.join("outside"), tree.join("e")).unwrap();
+        symlink(dir.join("nowhere"), tree.join("f")).unwrap();
+
+        let run = |opts: &Options| {
+            let mut out: Vec<u8> = Vec::new();
+            let mut skips: Vec<(PathBuf, Skipped)> = Vec::new();
+            filter_tree(
+                std::slice::from_ref(&tree),
+                &mut out,
+                opts,
+                &HashMap::new(),
+                |path, reason| skips.push((path.to_path_buf(), reason.clone())),
+            )
+            .unwrap();
+            (String::from_utf8(out).unwrap(), skips)
+        };
+        let (out, skips) = run(&Options::default());
+        assert_eq!(out, "a\n");
+        assert_eq!(
+            skips,
+            vec![(tree.join("b"), Skipped::Linked(tree.join("a")))]
+        );
+
+        let opts = Options {
+            follow_symlinks: true,
+            hard_links: HardLinks::Break,
+            ..Options::default()
+        };
+        let (out, skips) = run(&opts);
+        assert_eq!(out, "a\na\na\n");
+        assert_eq!(
+            skips,
+            vec![
+                (tree.join("d"), Skipped::Loop),
+                (tree.join("e"), Skipped::OutsideTree),
+                (tree.join("f"), Skipped::Dangling),
+            ]
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_filter_tree_in_place() {
+        use super::{filter_tree_in_place, HardLinks};
+        use crate::Options;
+        use std::collections::HashMap;
+        use std::fs;
+        use std::os::unix::fs::MetadataExt;
+
+        let dir = std::env::temp_dir()
+            .join(format!("ascii-filter-walk-in-place-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let run = |hard_links: HardLinks| {
+            fs::write(dir.join("a"), "aé\n").unwrap();
+            let _ = fs::remove_file(dir.join("b"));
+            fs::hard_link(dir.join("a"), dir.join("b")).unwrap();
+            let opts = Options {
+                ascii_only: true,
+                hard_links,
+                ..Options::default()
+            };
+            filter_tree_in_place(
+                std::slice::from_ref(&dir),
+                Some(".bak"),
+                &opts,
+                &HashMap::new(),
+                |_, _| (),
+            )
+            .unwrap();
+        };
+        let ino = |name: &str| fs::metadata(dir.join(name)).unwrap().ino();
+
+        run(HardLinks::Preserve);
+        assert_eq!(fs::read_to_string(dir.join("b")).unwrap(), "a\n");
+        assert_eq!(ino("a"), ino("b"));
+        assert_eq!(fs::read_to_string(dir.join("a.bak")).unwrap(), "aé\n");
+        assert!(!dir.join("b.bak").exists());
+
+        fs::remove_file(dir.join("a.bak")).unwrap();
+        run(HardLinks::Break);
+        assert_eq!(fs::read_to_string(dir.join("a")).unwrap(), "a\n");
+        assert_eq!(fs::read_to_string(dir.join("b")).unwrap(), "a\n");
+        assert_ne!(ino("a"), ino("b"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_filter_tree_hidden() {
+        use super::filter_tree;
+        use crate::Options;
+        use std::collections::HashMap;
+        use std::fs;
+
+        let dir = std::env::temp_dir()
+            .join(format!("ascii-filter-walk-hidden-{}", std::process::id()));
+        fs::create_dir_all(dir.join(".git")).unwrap();
+        fs::write(dir.join(".git").join("config"), "git\n").unwrap();
+        fs::write(dir.join(".gitignore"), "b\n").unwrap();
+        fs::write(dir.join("a"), "a\n").unwrap();
+        fs::write(dir.join("b"), "b\n").unwrap();
+        let run = |opts: &Options| {
+            let mut out: Vec<u8> = Vec::new();
+            let paths = std::slice::from_ref(&dir);
+            filter_tree(paths, &mut out, opts, &HashMap::new(), |_, _| ())
+                .unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        assert_eq!(run(&Options::default()), "a\n");
+        let opts = Options {
+            ignore: false,
+            ..Options::default()
+        };
+        assert_eq!(run(&opts), "a\nb\n");
+        let opts = Options {
+            hidden: true,
+            ..opts
+        };
+        assert_eq!(run(&opts), "git\nb\na\nb\n");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,142 @@
+//! Watching a file for changes, with inotify on Linux, or by polling its size
+//! and modification time elsewhere.
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+/// The time between polls, also waited after a change for more of them, so
+/// that a burst of writes is taken as one change.
+const INTERVAL: Duration = Duration::from_millis(200);
+
+/// Watches a file for changes from when it is created, including a new file
+/// renamed or written in its place.
+pub(crate) struct Watcher {
+    path: PathBuf,
+    #[cfg(target_os = "linux")]
+    events: std::fs::File,
+    #[cfg(not(target_os = "linux"))]
+    stamp: Option<(u64, std::time::SystemTime)>,
+}
+
+impl Watcher {
+    /// Start watching `path`, waiting for it to exist first.
+    #[cfg(target_os = "linux")]
+    pub(crate) fn new(path: &Path) -> io::Result<Self> {
+        use std::ffi::CString;
+        use std::os::fd::{FromRawFd, OwnedFd};
+        use std::os::unix::ffi::OsStrExt;
+        let c_path = CString::new(path.as_os_str().as_bytes())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        // SAFETY: no pointers are passed.
+        let fd = unsafe { libc::inotify_init1(libc::IN_CLOEXEC) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: `fd` is a fresh fd owned by nobody else.
+        let events = std::fs::File::from(unsafe { OwnedFd::from_raw_fd(fd) });
+        let mask = libc::IN_MODIFY
+            | libc::IN_CLOSE_WRITE
+            | libc::IN_ATTRIB
+            | libc::IN_MOVE_SELF
+            | libc::IN_DELETE_SELF;
+        loop {
+            // SAFETY: `c_path` is a valid C string.
+            let wd =
+                unsafe { libc::inotify_add_watch(fd, c_path.as_ptr(), mask) };
+            if wd >= 0 {
+                break;
+            }
+            let e = io::Error::last_os_error();
+            if e.kind() != io::ErrorKind::NotFound {
+                return Err(e);
+            }
+            thread::sleep(INTERVAL);
+        }
+        Ok(Self {
+            path: path.to_path_buf(),
+            events,
+        })
+    }
+
+    /// Wait for a change since the watching started.
+    #[cfg(target_os = "linux")]
+    pub(crate) fn wait(mut self) -> io::Result<()> {
+        use std::io::Read;
+        // Any event is a change, so what they are is not read.
+        let mut buf = [0u8; 4096];
+        loop {
+            match self.events.read(&mut buf) {
+                Ok(_) => break,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => {
+                    return Err(io::Error::new(
+                        e.kind(),
+                        format!("watching {}: {}", self.path.display(), e),
+                    ))
+                }
+            }
+        }
+        thread::sleep(INTERVAL);
+        Ok(())
+    }
+
+    /// Start watching `path`, waiting for it to exist first.
+    #[cfg(not(target_os = "linux"))]
+    pub(crate) fn new(path: &Path) -> io::Result<Self> {
+        let mut current = stamp(path);
+        while current.is_none() {
+            thread::sleep(INTERVAL);
+            current = stamp(path);
+        }
+        Ok(Self {
+            path: path.to_path_buf(),
+            stamp: current,
+        })
+    }
+
+    /// Wait for a change since the watching started.
+    #[cfg(not(target_os = "linux"))]
+    pub(crate) fn wait(self) -> io::Result<()> {
+        while stamp(&self.path) == self.stamp {
+            thread::sleep(INTERVAL);
+        }
+        thread::sleep(INTERVAL);
+        Ok(())
+    }
+}
+
+/// Return the size and modification time of the file `path`, or `None` if it
+/// does not exist.
+#[cfg(not(target_os = "linux"))]
+fn stamp(path: &Path) -> Option<(u64, std::time::SystemTime)> {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.len(), metadata.modified().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Watcher;
+    use std::fs;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn test_watcher() {
+        let path = std::env::temp_dir()
+            .join(format!("ascii-filter-watch-{}", std::process::id()));
+        fs::write(&path, "a").unwrap();
+        let watcher = Watcher::new(&path).unwrap();
+        let writer = {
+            let path = path.clone();
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(50));
+                fs::write(&path, "ab").unwrap();
+            })
+        };
+        watcher.wait().unwrap();
+        writer.join().unwrap();
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,353 @@
+//! Tests of the command line, running the binary.
+
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::process::{Child, Command, Output, Stdio};
+use std::time::Duration;
+
+/// Return a fresh temporary directory for the test `name`.
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "ascii-filter-cli-{}-{}",
+        name,
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Run a shell `script`, in which `$BIN` is the binary.
+#[cfg(unix)]
+fn sh(script: &str) -> Output {
+    Command::new("sh")
+        .arg("-c")
+        .arg(script)
+        .env("BIN", env!("CARGO_BIN_EXE_ascii-filter"))
+        .output()
+        .unwrap()
+}
+
+#[test]
+#[cfg(unix)]
+fn test_output_fd_and_checkpoint_fd_shared() {
+    let dir = temp_dir("fd");
+    let out = dir.join("out");
+    let output = sh(&format!(
+        "printf 'h\\303\\251llo\\nworld\\n' \
+         | \"$BIN\" -a --output-fd 3 --checkpoint-fd 3 3>'{}'",
+        out.display()
+    ));
+    assert!(output.status.success(), "{:?}", output);
+    let written = std::fs::read_to_string(&out).unwrap();
+    assert!(written.starts_with("hllo\nworld\n"), "{:?}", written);
+    assert!(written.ends_with("13\n"), "{:?}", written);
+    // stdout stays open given as the output fd
+    let output = sh("printf 'h\\303\\251llo\\n' | \"$BIN\" -a --output-fd 1");
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(output.stdout, b"hllo\n");
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+/// Start the binary with `args`, fed `input` on a stdin left open, which
+/// waiting for it closes.
+fn spawn_fed(args: &[&str], input: &[u8]) -> Child {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_ascii-filter"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.as_mut().unwrap().write_all(input).unwrap();
+    child
+}
+
+/// Return what `child` wrote to stdout and stderr, once it exited.
+fn read_output(child: &mut Child) -> (String, String) {
+    let mut stdout = String::new();
+    let mut stderr = String::new();
+    child
+        .stdout
+        .take()
+        .unwrap()
+        .read_to_string(&mut stdout)
+        .unwrap();
+    child
+        .stderr
+        .take()
+        .unwrap()
+        .read_to_string(&mut stderr)
+        .unwrap();
+    (stdout, stderr)
+}
+
+/// Run the binary with `args`.
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_ascii-filter"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_optional_values_leave_paths() {
+    let dir = temp_dir("optional");
+    let path = dir.join("a.txt");
+    std::fs::write(&path, "a\tb\u{e9}\n").unwrap();
+    let path = path.to_str().unwrap();
+    let output = run(&["-a", "--escape", path]);
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(output.stdout, b"a\tb\\u{00E9}\n");
+    let output = run(&["-a", "--escape=html", path]);
+    assert_eq!(output.stdout, b"a\tb&#xE9;\n");
+    let output = run(&["-a", "--expand-tabs", path]);
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(output.stdout, b"a       b\n");
+    let output = run(&["-a", "--expand-tabs=4", path]);
+    assert_eq!(output.stdout, b"a   b\n");
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_recursive_in_place_skips_hidden() {
+    let dir = temp_dir("hidden");
+    std::fs::create_dir_all(dir.join(".git")).unwrap();
+    std::fs::write(dir.join(".git").join("config"), "caf\u{e9}\n").unwrap();
+    std::fs::write(dir.join("a.txt"), "caf\u{e9}\n").unwrap();
+    let run_in = |args: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_ascii-filter"))
+            .args(args)
+            .current_dir(&dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{:?}", output);
+    };
+    let read = |path: &str| std::fs::read_to_string(dir.join(path)).unwrap();
+    run_in(&["-a", "-r", "--no-ignore", "-i", "."]);
+    assert_eq!(read("a.txt"), "caf\n");
+    assert_eq!(read(".git/config"), "caf\u{e9}\n");
+    run_in(&["-a", "-r", "--no-ignore", "--hidden", "-i", "."]);
+    assert_eq!(read(".git/config"), "caf\n");
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_exit_codes() {
+    let dir = temp_dir("exit");
+    let write = |name: &str, content: &[u8]| {
+        let path = dir.join(name);
+        std::fs::write(&path, content).unwrap();
+        path.to_str().unwrap().to_string()
+    };
+    let clean = write("clean.txt", b"abc\n");
+    let dirty = write("dirty.txt", "caf\u{e9}\n".as_bytes());
+    let invalid = write("invalid.txt", b"a\xffb\n");
+    // a gzip header, and nothing after it
+    let truncated = write("truncated.gz", b"\x1f\x8b\x08\x00\0\0\0\0\0\x03");
+    let missing = dir.join("missing.txt");
+    let missing = missing.to_str().unwrap();
+    let code = |args: &[&str]| run(args).status.code();
+    assert_eq!(code(&["-a", &clean]), Some(0));
+    assert_eq!(code(&["-a", "check", &dirty]), Some(1));
+    assert_eq!(code(&["--no-such-flag", &clean]), Some(2));
+    assert_eq!(code(&["-a", missing]), Some(3));
+    assert_eq!(code(&["-a", "--invalid-utf8", "error", &invalid]), Some(4));
+    assert_eq!(code(&["-a", "--decompress", &truncated]), Some(4));
+    #[cfg(unix)]
+    {
+        let output = sh("sleep 2 | \"$BIN\" -a --timeout 0.2");
+        assert_eq!(output.status.code(), Some(5));
+    }
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+#[cfg(unix)]
+fn test_broken_pipe() {
+    let dir = temp_dir("pipe");
+    let input = dir.join("in.txt");
+    std::fs::write(&input, "caf\u{e9}\n".repeat(1 << 16)).unwrap();
+    // the status of the binary, whose reader is gone after a byte
+    let output = sh(&format!(
+        "{{ {{ \"$BIN\" -a '{}'; echo $? >&3; }} | head -c 1 >/dev/null; }} \
+         3>&1",
+        input.display()
+    ));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "0\n");
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "");
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+#[cfg(unix)]
+fn test_sigint() {
+    let mut child = spawn_fed(&["-a", "--stats"], "caf\u{e9}\n".as_bytes());
+    std::thread::sleep(Duration::from_millis(500));
+    // SAFETY: kill has no memory safety preconditions.
+    assert_eq!(unsafe { libc::kill(child.id() as i32, libc::SIGINT) }, 0);
+    let status = child.wait().unwrap();
+    let (stdout, stderr) = read_output(&mut child);
+    assert_eq!(status.code(), Some(130));
+    // the output and the stats so far, flushed
+    assert_eq!(stdout, "caf\n");
+    assert!(
+        stderr.contains("6 bytes read, 4 bytes written"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn test_idle_timeout() {
+    let start = std::time::Instant::now();
+    let mut child =
+        spawn_fed(&["-a", "--idle-timeout", "300ms"], "caf\u{e9}\n".as_bytes());
+    // stdin stays open, but stalls, which waiting would close
+    let stdin = child.stdin.take();
+    let status = child.wait().unwrap();
+    let elapsed = start.elapsed();
+    drop(stdin);
+    assert!(elapsed >= Duration::from_millis(300), "{:?}", elapsed);
+    assert!(elapsed < Duration::from_secs(5), "{:?}", elapsed);
+    let (stdout, _) = read_output(&mut child);
+    assert_eq!(status.code(), Some(0));
+    assert_eq!(stdout, "caf\n");
+}
+
+#[test]
+fn test_checkpoint_file() {
+    let dir = temp_dir("checkpoint");
+    let checkpoint = dir.join("offset");
+    let mut child = spawn_fed(
+        &["-a", "--checkpoint-file", checkpoint.to_str().unwrap()],
+        b"abc\n",
+    );
+    // a checkpoint at the first chunk read a second after the start, while
+    // the input is still open
+    std::thread::sleep(Duration::from_millis(1200));
+    let stdin = child.stdin.as_mut().unwrap();
+    stdin.write_all("caf\u{e9}\n".as_bytes()).unwrap();
+    std::thread::sleep(Duration::from_millis(300));
+    assert_eq!(std::fs::read_to_string(&checkpoint).unwrap(), "10\n");
+    drop(child.stdin.take());
+    assert!(child.wait().unwrap().success());
+    let (stdout, _) = read_output(&mut child);
+    assert_eq!(stdout, "abc\ncaf\n");
+    assert_eq!(std::fs::read_to_string(&checkpoint).unwrap(), "10\n");
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_write_bom() {
+    let dir = temp_dir("bom");
+    let a = dir.join("a.txt");
+    let b = dir.join("b.txt");
+    std::fs::write(&a, "caf\u{e9}\n").unwrap();
+    std::fs::write(&b, "na\u{ef}ve\n").unwrap();
+    let (a, b) = (a.to_str().unwrap(), b.to_str().unwrap());
+    let output = run(&["-a", "--write-bom", a]);
+    assert_eq!(output.stdout, b"\xef\xbb\xbfcaf\n");
+    // once, at the start of the output
+    let output = run(&["-a", "--write-bom", a, b]);
+    assert_eq!(output.stdout, b"\xef\xbb\xbfcaf\nnave\n");
+    // that of the output encoding
+    let output = run(&["-a", "--write-bom", "--to-encoding", "UTF-16LE", a]);
+    assert_eq!(output.stdout, b"\xff\xfec\0a\0f\0\n\0");
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_with_filename() {
+    let dir = temp_dir("filename");
+    std::fs::write(dir.join("a.txt"), "caf\u{e9}\n").unwrap();
+    std::fs::write(dir.join("b.txt"), "na\u{ef}ve\n").unwrap();
+    // stdout, then stderr, where check reports
+    let written = |args: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_ascii-filter"))
+            .args(args)
+            .current_dir(&dir)
+            .output()
+            .unwrap();
+        String::from_utf8([output.stdout, output.stderr].concat()).unwrap()
+    };
+    assert_eq!(written(&["-a", "a.txt", "b.txt"]), "caf\nnave\n");
+    assert_eq!(
+        written(&["-a", "-H", "a.txt", "b.txt"]),
+        "a.txt:caf\nb.txt:nave\n"
+    );
+    assert_eq!(
+        written(&["-a", "--with-filename", "--no-filename", "a.txt", "b.txt"]),
+        "caf\nnave\n"
+    );
+    // the paths of the lines of report, and of check
+    let report = "a.txt:1:4: U+00E9 LATIN SMALL LETTER E WITH ACUTE\n";
+    assert_eq!(written(&["-a", "report", "a.txt"]), report);
+    assert_eq!(
+        written(&["-a", "--no-filename", "report", "a.txt"]),
+        "1:4: U+00E9 LATIN SMALL LETTER E WITH ACUTE\n"
+    );
+    assert_eq!(written(&["-a", "check", "a.txt"]), report);
+    assert_eq!(
+        written(&["-a", "--no-filename", "check", "a.txt"]),
+        "1:4: U+00E9 LATIN SMALL LETTER E WITH ACUTE\n"
+    );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+/// Open a pseudo-terminal, returning its master side and its terminal.
+#[cfg(unix)]
+fn open_pty() -> (std::fs::File, std::fs::File) {
+    use std::ffi::CStr;
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+    use std::os::unix::fs::OpenOptionsExt;
+    // SAFETY: the fd returned is checked before it is owned, and ptsname
+    // returns a nul-terminated name, copied before the next call.
+    let (master, name) = unsafe {
+        let fd = libc::posix_openpt(libc::O_RDWR | libc::O_NOCTTY);
+        assert!(fd >= 0);
+        let master = OwnedFd::from_raw_fd(fd);
+        assert_eq!(libc::grantpt(master.as_raw_fd()), 0);
+        assert_eq!(libc::unlockpt(master.as_raw_fd()), 0);
+        let name = CStr::from_ptr(libc::ptsname(master.as_raw_fd()));
+        (master, name.to_str().unwrap().to_string())
+    };
+    let terminal = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .custom_flags(libc::O_NOCTTY)
+        .open(name)
+        .unwrap();
+    (master.into(), terminal)
+}
+
+#[test]
+#[cfg(unix)]
+fn test_terminal_hint() {
+    let (_master, terminal) = open_pty();
+    let output = Command::new(env!("CARGO_BIN_EXE_ascii-filter"))
+        .arg("-a")
+        .stdin(terminal)
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.starts_with("ascii-filter: stdin is a terminal"),
+        "{}",
+        stderr
+    );
+    assert!(stderr.contains("--interactive"), "{}", stderr);
+
+    // a line typed, and Ctrl-D
+    let (mut master, terminal) = open_pty();
+    master.write_all("caf\u{e9}\n\x04".as_bytes()).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_ascii-filter"))
+        .args(["-a", "--interactive"])
+        .stdin(terminal)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(output.stdout, b"caf\n");
+}
@@ -0,0 +1,44 @@
+//! Tests of the Python bindings in python/, building the shared library with
+//! the `capi` feature and running python/test_ascii_filter.py against it.
+//! Skipped if there is no `python3`.
+
+use std::path::Path;
+use std::process::Command;
+
+#[test]
+fn test_python_bindings() {
+    if Command::new("python3").arg("--version").output().is_err() {
+        eprintln!("python3 not found, skipping the Python bindings");
+        return;
+    }
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    // in a target dir of its own, not to wait on the lock of the tests'
+    let target = Path::new(env!("CARGO_TARGET_TMPDIR")).join("capi");
+    let status = Command::new(option_env!("CARGO").unwrap_or("cargo"))
+        .args(["build", "--lib", "--features", "capi"])
+        .arg("--target-dir")
+        .arg(&target)
+        .current_dir(root)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let name = if cfg!(windows) {
+        "ascii_filter.dll"
+    } else if cfg!(target_os = "macos") {
+        "libascii_filter.dylib"
+    } else {
+        "libascii_filter.so"
+    };
+    let output = Command::new("python3")
+        .args(["-m", "unittest", "-v", "test_ascii_filter"])
+        .env("ASCII_FILTER_LIB", target.join("debug").join(name))
+        .env("PYTHONDONTWRITEBYTECODE", "1")
+        .current_dir(root.join("python"))
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}